@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+
+pub const USAGE: &str = "\
+Usage: openchamber-desktop [OPTIONS] [PATH | URL | HOST_ID | openchamber://LINK]
+
+Options:
+  --host <ID|URL>          Open this host instead of the default one
+  --project <PATH>         Open a project directory on the local server
+  --new-window             Open a new window instead of focusing one
+  --ssh-connect <ID>       Connect an SSH instance and open it
+  --data-dir <PATH>        Use another settings and data directory
+  --log-level <LEVEL>      off, error, warn, info, debug or trace
+  --headless               Run servers and connections without windows
+  --list-hosts             Print configured hosts and exit
+  -h, --help               Print this help and exit
+  -V, --version            Print the version and exit
+
+A running instance receives the arguments of later launches instead of a
+second instance starting. In headless mode, desktop-control.json in the data
+directory holds the port and bearer token of the local control API
+(GET /status, POST /shutdown); SIGTERM shuts down cleanly.";
+
+/// Parsed command line. Flags given to a second launch are forwarded to the
+/// running instance and parsed there again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliArgs {
+    pub host: Option<String>,
+    pub project: Option<String>,
+    pub new_window: bool,
+    pub ssh_connect: Option<String>,
+    pub data_dir: Option<PathBuf>,
+    pub log_level: Option<log::LevelFilter>,
+    pub headless: bool,
+    pub list_hosts: bool,
+    pub help: bool,
+    pub version: bool,
+    /// A bare path, URL, host id or link, as passed by file managers and
+    /// URL handlers.
+    pub positional: Option<String>,
+}
+
+fn parse_log_level(raw: &str) -> Result<log::LevelFilter, String> {
+    raw.parse::<log::LevelFilter>().map_err(|_| {
+        format!("Invalid --log-level {raw:?}: use off, error, warn, info, debug or trace")
+    })
+}
+
+pub fn parse(args: &[String]) -> Result<CliArgs, String> {
+    let mut cli = CliArgs::default();
+    let mut iter = args.iter().peekable();
+    while let Some(arg) = iter.next() {
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag, Some(value.to_string())),
+            _ => (arg.as_str(), None),
+        };
+        // A separate value never looks like an option, so `--host --project
+        // /x` is a missing host rather than a host named `--project`.
+        let mut value = |name: &str| -> Result<String, String> {
+            inline
+                .clone()
+                .or_else(|| iter.next_if(|next| !next.starts_with("--")).cloned())
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| format!("{name} needs a value"))
+        };
+        match flag {
+            "--host" => cli.host = Some(value(flag)?),
+            "--project" => cli.project = Some(value(flag)?),
+            "--ssh-connect" => cli.ssh_connect = Some(value(flag)?),
+            "--data-dir" => cli.data_dir = Some(PathBuf::from(value(flag)?)),
+            "--log-level" => cli.log_level = Some(parse_log_level(&value(flag)?)?),
+            "--new-window" | "--headless" | "--list-hosts" | "--help" | "--version"
+                if inline.is_some() =>
+            {
+                return Err(format!("{flag} does not take a value"));
+            }
+            "--new-window" => cli.new_window = true,
+            "--headless" => cli.headless = true,
+            "--list-hosts" => cli.list_hosts = true,
+            "-h" | "--help" => cli.help = true,
+            "-V" | "--version" => cli.version = true,
+            // macOS adds a process serial number when launched from Finder.
+            _ if flag.starts_with("-psn_") => {}
+            _ if flag.starts_with('-') => return Err(format!("Unknown option {flag}")),
+            _ if arg.trim().is_empty() => {}
+            _ => {
+                if cli.positional.is_some() {
+                    return Err(format!("Unexpected argument {arg:?}"));
+                }
+                cli.positional = Some(arg.trim().to_string());
+            }
+        }
+    }
+    Ok(cli)
+}
+
+/// Resolve `path` against `cwd` when it is relative.
+pub fn absolute_path(path: &Path, cwd: Option<&Path>) -> PathBuf {
+    match cwd {
+        Some(cwd) if path.is_relative() => cwd.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_flags_values_and_positional() {
+        let cli = parse(&args(&[
+            "--host=gpu-box",
+            "--project",
+            "../work",
+            "--new-window",
+            "--log-level",
+            "DEBUG",
+            "-psn_0_4711",
+            "openchamber://ssh/connect/prod",
+        ]))
+        .expect("parse");
+        assert_eq!(
+            cli,
+            CliArgs {
+                host: Some("gpu-box".to_string()),
+                project: Some("../work".to_string()),
+                new_window: true,
+                log_level: Some(log::LevelFilter::Debug),
+                positional: Some("openchamber://ssh/connect/prod".to_string()),
+                ..CliArgs::default()
+            }
+        );
+        assert_eq!(parse(&[]).expect("empty"), CliArgs::default());
+        let inline = parse(&args(&["--host=--odd", "--project", "-dashed"])).expect("inline");
+        assert_eq!(inline.host.as_deref(), Some("--odd"));
+        assert_eq!(inline.project.as_deref(), Some("-dashed"));
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        for bad in [
+            args(&["--host"]),
+            args(&["--host="]),
+            args(&["--log-level", "loud"]),
+            args(&["--frobnicate"]),
+            args(&["one", "two"]),
+            args(&["--new-window=false"]),
+            args(&["--headless=0"]),
+            args(&["--host", "--project", "/x"]),
+        ] {
+            assert!(parse(&bad).is_err(), "{bad:?} should be rejected");
+        }
+    }
+}
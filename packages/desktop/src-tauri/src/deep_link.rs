@@ -0,0 +1,162 @@
+use anyhow::{anyhow, bail, Result};
+use std::path::PathBuf;
+
+pub const DEEP_LINK_SCHEME: &str = "openchamber";
+
+/// A validated `openchamber://` link.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// `openchamber://open?project=<absolute path>`
+    OpenProject { path: PathBuf },
+    /// `openchamber://session/<id>?host=<hostId>`; the local server when no
+    /// host is given.
+    Session { id: String, host: Option<String> },
+    /// `openchamber://ssh/connect/<instanceId>`
+    SshConnect { instance_id: String },
+}
+
+impl DeepLink {
+    /// Question shown before acting on a link that came from outside the app.
+    pub fn confirmation_prompt(&self) -> String {
+        match self {
+            Self::OpenProject { path } => {
+                format!("Add {} to your projects and open it?", path.display())
+            }
+            Self::Session { id, host: None } => format!("Open session {id}?"),
+            Self::Session {
+                id,
+                host: Some(host),
+            } => format!("Open session {id} on host {host}? This connects to the host if needed."),
+            Self::SshConnect { instance_id } => format!(
+                "Connect to SSH instance {instance_id}? Managed instances may install or update OpenChamber on the remote host."
+            ),
+        }
+    }
+}
+
+/// Session, host and instance ids are opaque but never need more than this;
+/// anything else is rejected before it reaches a URL or the settings file.
+fn validate_id(kind: &str, value: &str) -> Result<String> {
+    let valid = !value.is_empty()
+        && value.len() <= 200
+        && value
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '-' | '.'))
+        && !value.starts_with('.');
+    if !valid {
+        bail!("Invalid {kind} id in link: {value:?}");
+    }
+    Ok(value.to_string())
+}
+
+fn query_value(url: &url::Url, key: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(name, _)| name == key)
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+pub fn is_deep_link(raw: &str) -> bool {
+    raw.trim()
+        .get(..DEEP_LINK_SCHEME.len() + 1)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{DEEP_LINK_SCHEME}:")))
+}
+
+pub fn parse(raw: &str) -> Result<DeepLink> {
+    let url = url::Url::parse(raw.trim()).map_err(|err| anyhow!("Invalid link: {err}"))?;
+    if url.scheme() != DEEP_LINK_SCHEME {
+        bail!("Unsupported link scheme: {}", url.scheme());
+    }
+    let route = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+
+    match (route.as_str(), segments.as_slice()) {
+        ("open", []) => {
+            let project =
+                query_value(&url, "project").ok_or_else(|| anyhow!("Link is missing ?project="))?;
+            let path = PathBuf::from(&project);
+            if !path.is_absolute() {
+                bail!("Project path must be absolute: {project}");
+            }
+            Ok(DeepLink::OpenProject { path })
+        }
+        ("session", [id]) => Ok(DeepLink::Session {
+            id: validate_id("session", id)?,
+            host: query_value(&url, "host")
+                .map(|host| validate_id("host", &host))
+                .transpose()?,
+        }),
+        ("ssh", ["connect", id]) => Ok(DeepLink::SshConnect {
+            instance_id: validate_id("SSH instance", id)?,
+        }),
+        _ => bail!("Unknown link: {raw}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_supported_routes() {
+        assert_eq!(
+            parse("openchamber://session/ses_01HX?host=gpu-box").expect("session"),
+            DeepLink::Session {
+                id: "ses_01HX".to_string(),
+                host: Some("gpu-box".to_string()),
+            }
+        );
+        assert_eq!(
+            parse("openchamber://session/ses_01HX/").expect("trailing slash"),
+            DeepLink::Session {
+                id: "ses_01HX".to_string(),
+                host: None,
+            }
+        );
+        assert_eq!(
+            parse("openchamber://ssh/connect/prod-1").expect("ssh"),
+            DeepLink::SshConnect {
+                instance_id: "prod-1".to_string(),
+            }
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            parse("openchamber://open?project=%2Fhome%2Fme%2Fwork%20dir").expect("open"),
+            DeepLink::OpenProject {
+                path: PathBuf::from("/home/me/work dir"),
+            }
+        );
+        assert!(is_deep_link("OpenChamber://open?project=/x"));
+        assert!(!is_deep_link("https://example.test"));
+    }
+
+    #[test]
+    fn confirmation_prompt_names_the_target() {
+        let connect = parse("openchamber://ssh/connect/prod-1").expect("ssh");
+        assert!(connect
+            .confirmation_prompt()
+            .starts_with("Connect to SSH instance prod-1?"));
+        let session = parse("openchamber://session/ses_1?host=gpu-box").expect("session");
+        assert!(session.confirmation_prompt().contains("on host gpu-box"));
+    }
+
+    #[test]
+    fn rejects_malformed_links() {
+        for raw in [
+            "https://session/abc",
+            "openchamber://unknown/abc",
+            "openchamber://open",
+            "openchamber://open?project=relative/path",
+            "openchamber://session/",
+            "openchamber://session/a%22b",
+            "openchamber://session/abc?host=..",
+            "openchamber://ssh/connect",
+            "openchamber://ssh/connect/a/b",
+        ] {
+            assert!(parse(raw).is_err(), "{raw} should be rejected");
+        }
+    }
+}
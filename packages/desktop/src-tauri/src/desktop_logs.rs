@@ -0,0 +1,480 @@
+use crate::remote_ssh::settings_file_path;
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Base name of the sidecar's stdout/stderr capture.
+pub const SIDECAR_LOG_NAME: &str = "sidecar";
+/// Base name of the desktop shell's own log (written by `tauri-plugin-log`).
+pub const DESKTOP_LOG_NAME: &str = "desktop";
+/// Size at which the active log file is rotated.
+pub const MAX_LOG_FILE_BYTES: u64 = 5 * 1024 * 1024;
+/// Rotated files kept next to the active one.
+pub const KEEP_ROTATED_LOG_FILES: usize = 3;
+
+const DEFAULT_TAIL_LINES: usize = 500;
+const MAX_TAIL_LINES: usize = 10_000;
+
+/// `logs/` next to `settings.json` in the data directory.
+pub fn logs_dir() -> PathBuf {
+    settings_file_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+        .join("logs")
+}
+
+/// Append-only log file that rotates to `name.1.log`, `name.2.log`, …
+/// once it grows past `max_bytes`.
+pub struct RotatingLogFile {
+    dir: PathBuf,
+    name: String,
+    max_bytes: u64,
+    keep: usize,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingLogFile {
+    pub fn new(dir: PathBuf, name: &str, max_bytes: u64, keep: usize) -> Self {
+        Self {
+            dir,
+            name: name.to_string(),
+            max_bytes,
+            keep,
+            file: None,
+            size: 0,
+        }
+    }
+
+    fn path_for(&self, index: usize) -> PathBuf {
+        if index == 0 {
+            self.dir.join(format!("{}.log", self.name))
+        } else {
+            self.dir.join(format!("{}.{index}.log", self.name))
+        }
+    }
+
+    fn open(&mut self) -> Result<&mut File> {
+        if self.file.is_none() {
+            fs::create_dir_all(&self.dir)
+                .with_context(|| format!("Failed to create {}", self.dir.display()))?;
+            let path = self.path_for(0);
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("Failed to open {}", path.display()))?;
+            self.size = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("log file opened"))
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file = None;
+        let _ = fs::remove_file(self.path_for(self.keep));
+        for index in (0..self.keep).rev() {
+            let from = self.path_for(index);
+            if from.exists() {
+                let to = self.path_for(index + 1);
+                let _ = fs::remove_file(&to);
+                fs::rename(&from, &to)
+                    .with_context(|| format!("Failed to rotate {}", from.display()))?;
+            }
+        }
+        if self.keep == 0 {
+            let _ = fs::remove_file(self.path_for(0));
+        }
+        self.size = 0;
+        Ok(())
+    }
+
+    pub fn write_line(&mut self, line: &str) -> Result<()> {
+        let len = line.len() as u64 + 1;
+        self.open()?;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        let file = self.open()?;
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+/// Sidecar capture shared by the reader tasks of every sidecar process.
+#[derive(Default)]
+pub struct DesktopLogsState {
+    sidecar: Mutex<Option<RotatingLogFile>>,
+}
+
+impl DesktopLogsState {
+    /// Append sidecar output from `stream` (`stdout`/`stderr`), one
+    /// timestamped line per output line. Write failures are dropped: losing
+    /// a log line must never take the sidecar down with it.
+    pub fn append_sidecar(&self, stream: &str, bytes: &[u8]) {
+        let text = String::from_utf8_lossy(bytes);
+        let Ok(mut guard) = self.sidecar.lock() else {
+            return;
+        };
+        let log = guard.get_or_insert_with(|| {
+            RotatingLogFile::new(
+                logs_dir(),
+                SIDECAR_LOG_NAME,
+                MAX_LOG_FILE_BYTES,
+                KEEP_ROTATED_LOG_FILES,
+            )
+        });
+        let stamp = format_utc_timestamp(SystemTime::now());
+        for line in text.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+            if log
+                .write_line(&format!("{stamp} [{stream}] {line}"))
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ` without pulling in a date crate.
+fn format_utc_timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let days = (secs / 86_400) as i64;
+    let rem = secs % 86_400;
+
+    // Civil-from-days (Howard Hinnant).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        rem / 3_600,
+        (rem % 3_600) / 60,
+        rem % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopLogFileInfo {
+    pub name: String,
+    pub size: u64,
+    /// Milliseconds since the Unix epoch.
+    pub modified_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopLogsReadResult {
+    pub source: String,
+    pub files: Vec<DesktopLogFileInfo>,
+    pub lines: Vec<String>,
+    /// More lines matched than were returned.
+    pub truncated: bool,
+}
+
+fn modified_millis(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_millis() as u64)
+}
+
+/// Log files of `source` in `dir`, oldest first. Covers both our own
+/// `name.N.log` rotation and the dated names `tauri-plugin-log` rotates to.
+fn source_files(dir: &Path, source: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(Option<u64>, PathBuf)> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| {
+                        name.ends_with(".log")
+                            && name
+                                .strip_prefix(source)
+                                .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('_'))
+                    })
+        })
+        .map(|path| (modified_millis(&path), path))
+        .collect();
+    // Same-tick mtimes fall back to the rotation index: higher is older.
+    files.sort_by_key(|(modified, path)| (*modified, std::cmp::Reverse(rotation_index(path))));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+fn rotation_index(path: &Path) -> usize {
+    path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.rsplit_once('.'))
+        .and_then(|(_, index)| index.parse().ok())
+        .unwrap_or(0)
+}
+
+fn validate_source(source: &str) -> Result<&str, String> {
+    match source {
+        SIDECAR_LOG_NAME | DESKTOP_LOG_NAME => Ok(source),
+        other => Err(format!("Unknown log source: {other}")),
+    }
+}
+
+/// Last `limit` lines of `source`, optionally only those containing `query`
+/// (case-insensitive).
+fn read_logs_in(
+    dir: &Path,
+    source: &str,
+    limit: Option<usize>,
+    query: Option<&str>,
+) -> DesktopLogsReadResult {
+    let files = source_files(dir, source);
+    let limit = limit.unwrap_or(DEFAULT_TAIL_LINES).clamp(1, MAX_TAIL_LINES);
+    let needle = query
+        .map(str::trim)
+        .filter(|query| !query.is_empty())
+        .map(str::to_lowercase);
+
+    let mut lines = Vec::new();
+    for path in &files {
+        let Ok(bytes) = fs::read(path) else {
+            continue;
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        lines.extend(
+            text.lines()
+                .filter(|line| {
+                    needle
+                        .as_ref()
+                        .is_none_or(|needle| line.to_lowercase().contains(needle))
+                })
+                .map(str::to_string),
+        );
+    }
+    let truncated = lines.len() > limit;
+    if truncated {
+        lines.drain(..lines.len() - limit);
+    }
+
+    DesktopLogsReadResult {
+        source: source.to_string(),
+        files: files
+            .iter()
+            .map(|path| DesktopLogFileInfo {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                size: fs::metadata(path).map(|meta| meta.len()).unwrap_or(0),
+                modified_at: modified_millis(path),
+            })
+            .collect(),
+        lines,
+        truncated,
+    }
+}
+
+/// Concatenate every desktop and sidecar log file into one text document.
+fn bundle_logs_in(dir: &Path, header: &str) -> String {
+    let mut content = String::from(header);
+    for source in [DESKTOP_LOG_NAME, SIDECAR_LOG_NAME] {
+        let files = source_files(dir, source);
+        if files.is_empty() {
+            content.push_str(&format!("\n===== {source}: no log files =====\n"));
+            continue;
+        }
+        for path in files {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let body = fs::read(&path)
+                .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+                .unwrap_or_else(|err| format!("(failed to read: {err})\n"));
+            content.push_str(&format!("\n===== {name} =====\n"));
+            content.push_str(&body);
+            if !body.ends_with('\n') {
+                content.push('\n');
+            }
+        }
+    }
+    content
+}
+
+#[tauri::command]
+pub fn desktop_logs_read(
+    source: String,
+    lines: Option<usize>,
+    query: Option<String>,
+) -> Result<DesktopLogsReadResult, String> {
+    let source = validate_source(source.trim())?;
+    Ok(read_logs_in(&logs_dir(), source, lines, query.as_deref()))
+}
+
+/// Assemble every log file, prefixed by the UI's status report, and save it
+/// where the user picks. Returns the saved path, or `None` if cancelled.
+#[tauri::command]
+pub async fn desktop_logs_save(
+    app: tauri::AppHandle,
+    status_report: Option<String>,
+) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let stamp = format_utc_timestamp(SystemTime::now());
+    let dir = logs_dir();
+    let mut header = format!(
+        "OpenChamber desktop logs\nVersion: {}\nPlatform: {} {}\nLog directory: {}\nGenerated: {stamp}\n",
+        app.package_info().version,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        dir.display(),
+    );
+    if let Some(report) = status_report.filter(|report| !report.trim().is_empty()) {
+        header.push_str("\n===== status =====\n");
+        header.push_str(report.trim_end());
+        header.push('\n');
+    }
+    let content = bundle_logs_in(&dir, &header);
+
+    let file_stamp: String = stamp
+        .chars()
+        .take(19)
+        .map(|c| if c == ':' { '-' } else { c })
+        .collect();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    app.dialog()
+        .file()
+        .add_filter("Text", &["txt", "log"])
+        .set_file_name(format!("openchamber-logs-{file_stamp}.txt"))
+        .save_file(move |file_path| {
+            let _ = tx.send(file_path);
+        });
+
+    let Some(file_path) = rx
+        .await
+        .map_err(|_| "Save dialog was closed unexpectedly".to_string())?
+    else {
+        return Ok(None);
+    };
+    let path = file_path
+        .into_path()
+        .map_err(|_| "Selected path is not a local filesystem path".to_string())?;
+    fs::write(&path, content).map_err(|err| format!("Failed to save logs: {err}"))?;
+    Ok(Some(path.to_string_lossy().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn temp_dir(test_name: &str) -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("clock drift")
+            .as_nanos();
+        std::env::temp_dir().join(format!("openchamber-{test_name}-{nanos}"))
+    }
+
+    #[test]
+    fn rotating_log_file_keeps_a_bounded_number_of_files() {
+        let dir = temp_dir("log-rotation");
+        let mut log = RotatingLogFile::new(dir.clone(), "sidecar", 32, 2);
+        for index in 0..20 {
+            log.write_line(&format!("line {index:02} padding"))
+                .expect("write");
+        }
+
+        let mut names: Vec<_> = fs::read_dir(&dir)
+            .expect("dir")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["sidecar.1.log", "sidecar.2.log", "sidecar.log"]);
+        let active = fs::read_to_string(dir.join("sidecar.log")).expect("active");
+        assert!(active.ends_with("line 19 padding\n"));
+        assert!(fs::metadata(dir.join("sidecar.1.log")).expect("meta").len() <= 32);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn read_logs_tails_and_searches_across_rotated_files() {
+        let dir = temp_dir("log-read");
+        let mut log = RotatingLogFile::new(dir.clone(), "sidecar", 64, 3);
+        for index in 0..12 {
+            let level = if index % 4 == 0 { "ERROR" } else { "info" };
+            log.write_line(&format!("{level} event {index}"))
+                .expect("write");
+        }
+        fs::write(dir.join("desktop.log"), "desktop only\n").expect("desktop log");
+
+        let tail = read_logs_in(&dir, "sidecar", Some(2), None);
+        assert_eq!(tail.lines, vec!["info event 10", "info event 11"]);
+        assert!(tail.truncated);
+        assert!(tail
+            .files
+            .iter()
+            .all(|file| file.name.starts_with("sidecar")));
+
+        let errors = read_logs_in(&dir, "sidecar", None, Some("error"));
+        assert_eq!(
+            errors.lines,
+            vec!["ERROR event 0", "ERROR event 4", "ERROR event 8"]
+        );
+        assert!(!errors.truncated);
+
+        let bundle = bundle_logs_in(&dir, "header\n");
+        assert!(bundle.starts_with("header\n"));
+        assert!(bundle.contains("===== desktop.log =====\ndesktop only\n"));
+        assert!(bundle.contains("info event 11"));
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn format_utc_timestamp_renders_calendar_dates() {
+        assert_eq!(format_utc_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_utc_timestamp(UNIX_EPOCH + Duration::from_millis(1_709_210_096_789)),
+            "2024-02-29T12:34:56.789Z"
+        );
+    }
+
+    #[test]
+    fn desktop_logs_read_rejects_unknown_sources() {
+        assert!(validate_source("sidecar").is_ok());
+        assert!(validate_source("../settings").is_err());
+    }
+}
@@ -0,0 +1,410 @@
+use anyhow::{anyhow, Context, Result};
+use std::process::{Command, Stdio};
+
+/// Observed state of a named container.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DockerContainerState {
+    pub running: bool,
+    pub image: String,
+}
+
+/// Everything needed to create a container with `docker run`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DockerRunSpec {
+    pub name: String,
+    pub image: String,
+    /// `-p` value, e.g. `127.0.0.1::3000` or `[::1]:4100:3000`.
+    pub publish: String,
+    pub volumes: Vec<String>,
+    pub env: Vec<String>,
+    pub workdir: Option<String>,
+    pub labels: Vec<(String, String)>,
+}
+
+/// Docker operations used by the docker manager. `docker_host` overrides
+/// `DOCKER_HOST` for a single call.
+pub trait DockerRuntime: Send + Sync {
+    /// Daemon version; fails when the CLI or daemon is unavailable.
+    fn server_version(&self, docker_host: Option<&str>) -> Result<String>;
+
+    /// `None` when no container with this name exists.
+    fn inspect(
+        &self,
+        docker_host: Option<&str>,
+        name: &str,
+    ) -> Result<Option<DockerContainerState>>;
+
+    fn run_detached(&self, docker_host: Option<&str>, spec: &DockerRunSpec) -> Result<()>;
+
+    fn start(&self, docker_host: Option<&str>, name: &str) -> Result<()>;
+
+    /// Host port published for `container_port/tcp`, if any.
+    fn published_port(
+        &self,
+        docker_host: Option<&str>,
+        name: &str,
+        container_port: u16,
+    ) -> Result<Option<u16>>;
+
+    /// Run a shell script in the container through a login shell.
+    fn exec(&self, docker_host: Option<&str>, name: &str, script: &str) -> Result<String>;
+
+    /// Stop the container and optionally remove it. Best effort.
+    fn stop(&self, docker_host: Option<&str>, name: &str, remove: bool);
+}
+
+/// Parse one `docker port` line such as `0.0.0.0:49153` or `[::]:49153`.
+fn parse_port_mapping(line: &str) -> Option<u16> {
+    line.trim().rsplit_once(':')?.1.trim().parse().ok()
+}
+
+/// Runtime backed by the `docker` CLI.
+#[derive(Default)]
+pub struct DockerCliRuntime;
+
+impl DockerCliRuntime {
+    fn command(docker_host: Option<&str>, args: &[&str]) -> Command {
+        let mut command = Command::new("docker");
+        command.args(args);
+        if let Some(host) = docker_host {
+            command.env("DOCKER_HOST", host);
+        }
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    fn run(docker_host: Option<&str>, args: &[&str]) -> Result<String> {
+        let output = Self::command(docker_host, args)
+            .output()
+            .context("Failed to run docker; is the Docker CLI installed?")?;
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(anyhow!(if stderr.is_empty() {
+            format!("docker {} failed ({})", args[0], output.status)
+        } else {
+            stderr
+        }))
+    }
+}
+
+impl DockerRuntime for DockerCliRuntime {
+    fn server_version(&self, docker_host: Option<&str>) -> Result<String> {
+        Self::run(docker_host, &["version", "--format", "{{.Server.Version}}"])
+            .map(|output| output.trim().to_string())
+    }
+
+    fn inspect(
+        &self,
+        docker_host: Option<&str>,
+        name: &str,
+    ) -> Result<Option<DockerContainerState>> {
+        match Self::run(
+            docker_host,
+            &[
+                "container",
+                "inspect",
+                "--format",
+                "{{.State.Running}} {{.Config.Image}}",
+                name,
+            ],
+        ) {
+            Ok(output) => {
+                let (running, image) = output.trim().split_once(' ').unwrap_or((output.trim(), ""));
+                Ok(Some(DockerContainerState {
+                    running: running == "true",
+                    image: image.to_string(),
+                }))
+            }
+            Err(err) if err.to_string().contains("No such") => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn run_detached(&self, docker_host: Option<&str>, spec: &DockerRunSpec) -> Result<()> {
+        let mut args: Vec<String> = vec![
+            "run".to_string(),
+            "-d".to_string(),
+            "--init".to_string(),
+            "--name".to_string(),
+            spec.name.clone(),
+            "-p".to_string(),
+            spec.publish.clone(),
+        ];
+        for (key, value) in &spec.labels {
+            args.push("--label".to_string());
+            args.push(format!("{key}={value}"));
+        }
+        for volume in &spec.volumes {
+            args.push("-v".to_string());
+            args.push(volume.clone());
+        }
+        for env in &spec.env {
+            args.push("-e".to_string());
+            args.push(env.clone());
+        }
+        if let Some(workdir) = spec.workdir.as_ref() {
+            args.push("-w".to_string());
+            args.push(workdir.clone());
+        }
+        // Keep the container alive regardless of the image's own command.
+        args.extend([
+            "--entrypoint".to_string(),
+            "tail".to_string(),
+            spec.image.clone(),
+            "-f".to_string(),
+            "/dev/null".to_string(),
+        ]);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        Self::run(docker_host, &args)
+            .map(|_| ())
+            .with_context(|| format!("Failed to create container {}", spec.name))
+    }
+
+    fn start(&self, docker_host: Option<&str>, name: &str) -> Result<()> {
+        Self::run(docker_host, &["start", name]).map(|_| ())
+    }
+
+    fn published_port(
+        &self,
+        docker_host: Option<&str>,
+        name: &str,
+        container_port: u16,
+    ) -> Result<Option<u16>> {
+        let spec = format!("{container_port}/tcp");
+        match Self::run(docker_host, &["port", name, &spec]) {
+            Ok(output) => Ok(output.lines().find_map(parse_port_mapping)),
+            Err(err) if err.to_string().contains("No public port") => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn exec(&self, docker_host: Option<&str>, name: &str, script: &str) -> Result<String> {
+        Self::run(docker_host, &["exec", name, "sh", "-lc", script])
+    }
+
+    fn stop(&self, docker_host: Option<&str>, name: &str, remove: bool) {
+        let _ = Self::run(docker_host, &["stop", "-t", "5", name]);
+        if remove {
+            let _ = Self::run(docker_host, &["rm", "-f", name]);
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use crate::ssh_transport::fake::serve_ok;
+    use std::{
+        collections::HashMap,
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    #[derive(Default)]
+    struct FakeState {
+        daemon_down: bool,
+        containers: HashMap<String, (DockerContainerState, Option<u16>)>,
+        runs: Vec<DockerRunSpec>,
+        execs: Vec<String>,
+        stopped: Vec<(String, bool)>,
+        rules: Vec<(String, Result<String, String>)>,
+    }
+
+    /// In-memory Docker daemon. Created containers publish `host_port`,
+    /// a loopback listener that answers every request with `200 OK`.
+    pub struct FakeDockerRuntime {
+        state: Mutex<FakeState>,
+        pub host_port: u16,
+        stop: Arc<AtomicBool>,
+    }
+
+    impl Drop for FakeDockerRuntime {
+        fn drop(&mut self) {
+            self.stop.store(true, Ordering::SeqCst);
+        }
+    }
+
+    impl FakeDockerRuntime {
+        pub fn new() -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind fake published port");
+            let host_port = listener.local_addr().expect("local addr").port();
+            listener.set_nonblocking(true).expect("nonblocking");
+            let stop = Arc::new(AtomicBool::new(false));
+            serve_ok(listener, Arc::clone(&stop));
+            Self {
+                state: Mutex::default(),
+                host_port,
+                stop,
+            }
+        }
+
+        pub fn set_daemon_down(&self) {
+            self.state.lock().expect("fake state mutex").daemon_down = true;
+        }
+
+        pub fn add_container(&self, name: &str, running: bool, published: Option<u16>) {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .containers
+                .insert(
+                    name.to_string(),
+                    (
+                        DockerContainerState {
+                            running,
+                            image: "existing:latest".to_string(),
+                        },
+                        published,
+                    ),
+                );
+        }
+
+        /// Exec scripts containing `needle` return `result`; newest rule wins.
+        pub fn respond(&self, needle: &str, result: Result<&str, &str>) {
+            self.state.lock().expect("fake state mutex").rules.insert(
+                0,
+                (
+                    needle.to_string(),
+                    result.map(str::to_string).map_err(str::to_string),
+                ),
+            );
+        }
+
+        pub fn runs(&self) -> Vec<DockerRunSpec> {
+            self.state.lock().expect("fake state mutex").runs.clone()
+        }
+
+        pub fn execs(&self) -> Vec<String> {
+            self.state.lock().expect("fake state mutex").execs.clone()
+        }
+
+        pub fn stopped(&self) -> Vec<(String, bool)> {
+            self.state.lock().expect("fake state mutex").stopped.clone()
+        }
+
+        pub fn is_running(&self, name: &str) -> bool {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .containers
+                .get(name)
+                .is_some_and(|(state, _)| state.running)
+        }
+    }
+
+    impl DockerRuntime for FakeDockerRuntime {
+        fn server_version(&self, _docker_host: Option<&str>) -> Result<String> {
+            if self.state.lock().expect("fake state mutex").daemon_down {
+                return Err(anyhow!("Cannot connect to the Docker daemon"));
+            }
+            Ok("27.0.0".to_string())
+        }
+
+        fn inspect(
+            &self,
+            _docker_host: Option<&str>,
+            name: &str,
+        ) -> Result<Option<DockerContainerState>> {
+            Ok(self
+                .state
+                .lock()
+                .expect("fake state mutex")
+                .containers
+                .get(name)
+                .map(|(state, _)| state.clone()))
+        }
+
+        fn run_detached(&self, _docker_host: Option<&str>, spec: &DockerRunSpec) -> Result<()> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            state.runs.push(spec.clone());
+            state.containers.insert(
+                spec.name.clone(),
+                (
+                    DockerContainerState {
+                        running: true,
+                        image: spec.image.clone(),
+                    },
+                    Some(self.host_port),
+                ),
+            );
+            Ok(())
+        }
+
+        fn start(&self, _docker_host: Option<&str>, name: &str) -> Result<()> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            let (container, _) = state
+                .containers
+                .get_mut(name)
+                .ok_or_else(|| anyhow!("No such container: {name}"))?;
+            container.running = true;
+            Ok(())
+        }
+
+        fn published_port(
+            &self,
+            _docker_host: Option<&str>,
+            name: &str,
+            _container_port: u16,
+        ) -> Result<Option<u16>> {
+            Ok(self
+                .state
+                .lock()
+                .expect("fake state mutex")
+                .containers
+                .get(name)
+                .and_then(|(_, port)| *port))
+        }
+
+        fn exec(&self, _docker_host: Option<&str>, name: &str, script: &str) -> Result<String> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            if !state
+                .containers
+                .get(name)
+                .is_some_and(|(container, _)| container.running)
+            {
+                return Err(anyhow!("container {name} is not running"));
+            }
+            state.execs.push(script.to_string());
+            let reply = state
+                .rules
+                .iter()
+                .find(|(needle, _)| script.contains(needle.as_str()))
+                .map(|(_, result)| result.clone());
+            match reply {
+                Some(Ok(output)) => Ok(output),
+                Some(Err(message)) => Err(anyhow!(message)),
+                None => Ok(String::new()),
+            }
+        }
+
+        fn stop(&self, _docker_host: Option<&str>, name: &str, remove: bool) {
+            let mut state = self.state.lock().expect("fake state mutex");
+            state.stopped.push((name.to_string(), remove));
+            if remove {
+                state.containers.remove(name);
+            } else if let Some((container, _)) = state.containers.get_mut(name) {
+                container.running = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_port_mapping_handles_ipv4_and_ipv6() {
+        assert_eq!(parse_port_mapping("0.0.0.0:49153"), Some(49153));
+        assert_eq!(parse_port_mapping("[::]:49153\n"), Some(49153));
+        assert_eq!(parse_port_mapping("garbage"), None);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::remote_ssh::settings_file_path;
+use crate::single_instance::{publish_record, ListenerRecord};
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const CONTROL_FILE_NAME: &str = "desktop-control.json";
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+const MAX_REQUEST_BYTES: u64 = 16 * 1024;
+
+/// The control API listener. Requests must send
+/// `Authorization: Bearer <token>` with the token of `record`.
+pub struct ControlServer {
+    listener: TcpListener,
+    pub record: ListenerRecord,
+}
+
+pub fn control_file_path() -> PathBuf {
+    settings_file_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+        .join(CONTROL_FILE_NAME)
+}
+
+/// Listen on a loopback port and publish it with a fresh token at `path`.
+pub fn bind(path: &Path) -> Result<ControlServer> {
+    let _ = fs::remove_file(path);
+    let (listener, record) = publish_record(path)?;
+    Ok(ControlServer { listener, record })
+}
+
+/// Answer control requests on a background thread until the process exits:
+/// `GET /status` returns `status()`, `POST /shutdown` calls `shutdown`.
+pub fn serve(
+    server: ControlServer,
+    status: impl Fn() -> Value + Send + 'static,
+    shutdown: impl Fn() + Send + 'static,
+) {
+    let ControlServer { listener, record } = server;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            let (code, body, then_shutdown) = match read_request(&stream, &record.token) {
+                Ok((method, path)) => match (method.as_str(), path.as_str()) {
+                    ("GET", "/status") => (200, status(), false),
+                    ("POST", "/shutdown") => (202, serde_json::json!({ "ok": true }), true),
+                    (_, "/status" | "/shutdown") => (405, error_body("Method not allowed"), false),
+                    _ => (404, error_body("Not found"), false),
+                },
+                Err(RequestError::Unauthorized) => (401, error_body("Unauthorized"), false),
+                Err(RequestError::Malformed(err)) => {
+                    log::warn!("[headless] malformed control request: {err}");
+                    (400, error_body("Bad request"), false)
+                }
+            };
+            let _ = write_response(&stream, code, &body);
+            if then_shutdown {
+                shutdown();
+            }
+        }
+    });
+}
+
+enum RequestError {
+    Unauthorized,
+    Malformed(anyhow::Error),
+}
+
+impl<E: Into<anyhow::Error>> From<E> for RequestError {
+    fn from(err: E) -> Self {
+        Self::Malformed(err.into())
+    }
+}
+
+fn error_body(message: &str) -> Value {
+    serde_json::json!({ "error": message })
+}
+
+/// Read the request line and headers; bodies are not used.
+fn read_request(stream: &TcpStream, token: &str) -> Result<(String, String), RequestError> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.take(MAX_REQUEST_BYTES));
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Err(anyhow!("missing request line").into());
+    };
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let mut authorized = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(anyhow!("headers ended early").into());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                authorized = value
+                    .trim()
+                    .strip_prefix("Bearer ")
+                    .is_some_and(|value| value.trim() == token);
+            }
+        }
+    }
+    if !authorized {
+        return Err(RequestError::Unauthorized);
+    }
+    Ok((method.to_string(), path))
+}
+
+fn write_response(mut stream: &TcpStream, code: u16, body: &Value) -> Result<()> {
+    let reason = match code {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        _ => "Method Not Allowed",
+    };
+    let body = serde_json::to_string(body)?;
+    write!(
+        stream,
+        "HTTP/1.1 {code} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn request(port: u16, method: &str, path: &str, token: Option<&str>) -> (u16, Value) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).expect("connect");
+        let auth = token
+            .map(|token| format!("Authorization: Bearer {token}\r\n"))
+            .unwrap_or_default();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: 127.0.0.1\r\n{auth}\r\n"
+        )
+        .expect("write");
+        let mut raw = String::new();
+        stream.read_to_string(&mut raw).expect("read");
+        let code = raw
+            .split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .expect("status code");
+        let body = raw.split("\r\n\r\n").nth(1).unwrap_or_default();
+        (code, serde_json::from_str(body).expect("json body"))
+    }
+
+    #[test]
+    fn control_api_requires_token_and_serves_status_and_shutdown() {
+        let dir = std::env::temp_dir().join(format!("openchamber-headless-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join(CONTROL_FILE_NAME);
+
+        let server = bind(&path).expect("bind");
+        let record: ListenerRecord =
+            serde_json::from_str(&fs::read_to_string(&path).expect("record")).expect("json");
+        assert_eq!(record, server.record);
+
+        let (tx, rx) = mpsc::channel();
+        serve(
+            server,
+            || serde_json::json!({ "sidecar": { "running": true } }),
+            move || {
+                let _ = tx.send(());
+            },
+        );
+
+        let token = record.token.as_str();
+        assert_eq!(request(record.port, "GET", "/status", None).0, 401);
+        assert_eq!(request(record.port, "GET", "/status", Some("wrong")).0, 401);
+        assert_eq!(
+            request(record.port, "GET", "/status?verbose=1", Some(token)),
+            (200, serde_json::json!({ "sidecar": { "running": true } }))
+        );
+        assert_eq!(request(record.port, "GET", "/shutdown", Some(token)).0, 405);
+        assert_eq!(request(record.port, "GET", "/nope", Some(token)).0, 404);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            request(record.port, "POST", "/shutdown", Some(token)).0,
+            202
+        );
+        rx.recv_timeout(IO_TIMEOUT).expect("shutdown requested");
+
+        crate::single_instance::release(&path);
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
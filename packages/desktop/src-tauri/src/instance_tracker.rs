@@ -0,0 +1,283 @@
+use crate::remote_ssh::{now_millis, DesktopSshInstanceStatus, DesktopSshPhase};
+use anyhow::{anyhow, Result};
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+use tauri::{AppHandle, Emitter};
+
+pub const MAX_LOG_LINES_PER_INSTANCE: usize = 1200;
+const CONNECT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Sink for manager side effects that would otherwise need a live `AppHandle`.
+pub trait ManagerEvents: Send + Sync {
+    fn app_version(&self) -> String;
+    fn emit_status(&self, event: &str, status: &DesktopSshInstanceStatus);
+}
+
+impl ManagerEvents for AppHandle {
+    fn app_version(&self) -> String {
+        self.package_info().version.to_string()
+    }
+
+    fn emit_status(&self, event: &str, status: &DesktopSshInstanceStatus) {
+        let _ = self.emit(event, status);
+    }
+}
+
+/// Last published status and a bounded log per instance of one manager.
+/// Every published status is logged and emitted as `event`.
+pub struct InstanceTracker {
+    event: &'static str,
+    statuses: Mutex<HashMap<String, DesktopSshInstanceStatus>>,
+    logs: Mutex<HashMap<String, Vec<String>>>,
+}
+
+impl InstanceTracker {
+    pub fn new(event: &'static str) -> Self {
+        Self {
+            event,
+            statuses: Mutex::default(),
+            logs: Mutex::default(),
+        }
+    }
+
+    pub fn append_log_with_level(&self, id: &str, level: &str, message: impl Into<String>) {
+        let line = format!("[{}] [{}] {}", now_millis(), level, message.into());
+        let mut logs = self.logs.lock().expect("instance logs mutex");
+        let entry = logs.entry(id.to_string()).or_default();
+        entry.push(line);
+        if entry.len() > MAX_LOG_LINES_PER_INSTANCE {
+            let overflow = entry.len() - MAX_LOG_LINES_PER_INSTANCE;
+            entry.drain(0..overflow);
+        }
+    }
+
+    pub fn append_log(&self, id: &str, message: impl Into<String>) {
+        self.append_log_with_level(id, "INFO", message);
+    }
+
+    /// The newest `limit` log lines of `id`; all of them when `limit` is 0.
+    pub fn logs(&self, id: &str, limit: usize) -> Vec<String> {
+        let logs = self.logs.lock().expect("instance logs mutex");
+        let mut lines = logs.get(id).cloned().unwrap_or_default();
+        if limit > 0 && lines.len() > limit {
+            let keep_from = lines.len() - limit;
+            lines.drain(0..keep_from);
+        }
+        lines
+    }
+
+    pub fn publish(&self, app: &dyn ManagerEvents, status: DesktopSshInstanceStatus) {
+        let level = match status.phase {
+            DesktopSshPhase::Error => "ERROR",
+            DesktopSshPhase::Degraded => "WARN",
+            _ => "INFO",
+        };
+        self.append_log_with_level(
+            &status.id,
+            level,
+            format!(
+                "phase={} detail={}",
+                serde_json::to_string(&status.phase).unwrap_or_else(|_| "\"unknown\"".to_string()),
+                status.detail.as_deref().unwrap_or("")
+            ),
+        );
+        self.statuses
+            .lock()
+            .expect("instance status mutex")
+            .insert(status.id.clone(), status.clone());
+        app.emit_status(self.event, &status);
+    }
+
+    pub fn set_phase(
+        &self,
+        app: &dyn ManagerEvents,
+        id: &str,
+        phase: DesktopSshPhase,
+        detail: impl Into<String>,
+    ) {
+        self.publish(
+            app,
+            DesktopSshInstanceStatus {
+                phase,
+                detail: Some(detail.into()),
+                ..DesktopSshInstanceStatus::idle(id)
+            },
+        );
+    }
+
+    pub fn snapshot(&self, id: &str) -> DesktopSshInstanceStatus {
+        self.statuses
+            .lock()
+            .expect("instance status mutex")
+            .get(id)
+            .cloned()
+            .unwrap_or_else(|| DesktopSshInstanceStatus::idle(id))
+    }
+
+    /// Local URL of `id` when it is ready.
+    pub fn ready_url(&self, id: &str) -> Option<String> {
+        let status = self.snapshot(id);
+        match status.phase {
+            DesktopSshPhase::Ready => status.local_url,
+            _ => None,
+        }
+    }
+
+    /// Wait while `connecting` reports a connect task for `id`, then return
+    /// its local URL, or the last status detail when it did not get ready.
+    pub async fn wait_for_ready_url(
+        &self,
+        id: &str,
+        connecting: impl Fn() -> bool,
+        failure: &str,
+    ) -> Result<String> {
+        while connecting() {
+            tokio::time::sleep(CONNECT_POLL_INTERVAL).await;
+        }
+        self.ready_url(id).ok_or_else(|| {
+            anyhow!(self
+                .snapshot(id)
+                .detail
+                .unwrap_or_else(|| failure.to_string()))
+        })
+    }
+
+    /// Snapshots of `ids` sorted by id; unknown ids report idle.
+    pub fn snapshots<'a>(
+        &self,
+        ids: impl IntoIterator<Item = &'a str>,
+    ) -> Vec<DesktopSshInstanceStatus> {
+        let mut result: Vec<DesktopSshInstanceStatus> =
+            ids.into_iter().map(|id| self.snapshot(id)).collect();
+        result.sort_by(|a, b| a.id.cmp(&b.id));
+        result
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Records emitted statuses in order.
+    #[derive(Default)]
+    pub struct RecordingEvents {
+        pub statuses: Mutex<Vec<(String, DesktopSshInstanceStatus)>>,
+    }
+
+    impl RecordingEvents {
+        pub fn last(&self) -> DesktopSshInstanceStatus {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .last()
+                .map(|(_, status)| status.clone())
+                .expect("status")
+        }
+
+        pub fn phases(&self) -> Vec<DesktopSshPhase> {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .iter()
+                .map(|(_, status)| status.phase.clone())
+                .collect()
+        }
+    }
+
+    impl ManagerEvents for RecordingEvents {
+        fn app_version(&self) -> String {
+            "1.9.10".to_string()
+        }
+
+        fn emit_status(&self, event: &str, status: &DesktopSshInstanceStatus) {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .push((event.to_string(), status.clone()));
+        }
+    }
+
+    /// Fresh settings file path in a per-test temp directory.
+    pub fn temp_settings_path(prefix: &str, name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "openchamber-{prefix}-test-{name}-{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        dir.join("settings.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fake::RecordingEvents;
+    use super::*;
+
+    #[test]
+    fn publish_records_logs_and_emits_on_the_manager_event() {
+        let tracker = InstanceTracker::new("openchamber:test-status");
+        let events = RecordingEvents::default();
+        tracker.set_phase(&events, "b", DesktopSshPhase::Ready, "Connected");
+        tracker.set_phase(&events, "a", DesktopSshPhase::Error, "Boom");
+
+        let emitted = events.statuses.lock().expect("events mutex").clone();
+        assert!(emitted
+            .iter()
+            .all(|(event, _)| event == "openchamber:test-status"));
+        assert_eq!(tracker.snapshot("a").phase, DesktopSshPhase::Error);
+        assert_eq!(tracker.snapshot("c").phase, DesktopSshPhase::Idle);
+        let ids: Vec<String> = tracker
+            .snapshots(["b", "c", "a"])
+            .into_iter()
+            .map(|status| status.id)
+            .collect();
+        assert_eq!(ids, ["a", "b", "c"]);
+        assert!(tracker.logs("a", 0)[0].contains("[ERROR] phase=\"error\" detail=Boom"));
+    }
+
+    #[test]
+    fn wait_for_ready_url_reports_the_settled_status() {
+        let tracker = InstanceTracker::new("openchamber:test-status");
+        let events = RecordingEvents::default();
+        tracker.publish(
+            &events,
+            DesktopSshInstanceStatus {
+                phase: DesktopSshPhase::Ready,
+                local_url: Some("http://127.0.0.1:4100".to_string()),
+                ..DesktopSshInstanceStatus::idle("a")
+            },
+        );
+        tracker.set_phase(&events, "b", DesktopSshPhase::Error, "Pod not found");
+
+        let polls = std::sync::atomic::AtomicUsize::new(0);
+        let connecting = || polls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 2;
+        let url =
+            tauri::async_runtime::block_on(tracker.wait_for_ready_url("a", connecting, "failed"));
+        assert_eq!(url.expect("ready"), "http://127.0.0.1:4100");
+        assert_eq!(polls.load(std::sync::atomic::Ordering::SeqCst), 3);
+        let err =
+            tauri::async_runtime::block_on(tracker.wait_for_ready_url("b", || false, "failed"))
+                .expect_err("error phase");
+        assert_eq!(err.to_string(), "Pod not found");
+        let err =
+            tauri::async_runtime::block_on(tracker.wait_for_ready_url("c", || false, "failed"))
+                .expect_err("idle");
+        assert_eq!(err.to_string(), "failed");
+    }
+
+    #[test]
+    fn logs_are_capped_per_instance() {
+        let tracker = InstanceTracker::new("openchamber:test-status");
+        for index in 0..MAX_LOG_LINES_PER_INSTANCE + 5 {
+            tracker.append_log("a", format!("line {index}"));
+        }
+        tracker.append_log("b", "other");
+
+        let lines = tracker.logs("a", 0);
+        assert_eq!(lines.len(), MAX_LOG_LINES_PER_INSTANCE);
+        assert!(lines[0].ends_with("line 5"));
+        assert_eq!(tracker.logs("a", 2).len(), 2);
+        assert_eq!(tracker.logs("b", 0).len(), 1);
+    }
+}
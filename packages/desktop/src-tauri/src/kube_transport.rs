@@ -0,0 +1,325 @@
+use crate::ssh_transport::SshProcess;
+use anyhow::{anyhow, Context, Result};
+use std::process::{Command, Stdio};
+
+/// Cluster coordinates shared by every `kubectl` call of an instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KubeTarget {
+    pub context: Option<String>,
+    pub namespace: Option<String>,
+    /// Container inside the pod for `exec`; `None` uses the default one.
+    pub container: Option<String>,
+}
+
+/// Kubernetes operations used by the kube manager.
+pub trait KubeRuntime: Send + Sync {
+    /// Name of a running pod: `pod` itself when set, otherwise the first
+    /// running pod matching `selector`.
+    fn resolve_pod(
+        &self,
+        target: &KubeTarget,
+        pod: Option<&str>,
+        selector: Option<&str>,
+    ) -> Result<String>;
+
+    /// Run a shell script in the pod through a login shell.
+    fn exec(&self, target: &KubeTarget, pod: &str, script: &str) -> Result<String>;
+
+    /// Start `kubectl port-forward` from `bind_host:local_port` to
+    /// `remote_port` in the pod.
+    fn spawn_port_forward(
+        &self,
+        target: &KubeTarget,
+        pod: &str,
+        bind_host: &str,
+        local_port: u16,
+        remote_port: u16,
+    ) -> Result<Box<dyn SshProcess>>;
+}
+
+fn target_args(target: &KubeTarget) -> Vec<String> {
+    let mut args = Vec::new();
+    if let Some(context) = target.context.as_ref() {
+        args.push("--context".to_string());
+        args.push(context.clone());
+    }
+    if let Some(namespace) = target.namespace.as_ref() {
+        args.push("--namespace".to_string());
+        args.push(namespace.clone());
+    }
+    args
+}
+
+/// Runtime backed by the `kubectl` CLI.
+#[derive(Default)]
+pub struct KubectlRuntime;
+
+impl KubectlRuntime {
+    fn command(target: &KubeTarget, args: &[&str]) -> Command {
+        let mut command = Command::new("kubectl");
+        command.args(target_args(target)).args(args);
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        command
+    }
+
+    fn run(target: &KubeTarget, args: &[&str]) -> Result<String> {
+        let output = Self::command(target, args)
+            .output()
+            .context("Failed to run kubectl; is it installed and on PATH?")?;
+        if output.status.success() {
+            return Ok(String::from_utf8_lossy(&output.stdout).to_string());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(anyhow!(if stderr.is_empty() {
+            format!("kubectl {} failed ({})", args[0], output.status)
+        } else {
+            stderr
+        }))
+    }
+}
+
+impl KubeRuntime for KubectlRuntime {
+    fn resolve_pod(
+        &self,
+        target: &KubeTarget,
+        pod: Option<&str>,
+        selector: Option<&str>,
+    ) -> Result<String> {
+        if let Some(pod) = pod {
+            let phase = Self::run(
+                target,
+                &["get", "pod", pod, "-o", "jsonpath={.status.phase}"],
+            )?;
+            let phase = phase.trim();
+            if phase != "Running" {
+                return Err(anyhow!("Pod {pod} is {phase}, not Running"));
+            }
+            return Ok(pod.to_string());
+        }
+        let selector =
+            selector.ok_or_else(|| anyhow!("A pod name or label selector is required"))?;
+        let names = Self::run(
+            target,
+            &[
+                "get",
+                "pods",
+                "--selector",
+                selector,
+                "--field-selector=status.phase=Running",
+                "-o",
+                "jsonpath={.items[*].metadata.name}",
+            ],
+        )?;
+        names
+            .split_whitespace()
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("No running pod matches selector {selector}"))
+    }
+
+    fn exec(&self, target: &KubeTarget, pod: &str, script: &str) -> Result<String> {
+        let mut args = vec!["exec", pod];
+        if let Some(container) = target.container.as_deref() {
+            args.extend(["--container", container]);
+        }
+        args.extend(["--", "sh", "-lc", script]);
+        Self::run(target, &args)
+    }
+
+    fn spawn_port_forward(
+        &self,
+        target: &KubeTarget,
+        pod: &str,
+        bind_host: &str,
+        local_port: u16,
+        remote_port: u16,
+    ) -> Result<Box<dyn SshProcess>> {
+        let resource = format!("pod/{pod}");
+        let ports = format!("{local_port}:{remote_port}");
+        let mut command = Self::command(
+            target,
+            &["port-forward", "--address", bind_host, &resource, &ports],
+        );
+        command.stdout(Stdio::null());
+        let child = command
+            .spawn()
+            .context("Failed to start kubectl port-forward")?;
+        Ok(Box::new(child))
+    }
+}
+
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use crate::ssh_transport::fake::{serve_ok, ExitCell, FakeProcess};
+    use crate::ssh_transport::SshProcessExit;
+    use std::{
+        net::TcpListener,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+    };
+
+    #[derive(Default)]
+    struct FakeState {
+        /// Running pods and the labels they carry.
+        pods: Vec<(String, Vec<String>)>,
+        rules: Vec<(String, Result<String, String>)>,
+        execs: Vec<(String, String)>,
+        forwards: Vec<String>,
+        live_forwards: Vec<(ExitCell, Arc<AtomicBool>)>,
+    }
+
+    /// In-memory cluster. Port-forwards bind a real loopback listener that
+    /// answers every request with `200 OK`.
+    #[derive(Default)]
+    pub struct FakeKubeRuntime {
+        state: Mutex<FakeState>,
+    }
+
+    impl FakeKubeRuntime {
+        /// Add a running pod; `labels` are `key=value` selectors it matches.
+        pub fn add_pod(&self, name: &str, labels: &[&str]) {
+            self.state.lock().expect("fake state mutex").pods.push((
+                name.to_string(),
+                labels.iter().map(|label| label.to_string()).collect(),
+            ));
+        }
+
+        pub fn remove_pod(&self, name: &str) {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .pods
+                .retain(|(pod, _)| pod != name);
+        }
+
+        /// Exec scripts containing `needle` return `result`; newest rule wins.
+        pub fn respond(&self, needle: &str, result: Result<&str, &str>) {
+            self.state.lock().expect("fake state mutex").rules.insert(
+                0,
+                (
+                    needle.to_string(),
+                    result.map(str::to_string).map_err(str::to_string),
+                ),
+            );
+        }
+
+        /// Make every live port-forward exit as if the pod went away.
+        pub fn drop_forwards(&self) {
+            let mut state = self.state.lock().expect("fake state mutex");
+            for (exit, stop) in state.live_forwards.drain(..) {
+                stop.store(true, Ordering::SeqCst);
+                *exit.lock().expect("fake exit mutex") = Some(SshProcessExit {
+                    success: false,
+                    detail: "exit status: 1".to_string(),
+                });
+            }
+        }
+
+        /// `(pod, script)` for every exec so far.
+        pub fn execs(&self) -> Vec<(String, String)> {
+            self.state.lock().expect("fake state mutex").execs.clone()
+        }
+
+        /// `pod local_port:remote_port` for every port-forward so far.
+        pub fn forwards(&self) -> Vec<String> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .forwards
+                .clone()
+        }
+    }
+
+    impl KubeRuntime for FakeKubeRuntime {
+        fn resolve_pod(
+            &self,
+            _target: &KubeTarget,
+            pod: Option<&str>,
+            selector: Option<&str>,
+        ) -> Result<String> {
+            let state = self.state.lock().expect("fake state mutex");
+            state
+                .pods
+                .iter()
+                .find(|(name, labels)| match (pod, selector) {
+                    (Some(pod), _) => name == pod,
+                    (None, Some(selector)) => labels.iter().any(|label| label == selector),
+                    (None, None) => false,
+                })
+                .map(|(name, _)| name.clone())
+                .ok_or_else(|| anyhow!("pods not found"))
+        }
+
+        fn exec(&self, _target: &KubeTarget, pod: &str, script: &str) -> Result<String> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            if !state.pods.iter().any(|(name, _)| name == pod) {
+                return Err(anyhow!("pods \"{pod}\" not found"));
+            }
+            state.execs.push((pod.to_string(), script.to_string()));
+            let reply = state
+                .rules
+                .iter()
+                .find(|(needle, _)| script.contains(needle.as_str()))
+                .map(|(_, result)| result.clone());
+            match reply {
+                Some(Ok(output)) => Ok(output),
+                Some(Err(message)) => Err(anyhow!(message)),
+                None => Ok(String::new()),
+            }
+        }
+
+        fn spawn_port_forward(
+            &self,
+            _target: &KubeTarget,
+            pod: &str,
+            bind_host: &str,
+            local_port: u16,
+            remote_port: u16,
+        ) -> Result<Box<dyn SshProcess>> {
+            let listener = TcpListener::bind((bind_host, local_port))
+                .with_context(|| format!("fake port-forward failed to bind {local_port}"))?;
+            listener.set_nonblocking(true)?;
+            let stop = Arc::new(AtomicBool::new(false));
+            serve_ok(listener, Arc::clone(&stop));
+
+            let exit: ExitCell = Arc::new(Mutex::new(None));
+            let mut state = self.state.lock().expect("fake state mutex");
+            state
+                .forwards
+                .push(format!("{pod} {local_port}:{remote_port}"));
+            state
+                .live_forwards
+                .push((Arc::clone(&exit), Arc::clone(&stop)));
+            Ok(Box::new(FakeProcess {
+                exit,
+                stderr: String::new(),
+                stop: Some(stop),
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_args_include_context_and_namespace() {
+        let target = KubeTarget {
+            context: Some("dev-cluster".to_string()),
+            namespace: Some("team-a".to_string()),
+            container: Some("app".to_string()),
+        };
+        assert_eq!(
+            target_args(&target),
+            vec!["--context", "dev-cluster", "--namespace", "team-a"]
+        );
+        assert!(target_args(&KubeTarget::default()).is_empty());
+    }
+}
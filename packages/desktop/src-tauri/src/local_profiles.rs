@@ -0,0 +1,729 @@
+use crate::instance_tracker::{InstanceTracker, ManagerEvents, MAX_LOG_LINES_PER_INSTANCE};
+use crate::remote_ssh::{
+    default_true, ensure_host_id_unused, read_settings_root, settings_file_path,
+    sync_desktop_hosts, trimmed_non_empty, update_ssh_host_url, write_settings_root,
+    DesktopSshInstanceStatus, DesktopSshPhase, HostKind, DEFAULT_LOCAL_BIND_HOST, LOCAL_HOST_ID,
+};
+use crate::{opencode_detect, sidecar_env, sidecar_lock};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    net::TcpListener,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+use tauri::{AppHandle, State};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+
+const LOCAL_PROFILE_STATUS_EVENT: &str = "openchamber:local-profile-status";
+const LOCAL_PROFILES_KEY: &str = "desktopLocalProfiles";
+/// Set from the profile's own `dataDir`.
+const PROFILE_DATA_DIR_ENV: &str = "OPENCHAMBER_DATA_DIR";
+
+/// A named local OpenChamber server with its own port, data directory,
+/// opencode binary and environment, listed in the host switcher next to
+/// remote instances.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopLocalProfile {
+    pub id: String,
+    pub nickname: Option<String>,
+    /// Fixed port; a free one is picked on every start when unset.
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// Defaults to `profiles/<id>` next to `settings.json`.
+    #[serde(default)]
+    pub data_dir: Option<String>,
+    /// Overrides the global `opencodeBinary` for this profile.
+    #[serde(default)]
+    pub opencode_binary: Option<String>,
+    /// Point `XDG_CONFIG_HOME` and `XDG_DATA_HOME` into the data directory
+    /// so opencode config, agents and provider auth are kept apart from the
+    /// user's own.
+    #[serde(default = "default_true")]
+    pub isolate_opencode: bool,
+    /// `KEY=VALUE` pairs applied over the login environment.
+    #[serde(default)]
+    pub env: Vec<String>,
+    /// Extra server arguments appended after `--port`.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopLocalProfilesConfig {
+    pub profiles: Vec<DesktopLocalProfile>,
+}
+
+struct ProfileSession {
+    child: CommandChild,
+    pid: u32,
+    local_url: String,
+}
+
+struct DesktopLocalProfileManagerInner {
+    settings_path: PathBuf,
+    tracker: InstanceTracker,
+    sessions: Mutex<HashMap<String, ProfileSession>>,
+    starting: Mutex<HashSet<String>>,
+}
+
+impl Default for DesktopLocalProfileManagerInner {
+    fn default() -> Self {
+        Self::with_settings_path(settings_file_path())
+    }
+}
+
+#[derive(Default)]
+pub struct DesktopLocalProfileManagerState {
+    inner: Arc<DesktopLocalProfileManagerInner>,
+}
+
+fn build_display_label(profile: &DesktopLocalProfile) -> String {
+    trimmed_non_empty(profile.nickname.as_deref()).unwrap_or_else(|| profile.id.clone())
+}
+
+/// Ids end up in directory names, so keep them to a safe character set.
+fn is_valid_profile_id(id: &str) -> bool {
+    let mut chars = id.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphanumeric())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-'))
+}
+
+fn sanitize_profile(mut profile: DesktopLocalProfile) -> Result<DesktopLocalProfile> {
+    profile.id = profile.id.trim().to_string();
+    if profile.id == LOCAL_HOST_ID || !is_valid_profile_id(&profile.id) {
+        return Err(anyhow!(
+            "Invalid profile id '{}': use letters, digits, '_', '.' or '-'",
+            profile.id
+        ));
+    }
+    profile.nickname = trimmed_non_empty(profile.nickname.as_deref());
+    profile.port = profile.port.filter(|port| *port != 0);
+    profile.data_dir = trimmed_non_empty(profile.data_dir.as_deref());
+    profile.opencode_binary = trimmed_non_empty(profile.opencode_binary.as_deref());
+
+    profile.env = profile
+        .env
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(str::to_string)
+        .collect();
+    for entry in &profile.env {
+        let Some((name, _)) = entry.split_once('=') else {
+            return Err(anyhow!("Environment entry '{entry}' must be KEY=VALUE"));
+        };
+        sidecar_env::validate_env_name(name)?;
+        if name.eq_ignore_ascii_case(PROFILE_DATA_DIR_ENV) {
+            return Err(anyhow!("Set the profile data directory instead of {name}"));
+        }
+    }
+    sidecar_env::validate_args(&profile.args)?;
+    Ok(profile)
+}
+
+/// Ids of local profiles stored in a settings root.
+pub(crate) fn local_profile_ids(root: &Value) -> HashSet<String> {
+    root.get(LOCAL_PROFILES_KEY)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("id").and_then(Value::as_str))
+                .map(|id| id.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub(crate) fn read_profiles_from_path(path: &Path) -> DesktopLocalProfilesConfig {
+    let root = read_settings_root(path);
+    let Some(items) = root.get(LOCAL_PROFILES_KEY).and_then(Value::as_array) else {
+        return DesktopLocalProfilesConfig::default();
+    };
+    let mut seen = HashSet::new();
+    let profiles = items
+        .iter()
+        .filter_map(|item| serde_json::from_value::<DesktopLocalProfile>(item.clone()).ok())
+        .filter(|profile| {
+            let id = profile.id.trim();
+            !id.is_empty() && id != LOCAL_HOST_ID && seen.insert(id.to_string())
+        })
+        .collect();
+    DesktopLocalProfilesConfig { profiles }
+}
+
+fn write_profiles_to_path(
+    path: &Path,
+    config: DesktopLocalProfilesConfig,
+) -> Result<DesktopLocalProfilesConfig> {
+    let mut root = read_settings_root(path);
+    let previous_ids = local_profile_ids(&root);
+
+    let mut seen = HashSet::new();
+    let mut ports = HashSet::new();
+    let mut sanitized = Vec::new();
+    for profile in config.profiles {
+        let normalized = sanitize_profile(profile)?;
+        if !seen.insert(normalized.id.clone()) {
+            continue;
+        }
+        ensure_host_id_unused(&root, &normalized.id, HostKind::LocalProfile)?;
+        if let Some(port) = normalized.port {
+            if !ports.insert(port) {
+                return Err(anyhow!("Port {port} is used by more than one profile"));
+            }
+        }
+        sanitized.push(normalized);
+    }
+
+    let entries: Vec<(String, String)> = sanitized
+        .iter()
+        .map(|profile| (profile.id.clone(), build_display_label(profile)))
+        .collect();
+    sync_desktop_hosts(&mut root, &previous_ids, &entries);
+    root[LOCAL_PROFILES_KEY] = serde_json::to_value(&sanitized)?;
+    write_settings_root(path, &root)?;
+    Ok(DesktopLocalProfilesConfig {
+        profiles: sanitized,
+    })
+}
+
+fn profile_data_dir(profile: &DesktopLocalProfile, settings_path: &Path) -> PathBuf {
+    match profile.data_dir.as_deref() {
+        Some(dir) => PathBuf::from(dir),
+        None => settings_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+            .join("profiles")
+            .join(&profile.id),
+    }
+}
+
+/// Environment layered over the login environment for a profile sidecar.
+/// The profile's own entries come last so they can override isolation.
+fn profile_env(profile: &DesktopLocalProfile, data_dir: &Path) -> Vec<(String, String)> {
+    let mut env = vec![(
+        PROFILE_DATA_DIR_ENV.to_string(),
+        data_dir.to_string_lossy().to_string(),
+    )];
+    if profile.isolate_opencode {
+        for (name, dir) in [("XDG_CONFIG_HOME", "config"), ("XDG_DATA_HOME", "data")] {
+            let path = data_dir.join("opencode").join(dir);
+            env.push((name.to_string(), path.to_string_lossy().to_string()));
+        }
+    }
+    env.extend(profile.env.iter().filter_map(|entry| {
+        entry
+            .split_once('=')
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+    }));
+    env
+}
+
+impl DesktopLocalProfileManagerInner {
+    fn with_settings_path(settings_path: PathBuf) -> Self {
+        Self {
+            settings_path,
+            tracker: InstanceTracker::new(LOCAL_PROFILE_STATUS_EVENT),
+            sessions: Mutex::default(),
+            starting: Mutex::default(),
+        }
+    }
+
+    fn statuses_with_defaults(&self) -> Vec<DesktopSshInstanceStatus> {
+        let config = read_profiles_from_path(&self.settings_path);
+        self.tracker
+            .snapshots(config.profiles.iter().map(|profile| profile.id.as_str()))
+    }
+
+    fn running_url(&self, id: &str) -> Option<String> {
+        self.sessions
+            .lock()
+            .expect("profile sessions mutex")
+            .get(id)
+            .map(|session| session.local_url.clone())
+    }
+
+    /// Start the profile's sidecar unless it is already running, and wait
+    /// until it is healthy. Returns its URL.
+    async fn start(self: &Arc<Self>, app: &AppHandle, id: &str) -> Result<String> {
+        if let Some(url) = self.running_url(id) {
+            return Ok(url);
+        }
+        let config = read_profiles_from_path(&self.settings_path);
+        let profile = config
+            .profiles
+            .into_iter()
+            .find(|profile| profile.id == id)
+            .ok_or_else(|| anyhow!("Local profile not found"))?;
+        if !self
+            .starting
+            .lock()
+            .expect("profile starting mutex")
+            .insert(id.to_string())
+        {
+            return Err(anyhow!("Local profile {id} is already starting"));
+        }
+
+        let result = self.launch(app, &profile).await;
+        self.starting
+            .lock()
+            .expect("profile starting mutex")
+            .remove(id);
+        match result {
+            Ok(url) => Ok(url),
+            Err(err) => {
+                let inner = Arc::clone(self);
+                let (stop_app, stop_id) = (app.clone(), id.to_string());
+                let _ =
+                    tauri::async_runtime::spawn_blocking(move || inner.stop(&stop_app, &stop_id))
+                        .await;
+                self.tracker
+                    .set_phase(app, id, DesktopSshPhase::Error, err.to_string());
+                Err(err)
+            }
+        }
+    }
+
+    async fn launch(
+        self: &Arc<Self>,
+        app: &AppHandle,
+        profile: &DesktopLocalProfile,
+    ) -> Result<String> {
+        let id = profile.id.as_str();
+        self.tracker.set_phase(
+            app,
+            id,
+            DesktopSshPhase::ServerStarting,
+            "Starting local server",
+        );
+
+        let data_dir = profile_data_dir(profile, &self.settings_path);
+        fs::create_dir_all(&data_dir)
+            .map_err(|err| anyhow!("Failed to create {}: {err}", data_dir.display()))?;
+
+        let mut launch = crate::resolve_sidecar_launch(app).await?;
+        // Profiles are only reachable from this machine.
+        launch.bind_host = DEFAULT_LOCAL_BIND_HOST;
+        if let Some(binary) = profile
+            .opencode_binary
+            .as_deref()
+            .and_then(opencode_detect::resolve_binary_path)
+        {
+            launch.opencode_binary = Some(binary.to_string_lossy().to_string());
+        }
+        launch.user_env = profile_env(profile, &data_dir);
+        launch.extra_args = profile.args.clone();
+
+        let port = match profile.port {
+            Some(port) => {
+                TcpListener::bind((DEFAULT_LOCAL_BIND_HOST, port))
+                    .map_err(|err| anyhow!("Port {port} is not available: {err}"))?;
+                port
+            }
+            None => crate::pick_unused_port()?,
+        };
+
+        let (rx, child) = crate::sidecar_command(app, &launch, port)?.spawn()?;
+        let pid = child.pid();
+        crate::write_sidecar_lock(app, &launch, pid, port, Some(id));
+        let local_url = crate::build_local_url(port);
+        self.tracker.append_log(
+            id,
+            format!(
+                "Spawned server pid {pid} on port {port} with data dir {}",
+                data_dir.display()
+            ),
+        );
+        self.sessions
+            .lock()
+            .expect("profile sessions mutex")
+            .insert(
+                id.to_string(),
+                ProfileSession {
+                    child,
+                    pid,
+                    local_url: local_url.clone(),
+                },
+            );
+        self.forward_output(Arc::new(app.clone()), id.to_string(), pid, rx);
+
+        if !crate::wait_for_health_with(
+            &local_url,
+            crate::LOCAL_SIDECAR_HEALTH_TIMEOUT,
+            crate::LOCAL_SIDECAR_HEALTH_POLL_INITIAL_INTERVAL,
+            crate::LOCAL_SIDECAR_HEALTH_POLL_MAX_INTERVAL,
+        )
+        .await
+        {
+            return Err(anyhow!("Local server for {id} did not become healthy"));
+        }
+
+        let _ = update_ssh_host_url(
+            &self.settings_path,
+            id,
+            &build_display_label(profile),
+            &local_url,
+        );
+        self.tracker.publish(
+            app,
+            DesktopSshInstanceStatus {
+                phase: DesktopSshPhase::Ready,
+                detail: Some("Local profile is ready".to_string()),
+                local_url: Some(local_url.clone()),
+                local_port: Some(port),
+                started_by_us: true,
+                ..DesktopSshInstanceStatus::idle(id)
+            },
+        );
+        Ok(local_url)
+    }
+
+    /// Copy the sidecar's output into the profile log and report exits
+    /// that were not requested through `stop`.
+    fn forward_output(
+        self: &Arc<Self>,
+        app: Arc<dyn ManagerEvents>,
+        id: String,
+        pid: u32,
+        mut rx: tauri::async_runtime::Receiver<CommandEvent>,
+    ) {
+        let inner = Arc::clone(self);
+        tauri::async_runtime::spawn(async move {
+            while let Some(event) = rx.recv().await {
+                match event {
+                    CommandEvent::Stdout(bytes) => {
+                        inner
+                            .tracker
+                            .append_log(&id, String::from_utf8_lossy(&bytes).trim_end());
+                    }
+                    CommandEvent::Stderr(bytes) => {
+                        inner.tracker.append_log_with_level(
+                            &id,
+                            "WARN",
+                            String::from_utf8_lossy(&bytes).trim_end(),
+                        );
+                    }
+                    CommandEvent::Terminated(payload) => {
+                        sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), pid);
+                        let mut sessions = inner.sessions.lock().expect("profile sessions mutex");
+                        let unexpected =
+                            sessions.get(&id).is_some_and(|session| session.pid == pid);
+                        if unexpected {
+                            sessions.remove(&id);
+                        }
+                        drop(sessions);
+                        if unexpected {
+                            inner.tracker.set_phase(
+                                app.as_ref(),
+                                &id,
+                                DesktopSshPhase::Error,
+                                format!(
+                                    "Local server exited code={:?} signal={:?}",
+                                    payload.code, payload.signal
+                                ),
+                            );
+                        }
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        });
+    }
+
+    /// Stop the profile's sidecar the same way as the main one: ask it to
+    /// shut down, wait, then escalate. Blocks until it is gone.
+    fn stop(&self, app: &dyn ManagerEvents, id: &str) {
+        self.stop_all(app, &[id.to_string()]);
+    }
+
+    /// Ask every running profile in `ids` to shut down first, then wait for
+    /// them against one shared deadline and force the stragglers.
+    fn stop_all(&self, app: &dyn ManagerEvents, ids: &[String]) {
+        let stopping: Vec<(String, ProfileSession)> = {
+            let mut sessions = self.sessions.lock().expect("profile sessions mutex");
+            ids.iter()
+                .filter_map(|id| sessions.remove(id).map(|session| (id.clone(), session)))
+                .collect()
+        };
+        if stopping.is_empty() {
+            return;
+        }
+        let timeout = crate::sidecar_shutdown_timeout();
+        let requested: Vec<bool> = stopping
+            .iter()
+            .map(|(id, session)| {
+                self.tracker
+                    .append_log(id, format!("Stopping server pid {}", session.pid));
+                crate::request_sidecar_shutdown(&session.local_url, timeout)
+            })
+            .collect();
+        let deadline = Instant::now() + timeout;
+        for ((id, session), requested) in stopping.into_iter().zip(requested) {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if !(requested && sidecar_lock::wait_for_exit(session.pid, remaining)) {
+                sidecar_lock::terminate_process(session.pid);
+                let _ = session.child.kill();
+            }
+            sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), session.pid);
+            self.tracker
+                .publish(app, DesktopSshInstanceStatus::idle(&id));
+        }
+    }
+}
+
+impl DesktopLocalProfileManagerState {
+    pub fn statuses(&self) -> Vec<DesktopSshInstanceStatus> {
+        self.inner.statuses_with_defaults()
+    }
+
+    pub fn has_profile(&self, id: &str) -> bool {
+        read_profiles_from_path(&self.inner.settings_path)
+            .profiles
+            .iter()
+            .any(|profile| profile.id == id)
+    }
+
+    /// Start `id` if needed and return its URL once healthy.
+    pub async fn start(&self, app: &AppHandle, id: &str) -> Result<String> {
+        let inner = Arc::clone(&self.inner);
+        inner.start(app, id).await
+    }
+
+    pub fn shutdown_all(&self, app: &AppHandle) {
+        let ids: Vec<String> = self
+            .inner
+            .sessions
+            .lock()
+            .expect("profile sessions mutex")
+            .keys()
+            .cloned()
+            .collect();
+        self.inner.stop_all(app, &ids);
+    }
+}
+
+fn required_id(id: &str) -> Result<String, String> {
+    let id = id.trim();
+    if id.is_empty() || id == LOCAL_HOST_ID {
+        return Err("Local profile id is required".to_string());
+    }
+    Ok(id.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_local_profiles_get() -> Result<DesktopLocalProfilesConfig, String> {
+    Ok(read_profiles_from_path(&settings_file_path()))
+}
+
+#[tauri::command]
+pub fn desktop_local_profiles_set(config: DesktopLocalProfilesConfig) -> Result<(), String> {
+    write_profiles_to_path(&settings_file_path(), config)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Start a profile on demand and return its URL once healthy.
+#[tauri::command]
+pub async fn desktop_local_profile_start(
+    app: AppHandle,
+    state: State<'_, DesktopLocalProfileManagerState>,
+    id: String,
+) -> Result<String, String> {
+    let id = required_id(&id)?;
+    let inner = Arc::clone(&state.inner);
+    inner.start(&app, &id).await.map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub async fn desktop_local_profile_stop(
+    app: AppHandle,
+    state: State<'_, DesktopLocalProfileManagerState>,
+    id: String,
+) -> Result<(), String> {
+    let id = required_id(&id)?;
+    let inner = Arc::clone(&state.inner);
+    tauri::async_runtime::spawn_blocking(move || inner.stop(&app, &id))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_local_profile_status(
+    state: State<'_, DesktopLocalProfileManagerState>,
+    id: Option<String>,
+) -> Result<Vec<DesktopSshInstanceStatus>, String> {
+    let id = id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    Ok(match id {
+        Some(id) => vec![state.inner.tracker.snapshot(&id)],
+        None => state.inner.statuses_with_defaults(),
+    })
+}
+
+#[tauri::command]
+pub fn desktop_local_profile_logs(
+    state: State<'_, DesktopLocalProfileManagerState>,
+    id: String,
+    limit: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let id = required_id(&id)?;
+    let cap = limit.unwrap_or(200).min(MAX_LOG_LINES_PER_INSTANCE);
+    Ok(state.inner.tracker.logs(&id, cap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instance_tracker::fake::{temp_settings_path, RecordingEvents};
+    use serde_json::json;
+
+    fn profile(id: &str) -> DesktopLocalProfile {
+        serde_json::from_value(json!({ "id": id })).expect("profile")
+    }
+
+    #[test]
+    fn write_profiles_syncs_hosts_and_rejects_conflicts() {
+        let path = temp_settings_path("profiles", "write");
+        write_settings_root(
+            &path,
+            &json!({ "desktopDockerInstances": [{ "id": "dev", "container": { "name": "dev" } }] }),
+        )
+        .expect("seed settings");
+
+        let saved = write_profiles_to_path(
+            &path,
+            DesktopLocalProfilesConfig {
+                profiles: vec![DesktopLocalProfile {
+                    nickname: Some(" Client A ".to_string()),
+                    env: vec!["API_BASE=https://example.test".to_string(), " ".to_string()],
+                    ..profile(" client-a ")
+                }],
+            },
+        )
+        .expect("write profiles");
+        assert_eq!(saved.profiles[0].id, "client-a");
+        assert!(saved.profiles[0].isolate_opencode);
+        assert_eq!(saved.profiles[0].env.len(), 1);
+
+        let root = read_settings_root(&path);
+        let hosts = root["desktopHosts"].as_array().expect("hosts");
+        assert_eq!(hosts[0]["id"], "client-a");
+        assert_eq!(hosts[0]["label"], "Client A");
+
+        let conflict = write_profiles_to_path(
+            &path,
+            DesktopLocalProfilesConfig {
+                profiles: vec![profile("dev")],
+            },
+        );
+        assert!(conflict.is_err());
+
+        for bad in [
+            DesktopLocalProfile {
+                env: vec!["PATH=/tmp".to_string()],
+                ..profile("a")
+            },
+            DesktopLocalProfile {
+                env: vec!["OPENCHAMBER_DATA_DIR=/tmp".to_string()],
+                ..profile("a")
+            },
+            DesktopLocalProfile {
+                args: vec!["--port=1".to_string()],
+                ..profile("a")
+            },
+            profile("../escape"),
+            profile(LOCAL_HOST_ID),
+        ] {
+            assert!(sanitize_profile(bad).is_err());
+        }
+
+        write_profiles_to_path(&path, DesktopLocalProfilesConfig::default()).expect("clear");
+        let root = read_settings_root(&path);
+        assert!(root["desktopHosts"].as_array().expect("hosts").is_empty());
+        let _ = fs::remove_dir_all(path.parent().expect("dir"));
+    }
+
+    #[test]
+    fn statuses_cover_configured_profiles_and_emit_profile_events() {
+        let path = temp_settings_path("profiles", "statuses");
+        write_profiles_to_path(
+            &path,
+            DesktopLocalProfilesConfig {
+                profiles: vec![profile("b"), profile("a")],
+            },
+        )
+        .expect("write profiles");
+        let manager = DesktopLocalProfileManagerInner::with_settings_path(path.clone());
+        let events = RecordingEvents::default();
+
+        manager.tracker.set_phase(
+            &events,
+            "b",
+            DesktopSshPhase::Error,
+            "Port 4100 is not available",
+        );
+        // Profiles that are not running have nothing to stop or report.
+        manager.stop_all(&events, &["a".to_string()]);
+
+        let statuses = manager.statuses_with_defaults();
+        assert_eq!(
+            statuses
+                .iter()
+                .map(|status| (status.id.as_str(), status.phase.clone()))
+                .collect::<Vec<_>>(),
+            vec![("a", DesktopSshPhase::Idle), ("b", DesktopSshPhase::Error)]
+        );
+        let emitted = events.statuses.lock().expect("events mutex").clone();
+        assert_eq!(emitted.len(), 1);
+        assert_eq!(emitted[0].0, LOCAL_PROFILE_STATUS_EVENT);
+        assert!(manager.tracker.logs("b", 0)[0].contains("Port 4100 is not available"));
+        let _ = fs::remove_dir_all(path.parent().expect("dir"));
+    }
+
+    #[test]
+    fn profile_env_isolates_data_and_opencode_config() {
+        let settings_path = PathBuf::from("/data/openchamber/settings.json");
+        let client = DesktopLocalProfile {
+            env: vec!["XDG_DATA_HOME=/custom".to_string(), "FLAG=a=b".to_string()],
+            ..profile("client")
+        };
+        let data_dir = profile_data_dir(&client, &settings_path);
+        assert_eq!(data_dir, PathBuf::from("/data/openchamber/profiles/client"));
+
+        let env = profile_env(&client, &data_dir);
+        let names: Vec<&str> = env.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "OPENCHAMBER_DATA_DIR",
+                "XDG_CONFIG_HOME",
+                "XDG_DATA_HOME",
+                "XDG_DATA_HOME",
+                "FLAG"
+            ]
+        );
+        assert_eq!(env.last().expect("flag").1, "a=b");
+
+        let shared = DesktopLocalProfile {
+            isolate_opencode: false,
+            data_dir: Some("/elsewhere".to_string()),
+            ..profile("shared")
+        };
+        let data_dir = profile_data_dir(&shared, &settings_path);
+        assert_eq!(
+            profile_env(&shared, &data_dir),
+            vec![("OPENCHAMBER_DATA_DIR".to_string(), "/elsewhere".to_string())]
+        );
+    }
+}
@@ -0,0 +1,467 @@
+use crate::remote_ssh::{read_settings_root, settings_file_path};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus, Stdio},
+    sync::mpsc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// How long the login shell may take to print its environment.
+pub const LOGIN_ENV_TIMEOUT: Duration = Duration::from_secs(5);
+/// Cached environments older than this are resolved again even if no rc
+/// file changed, to pick up edits in files the rc files source.
+const CACHE_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+const CACHE_FILE_NAME: &str = "login-env.json";
+const ENV_BEGIN_MARKER: &str = "__OPENCHAMBER_ENV_BEGIN__";
+const ENV_END_MARKER: &str = "__OPENCHAMBER_ENV_END__";
+
+/// Variables forwarded to the sidecar when settings do not define
+/// `sidecarEnvAllowlist`. A trailing `*` matches by prefix. `PATH` is
+/// always merged separately.
+pub const DEFAULT_ENV_ALLOWLIST: &[&str] = &[
+    "LANG",
+    "LC_*",
+    "NVM_DIR",
+    "NVM_BIN",
+    "ASDF_DIR",
+    "ASDF_DATA_DIR",
+    "MISE_*",
+    "VOLTA_HOME",
+    "BUN_INSTALL",
+    "PNPM_HOME",
+    "GOPATH",
+    "GOROOT",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "JAVA_HOME",
+    "PYENV_ROOT",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "no_proxy",
+    "SSL_CERT_FILE",
+    "NODE_EXTRA_CA_CERTS",
+    "REQUESTS_CA_BUNDLE",
+    "SSH_AUTH_SOCK",
+];
+
+/// Rc files whose modification invalidates the cached environment.
+const RC_FILES: &[&str] = &[
+    ".zshenv",
+    ".zprofile",
+    ".zshrc",
+    ".zlogin",
+    ".bash_profile",
+    ".bash_login",
+    ".bashrc",
+    ".profile",
+    ".config/fish/config.fish",
+    ".tool-versions",
+    ".config/mise/config.toml",
+];
+
+/// Allowlisted login-shell variables (plus `PATH`), cached on disk.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginEnvCache {
+    pub shell: String,
+    pub fingerprint: String,
+    /// Unix seconds.
+    pub resolved_at: u64,
+    pub env: BTreeMap<String, String>,
+}
+
+/// Outcome of resolving the login environment. `env` is empty when the
+/// shell could not be run; `error` then says why.
+#[derive(Debug, Clone, Default)]
+pub struct LoginEnv {
+    pub shell: Option<String>,
+    pub resolved_at: Option<u64>,
+    pub from_cache: bool,
+    pub error: Option<String>,
+    pub env: BTreeMap<String, String>,
+}
+
+impl LoginEnv {
+    /// `PATH` entries reported by the login shell, in order.
+    pub fn path_segments(&self) -> Vec<String> {
+        self.env
+            .get("PATH")
+            .map(|path| {
+                path.split(':')
+                    .filter(|segment| !segment.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Variables to set on the sidecar, excluding `PATH`.
+    pub fn forwarded(&self) -> BTreeMap<String, String> {
+        self.env
+            .iter()
+            .filter(|(name, _)| name.as_str() != "PATH")
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginEnvInfo {
+    pub shell: Option<String>,
+    pub resolved_at: Option<u64>,
+    pub from_cache: bool,
+    pub error: Option<String>,
+    pub path: Vec<String>,
+    pub allowlist: Vec<String>,
+    pub forwarded: BTreeMap<String, String>,
+}
+
+fn data_dir() -> PathBuf {
+    settings_file_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// The user's login shell, or `None` where there is no such concept.
+pub fn login_shell() -> Option<String> {
+    if cfg!(windows) {
+        return None;
+    }
+    std::env::var("SHELL")
+        .ok()
+        .map(|shell| shell.trim().to_string())
+        .filter(|shell| !shell.is_empty())
+        .or_else(|| {
+            let fallback = if cfg!(target_os = "macos") {
+                "/bin/zsh"
+            } else {
+                "/bin/sh"
+            };
+            Path::new(fallback).exists().then(|| fallback.to_string())
+        })
+}
+
+/// `sidecarEnvAllowlist` from settings, or the default list.
+pub fn read_allowlist() -> Vec<String> {
+    allowlist_from_settings(&read_settings_root(&settings_file_path()))
+}
+
+fn allowlist_from_settings(settings: &serde_json::Value) -> Vec<String> {
+    match settings
+        .get("sidecarEnvAllowlist")
+        .and_then(|value| value.as_array())
+    {
+        Some(entries) => entries
+            .iter()
+            .filter_map(|entry| entry.as_str())
+            .map(|entry| entry.trim().to_string())
+            .filter(|entry| !entry.is_empty())
+            .collect(),
+        None => DEFAULT_ENV_ALLOWLIST
+            .iter()
+            .map(|entry| entry.to_string())
+            .collect(),
+    }
+}
+
+/// True when `name` is listed exactly or matches a `PREFIX*` entry.
+pub fn env_matches_allowlist(name: &str, allowlist: &[String]) -> bool {
+    allowlist.iter().any(|entry| match entry.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => name == entry,
+    })
+}
+
+/// Shell script printing the environment between the markers. `env -0`
+/// keeps multi-line values intact; BusyBox and older BSDs lack `-0`, so
+/// plain `env` is the fallback there.
+fn env_capture_script() -> String {
+    format!("printf '%s' '{ENV_BEGIN_MARKER}'; env -0 2>/dev/null || env; printf '%s' '{ENV_END_MARKER}'")
+}
+
+/// Extract the variables printed between the markers by
+/// [`env_capture_script`], ignoring anything the rc files print around
+/// them. `None` when the markers or every variable are missing.
+pub fn parse_env_output(raw: &[u8]) -> Option<BTreeMap<String, String>> {
+    let raw = String::from_utf8_lossy(raw);
+    let start = raw.find(ENV_BEGIN_MARKER)? + ENV_BEGIN_MARKER.len();
+    let end = start + raw[start..].find(ENV_END_MARKER)?;
+    let payload = &raw[start..end];
+    let separator = if payload.contains('\0') { '\0' } else { '\n' };
+
+    let mut env = BTreeMap::new();
+    for entry in payload.split(separator) {
+        let Some((name, value)) = entry.split_once('=') else {
+            continue;
+        };
+        let name = name.trim_start_matches('\n');
+        let valid_name =
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if valid_name {
+            env.insert(name.to_string(), value.to_string());
+        }
+    }
+    (!env.is_empty()).then_some(env)
+}
+
+/// Run `command` with stdin and stderr closed, killing it after `timeout`.
+/// Returns its exit status and everything it printed to stdout.
+pub(crate) fn run_with_timeout(
+    mut command: Command,
+    timeout: Duration,
+) -> Result<(ExitStatus, Vec<u8>)> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {program}"))?;
+
+    let mut stdout = child.stdout.take().context("Missing stdout")?;
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = stdout.read_to_end(&mut buf);
+        let _ = tx.send(buf);
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            bail!("{program} did not finish within {}s", timeout.as_secs());
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    // Background jobs it started can keep stdout open.
+    let output = rx
+        .recv_timeout(Duration::from_secs(1))
+        .map_err(|_| anyhow!("{program} left its output open"))?;
+    Ok((status, output))
+}
+
+/// Run `shell -lic` and capture its environment, killing it after
+/// `timeout`.
+fn run_login_shell(shell: &str, timeout: Duration) -> Result<BTreeMap<String, String>> {
+    let mut command = Command::new(shell);
+    command.args(["-l", "-i", "-c", &env_capture_script()]);
+    let (_, output) = run_with_timeout(command, timeout)?;
+    parse_env_output(&output).ok_or_else(|| anyhow!("{shell} did not print an environment"))
+}
+
+/// Changes whenever the shell, the allowlist or any rc file changes.
+fn fingerprint(shell: &str, home: Option<&Path>, allowlist: &[String]) -> String {
+    let mut parts = vec![shell.to_string(), allowlist.join(",")];
+    if let Some(home) = home {
+        for file in RC_FILES {
+            let modified = fs::metadata(home.join(file))
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_millis().to_string())
+                .unwrap_or_default();
+            parts.push(format!("{file}={modified}"));
+        }
+    }
+    parts.join("|")
+}
+
+fn read_cache_in(dir: &Path) -> Option<LoginEnvCache> {
+    let raw = fs::read_to_string(dir.join(CACHE_FILE_NAME)).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_cache_in(dir: &Path, cache: &LoginEnvCache) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(CACHE_FILE_NAME);
+    fs::write(&path, serde_json::to_string_pretty(cache)?)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perm = fs::metadata(&path)?.permissions();
+        perm.set_mode(0o600);
+        fs::set_permissions(&path, perm)?;
+    }
+    Ok(())
+}
+
+/// Resolve the login environment, reusing the cache unless it is stale or
+/// `refresh` is set. Only `PATH` and allowlisted variables are kept.
+pub fn resolve_login_env(refresh: bool) -> LoginEnv {
+    let Some(shell) = login_shell() else {
+        return LoginEnv {
+            error: Some("No login shell on this platform".to_string()),
+            ..Default::default()
+        };
+    };
+    let allowlist = read_allowlist();
+    let home = std::env::var("HOME").ok().map(PathBuf::from);
+    let fingerprint = fingerprint(&shell, home.as_deref(), &allowlist);
+    let dir = data_dir();
+
+    if !refresh {
+        if let Some(cache) = read_cache_in(&dir) {
+            let fresh = now_unix_secs().saturating_sub(cache.resolved_at) < CACHE_MAX_AGE_SECS;
+            if cache.fingerprint == fingerprint && fresh {
+                return LoginEnv {
+                    shell: Some(cache.shell),
+                    resolved_at: Some(cache.resolved_at),
+                    from_cache: true,
+                    error: None,
+                    env: cache.env,
+                };
+            }
+        }
+    }
+
+    match run_login_shell(&shell, LOGIN_ENV_TIMEOUT) {
+        Ok(env) => {
+            let env: BTreeMap<String, String> = env
+                .into_iter()
+                .filter(|(name, _)| name == "PATH" || env_matches_allowlist(name, &allowlist))
+                .collect();
+            let cache = LoginEnvCache {
+                shell: shell.clone(),
+                fingerprint,
+                resolved_at: now_unix_secs(),
+                env,
+            };
+            if let Err(err) = write_cache_in(&dir, &cache) {
+                log::warn!("[desktop] failed to cache login environment: {err}");
+            }
+            log::info!(
+                "[desktop] resolved login environment from {shell} ({} variables)",
+                cache.env.len()
+            );
+            LoginEnv {
+                shell: Some(shell),
+                resolved_at: Some(cache.resolved_at),
+                from_cache: false,
+                error: None,
+                env: cache.env,
+            }
+        }
+        Err(err) => {
+            log::warn!("[desktop] failed to resolve login environment: {err}");
+            LoginEnv {
+                shell: Some(shell),
+                error: Some(err.to_string()),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Inspect the login environment the sidecar is launched with. `refresh`
+/// bypasses the cache; the new values apply on the next sidecar start.
+#[tauri::command]
+pub async fn desktop_login_env_get(refresh: Option<bool>) -> Result<LoginEnvInfo, String> {
+    let refresh = refresh.unwrap_or(false);
+    let login_env = tauri::async_runtime::spawn_blocking(move || resolve_login_env(refresh))
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(LoginEnvInfo {
+        path: login_env.path_segments(),
+        forwarded: login_env.forwarded(),
+        allowlist: read_allowlist(),
+        shell: login_env.shell,
+        resolved_at: login_env.resolved_at,
+        from_cache: login_env.from_cache,
+        error: login_env.error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_env_output_skips_rc_noise_and_keeps_multiline_values() {
+        let raw = format!(
+            "Welcome back!\n{ENV_BEGIN_MARKER}PATH=/usr/bin:/bin\0NOTE=line one\nline two\0=bad\0{ENV_END_MARKER}\nbye"
+        );
+        let env = parse_env_output(raw.as_bytes()).expect("env");
+        assert_eq!(env.get("PATH").map(String::as_str), Some("/usr/bin:/bin"));
+        assert_eq!(
+            env.get("NOTE").map(String::as_str),
+            Some("line one\nline two")
+        );
+        assert_eq!(env.len(), 2);
+
+        let plain = format!("{ENV_BEGIN_MARKER}\nHOME=/home/me\nLANG=C\n{ENV_END_MARKER}");
+        let env = parse_env_output(plain.as_bytes()).expect("env");
+        assert_eq!(env.get("LANG").map(String::as_str), Some("C"));
+
+        assert_eq!(parse_env_output(b"zsh: command not found: env"), None);
+        let empty = format!("{ENV_BEGIN_MARKER}{ENV_END_MARKER}");
+        assert_eq!(parse_env_output(empty.as_bytes()), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn env_capture_script_falls_back_to_plain_env() {
+        let dir = std::env::temp_dir().join(format!("openchamber-env-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        // An `env` without `-0`, as on BusyBox.
+        let fake_env = dir.join("env");
+        std::fs::write(
+            &fake_env,
+            "#!/bin/sh\n[ \"$1\" = -0 ] && { echo 'env: invalid option' >&2; exit 1; }\nprintf 'PATH=/opt/bin\\nLANG=C\\n'\n",
+        )
+        .expect("fake env");
+        Command::new("chmod")
+            .args(["+x", &fake_env.to_string_lossy()])
+            .status()
+            .expect("chmod");
+
+        let output = Command::new("/bin/sh")
+            .args(["-c", &env_capture_script()])
+            .env("PATH", &dir)
+            .output()
+            .expect("run script");
+        let env = parse_env_output(&output.stdout).expect("env");
+        assert_eq!(env.get("PATH").map(String::as_str), Some("/opt/bin"));
+        assert_eq!(env.get("LANG").map(String::as_str), Some("C"));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn allowlist_supports_prefixes_and_settings_override() {
+        let defaults = allowlist_from_settings(&serde_json::json!({}));
+        assert!(env_matches_allowlist("LC_ALL", &defaults));
+        assert!(env_matches_allowlist("NVM_DIR", &defaults));
+        assert!(!env_matches_allowlist("AWS_SECRET_ACCESS_KEY", &defaults));
+
+        let custom = allowlist_from_settings(&serde_json::json!({
+            "sidecarEnvAllowlist": ["ANTHROPIC_*", " JAVA_HOME ", 3]
+        }));
+        assert_eq!(custom, vec!["ANTHROPIC_*", "JAVA_HOME"]);
+        assert!(env_matches_allowlist("ANTHROPIC_BASE_URL", &custom));
+        assert!(!env_matches_allowlist("LANG", &custom));
+    }
+}
@@ -1,11 +1,38 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
+mod deep_link;
+mod desktop_logs;
+mod docker_transport;
+mod headless;
+mod instance_tracker;
+mod kube_transport;
+mod local_profiles;
+mod login_env;
+mod opencode_detect;
+mod remote_docker;
+mod remote_kube;
 mod remote_ssh;
+mod sidecar_env;
+mod sidecar_lock;
+mod sidecar_protocol;
+mod single_instance;
+mod ssh_transport;
 
 use anyhow::{anyhow, Result};
 use base64::{engine::general_purpose, Engine as _};
+use desktop_logs::DesktopLogsState;
+use local_profiles::DesktopLocalProfileManagerState;
+use remote_docker::DesktopDockerManagerState;
+use remote_kube::DesktopKubeManagerState;
 use remote_ssh::DesktopSshManagerState;
 use serde::{Deserialize, Serialize};
+use sidecar_lock::SidecarLock;
+use sidecar_protocol::{
+    encode_desktop_message, parse_sidecar_line, DesktopMessage, SidecarMessage,
+    SidecarNotifyPayload, SidecarProgressState, SidecarQuitRisk, SidecarRequest,
+    SIDECAR_AUTH_HEADER, SIDECAR_AUTH_TOKEN_ENV, SIDECAR_PROTOCOL_VERSION,
+};
 use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
 use std::{
@@ -17,7 +44,7 @@ use std::{
     net::{TcpListener, UdpSocket},
     process::Command,
     sync::{
-        atomic::{AtomicU32, AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
         Mutex,
     },
     time::Duration,
@@ -177,11 +204,32 @@ static QUIT_RISK_ENABLED_SCHEDULED_TASKS_COUNT: AtomicU32 = AtomicU32::new(0);
 static QUIT_RISK_RUNNING_SCHEDULED_TASKS_COUNT: AtomicU32 = AtomicU32::new(0);
 static QUIT_RISK_HAS_ACTIVE_TUNNEL: std::sync::atomic::AtomicBool =
     std::sync::atomic::AtomicBool::new(false);
-static QUIT_RISK_POLLER_STARTED: std::sync::atomic::AtomicBool =
+static QUIT_RISK_BUSY_SESSIONS_COUNT: AtomicU32 = AtomicU32::new(0);
+/// Set once the post-confirmation shutdown of background services starts /
+/// completes, so the exit request can wait for it off the main thread.
+static QUIT_SHUTDOWN_STARTED: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+static QUIT_SHUTDOWN_FINISHED: std::sync::atomic::AtomicBool =
     std::sync::atomic::AtomicBool::new(false);
+static DESKTOP_AUTH_TOKEN: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+/// `--headless`: no windows; notifications go to the log and status is
+/// served by the control API.
+static HEADLESS: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
 
-#[cfg(target_os = "macos")]
-const QUIT_RISK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+fn is_headless() -> bool {
+    HEADLESS.load(Ordering::Relaxed)
+}
+
+/// Per-launch token the sidecar requires from the desktop and its windows.
+/// Kept across sidecar restarts so already-open windows stay authorized.
+fn desktop_auth_token() -> &'static str {
+    DESKTOP_AUTH_TOKEN.get_or_init(|| {
+        sidecar_protocol::generate_sidecar_auth_token().unwrap_or_else(|err| {
+            log::error!("[desktop] {err}; sidecar auth disabled for this launch");
+            String::new()
+        })
+    })
+}
 
 #[cfg(target_os = "macos")]
 fn should_require_quit_confirmation() -> bool {
@@ -190,6 +238,7 @@ fn should_require_quit_confirmation() -> bool {
     QUIT_RISK_HAS_ACTIVE_TUNNEL.load(Ordering::Relaxed)
         || QUIT_RISK_HAS_RUNNING_SCHEDULED_TASKS.load(Ordering::Relaxed)
         || QUIT_RISK_HAS_ENABLED_SCHEDULED_TASKS.load(Ordering::Relaxed)
+        || QUIT_RISK_BUSY_SESSIONS_COUNT.load(Ordering::Relaxed) > 0
 }
 
 #[cfg(target_os = "macos")]
@@ -199,8 +248,16 @@ fn quit_confirmation_message() -> String {
     let has_active_tunnel = QUIT_RISK_HAS_ACTIVE_TUNNEL.load(Ordering::Relaxed);
     let running_tasks_count = QUIT_RISK_RUNNING_SCHEDULED_TASKS_COUNT.load(Ordering::Relaxed);
     let enabled_tasks_count = QUIT_RISK_ENABLED_SCHEDULED_TASKS_COUNT.load(Ordering::Relaxed);
+    let busy_sessions_count = QUIT_RISK_BUSY_SESSIONS_COUNT.load(Ordering::Relaxed);
 
     let mut reasons: Vec<String> = Vec::new();
+    if busy_sessions_count > 0 {
+        reasons.push(format!(
+            "{} session{} still working",
+            busy_sessions_count,
+            if busy_sessions_count == 1 { "" } else { "s" }
+        ));
+    }
     if has_active_tunnel {
         reasons.push("an active tunnel".to_string());
     }
@@ -221,6 +278,12 @@ fn quit_confirmation_message() -> String {
 
     if reasons.is_empty() {
         "Background processes (sidecar, SSH sessions) will be stopped.".to_string()
+    } else if busy_sessions_count > 0 {
+        format!(
+            "OpenChamber detected {}. Quitting waits up to {} seconds for sessions to finish before stopping sidecar/background processes.",
+            reasons.join(", "),
+            sidecar_shutdown_timeout().as_secs()
+        )
     } else {
         format!(
             "OpenChamber detected {}. Quitting now will stop sidecar/background processes and may interrupt pending work.",
@@ -1395,13 +1458,31 @@ fn is_app_bundle_installed(bundle_name: &str) -> bool {
 }
 
 const SIDECAR_NAME: &str = "openchamber-server";
-const SIDECAR_NOTIFY_PREFIX: &str = "[OpenChamberDesktopNotify] ";
 const HEALTH_TIMEOUT: Duration = Duration::from_secs(20);
 const HEALTH_POLL_INITIAL_INTERVAL: Duration = Duration::from_millis(250);
 const HEALTH_POLL_MAX_INTERVAL: Duration = Duration::from_millis(2000);
 const LOCAL_SIDECAR_HEALTH_TIMEOUT: Duration = Duration::from_secs(8);
 const LOCAL_SIDECAR_HEALTH_POLL_INITIAL_INTERVAL: Duration = Duration::from_millis(100);
 const LOCAL_SIDECAR_HEALTH_POLL_MAX_INTERVAL: Duration = Duration::from_millis(1000);
+const ADOPTED_SIDECAR_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Crashes tolerated inside `SIDECAR_CRASH_WINDOW` before the supervisor
+/// gives up and shows the recovery screen.
+const SIDECAR_MAX_CRASHES: usize = 5;
+const SIDECAR_CRASH_WINDOW: Duration = Duration::from_secs(300);
+const SIDECAR_RESTART_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const SIDECAR_RESTART_MAX_BACKOFF: Duration = Duration::from_secs(15);
+const SIDECAR_DOWN_EVENT: &str = "openchamber:sidecar-down";
+const SIDECAR_UP_EVENT: &str = "openchamber:sidecar-up";
+const SIDECAR_SHUTDOWN_EVENT: &str = "openchamber:sidecar-shutdown";
+/// Window event asking a local window to pick up queued project paths.
+const OPEN_PROJECT_EVENT: &str = "openchamber:open-project";
+/// How long a quitting sidecar may take to finish busy sessions and exit
+/// before it is sent SIGTERM. Overridable via `desktopShutdownTimeoutSeconds`.
+const DEFAULT_SIDECAR_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_SIDECAR_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(600);
+/// Part of the shutdown deadline kept for the sidecar to stop OpenCode and
+/// close its server after sessions have drained.
+const SIDECAR_SHUTDOWN_CLEANUP_RESERVE: Duration = Duration::from_secs(5);
 const STARTUP_REMOTE_PROBE_SOFT_TIMEOUT: Duration = Duration::from_secs(2);
 const STARTUP_REMOTE_PROBE_HARD_TIMEOUT: Duration = Duration::from_secs(10);
 
@@ -1442,6 +1523,60 @@ fn same_server_url(a: &str, b: &str) -> bool {
 struct SidecarState {
     child: Mutex<Option<CommandChild>>,
     url: Mutex<Option<String>>,
+    /// Bumped whenever the desktop spawns or stops the sidecar on purpose,
+    /// so the exit of a replaced process is not mistaken for a crash.
+    generation: AtomicU64,
+    /// Set while the supervisor is restarting a crashed sidecar.
+    restarting: std::sync::atomic::AtomicBool,
+    launch: Mutex<Option<SidecarLaunch>>,
+    restart_policy: Mutex<SidecarRestartPolicy>,
+    /// Sidecar left running by a crashed shell and reused at startup. It has
+    /// no child handle or stdio, so it is stopped and watched by PID.
+    adopted_pid: Mutex<Option<u32>>,
+}
+
+/// Launch settings resolved once at startup and reused for restarts.
+#[derive(Clone)]
+struct SidecarLaunch {
+    dist_dir: PathBuf,
+    bind_host: &'static str,
+    path: String,
+    home: Option<String>,
+    opencode_binary: Option<String>,
+    /// Allowlisted variables from the user's login shell.
+    login_env: std::collections::BTreeMap<String, String>,
+    /// `sidecarEnv` from settings; applied over the login environment.
+    user_env: Vec<(String, String)>,
+    /// `sidecarArgs` from settings, appended after `--port`.
+    extra_args: Vec<String>,
+}
+
+/// Crash bookkeeping for the sidecar supervisor.
+#[derive(Default)]
+struct SidecarRestartPolicy {
+    crashes: std::collections::VecDeque<std::time::Instant>,
+}
+
+impl SidecarRestartPolicy {
+    /// Record a crash at `now`. Returns the backoff before the next restart,
+    /// or `None` once more than `SIDECAR_MAX_CRASHES` happened inside
+    /// `SIDECAR_CRASH_WINDOW`.
+    fn record_crash(&mut self, now: std::time::Instant) -> Option<Duration> {
+        while self
+            .crashes
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > SIDECAR_CRASH_WINDOW)
+        {
+            self.crashes.pop_front();
+        }
+        self.crashes.push_back(now);
+        let recent = self.crashes.len();
+        if recent > SIDECAR_MAX_CRASHES {
+            return None;
+        }
+        let factor = 1u32 << (recent.saturating_sub(1)).min(8);
+        Some((SIDECAR_RESTART_INITIAL_BACKOFF * factor).min(SIDECAR_RESTART_MAX_BACKOFF))
+    }
 }
 
 /// Holds per-window initialization scripts and a global local origin.
@@ -1456,6 +1591,53 @@ struct DesktopUiInjectionState {
     local_origin: Mutex<Option<String>>,
 }
 
+/// Launch requests and links that arrive before the first window is ready,
+/// project paths waiting for a local window to open them, and the host
+/// forced on the command line.
+#[derive(Default)]
+struct LaunchRequestState {
+    ready: AtomicBool,
+    pending: Mutex<Vec<single_instance::LaunchRequest>>,
+    pending_projects: Mutex<Vec<String>>,
+    forced_host: Mutex<Option<ForcedHost>>,
+}
+
+/// `--host` of the first launch. Like `OPENCHAMBER_SERVER_URL` it applies
+/// to the startup window and to windows opened later.
+#[derive(Debug, Clone)]
+enum ForcedHost {
+    /// Boots through the same branch as the env override.
+    Url(String),
+    /// `local` or a configured host, used in place of the default host.
+    Id(String),
+}
+
+fn forced_host(app: &tauri::AppHandle) -> Option<ForcedHost> {
+    app.try_state::<LaunchRequestState>()
+        .and_then(|state| state.forced_host.lock().expect("forced host mutex").clone())
+}
+
+/// URL forced by `--host <url>` or `OPENCHAMBER_SERVER_URL`, unless it is
+/// the local server itself. `--host <id>` disables the env override.
+fn forced_server_url(app: &tauri::AppHandle, local_ui_url: &str) -> Option<String> {
+    let raw = match forced_host(app) {
+        Some(ForcedHost::Url(url)) => Some(url),
+        Some(ForcedHost::Id(_)) => None,
+        None => std::env::var("OPENCHAMBER_SERVER_URL").ok(),
+    };
+    raw.and_then(|raw| normalize_server_url(&raw))
+        .filter(|url| !same_server_url(url, local_ui_url))
+}
+
+/// Hosts config with `--host <id>` standing in for the default host.
+fn boot_hosts_config(app: &tauri::AppHandle) -> DesktopHostsConfig {
+    let mut cfg = read_desktop_hosts_config_from_disk();
+    if let Some(ForcedHost::Id(id)) = forced_host(app) {
+        cfg.default_host_id = Some(id);
+    }
+    cfg
+}
+
 /// Tracks the set of currently-focused window labels.
 /// Notification suppression triggers when ANY window is focused.
 struct WindowFocusState {
@@ -1636,6 +1818,19 @@ fn read_desktop_settings_json() -> Option<serde_json::Value> {
         .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
 }
 
+fn sidecar_shutdown_timeout_from_settings(settings: Option<&serde_json::Value>) -> Duration {
+    settings
+        .and_then(|v| v.get("desktopShutdownTimeoutSeconds"))
+        .and_then(|v| v.as_f64())
+        .filter(|secs| secs.is_finite() && *secs >= 1.0)
+        .map(|secs| Duration::from_secs(secs.round() as u64).min(MAX_SIDECAR_SHUTDOWN_TIMEOUT))
+        .unwrap_or(DEFAULT_SIDECAR_SHUTDOWN_TIMEOUT)
+}
+
+fn sidecar_shutdown_timeout() -> Duration {
+    sidecar_shutdown_timeout_from_settings(read_desktop_settings_json().as_ref())
+}
+
 fn read_desktop_local_port_from_disk() -> Option<u16> {
     read_desktop_settings_json()
         .as_ref()
@@ -1650,6 +1845,28 @@ fn read_desktop_local_port_from_disk() -> Option<u16> {
         })
 }
 
+fn write_desktop_setting_to_disk(key: &str, value: serde_json::Value) -> Result<()> {
+    let _guard = SETTINGS_FILE_MUTEX.lock().expect("settings file mutex");
+    let path = settings_file_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut root: serde_json::Value = if let Ok(raw) = fs::read_to_string(&path) {
+        serde_json::from_str(&raw).unwrap_or(serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    if !root.is_object() {
+        root = serde_json::json!({});
+    }
+
+    root[key] = value;
+    fs::write(&path, serde_json::to_string_pretty(&root)?)?;
+    Ok(())
+}
+
 fn write_desktop_local_port_to_disk(port: u16) -> Result<()> {
     let _guard = SETTINGS_FILE_MUTEX.lock().expect("settings file mutex");
     let path = settings_file_path();
@@ -2125,93 +2342,25 @@ async fn wait_for_local_opencode_ready_with(
     last_probe
 }
 
-#[cfg(target_os = "macos")]
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ScheduledTasksQuitRiskResponse {
-    has_enabled_scheduled_tasks: bool,
-    has_running_scheduled_tasks: bool,
-    #[serde(default)]
-    enabled_scheduled_tasks_count: u32,
-    #[serde(default)]
-    running_scheduled_tasks_count: u32,
-}
-
-#[cfg(target_os = "macos")]
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct TunnelStatusResponse {
-    active: bool,
-}
-
-#[cfg(target_os = "macos")]
-async fn refresh_quit_risk_flags(local_base_url: &str) {
-    use std::sync::atomic::Ordering;
-
-    let trimmed = local_base_url.trim_end_matches('/');
-    if trimmed.is_empty() {
-        return;
-    }
-
-    let client = match reqwest::Client::builder()
-        .no_proxy()
-        .timeout(Duration::from_secs(2))
-        .build()
-    {
-        Ok(client) => client,
-        Err(_) => return,
-    };
-
-    let scheduled_url = format!("{trimmed}/api/openchamber/scheduled-tasks/status");
-    let tunnel_url = format!("{trimmed}/api/openchamber/tunnel/status");
-
-    let scheduled_future = client.get(scheduled_url).send();
-    let tunnel_future = client.get(tunnel_url).send();
-    let (scheduled_result, tunnel_result) = tokio::join!(scheduled_future, tunnel_future);
-
-    if let Ok(response) = scheduled_result {
-        if response.status().is_success() {
-            if let Ok(payload) = response.json::<ScheduledTasksQuitRiskResponse>().await {
-                let enabled_count = payload.enabled_scheduled_tasks_count;
-                let running_count = payload.running_scheduled_tasks_count;
-                QUIT_RISK_ENABLED_SCHEDULED_TASKS_COUNT.store(enabled_count, Ordering::Relaxed);
-                QUIT_RISK_RUNNING_SCHEDULED_TASKS_COUNT.store(running_count, Ordering::Relaxed);
-                QUIT_RISK_HAS_ENABLED_SCHEDULED_TASKS
-                    .store(payload.has_enabled_scheduled_tasks || enabled_count > 0, Ordering::Relaxed);
-                QUIT_RISK_HAS_RUNNING_SCHEDULED_TASKS
-                    .store(payload.has_running_scheduled_tasks || running_count > 0, Ordering::Relaxed);
-            }
-        }
-    }
-
-    if let Ok(response) = tunnel_result {
-        if response.status().is_success() {
-            if let Ok(payload) = response.json::<TunnelStatusResponse>().await {
-                QUIT_RISK_HAS_ACTIVE_TUNNEL.store(payload.active, Ordering::Relaxed);
-            }
-        }
-    }
-}
-
-#[cfg(target_os = "macos")]
-fn start_quit_risk_poller(local_base_url: String) {
-    use std::sync::atomic::Ordering;
-
-    if QUIT_RISK_POLLER_STARTED.swap(true, Ordering::SeqCst) {
-        return;
-    }
-
-    tauri::async_runtime::spawn(async move {
-        loop {
-            refresh_quit_risk_flags(&local_base_url).await;
-            tokio::time::sleep(QUIT_RISK_POLL_INTERVAL).await;
-        }
-    });
+/// Quit-risk state pushed by the sidecar; read by the macOS quit
+/// confirmation.
+fn apply_sidecar_quit_risk(risk: SidecarQuitRisk) {
+    let enabled_count = risk.enabled_scheduled_tasks_count;
+    let running_count = risk.running_scheduled_tasks_count;
+    QUIT_RISK_ENABLED_SCHEDULED_TASKS_COUNT.store(enabled_count, Ordering::Relaxed);
+    QUIT_RISK_RUNNING_SCHEDULED_TASKS_COUNT.store(running_count, Ordering::Relaxed);
+    QUIT_RISK_HAS_ENABLED_SCHEDULED_TASKS.store(
+        risk.has_enabled_scheduled_tasks || enabled_count > 0,
+        Ordering::Relaxed,
+    );
+    QUIT_RISK_HAS_RUNNING_SCHEDULED_TASKS.store(
+        risk.has_running_scheduled_tasks || running_count > 0,
+        Ordering::Relaxed,
+    );
+    QUIT_RISK_HAS_ACTIVE_TUNNEL.store(risk.has_active_tunnel, Ordering::Relaxed);
+    QUIT_RISK_BUSY_SESSIONS_COUNT.store(risk.busy_sessions_count, Ordering::Relaxed);
 }
 
-#[cfg(not(target_os = "macos"))]
-fn start_quit_risk_poller(_local_base_url: String) {}
-
 /// Uses the same probe_with_retry policy as startup/new-window (soft + hard)
 /// so that first-launch/recovery remote connect accepts slow-but-valid hosts.
 #[tauri::command]
@@ -2355,13 +2504,116 @@ async fn fetch_changelog_notes(from_version: &str, to_version: &str) -> Option<S
     }
 }
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct SidecarNotifyPayload {
-    title: Option<String>,
-    body: Option<String>,
-    tag: Option<String>,
-    require_hidden: Option<bool>,
+/// Write a protocol message to the sidecar's stdin. Dropped when no
+/// sidecar is running (e.g. the Vite dev server).
+fn send_to_sidecar(app: &tauri::AppHandle, message: &DesktopMessage) {
+    let Some(state) = app.try_state::<SidecarState>() else {
+        return;
+    };
+    let mut guard = state.child.lock().expect("sidecar mutex");
+    if let Some(child) = guard.as_mut() {
+        if let Err(err) = child.write(encode_desktop_message(message).as_bytes()) {
+            log::warn!("[sidecar] failed to write to stdin: {err}");
+        }
+    }
+}
+
+fn handle_sidecar_request(app: &tauri::AppHandle, request: SidecarRequest) {
+    let SidecarRequest { id, message } = request;
+    let message = match message {
+        Ok(message) => message,
+        Err(err) => {
+            log::warn!("[sidecar] protocol: {err}");
+            if let Some(id) = id {
+                send_to_sidecar(app, &DesktopMessage::reply(id, Err(err)));
+            }
+            return;
+        }
+    };
+
+    // Opening a window probes remote hosts, so it replies asynchronously.
+    if let SidecarMessage::OpenWindow { url } = message {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            let result = desktop_new_window_at_url(app.clone(), url)
+                .await
+                .map(|()| serde_json::Value::Null);
+            if let Some(id) = id {
+                send_to_sidecar(&app, &DesktopMessage::reply(id, result));
+            }
+        });
+        return;
+    }
+
+    let result = apply_sidecar_message(app, message);
+    if let Err(err) = &result {
+        log::warn!("[sidecar] protocol request failed: {err}");
+    }
+    if let Some(id) = id {
+        send_to_sidecar(app, &DesktopMessage::reply(id, result));
+    }
+}
+
+fn apply_sidecar_message(
+    app: &tauri::AppHandle,
+    message: SidecarMessage,
+) -> Result<serde_json::Value, String> {
+    match message {
+        SidecarMessage::Hello { protocol } => {
+            log::info!("[sidecar] protocol v{protocol}");
+        }
+        SidecarMessage::Notify(payload) => maybe_show_sidecar_notification(app, payload),
+        SidecarMessage::OpenWindow { .. } => {}
+        SidecarMessage::QuitRisk(risk) => apply_sidecar_quit_risk(risk),
+        SidecarMessage::SetBadge { count } => {
+            let count = count.filter(|count| *count > 0);
+            // The badge is app-wide on macOS and Linux; set it on every
+            // window so Windows taskbar entries agree.
+            for window in app.webview_windows().into_values() {
+                window
+                    .set_badge_count(count)
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+        SidecarMessage::RequestAttention { critical } => {
+            let kind = if critical {
+                tauri::UserAttentionType::Critical
+            } else {
+                tauri::UserAttentionType::Informational
+            };
+            let windows = app.webview_windows();
+            let window = windows
+                .get("main")
+                .or_else(|| windows.values().next())
+                .ok_or_else(|| "No window to highlight".to_string())?;
+            window
+                .request_user_attention(Some(kind))
+                .map_err(|err| err.to_string())?;
+        }
+        SidecarMessage::Progress { value, state } => {
+            use tauri::window::{ProgressBarState, ProgressBarStatus};
+
+            let status = match (value, state.unwrap_or_default()) {
+                (None, SidecarProgressState::Normal) | (_, SidecarProgressState::None) => {
+                    ProgressBarStatus::None
+                }
+                (_, SidecarProgressState::Normal) => ProgressBarStatus::Normal,
+                (_, SidecarProgressState::Indeterminate) => ProgressBarStatus::Indeterminate,
+                (_, SidecarProgressState::Paused) => ProgressBarStatus::Paused,
+                (_, SidecarProgressState::Error) => ProgressBarStatus::Error,
+            };
+            let progress = value.map(|value| (value.clamp(0.0, 1.0) * 100.0).round() as u64);
+            for window in app.webview_windows().into_values() {
+                window
+                    .set_progress_bar(ProgressBarState {
+                        status: Some(status),
+                        progress,
+                    })
+                    .map_err(|err| err.to_string())?;
+            }
+        }
+    }
+    Ok(serde_json::Value::Null)
 }
 
 fn maybe_show_sidecar_notification(app: &tauri::AppHandle, payload: SidecarNotifyPayload) {
@@ -2383,6 +2635,11 @@ fn maybe_show_sidecar_notification(app: &tauri::AppHandle, payload: SidecarNotif
     let body = payload.body.filter(|b| is_nonempty_string(b));
     let _tag = payload.tag;
 
+    if is_headless() {
+        log::info!("[notify] {title}: {}", body.as_deref().unwrap_or_default());
+        return;
+    }
+
     use tauri_plugin_notification::NotificationExt;
 
     let mut builder = app.notification().builder().title(title);
@@ -2429,114 +2686,332 @@ async fn wait_for_health(url: &str) -> bool {
     wait_for_health_with(url, HEALTH_TIMEOUT, HEALTH_POLL_INITIAL_INTERVAL, HEALTH_POLL_MAX_INTERVAL).await
 }
 
+/// Ask the sidecar at `url` to shut down, letting busy sessions run for up
+/// to `session_drain`. Uses a raw HTTP POST to avoid pulling in
+/// reqwest::blocking (and its extra thread pool) just for this one call.
+/// Returns `false` when the request could not be delivered.
+fn request_sidecar_shutdown(url: &str, session_drain: Duration) -> bool {
+    use std::io::Write;
+
+    let Ok(parsed) = url::Url::parse(url) else {
+        return false;
+    };
+    let host = parsed.host_str().unwrap_or("127.0.0.1");
+    let port = parsed.port().unwrap_or(80);
+    let Ok(addr) = format!("{host}:{port}").parse::<std::net::SocketAddr>() else {
+        return false;
+    };
+    let Ok(mut stream) = std::net::TcpStream::connect_timeout(&addr, Duration::from_millis(1500))
+    else {
+        return false;
+    };
+    let _ = stream.set_write_timeout(Some(Duration::from_millis(1500)));
+    let _ = stream.set_read_timeout(Some(Duration::from_millis(1500)));
+    let token = desktop_auth_token();
+    let body = serde_json::json!({ "sessionDrainTimeoutMs": session_drain.as_millis() as u64 })
+        .to_string();
+    let request = format!(
+        "POST /api/system/shutdown HTTP/1.1\r\nHost: {host}:{port}\r\n{SIDECAR_AUTH_HEADER}: {token}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(request.as_bytes()).is_ok() && stream.flush().is_ok()
+}
+
+/// Stop the sidecar: ask it to shut down, wait up to the configured
+/// deadline for it to finish busy sessions and exit, then escalate to
+/// SIGTERM and finally SIGKILL. Blocks until the process is gone.
 fn kill_sidecar(app: tauri::AppHandle) {
     let Some(state) = app.try_state::<SidecarState>() else {
         return;
     };
 
+    // Bump the generation up front so the exit is not treated as a crash.
+    state.generation.fetch_add(1, Ordering::SeqCst);
+    send_to_sidecar(
+        &app,
+        &DesktopMessage::event("quitting", serde_json::Value::Null),
+    );
+
+    let pid = state
+        .child
+        .lock()
+        .expect("sidecar mutex")
+        .as_ref()
+        .map(CommandChild::pid)
+        .or(*state.adopted_pid.lock().expect("sidecar adopted pid mutex"));
     let sidecar_url = state.url.lock().expect("sidecar url mutex").clone();
-    if let Some(url) = sidecar_url {
-        // Attempt graceful shutdown via a raw HTTP POST to avoid pulling in
-        // reqwest::blocking (and its extra thread pool) just for this one call.
-        if let Ok(parsed) = url::Url::parse(&url) {
-            let host = parsed.host_str().unwrap_or("127.0.0.1");
-            let port = parsed.port().unwrap_or(80);
-            let path = "/api/system/shutdown";
-            if let Ok(mut stream) =
-                std::net::TcpStream::connect_timeout(
-                    &format!("{host}:{port}").parse().unwrap(),
-                    Duration::from_millis(1500),
-                )
-            {
-                use std::io::Write;
-                let _ = stream.set_write_timeout(Some(Duration::from_millis(1500)));
-                let _ = stream.set_read_timeout(Some(Duration::from_millis(1500)));
-                let request = format!(
-                    "POST {path} HTTP/1.1\r\nHost: {host}:{port}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+
+    if let (Some(pid), Some(url)) = (pid, sidecar_url) {
+        let timeout = sidecar_shutdown_timeout();
+        let session_drain = timeout.saturating_sub(SIDECAR_SHUTDOWN_CLEANUP_RESERVE);
+        if request_sidecar_shutdown(&url, session_drain) {
+            let busy = QUIT_RISK_BUSY_SESSIONS_COUNT.load(Ordering::Relaxed);
+            log::info!(
+                "[sidecar] shutdown requested; waiting up to {}s for pid {pid} to exit ({busy} busy sessions)",
+                timeout.as_secs()
+            );
+            let started = std::time::Instant::now();
+            if sidecar_lock::wait_for_exit(pid, timeout) {
+                log::info!(
+                    "[sidecar] pid {pid} exited after {}ms",
+                    started.elapsed().as_millis()
+                );
+            } else {
+                log::warn!(
+                    "[sidecar] pid {pid} still running after {}s; escalating",
+                    timeout.as_secs()
                 );
-                let _ = stream.write_all(request.as_bytes());
-                let _ = stream.flush();
-                // Brief pause to let the sidecar begin its shutdown sequence.
-                std::thread::sleep(Duration::from_millis(100));
             }
+        } else {
+            log::warn!("[sidecar] shutdown request to {url} failed; escalating");
         }
     }
+    if let Some(pid) = pid {
+        sidecar_lock::terminate_process(pid);
+    }
 
-    let mut guard = state.child.lock().expect("sidecar mutex");
-    if let Some(child) = guard.take() {
+    stop_sidecar_child(&state);
+    *state.url.lock().expect("sidecar url mutex") = None;
+}
+
+/// Stop SSH/Docker/Kubernetes sessions and the sidecar.
+fn shutdown_background_services(app: &tauri::AppHandle) {
+    if let Some(state) = app.try_state::<DesktopSshManagerState>() {
+        state.shutdown_all(app);
+    }
+    if let Some(state) = app.try_state::<DesktopDockerManagerState>() {
+        state.shutdown_all(app);
+    }
+    if let Some(state) = app.try_state::<DesktopKubeManagerState>() {
+        state.shutdown_all(app);
+    }
+    if let Some(state) = app.try_state::<DesktopLocalProfileManagerState>() {
+        state.shutdown_all(app);
+    }
+    kill_sidecar(app.clone());
+}
+
+/// Kill the sidecar child without a graceful request. Bumps the generation
+/// first so its exit is not reported as a crash; returns the new generation.
+fn stop_sidecar_child(state: &SidecarState) -> u64 {
+    let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    if let Some(child) = state.child.lock().expect("sidecar mutex").take() {
+        sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), child.pid());
         let _ = child.kill();
     }
-    *state.url.lock().expect("sidecar url mutex") = None;
+    if let Some(pid) = state
+        .adopted_pid
+        .lock()
+        .expect("sidecar adopted pid mutex")
+        .take()
+    {
+        sidecar_lock::terminate_process(pid);
+        sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), pid);
+    }
+    generation
 }
 
 fn build_local_url(port: u16) -> String {
     format!("http://127.0.0.1:{port}")
 }
 
-/// Kills any stale openchamber-server processes that may be lingering from
-/// previous app sessions or incomplete shutdowns. This ensures a clean
-/// startup and prevents port conflicts.
-fn kill_stale_sidecar_processes() {
-    let process_name = if cfg!(windows) {
-        "openchamber-server.exe"
-    } else {
-        "openchamber-server"
+/// Confirm the sidecar in `lock` answers on its port with the recorded token
+/// and PID before reusing it.
+async fn probe_owned_sidecar(lock: &SidecarLock) -> bool {
+    let Ok(client) = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(2))
+        .build()
+    else {
+        return false;
     };
-
-    let result = if cfg!(target_os = "macos") {
-        // macOS: use pkill to terminate by process name
-        std::process::Command::new("pkill")
-            .arg("-x") // exact match
-            .arg(process_name)
-            .output()
-    } else if cfg!(target_os = "linux") {
-        // Linux: use pkill
-        std::process::Command::new("pkill")
-            .arg("-x")
-            .arg(process_name)
-            .output()
-    } else if cfg!(windows) {
-        // Windows: use taskkill
-        std::process::Command::new("taskkill")
-            .arg("/F")
-            .arg("/IM")
-            .arg(process_name)
-            .output()
-    } else {
-        return;
+    let url = format!("{}/api/system/info", build_local_url(lock.port));
+    let Ok(response) = client
+        .get(&url)
+        .header(SIDECAR_AUTH_HEADER, &lock.token)
+        .send()
+        .await
+    else {
+        return false;
     };
+    if !response.status().is_success() {
+        return false;
+    }
+    response
+        .json::<serde_json::Value>()
+        .await
+        .ok()
+        .and_then(|info| info.get("pid").and_then(|pid| pid.as_u64()))
+        == Some(u64::from(lock.pid))
+}
 
-    // Log result for debugging (pkill returns 1 if no processes found, which is fine)
-    if let Ok(output) = result {
-        log::debug!(
-            "[sidecar] cleanup result: exit_code={:?}, stdout={}, stderr={}",
-            output.status.code(),
-            String::from_utf8_lossy(&output.stdout).trim(),
-            String::from_utf8_lossy(&output.stderr).trim()
+/// Deal with sidecars recorded by earlier shells. A healthy sidecar left by
+/// a crashed shell of the same build is reused; other stale sidecars are
+/// stopped, but only once their PID is proven to still be theirs, by start
+/// time or, where the OS does not report one, by probing the port. Sidecars
+/// owned by another running shell are left alone.
+async fn reclaim_stale_sidecars(app: &tauri::AppHandle, launch: &SidecarLaunch) -> Option<String> {
+    let dir = sidecar_lock::locks_dir();
+    let app_version = app.package_info().version.to_string();
+    let mut adopted: Option<String> = None;
+
+    for lock in sidecar_lock::read_locks_in(&dir) {
+        if sidecar_lock::is_other_shell_alive(lock.shell_pid) {
+            continue;
+        }
+        let Some(info) = sidecar_lock::process_info(lock.pid) else {
+            sidecar_lock::remove_lock_in(&dir, lock.pid);
+            continue;
+        };
+        // Without a start time only the sidecar itself, answering with the
+        // lock's token and PID, proves the PID was not reused.
+        let owned = sidecar_lock::is_owned_process(&lock, &info)
+            || (info.started_at.is_none() && probe_owned_sidecar(&lock).await);
+        if !owned {
+            log::info!(
+                "[sidecar] pid {} from a stale lock now belongs to '{}'; leaving it alone",
+                lock.pid,
+                info.name
+            );
+            sidecar_lock::remove_lock_in(&dir, lock.pid);
+            continue;
+        }
+
+        let reusable = adopted.is_none()
+            && lock.profile.is_none()
+            && lock.app_version == app_version
+            && lock.bind_host == launch.bind_host
+            && probe_owned_sidecar(&lock).await;
+        if reusable && DESKTOP_AUTH_TOKEN.set(lock.token.clone()).is_ok() {
+            adopted = adopt_sidecar(app, &dir, lock);
+            continue;
+        }
+
+        log::info!(
+            "[sidecar] stopping stale sidecar pid {} on port {}",
+            lock.pid,
+            lock.port
         );
+        let pid = lock.pid;
+        let _ = tauri::async_runtime::spawn_blocking(move || sidecar_lock::terminate_process(pid))
+            .await;
+        sidecar_lock::remove_lock_in(&dir, pid);
     }
 
-    // Brief pause to let the OS clean up the processes
-    std::thread::sleep(Duration::from_millis(100));
+    adopted
 }
 
-async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
-    // Clean up any stale sidecar processes from previous sessions
-    kill_stale_sidecar_processes();
+/// Quit-risk snapshot of an adopted sidecar, read over authenticated HTTP.
+/// `None` when any endpoint fails, keeping the last known state.
+async fn fetch_adopted_quit_risk(base_url: &str, token: &str) -> Option<SidecarQuitRisk> {
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(Duration::from_secs(2))
+        .build()
+        .ok()?;
+    let get = |path: &str| {
+        let request = client
+            .get(format!("{base_url}{path}"))
+            .header(SIDECAR_AUTH_HEADER, token)
+            .send();
+        async move {
+            let response = request.await.ok()?.error_for_status().ok()?;
+            response.json::<serde_json::Value>().await.ok()
+        }
+    };
+    let (scheduled, tunnel, activity) = tokio::join!(
+        get("/api/openchamber/scheduled-tasks/status"),
+        get("/api/openchamber/tunnel/status"),
+        get("/api/session-activity"),
+    );
+    Some(SidecarQuitRisk::from_http_status(
+        scheduled?, &tunnel?, &activity?,
+    ))
+}
 
-    let stored_port = read_desktop_local_port_from_disk();
-    let mut candidates: Vec<Option<u16>> = Vec::new();
-    if let Some(port) = stored_port {
-        candidates.push(Some(port));
+fn adopt_sidecar(
+    app: &tauri::AppHandle,
+    dir: &std::path::Path,
+    lock: SidecarLock,
+) -> Option<String> {
+    let state = app.try_state::<SidecarState>()?;
+    let url = build_local_url(lock.port);
+    let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+    *state.url.lock().expect("sidecar url mutex") = Some(url.clone());
+    *state.adopted_pid.lock().expect("sidecar adopted pid mutex") = Some(lock.pid);
+
+    log::info!(
+        "[sidecar] reusing sidecar pid {} on port {} from a previous session",
+        lock.pid,
+        lock.port
+    );
+    let (pid, port) = (lock.pid, lock.port);
+    let (url_for_poll, token) = (url.clone(), lock.token.clone());
+    let lock = SidecarLock {
+        shell_pid: std::process::id(),
+        ..lock
+    };
+    if let Err(err) = sidecar_lock::write_lock_in(dir, &lock) {
+        log::warn!("[sidecar] failed to update lock for pid {pid}: {err}");
     }
-    candidates.push(Some(DEFAULT_DESKTOP_PORT));
-    candidates.push(None);
+    let _ = write_desktop_local_port_to_disk(port);
 
-    let dist_dir = resolve_web_dist_dir(app)?;
-    let no_proxy = "localhost,127.0.0.1";
+    // No stdio to notice the exit or receive quit-risk updates on, so poll
+    // for both.
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        loop {
+            if let Some(risk) = fetch_adopted_quit_risk(&url_for_poll, &token).await {
+                apply_sidecar_quit_risk(risk);
+            }
+            tokio::time::sleep(ADOPTED_SIDECAR_POLL_INTERVAL).await;
+            let Some(state) = app.try_state::<SidecarState>() else {
+                return;
+            };
+            if state.generation.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            let alive = tauri::async_runtime::spawn_blocking(move || {
+                sidecar_lock::process_info(pid).is_some()
+            })
+            .await
+            .unwrap_or(true);
+            if !alive {
+                state
+                    .adopted_pid
+                    .lock()
+                    .expect("sidecar adopted pid mutex")
+                    .take();
+                sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), pid);
+                handle_sidecar_exit(&app, generation, port, "reused sidecar exited".to_string());
+                return;
+            }
+        }
+    });
 
-    // macOS app launch env often lacks user PATH entries.
-    let mut path_segments: Vec<String> = Vec::new();
+    Some(url)
+}
+
+/// `opencodeBinary` from settings, resolved to the binary when it names a
+/// directory.
+fn opencode_binary_from_settings(settings: Option<&serde_json::Value>) -> Option<String> {
+    let value = settings?.get("opencodeBinary")?.as_str()?;
+    opencode_detect::resolve_binary_path(value).map(|path| path.to_string_lossy().to_string())
+}
+
+/// Resolve everything the sidecar is launched with from the current
+/// settings and environment.
+async fn resolve_sidecar_launch(app: &tauri::AppHandle) -> Result<SidecarLaunch> {
+    let dist_dir = resolve_web_dist_dir(app)?;
+
+    // GUI launches do not inherit the user's shell setup (nvm, asdf, mise,
+    // direnv, ...), so ask the login shell for it.
+    let login_env = tauri::async_runtime::spawn_blocking(|| login_env::resolve_login_env(false))
+        .await
+        .unwrap_or_default();
+
+    // macOS app launch env often lacks user PATH entries.
+    let mut path_segments: Vec<String> = Vec::new();
     let mut seen = std::collections::HashSet::<String>::new();
 
     let resolved_home_dir_path = app.path().home_dir().ok();
@@ -2551,30 +3026,8 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
 
     let desktop_settings = read_desktop_settings_json();
 
-    let opencode_binary_from_settings: Option<String> = (|| {
-        let value = desktop_settings.as_ref()?.get("opencodeBinary")?.as_str()?.trim();
-        if value.is_empty() {
-            return None;
-        }
-
-        let mut candidate = value.to_string();
-        if fs::metadata(&candidate)
-            .map(|m| m.is_dir())
-            .unwrap_or(false)
-        {
-            let bin_name = if cfg!(windows) {
-                "opencode.exe"
-            } else {
-                "opencode"
-            };
-            candidate = PathBuf::from(candidate)
-                .join(bin_name)
-                .to_string_lossy()
-                .to_string();
-        }
-
-        Some(candidate)
-    })();
+    let opencode_binary_from_settings = opencode_binary_from_settings(desktop_settings.as_ref());
+    let (user_env, extra_args) = sidecar_env::read_from_settings(desktop_settings.as_ref());
 
     let sidecar_bind_host = desktop_settings
         .as_ref()
@@ -2604,12 +3057,7 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         }
     }
 
-    for var in [
-        "OPENCHAMBER_OPENCODE_PATH",
-        "OPENCHAMBER_OPENCODE_BIN",
-        "OPENCODE_PATH",
-        "OPENCODE_BINARY",
-    ] {
+    for var in opencode_detect::OPENCODE_BINARY_ENV_VARS {
         if let Ok(val) = env::var(var) {
             let trimmed = val.trim();
             if trimmed.is_empty() {
@@ -2622,6 +3070,10 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         }
     }
 
+    for segment in login_env.path_segments() {
+        push_unique(segment);
+    }
+
     // Common locations.
     push_unique("/opt/homebrew/bin".to_string());
     push_unique("/usr/local/bin".to_string());
@@ -2647,6 +3099,39 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
 
     let augmented_path = path_segments.join(":");
 
+    Ok(SidecarLaunch {
+        dist_dir,
+        bind_host: sidecar_bind_host,
+        path: augmented_path,
+        home: resolved_home_dir,
+        opencode_binary: opencode_binary_from_settings,
+        login_env: login_env.forwarded(),
+        user_env: user_env
+            .into_iter()
+            .map(|entry| (entry.name, entry.value))
+            .collect(),
+        extra_args,
+    })
+}
+
+async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
+    let stored_port = read_desktop_local_port_from_disk();
+    let mut candidates: Vec<Option<u16>> = Vec::new();
+    if let Some(port) = stored_port {
+        candidates.push(Some(port));
+    }
+    candidates.push(Some(DEFAULT_DESKTOP_PORT));
+    candidates.push(None);
+
+    let launch = resolve_sidecar_launch(app).await?;
+    if let Some(state) = app.try_state::<SidecarState>() {
+        *state.launch.lock().expect("sidecar launch mutex") = Some(launch.clone());
+    }
+
+    if let Some(url) = reclaim_stale_sidecars(app, &launch).await {
+        return Ok(url);
+    }
+
     for candidate in candidates {
         let port = match candidate {
             Some(p) => p,
@@ -2654,82 +3139,524 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         };
         let url = build_local_url(port);
 
-        let mut cmd = app
-            .shell()
-            .sidecar(SIDECAR_NAME)
-            .map_err(|err| anyhow!("Failed to resolve sidecar '{SIDECAR_NAME}': {err}"))?
-            .args(["--port", &port.to_string()])
-            .env("OPENCHAMBER_HOST", sidecar_bind_host)
-            .env("OPENCHAMBER_DIST_DIR", dist_dir.clone())
-            .env("OPENCHAMBER_RUNTIME", "desktop")
-            .env("OPENCHAMBER_DESKTOP_NOTIFY", "true")
-            .env("PATH", augmented_path.clone())
-            .env("NO_PROXY", no_proxy)
-            .env("no_proxy", no_proxy);
-
-        if let Some(home) = resolved_home_dir.as_deref() {
-            cmd = cmd.env("HOME", home);
+        if let Err(err) = spawn_sidecar_process(app, &launch, port) {
+            log::warn!("[sidecar] spawn failed on port {port}: {err}");
+            continue;
         }
 
-        if let Some(bin) = opencode_binary_from_settings.as_deref() {
-            let trimmed = bin.trim();
-            if !trimmed.is_empty() {
-                cmd = cmd.env("OPENCODE_BINARY", trimmed);
+        if !wait_for_health_with(
+            &url,
+            LOCAL_SIDECAR_HEALTH_TIMEOUT,
+            LOCAL_SIDECAR_HEALTH_POLL_INITIAL_INTERVAL,
+            LOCAL_SIDECAR_HEALTH_POLL_MAX_INTERVAL,
+        )
+        .await
+        {
+            // Never became healthy, so there is no work to wait for.
+            if let Some(state) = app.try_state::<SidecarState>() {
+                stop_sidecar_child(&state);
+                *state.url.lock().expect("sidecar url mutex") = None;
             }
+            continue;
         }
 
-        if let Ok(password) = env::var("OPENCODE_SERVER_PASSWORD") {
-            let trimmed = password.trim();
-            if !trimmed.is_empty() {
-                cmd = cmd.env("OPENCODE_SERVER_PASSWORD", trimmed);
-            }
+        let _ = write_desktop_local_port_to_disk(port);
+        return Ok(url);
+    }
+
+    Err(anyhow!("Sidecar health check failed"))
+}
+
+/// Sidecar command for `launch` on `port`, shared by the main sidecar and
+/// local profiles.
+fn sidecar_command(
+    app: &tauri::AppHandle,
+    launch: &SidecarLaunch,
+    port: u16,
+) -> Result<tauri_plugin_shell::process::Command> {
+    // Keep loopback off any proxy while honouring the user's own exclusions.
+    let mut no_proxy = "localhost,127.0.0.1".to_string();
+    let configured_no_proxy = launch
+        .user_env
+        .iter()
+        .rev()
+        .find(|(name, _)| name.eq_ignore_ascii_case("NO_PROXY"))
+        .map(|(_, value)| value);
+    if let Some(user) = configured_no_proxy
+        .or_else(|| launch.login_env.get("NO_PROXY"))
+        .or_else(|| launch.login_env.get("no_proxy"))
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+    {
+        no_proxy = format!("{no_proxy},{user}");
+    }
+    let no_proxy = no_proxy.as_str();
+    let mut cmd = app
+        .shell()
+        .sidecar(SIDECAR_NAME)
+        .map_err(|err| anyhow!("Failed to resolve sidecar '{SIDECAR_NAME}': {err}"))?
+        .args(["--port", &port.to_string()])
+        .args(launch.extra_args.clone())
+        .envs(launch.login_env.clone())
+        .envs(launch.user_env.clone())
+        .env("OPENCHAMBER_HOST", launch.bind_host)
+        .env("OPENCHAMBER_DIST_DIR", launch.dist_dir.clone())
+        .env("OPENCHAMBER_RUNTIME", "desktop")
+        .env("PATH", launch.path.clone())
+        .env("NO_PROXY", no_proxy)
+        .env("no_proxy", no_proxy);
+
+    if let Some(home) = launch.home.as_deref() {
+        cmd = cmd.env("HOME", home);
+    }
+
+    let token = desktop_auth_token();
+    if !token.is_empty() {
+        cmd = cmd.env(SIDECAR_AUTH_TOKEN_ENV, token);
+    }
+
+    if let Some(bin) = launch.opencode_binary.as_deref() {
+        let trimmed = bin.trim();
+        if !trimmed.is_empty() {
+            cmd = cmd.env("OPENCODE_BINARY", trimmed);
         }
+    }
 
-        let (rx, child) = match cmd.spawn() {
-            Ok(v) => v,
-            Err(err) => {
-                log::warn!("[sidecar] spawn failed on port {port}: {err}");
-                continue;
-            }
-        };
+    if let Ok(password) = env::var("OPENCODE_SERVER_PASSWORD") {
+        let trimmed = password.trim();
+        if !trimmed.is_empty() {
+            cmd = cmd.env("OPENCODE_SERVER_PASSWORD", trimmed);
+        }
+    }
 
-        let app_handle = app.clone();
-        tauri::async_runtime::spawn(async move {
-            let mut rx = rx;
-            while let Some(event) = rx.recv().await {
-                match event {
-                    CommandEvent::Stdout(bytes) => {
-                        let line = String::from_utf8_lossy(&bytes);
-                        if let Some(rest) = line.strip_prefix(SIDECAR_NOTIFY_PREFIX) {
-                            if let Ok(parsed) =
-                                serde_json::from_str::<SidecarNotifyPayload>(rest.trim())
-                            {
-                                maybe_show_sidecar_notification(&app_handle, parsed);
-                            }
-                        }
+    if !launch.user_env.is_empty() || !launch.extra_args.is_empty() {
+        // Values may be secrets; only log what was applied.
+        log::info!(
+            "[sidecar] applying {} custom env var(s) and {} extra arg(s)",
+            launch.user_env.len(),
+            launch.extra_args.len()
+        );
+    }
+    Ok(cmd)
+}
+
+/// Record a freshly spawned sidecar so later shells can reclaim it.
+fn write_sidecar_lock(
+    app: &tauri::AppHandle,
+    launch: &SidecarLaunch,
+    pid: u32,
+    port: u16,
+    profile: Option<&str>,
+) {
+    let lock = SidecarLock {
+        pid,
+        port,
+        started_at: sidecar_lock::now_unix_secs(),
+        shell_pid: std::process::id(),
+        app_version: app.package_info().version.to_string(),
+        bind_host: launch.bind_host.to_string(),
+        token: desktop_auth_token().to_string(),
+        profile: profile.map(str::to_string),
+    };
+    if let Err(err) = sidecar_lock::write_lock_in(&sidecar_lock::locks_dir(), &lock) {
+        log::warn!("[sidecar] failed to write lock for pid {pid}: {err}");
+    }
+}
+
+/// Spawn the sidecar on `port` and start forwarding its output. The child
+/// and URL are stored in `SidecarState`; an unexpected exit hands over to
+/// the crash supervisor.
+fn spawn_sidecar_process(app: &tauri::AppHandle, launch: &SidecarLaunch, port: u16) -> Result<()> {
+    let (rx, child) = sidecar_command(app, launch, port)?
+        .env("OPENCHAMBER_DESKTOP_NOTIFY", "true")
+        .env(
+            "OPENCHAMBER_DESKTOP_PROTOCOL",
+            SIDECAR_PROTOCOL_VERSION.to_string(),
+        )
+        .spawn()?;
+    let pid = child.pid();
+    write_sidecar_lock(app, launch, pid, port, None);
+
+    let generation = match app.try_state::<SidecarState>() {
+        Some(state) => {
+            let generation = state.generation.fetch_add(1, Ordering::SeqCst) + 1;
+            *state.child.lock().expect("sidecar mutex") = Some(child);
+            *state.url.lock().expect("sidecar url mutex") = Some(build_local_url(port));
+            generation
+        }
+        None => 0,
+    };
+    send_to_sidecar(
+        app,
+        &DesktopMessage::Hello {
+            protocol: SIDECAR_PROTOCOL_VERSION,
+            app_version: app.package_info().version.to_string(),
+        },
+    );
+
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let mut rx = rx;
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(bytes) => {
+                    let line = String::from_utf8_lossy(&bytes);
+                    if let Some(request) = parse_sidecar_line(&line) {
+                        handle_sidecar_request(&app_handle, request);
+                    } else if let Some(logs) = app_handle.try_state::<DesktopLogsState>() {
+                        logs.append_sidecar("stdout", &bytes);
                     }
-                    CommandEvent::Error(error) => {
-                        log::warn!("[sidecar] error: {error}");
+                }
+                CommandEvent::Stderr(bytes) => {
+                    if let Some(logs) = app_handle.try_state::<DesktopLogsState>() {
+                        logs.append_sidecar("stderr", &bytes);
                     }
-                    CommandEvent::Terminated(payload) => {
-                        log::warn!(
-                            "[sidecar] terminated code={:?} signal={:?}",
-                            payload.code,
-                            payload.signal
+                }
+                CommandEvent::Error(error) => {
+                    log::warn!("[sidecar] error: {error}");
+                }
+                CommandEvent::Terminated(payload) => {
+                    sidecar_lock::remove_lock_in(&sidecar_lock::locks_dir(), pid);
+                    log::warn!(
+                        "[sidecar] terminated code={:?} signal={:?}",
+                        payload.code,
+                        payload.signal
+                    );
+                    if let Some(logs) = app_handle.try_state::<DesktopLogsState>() {
+                        let line = format!(
+                            "process exited code={:?} signal={:?}",
+                            payload.code, payload.signal
                         );
-                        break;
+                        logs.append_sidecar("desktop", line.as_bytes());
                     }
-                    _ => {}
+                    let reason = match (payload.code, payload.signal) {
+                        (Some(code), _) => format!("exited with code {code}"),
+                        (None, Some(signal)) => format!("killed by signal {signal}"),
+                        (None, None) => "exited".to_string(),
+                    };
+                    handle_sidecar_exit(&app_handle, generation, port, reason);
+                    break;
                 }
+                _ => {}
             }
-        });
+        }
+    });
+
+    Ok(())
+}
 
+/// Decide whether a sidecar exit is a crash and, if so, start the
+/// supervisor. Exits of replaced or deliberately stopped processes and
+/// exits during a restart already in progress are ignored.
+fn handle_sidecar_exit(app: &tauri::AppHandle, generation: u64, port: u16, reason: String) {
+    let Some(state) = app.try_state::<SidecarState>() else {
+        return;
+    };
+    if state.generation.load(Ordering::SeqCst) != generation {
+        return;
+    }
+    if state
+        .restarting
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    log::error!("[sidecar] crashed ({reason}); supervising restart on port {port}");
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        supervise_sidecar_restart(&app, generation, port, reason).await;
         if let Some(state) = app.try_state::<SidecarState>() {
-            *state.child.lock().expect("sidecar mutex") = Some(child);
-            *state.url.lock().expect("sidecar url mutex") = Some(url.clone());
+            state.restarting.store(false, Ordering::SeqCst);
         }
+    });
+}
 
-        if !wait_for_health_with(
+fn dispatch_sidecar_event(app: &tauri::AppHandle, event: &str, detail: &serde_json::Value) {
+    let _ = app.emit(event, detail);
+
+    let event = serde_json::to_string(event).unwrap_or_else(|_| "\"\"".into());
+    let detail = serde_json::to_string(detail).unwrap_or_else(|_| "{}".into());
+    let script = format!("window.dispatchEvent(new CustomEvent({event}, {{ detail: {detail} }}));");
+    eval_in_all_windows(app, &script);
+}
+
+/// Windows whose page is served by the local sidecar.
+fn local_origin_windows(app: &tauri::AppHandle) -> Vec<tauri::WebviewWindow> {
+    let local_origin = app
+        .try_state::<DesktopUiInjectionState>()
+        .and_then(|state| {
+            state
+                .local_origin
+                .lock()
+                .ok()
+                .and_then(|guard| guard.clone())
+        });
+    let Some(local_origin) = local_origin.filter(|origin| !origin.is_empty()) else {
+        return Vec::new();
+    };
+    app.webview_windows()
+        .into_values()
+        .filter(|window| {
+            window
+                .url()
+                .map(|url| url.origin().ascii_serialization() == local_origin)
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Port of the running local sidecar, if this shell owns or adopted one.
+fn local_sidecar_port(app: &tauri::AppHandle) -> Option<u16> {
+    let state = app.try_state::<SidecarState>()?;
+    let running = state.child.lock().expect("sidecar mutex").is_some()
+        || state
+            .adopted_pid
+            .lock()
+            .expect("sidecar adopted pid mutex")
+            .is_some();
+    if !running {
+        return None;
+    }
+    let url = state.url.lock().expect("sidecar url mutex").clone()?;
+    url::Url::parse(&url).ok()?.port()
+}
+
+/// Stop the local sidecar gracefully and start it again on the same port
+/// with freshly resolved launch settings, then reload its windows. Used
+/// when a setting the sidecar only reads at launch changes.
+async fn restart_local_sidecar(app: &tauri::AppHandle) -> Result<String> {
+    let state = app
+        .try_state::<SidecarState>()
+        .ok_or_else(|| anyhow!("Sidecar state unavailable"))?;
+    let Some(port) = local_sidecar_port(app) else {
+        return Err(anyhow!("The local OpenChamber server is not running"));
+    };
+
+    log::info!("[sidecar] restarting on port {port} to apply new settings");
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || kill_sidecar(handle))
+        .await
+        .map_err(|err| anyhow!("Failed to stop sidecar: {err}"))?;
+
+    let launch = resolve_sidecar_launch(app).await?;
+    *state.launch.lock().expect("sidecar launch mutex") = Some(launch.clone());
+    spawn_sidecar_process(app, &launch, port)?;
+
+    let url = build_local_url(port);
+    if !wait_for_health_with(
+        &url,
+        LOCAL_SIDECAR_HEALTH_TIMEOUT,
+        LOCAL_SIDECAR_HEALTH_POLL_INITIAL_INTERVAL,
+        LOCAL_SIDECAR_HEALTH_POLL_MAX_INTERVAL,
+    )
+    .await
+    {
+        return Err(anyhow!("Restarted sidecar did not become healthy"));
+    }
+
+    for window in local_origin_windows(app) {
+        let _ = window.eval("window.location.reload();");
+    }
+    Ok(url)
+}
+
+/// PATH the sidecar runs with, falling back to the shell's own.
+fn sidecar_path_env(app: &tauri::AppHandle) -> String {
+    app.try_state::<SidecarState>()
+        .and_then(|state| {
+            state
+                .launch
+                .lock()
+                .expect("sidecar launch mutex")
+                .as_ref()
+                .map(|launch| launch.path.clone())
+        })
+        .unwrap_or_else(|| env::var("PATH").unwrap_or_default())
+}
+
+/// List every opencode binary the desktop can find, with its version and
+/// whether the bundled server supports it.
+#[tauri::command]
+async fn desktop_detect_opencode(
+    app: tauri::AppHandle,
+) -> Result<opencode_detect::OpencodeDetection, String> {
+    let configured =
+        opencode_binary_from_settings(read_desktop_settings_json().as_ref()).map(PathBuf::from);
+    let path_env = sidecar_path_env(&app);
+    let home = app.path().home_dir().ok();
+    tauri::async_runtime::spawn_blocking(move || {
+        opencode_detect::detect_opencode(configured, &path_env, home.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+/// Persist `path` as `opencodeBinary` (`None` clears the override) and
+/// restart the local sidecar so it takes effect.
+#[tauri::command]
+async fn desktop_select_opencode(
+    app: tauri::AppHandle,
+    path: Option<String>,
+) -> Result<Option<opencode_detect::OpencodeCandidate>, String> {
+    let binary = path
+        .as_deref()
+        .and_then(opencode_detect::resolve_binary_path);
+    let candidate = match binary {
+        Some(binary) => {
+            let path_env = sidecar_path_env(&app);
+            let candidate = tauri::async_runtime::spawn_blocking(move || {
+                opencode_detect::probe_candidate(&binary, Vec::new(), &path_env)
+            })
+            .await
+            .map_err(|err| err.to_string())?;
+            if !candidate.compatible {
+                return Err(candidate
+                    .error
+                    .unwrap_or_else(|| "Incompatible opencode binary".to_string()));
+            }
+            Some(candidate)
+        }
+        None => None,
+    };
+
+    // The server treats an empty string as "clear the override".
+    let value = candidate
+        .as_ref()
+        .map(|candidate| candidate.path.clone())
+        .unwrap_or_default();
+    write_desktop_setting_to_disk("opencodeBinary", serde_json::Value::String(value))
+        .map_err(|err| err.to_string())?;
+
+    restart_local_sidecar(&app)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(candidate)
+}
+
+/// `sidecarEnv` and `sidecarArgs`, with secret values withheld.
+#[tauri::command]
+fn desktop_sidecar_env_get() -> sidecar_env::SidecarEnvSettings {
+    let (env, args) = sidecar_env::read_from_settings(read_desktop_settings_json().as_ref());
+    sidecar_env::to_settings_view(&env, &args)
+}
+
+/// Save `sidecarEnv` and `sidecarArgs`. A secret sent without a value keeps
+/// its stored one. When anything changed and the local sidecar is running
+/// it is restarted so the new values apply.
+#[tauri::command]
+async fn desktop_sidecar_env_set(
+    app: tauri::AppHandle,
+    env: Vec<sidecar_env::SidecarEnvEntryEdit>,
+    args: Vec<String>,
+) -> Result<sidecar_env::SidecarEnvSettings, String> {
+    let (current_env, current_args) =
+        sidecar_env::read_from_settings(read_desktop_settings_json().as_ref());
+    let next_env = sidecar_env::apply_edits(&current_env, env).map_err(|err| err.to_string())?;
+    sidecar_env::validate_args(&args).map_err(|err| err.to_string())?;
+
+    let view = sidecar_env::to_settings_view(&next_env, &args);
+    if next_env == current_env && args == current_args {
+        return Ok(view);
+    }
+
+    let env_value = serde_json::to_value(&next_env).map_err(|err| err.to_string())?;
+    write_desktop_setting_to_disk(sidecar_env::SIDECAR_ENV_SETTINGS_KEY, env_value)
+        .map_err(|err| err.to_string())?;
+    write_desktop_setting_to_disk(
+        sidecar_env::SIDECAR_ARGS_SETTINGS_KEY,
+        serde_json::json!(args),
+    )
+    .map_err(|err| err.to_string())?;
+
+    if local_sidecar_port(&app).is_some() {
+        restart_local_sidecar(&app)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(view)
+}
+
+/// Restart a crashed sidecar on the same port with backoff. Once health is
+/// back, windows on the local origin are reloaded; after too many crashes
+/// they are switched to the recovery boot outcome instead.
+async fn supervise_sidecar_restart(
+    app: &tauri::AppHandle,
+    generation: u64,
+    port: u16,
+    reason: String,
+) {
+    let Some(state) = app.try_state::<SidecarState>() else {
+        return;
+    };
+    let Some(launch) = state.launch.lock().expect("sidecar launch mutex").clone() else {
+        return;
+    };
+    let url = build_local_url(port);
+    let mut expected = generation;
+    let mut reason = reason;
+    let mut attempt: u32 = 0;
+
+    loop {
+        let backoff = state
+            .restart_policy
+            .lock()
+            .expect("sidecar restart policy mutex")
+            .record_crash(std::time::Instant::now());
+        attempt += 1;
+
+        let Some(backoff) = backoff else {
+            log::error!("[sidecar] giving up after repeated crashes: {reason}");
+            let cfg = read_desktop_hosts_config_from_disk();
+            let boot_outcome = compute_local_startup_failure_boot_outcome(&cfg);
+            let init_script = build_startup_failure_init_script(&boot_outcome);
+            if let Some(injection) = app.try_state::<DesktopUiInjectionState>() {
+                let mut scripts = injection
+                    .scripts
+                    .lock()
+                    .expect("desktop ui injection mutex");
+                for window in local_origin_windows(app) {
+                    scripts.insert(window.label().to_string(), init_script.clone());
+                    let _ = window.eval(&init_script);
+                }
+            }
+            dispatch_sidecar_event(
+                app,
+                SIDECAR_DOWN_EVENT,
+                &serde_json::json!({
+                    "reason": reason,
+                    "attempt": attempt,
+                    "gaveUp": true,
+                    "bootOutcome": boot_outcome,
+                }),
+            );
+            return;
+        };
+
+        dispatch_sidecar_event(
+            app,
+            SIDECAR_DOWN_EVENT,
+            &serde_json::json!({
+                "reason": reason,
+                "attempt": attempt,
+                "retryInMs": backoff.as_millis() as u64,
+                "gaveUp": false,
+            }),
+        );
+        tokio::time::sleep(backoff).await;
+
+        // The app stopped or replaced the sidecar while we were waiting.
+        if state.generation.load(Ordering::SeqCst) != expected {
+            return;
+        }
+
+        if let Err(err) = spawn_sidecar_process(app, &launch, port) {
+            reason = format!("restart failed: {err}");
+            continue;
+        }
+        expected = state.generation.load(Ordering::SeqCst);
+
+        if wait_for_health_with(
             &url,
             LOCAL_SIDECAR_HEALTH_TIMEOUT,
             LOCAL_SIDECAR_HEALTH_POLL_INITIAL_INTERVAL,
@@ -2737,15 +3664,24 @@ async fn spawn_local_server(app: &tauri::AppHandle) -> Result<String> {
         )
         .await
         {
-            kill_sidecar(app.clone());
-            continue;
+            log::info!("[sidecar] restarted on port {port} after {attempt} attempt(s)");
+            dispatch_sidecar_event(
+                app,
+                SIDECAR_UP_EVENT,
+                &serde_json::json!({ "url": url, "attempt": attempt }),
+            );
+            for window in local_origin_windows(app) {
+                let _ = window.eval("window.location.reload();");
+            }
+            return;
         }
 
-        let _ = write_desktop_local_port_to_disk(port);
-        return Ok(url);
+        if state.generation.load(Ordering::SeqCst) != expected {
+            return;
+        }
+        expected = stop_sidecar_child(&state);
+        reason = "restarted sidecar did not become healthy".to_string();
     }
-
-    Err(anyhow!("Sidecar health check failed"))
 }
 
 fn resolve_web_dist_dir(app: &tauri::AppHandle) -> Result<PathBuf> {
@@ -3172,6 +4108,14 @@ fn build_init_script(local_origin: &str, boot_outcome: Option<&DesktopBootOutcom
         "(function(){{try{{window.__OPENCHAMBER_HOME__={home_json};window.__OPENCHAMBER_MACOS_MAJOR__={macos_major};window.__OPENCHAMBER_LOCAL_ORIGIN__={local_json};window.__OPENCHAMBER_DESKTOP_BOOT_OUTCOME__={boot_outcome_json};}}catch(_e){{}}}})();"
     );
 
+    if !local_origin.is_empty() {
+        init_script.push('\n');
+        init_script.push_str(&sidecar_protocol::sidecar_auth_token_script(
+            local_origin,
+            desktop_auth_token(),
+        ));
+    }
+
     // Cleanup: older builds injected a native-ish Instance switcher button into pages.
     // Remove it if present so the UI-owned host switcher is the only one.
     init_script.push_str("\ntry{var old=document.getElementById('__oc-instance-switcher');if(old)old.remove();}catch(_e){}");
@@ -3664,7 +4608,8 @@ fn open_new_window(app: &tauri::AppHandle) {
         return;
     };
 
-    // Resolve the URL the same way as initial setup: env override, then default host, else local.
+    // Resolve the URL the same way as initial setup: forced host or env
+    // override, then default host, else local.
     let local_url = app
         .try_state::<SidecarState>()
         .and_then(|state| state.url.lock().expect("sidecar url mutex").clone())
@@ -3677,12 +4622,9 @@ fn open_new_window(app: &tauri::AppHandle) {
         local_url.clone()
     };
 
-    let env_target = std::env::var("OPENCHAMBER_SERVER_URL")
-        .ok()
-        .and_then(|raw| normalize_server_url(&raw))
-        .filter(|url| !same_server_url(url, &local_ui_url));
+    let env_target = forced_server_url(app, &local_ui_url);
 
-    let cfg = read_desktop_hosts_config_from_disk();
+    let cfg = boot_hosts_config(app);
 
     let target_url = if let Some(ref env_url) = env_target {
         env_url.clone()
@@ -3756,7 +4698,591 @@ fn open_new_window(app: &tauri::AppHandle) {
     });
 }
 
+/// Bring an existing window forward, or open one if all were closed.
+fn focus_any_window(app: &tauri::AppHandle) {
+    let windows = app.webview_windows();
+    let window = windows
+        .values()
+        .find(|w| w.is_visible().unwrap_or(false))
+        .or_else(|| windows.values().next());
+    if let Some(window) = window {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    } else {
+        drop(windows);
+        open_new_window(app);
+    }
+}
+
+/// Focus a window already showing `url`'s server, else open a new one there.
+async fn focus_or_open_url(app: tauri::AppHandle, url: String, new_window: bool) {
+    let existing = app.webview_windows().into_values().find(|window| {
+        !new_window
+            && window
+                .url()
+                .is_ok_and(|current| same_server_url(current.as_str(), &url))
+    });
+    if let Some(window) = existing {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    if let Err(err) = desktop_new_window_at_url(app, url.clone()).await {
+        log::warn!("[desktop] failed to open window for {url}: {err}");
+    }
+}
+
+/// Open `path` as a project in a local window. The path is queued for the
+/// UI to take; the focused local window is poked to do so, otherwise a new
+/// local window takes it once it has loaded.
+fn open_project_in_local_window(app: &tauri::AppHandle, path: &Path, new_window: bool) {
+    let Some(local_origin) = app
+        .try_state::<DesktopUiInjectionState>()
+        .and_then(|state| {
+            state
+                .local_origin
+                .lock()
+                .expect("desktop local origin mutex")
+                .clone()
+        })
+    else {
+        log::warn!("[desktop] cannot open project yet: local origin not known");
+        return;
+    };
+    if let Some(state) = app.try_state::<LaunchRequestState>() {
+        state
+            .pending_projects
+            .lock()
+            .expect("pending projects mutex")
+            .push(path.to_string_lossy().to_string());
+    }
+
+    let windows = app.webview_windows();
+    let mut local_windows: Vec<_> = windows
+        .values()
+        .filter(|window| {
+            !new_window
+                && window.url().is_ok_and(|url| {
+                    same_server_url(&url.origin().ascii_serialization(), &local_origin)
+                })
+        })
+        .collect();
+    local_windows.sort_by_key(|window| !window.is_focused().unwrap_or(false));
+    if let Some(window) = local_windows.first() {
+        let event = serde_json::to_string(OPEN_PROJECT_EVENT).unwrap_or_default();
+        let _ = window.eval(format!("window.dispatchEvent(new Event({event}));"));
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    drop(windows);
+
+    let boot_outcome = DesktopBootOutcome {
+        target: Some("local".to_string()),
+        status: "ok".to_string(),
+        host_id: None,
+        url: None,
+    };
+    if let Err(err) = create_window(
+        app,
+        &local_origin,
+        &local_origin,
+        Some(&boot_outcome),
+        false,
+    ) {
+        log::error!("[desktop] failed to open project window: {err}");
+    }
+}
+
+/// Project paths queued by launches and links, handed to the first local
+/// window that asks.
+#[tauri::command]
+fn desktop_take_pending_projects(
+    state: tauri::State<'_, LaunchRequestState>,
+) -> Result<Vec<String>, String> {
+    let mut pending = state
+        .pending_projects
+        .lock()
+        .expect("pending projects mutex");
+    Ok(std::mem::take(&mut *pending))
+}
+
+/// Show `session` on the server at `base_url`, reusing a window already on
+/// that server.
+async fn open_session_window(app: tauri::AppHandle, base_url: String, session: String) {
+    let Ok(mut target) = url::Url::parse(&base_url) else {
+        log::warn!("[desktop] cannot open session: invalid host URL");
+        return;
+    };
+    target.query_pairs_mut().append_pair("session", &session);
+
+    let target_origin = target.origin().ascii_serialization();
+    let existing = app.webview_windows().into_values().find(|window| {
+        window.url().is_ok_and(|current| {
+            same_server_url(&current.origin().ascii_serialization(), &target_origin)
+        })
+    });
+    if let Some(window) = existing {
+        let _ = window.navigate(target);
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    if let Err(err) = desktop_new_window_at_url(app, target.to_string()).await {
+        log::warn!("[desktop] failed to open session window: {err}");
+    }
+}
+
+/// Handle an `openchamber://` link delivered by macOS. Links that arrive
+/// while the app is still starting are queued until the first window is
+/// ready; other platforms pass links as launch arguments.
+#[cfg(target_os = "macos")]
+fn handle_deep_link(app: &tauri::AppHandle, raw: &str) {
+    handle_launch_request(
+        app,
+        single_instance::LaunchRequest {
+            target: single_instance::LaunchTarget::DeepLink(raw.to_string()),
+            new_window: false,
+        },
+    );
+}
+
+/// Links reach the app from browsers, chats and other apps, so each one is
+/// confirmed before it connects a host or adds a project. `--project`,
+/// `--host` and `--ssh-connect` launches, first or forwarded, never get here.
+fn open_deep_link(app: &tauri::AppHandle, raw: &str) {
+    use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+
+    let link = match deep_link::parse(raw) {
+        Ok(link) => link,
+        Err(err) => {
+            log::warn!("[desktop] ignoring link: {err}");
+            return;
+        }
+    };
+    log::info!("[desktop] confirming link: {link:?}");
+
+    // A hidden window keeps the native dialog from appearing reliably.
+    let windows = app.webview_windows();
+    if !windows.values().any(|w| w.is_visible().unwrap_or(false)) {
+        if let Some(hidden) = windows.values().next() {
+            let _ = hidden.show();
+            let _ = hidden.set_focus();
+        }
+    }
+    drop(windows);
+
+    let handle = app.clone();
+    app.dialog()
+        .message(link.confirmation_prompt())
+        .title("Open link in OpenChamber?")
+        .buttons(MessageDialogButtons::OkCancelCustom(
+            "Open".to_string(),
+            "Cancel".to_string(),
+        ))
+        .kind(tauri_plugin_dialog::MessageDialogKind::Info)
+        .show(move |confirmed| {
+            if confirmed {
+                dispatch_deep_link(&handle, link);
+            } else {
+                log::info!("[desktop] link declined");
+            }
+        });
+}
+
+fn dispatch_deep_link(app: &tauri::AppHandle, link: deep_link::DeepLink) {
+    log::info!("[desktop] opening link: {link:?}");
+    let target = match link {
+        deep_link::DeepLink::OpenProject { path } => single_instance::LaunchTarget::Project(path),
+        deep_link::DeepLink::SshConnect { instance_id } => {
+            single_instance::LaunchTarget::SshConnect(instance_id)
+        }
+        deep_link::DeepLink::Session { id, host } => {
+            let handle = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let host = host.as_deref().unwrap_or(LOCAL_HOST_ID);
+                match resolve_host_url(&handle, host).await {
+                    Ok(url) => open_session_window(handle, url, id).await,
+                    Err(err) => log::warn!("[desktop] cannot open session on host {host}: {err}"),
+                }
+            });
+            return;
+        }
+    };
+    handle_launch_request(
+        app,
+        single_instance::LaunchRequest {
+            target,
+            new_window: false,
+        },
+    );
+}
+
+/// Mark startup as finished and handle launches queued until now.
+fn release_pending_launches(app: &tauri::AppHandle) {
+    let Some(state) = app.try_state::<LaunchRequestState>() else {
+        return;
+    };
+    let pending = {
+        let mut pending = state.pending.lock().expect("pending launches mutex");
+        state.ready.store(true, Ordering::SeqCst);
+        std::mem::take(&mut *pending)
+    };
+    for request in pending {
+        handle_launch_request(app, request);
+    }
+}
+
+/// Act on the arguments of a second launch that was forwarded here.
+fn handle_forwarded_launch(app: &tauri::AppHandle, launch: single_instance::ForwardedLaunch) {
+    let request = single_instance::parse_request(&launch).unwrap_or_else(|err| {
+        log::warn!("[desktop] ignoring forwarded arguments: {err}");
+        single_instance::LaunchRequest {
+            target: single_instance::LaunchTarget::Focus,
+            new_window: false,
+        }
+    });
+    log::info!("[desktop] second launch forwarded: {request:?}");
+    handle_launch_request(app, request);
+}
+
+/// Show what a launch or link asks for. Requests that arrive while the app
+/// is still starting are queued until the first window is ready.
+fn handle_launch_request(app: &tauri::AppHandle, request: single_instance::LaunchRequest) {
+    if let Some(state) = app.try_state::<LaunchRequestState>() {
+        let mut pending = state.pending.lock().expect("pending launches mutex");
+        if !state.ready.load(Ordering::SeqCst) {
+            pending.push(request);
+            return;
+        }
+    }
+
+    let single_instance::LaunchRequest { target, new_window } = request;
+    let handle = app.clone();
+    if is_headless() {
+        handle_headless_launch(handle, target);
+        return;
+    }
+    match target {
+        single_instance::LaunchTarget::Focus if new_window => {
+            let _ = app.run_on_main_thread(move || open_new_window(&handle));
+        }
+        single_instance::LaunchTarget::Focus => {
+            let _ = app.run_on_main_thread(move || focus_any_window(&handle));
+        }
+        single_instance::LaunchTarget::Project(path) => {
+            if !path.is_dir() {
+                log::warn!("[desktop] project is not a directory: {}", path.display());
+                let _ = app.run_on_main_thread(move || focus_any_window(&handle));
+                return;
+            }
+            let _ = app.run_on_main_thread(move || {
+                open_project_in_local_window(&handle, &path, new_window)
+            });
+        }
+        single_instance::LaunchTarget::Url(url) => {
+            tauri::async_runtime::spawn(focus_or_open_url(handle, url, new_window));
+        }
+        single_instance::LaunchTarget::DeepLink(link) => open_deep_link(app, &link),
+        single_instance::LaunchTarget::HostId(id) => {
+            tauri::async_runtime::spawn(async move {
+                match resolve_host_url(&handle, &id).await {
+                    Ok(url) => focus_or_open_url(handle, url, new_window).await,
+                    Err(err) => {
+                        log::warn!("[desktop] cannot open host {id}: {err}");
+                        let focus_handle = handle.clone();
+                        let _ = handle.run_on_main_thread(move || focus_any_window(&focus_handle));
+                    }
+                }
+            });
+        }
+        single_instance::LaunchTarget::SshConnect(id) => {
+            tauri::async_runtime::spawn(async move {
+                let result = match handle.try_state::<DesktopSshManagerState>() {
+                    Some(state) => state.connect_and_wait(&handle, &id).await,
+                    None => Err(anyhow!("SSH manager unavailable")),
+                };
+                match result {
+                    Ok(url) => focus_or_open_url(handle, url, new_window).await,
+                    Err(err) => log::warn!("[desktop] SSH connect for {id} failed: {err}"),
+                }
+            });
+        }
+    }
+}
+
+/// Without windows only connections and local profiles can be started;
+/// everything else is logged and ignored.
+fn handle_headless_launch(app: tauri::AppHandle, target: single_instance::LaunchTarget) {
+    let id = match target {
+        single_instance::LaunchTarget::SshConnect(id) => id,
+        single_instance::LaunchTarget::HostId(id) if id != LOCAL_HOST_ID => id,
+        single_instance::LaunchTarget::Focus => return,
+        other => {
+            log::info!("[desktop] headless: ignoring launch {other:?}");
+            return;
+        }
+    };
+    tauri::async_runtime::spawn(async move {
+        match resolve_host_url(&app, &id).await {
+            Ok(url) => log::info!("[desktop] headless: {id} ready at {url}"),
+            Err(err) => log::warn!("[desktop] headless: cannot start {id}: {err}"),
+        }
+    });
+}
+
+/// Snapshot served at `GET /status` by the headless control API.
+fn headless_status(app: &tauri::AppHandle) -> serde_json::Value {
+    let sidecar = app.try_state::<SidecarState>().map(|state| {
+        let running = state.child.lock().expect("sidecar mutex").is_some()
+            || state
+                .adopted_pid
+                .lock()
+                .expect("sidecar adopted pid mutex")
+                .is_some();
+        serde_json::json!({
+            "url": state.url.lock().expect("sidecar url mutex").clone(),
+            "running": running,
+            "restarting": state.restarting.load(Ordering::SeqCst),
+        })
+    });
+    serde_json::json!({
+        "pid": std::process::id(),
+        "version": env!("CARGO_PKG_VERSION"),
+        "shuttingDown": QUIT_SHUTDOWN_STARTED.load(Ordering::SeqCst),
+        "sidecar": sidecar,
+        "quitRisk": {
+            "busySessionsCount": QUIT_RISK_BUSY_SESSIONS_COUNT.load(Ordering::Relaxed),
+            "runningScheduledTasksCount":
+                QUIT_RISK_RUNNING_SCHEDULED_TASKS_COUNT.load(Ordering::Relaxed),
+            "enabledScheduledTasksCount":
+                QUIT_RISK_ENABLED_SCHEDULED_TASKS_COUNT.load(Ordering::Relaxed),
+            "hasActiveTunnel": QUIT_RISK_HAS_ACTIVE_TUNNEL.load(Ordering::Relaxed),
+        },
+        "ssh": app
+            .try_state::<DesktopSshManagerState>()
+            .map(|state| state.statuses())
+            .unwrap_or_default(),
+        "docker": app
+            .try_state::<DesktopDockerManagerState>()
+            .map(|state| state.statuses())
+            .unwrap_or_default(),
+        "kube": app
+            .try_state::<DesktopKubeManagerState>()
+            .map(|state| state.statuses())
+            .unwrap_or_default(),
+        "localProfiles": app
+            .try_state::<DesktopLocalProfileManagerState>()
+            .map(|state| state.statuses())
+            .unwrap_or_default(),
+    })
+}
+
+/// Quit as if confirmed: the exit request waits for busy sessions and stops
+/// background services before the process ends.
+fn request_headless_shutdown(app: &tauri::AppHandle) {
+    if QUIT_CONFIRMED.swap(true, Ordering::SeqCst) {
+        return;
+    }
+    log::info!("[desktop] headless: shutting down");
+    app.exit(0);
+}
+
+/// Resolves on SIGTERM or SIGINT (Ctrl+C elsewhere).
+async fn wait_for_termination_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = terminate.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+            }
+            Err(err) => {
+                log::warn!("[desktop] cannot listen for SIGTERM: {err}");
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Serve the control API and shut down on a termination signal.
+fn start_headless_control(app: &tauri::AppHandle) {
+    let path = headless::control_file_path();
+    match headless::bind(&path) {
+        Ok(server) => {
+            log::info!(
+                "[desktop] headless: control API on http://127.0.0.1:{} (token in {})",
+                server.record.port,
+                path.display()
+            );
+            let status_handle = app.clone();
+            let shutdown_handle = app.clone();
+            headless::serve(
+                server,
+                move || headless_status(&status_handle),
+                move || request_headless_shutdown(&shutdown_handle),
+            );
+        }
+        Err(err) => log::error!("[desktop] headless: control API unavailable: {err}"),
+    }
+
+    let handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        wait_for_termination_signal().await;
+        request_headless_shutdown(&handle);
+    });
+}
+
+/// URL for a configured host id. Local profiles and SSH, Docker and
+/// Kubernetes instances are started or connected on demand.
+async fn resolve_host_url(app: &tauri::AppHandle, id: &str) -> Result<String> {
+    if id == LOCAL_HOST_ID {
+        return app
+            .try_state::<SidecarState>()
+            .and_then(|state| state.url.lock().expect("sidecar url mutex").clone())
+            .ok_or_else(|| anyhow!("Local server is not running yet"));
+    }
+    if let Some(state) = app.try_state::<DesktopLocalProfileManagerState>() {
+        if state.has_profile(id) {
+            return state.start(app, id).await;
+        }
+    }
+    if let Some(state) = app.try_state::<DesktopSshManagerState>() {
+        if state.has_instance(id) {
+            return state.connect_and_wait(app, id).await;
+        }
+    }
+    if let Some(state) = app.try_state::<DesktopDockerManagerState>() {
+        if state.has_instance(id) {
+            return state.connect_and_wait(app, id).await;
+        }
+    }
+    if let Some(state) = app.try_state::<DesktopKubeManagerState>() {
+        if state.has_instance(id) {
+            return state.connect_and_wait(app, id).await;
+        }
+    }
+    read_desktop_hosts_config_from_disk()
+        .hosts
+        .into_iter()
+        .find(|host| host.id == id)
+        .map(|host| host.url)
+        .ok_or_else(|| anyhow!("Unknown host id"))
+}
+
+/// `--list-hosts`: one tab-separated line per host with its id, kind, URL,
+/// label and whether it is the default.
+fn print_host_list() {
+    let settings_path = settings_file_path();
+    let cfg = read_desktop_hosts_config_from_path(&settings_path);
+    let root = remote_ssh::read_settings_root(&settings_path);
+    let default_id = cfg.default_host_id.as_deref().unwrap_or("");
+    let marker = |id: &str| if id == default_id { "default" } else { "" };
+
+    println!("{LOCAL_HOST_ID}\tlocal\t\tLocal\t{}", marker(LOCAL_HOST_ID));
+    for host in &cfg.hosts {
+        let kind = remote_ssh::HostKind::of(&root, &host.id)
+            .map(remote_ssh::HostKind::label)
+            .unwrap_or("remote");
+        println!(
+            "{}\t{kind}\t{}\t{}\t{}",
+            host.id,
+            host.url,
+            host.label,
+            marker(&host.id)
+        );
+    }
+}
+
+/// Decide how the first launch's target is shown. `--host` with a URL,
+/// `local` or a plain configured host replaces the startup host so it gets
+/// the boot probe and recovery screens; everything else is opened once the
+/// startup window is ready.
+fn apply_initial_launch(app: &tauri::AppHandle, request: single_instance::LaunchRequest) {
+    let forced = match &request.target {
+        single_instance::LaunchTarget::Focus => return,
+        single_instance::LaunchTarget::Url(url) => Some(ForcedHost::Url(url.clone())),
+        single_instance::LaunchTarget::HostId(id) => {
+            let managed = app
+                .try_state::<DesktopSshManagerState>()
+                .is_some_and(|state| state.has_instance(id))
+                || app
+                    .try_state::<DesktopLocalProfileManagerState>()
+                    .is_some_and(|state| state.has_profile(id));
+            let configured = read_desktop_hosts_config_from_disk()
+                .hosts
+                .iter()
+                .any(|host| &host.id == id);
+            (id == LOCAL_HOST_ID || (configured && !managed)).then(|| ForcedHost::Id(id.clone()))
+        }
+        _ => None,
+    };
+    match forced {
+        Some(forced) => {
+            log::info!("[desktop] startup host forced from the command line: {forced:?}");
+            if let Some(state) = app.try_state::<LaunchRequestState>() {
+                *state.forced_host.lock().expect("forced host mutex") = Some(forced);
+            }
+        }
+        None => handle_launch_request(app, request),
+    }
+}
+
 fn main() {
+    let cli_args = match cli::parse(&env::args().skip(1).collect::<Vec<_>>()) {
+        Ok(cli_args) => cli_args,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", cli::USAGE);
+            std::process::exit(2);
+        }
+    };
+    if cli_args.help {
+        println!("{}", cli::USAGE);
+        return;
+    }
+    if cli_args.version {
+        println!("openchamber-desktop {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+    HEADLESS.store(cli_args.headless, Ordering::Relaxed);
+    if let Some(dir) = cli_args.data_dir.as_deref() {
+        // Set before the instance lock so another data dir is another
+        // instance; the sidecar inherits it too.
+        let cwd = env::current_dir().ok();
+        env::set_var(
+            "OPENCHAMBER_DATA_DIR",
+            cli::absolute_path(dir, cwd.as_deref()),
+        );
+    }
+    if cli_args.list_hosts {
+        print_host_list();
+        return;
+    }
+
+    let instance_path = single_instance::instance_file_path();
+    // A headless launch must not make a running GUI open windows, so it
+    // only probes for one.
+    let launch = (!cli_args.headless).then(single_instance::ForwardedLaunch::from_env);
+    let primary_instance = match single_instance::acquire(&instance_path, launch.as_ref()) {
+        Ok(single_instance::Acquired::Running) => {
+            eprintln!("[desktop] another instance is already running for this data directory");
+            std::process::exit(1);
+        }
+        Ok(single_instance::Acquired::Forwarded) => return,
+        Ok(single_instance::Acquired::Primary(instance)) => Some(instance),
+        Err(err) => {
+            // Logging is not set up yet; run without the lock rather than not at all.
+            eprintln!("[desktop] single-instance lock unavailable: {err}");
+            None
+        }
+    };
+
     // Ensure localhost traffic never routes through a system/VPN proxy.
     for key in ["NO_PROXY", "no_proxy"] {
         let existing = env::var(key).unwrap_or_default();
@@ -3776,26 +5302,41 @@ fn main() {
         }
     }
 
+    let log_file_target = tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Folder {
+        path: desktop_logs::logs_dir(),
+        file_name: Some(desktop_logs::DESKTOP_LOG_NAME.to_string()),
+    });
     let log_builder = tauri_plugin_log::Builder::default()
-        .level(log::LevelFilter::Info)
+        .level(cli_args.log_level.unwrap_or(log::LevelFilter::Info))
         .clear_targets()
+        .max_file_size(u128::from(desktop_logs::MAX_LOG_FILE_BYTES))
+        .rotation_strategy(tauri_plugin_log::RotationStrategy::KeepSome(
+            desktop_logs::KEEP_ROTATED_LOG_FILES,
+        ))
         .targets(if cfg!(debug_assertions) {
             vec![
                 tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
                 tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Webview),
+                log_file_target,
             ]
         } else {
-            vec![tauri_plugin_log::Target::new(
-                tauri_plugin_log::TargetKind::Stdout,
-            )]
+            vec![
+                tauri_plugin_log::Target::new(tauri_plugin_log::TargetKind::Stdout),
+                log_file_target,
+            ]
         });
 
     let builder = tauri::Builder::default()
         .manage(SidecarState::default())
         .manage(DesktopUiInjectionState::default())
+        .manage(LaunchRequestState::default())
         .manage(WindowFocusState::default())
         .manage(WindowGeometryDebounceState::default())
         .manage(DesktopSshManagerState::default())
+        .manage(DesktopDockerManagerState::default())
+        .manage(DesktopKubeManagerState::default())
+        .manage(DesktopLocalProfileManagerState::default())
+        .manage(DesktopLogsState::default())
         .manage(PendingUpdate(Mutex::new(None)))
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
@@ -3974,7 +5515,18 @@ fn main() {
 
             if let tauri::WindowEvent::Focused(focused) = event {
                 if let Some(state) = app.try_state::<WindowFocusState>() {
+                    let was_focused = state.any_focused();
                     state.set_focused(&label, *focused);
+                    let any_focused = state.any_focused();
+                    if any_focused != was_focused {
+                        send_to_sidecar(
+                            app,
+                            &DesktopMessage::event(
+                                "focusChanged",
+                                serde_json::json!({ "anyFocused": any_focused }),
+                            ),
+                        );
+                    }
                 }
             }
 
@@ -4032,21 +5584,75 @@ fn main() {
             desktop_get_lan_address,
             remote_ssh::desktop_ssh_instances_get,
             remote_ssh::desktop_ssh_instances_set,
+            remote_ssh::desktop_ssh_instances_export,
+            remote_ssh::desktop_ssh_instances_import,
             remote_ssh::desktop_ssh_import_hosts,
             remote_ssh::desktop_ssh_connect,
             remote_ssh::desktop_ssh_disconnect,
             remote_ssh::desktop_ssh_status,
+            remote_ssh::desktop_ssh_metrics,
             remote_ssh::desktop_ssh_logs,
             remote_ssh::desktop_ssh_logs_clear,
+            remote_ssh::desktop_ssh_list_identities,
+            remote_ssh::desktop_ssh_bulk_connect,
+            remote_ssh::desktop_ssh_bulk_disconnect,
+            remote_ssh::desktop_ssh_bulk_status,
+            remote_docker::desktop_docker_instances_get,
+            remote_docker::desktop_docker_instances_set,
+            remote_docker::desktop_docker_connect,
+            remote_docker::desktop_docker_disconnect,
+            remote_docker::desktop_docker_status,
+            remote_docker::desktop_docker_logs,
+            remote_kube::desktop_kube_instances_get,
+            remote_kube::desktop_kube_instances_set,
+            remote_kube::desktop_kube_connect,
+            remote_kube::desktop_kube_disconnect,
+            remote_kube::desktop_kube_status,
+            remote_kube::desktop_kube_logs,
+            local_profiles::desktop_local_profiles_get,
+            local_profiles::desktop_local_profiles_set,
+            local_profiles::desktop_local_profile_start,
+            local_profiles::desktop_local_profile_stop,
+            local_profiles::desktop_local_profile_status,
+            local_profiles::desktop_local_profile_logs,
+            desktop_logs::desktop_logs_read,
+            desktop_logs::desktop_logs_save,
+            login_env::desktop_login_env_get,
+            desktop_detect_opencode,
+            desktop_select_opencode,
+            desktop_sidecar_env_get,
+            desktop_sidecar_env_set,
+            desktop_take_pending_projects,
             desktop_read_file,
         ])
-        .setup(|app| {
+        .setup(move |app| {
             let handle = app.handle().clone();
 
-            if let Err(err) = create_startup_window(&handle, true) {
+            if let Some(instance) = primary_instance {
+                let forward_handle = handle.clone();
+                single_instance::serve(instance, move |launch| {
+                    handle_forwarded_launch(&forward_handle, launch)
+                });
+            }
+
+            // Windows and Linux pass a clicked link as the first argument.
+            match single_instance::parse_request(&single_instance::ForwardedLaunch::from_env()) {
+                Ok(request) => apply_initial_launch(&handle, request),
+                Err(err) => log::warn!("[desktop] ignoring launch arguments: {err}"),
+            }
+
+            if is_headless() {
+                #[cfg(target_os = "macos")]
+                app.set_activation_policy(tauri::ActivationPolicy::Accessory);
+                start_headless_control(&handle);
+            } else if let Err(err) = create_startup_window(&handle, true) {
                 log::error!("[desktop] failed to create startup window: {err}");
             }
 
+            if let Some(state) = handle.try_state::<DesktopSshManagerState>() {
+                state.auto_connect_groups(&handle);
+            }
+
             tauri::async_runtime::spawn(async move {
                 // Helper: inject a fallback boot outcome when the local server
                 // cannot start, so the UI leaves the splash and shows
@@ -4110,25 +5716,27 @@ fn main() {
                 if let Some(state) = handle.try_state::<SidecarState>() {
                     *state.url.lock().expect("sidecar url mutex") = Some(local_url.clone());
                 }
-                start_quit_risk_poller(local_url.clone());
+
+                if is_headless() {
+                    log::info!("[desktop] headless: local server ready at {local_url}");
+                    release_pending_launches(&handle);
+                    return;
+                }
 
                 let local_origin = url::Url::parse(&local_ui_url)
                     .ok()
                     .map(|u| u.origin().ascii_serialization())
                     .unwrap_or_else(|| local_ui_url.clone());
 
-                // Selected host: env override first, then desktop default host, else local.
-                // If env override points to the local server, ignore it and use
-                // config-based resolution instead.
-                let env_target = std::env::var("OPENCHAMBER_SERVER_URL")
-                    .ok()
-                    .and_then(|raw| normalize_server_url(&raw))
-                    .filter(|url| !same_server_url(url, &local_ui_url));
+                // Selected host: `--host` or env override first, then desktop default
+                // host, else local. If the override points to the local server,
+                // ignore it and use config-based resolution instead.
+                let env_target = forced_server_url(&handle, &local_ui_url);
 
                 let mut initial_url = env_target.as_deref().unwrap_or(&local_ui_url).to_string();
 
                 // Compute boot outcome and legacy-upgrade if needed.
-                let cfg = read_desktop_hosts_config_from_disk();
+                let cfg = boot_hosts_config(&handle);
 
                 if env_target.is_none() {
                     if let Some(default_id) = cfg.default_host_id.as_deref() {
@@ -4202,6 +5810,7 @@ fn main() {
                 ) {
                     log::error!("[desktop] failed to activate main window: {err}");
                 }
+                release_pending_launches(&handle);
             });
 
             Ok(())
@@ -4224,16 +5833,43 @@ fn main() {
                     request_quit_with_confirmation(app_handle);
                     return;
                 }
-                if let Some(state) = app_handle.try_state::<DesktopSshManagerState>() {
-                    state.shutdown_all(app_handle);
+                if !QUIT_SHUTDOWN_FINISHED.load(Ordering::SeqCst) {
+                    // Waiting for busy sessions can take a while; do it off the
+                    // main thread so windows keep rendering, then exit again.
+                    api.prevent_exit();
+                    if !QUIT_SHUTDOWN_STARTED.swap(true, Ordering::SeqCst) {
+                        let busy = QUIT_RISK_BUSY_SESSIONS_COUNT.load(Ordering::Relaxed);
+                        let _ = app_handle.emit(
+                            SIDECAR_SHUTDOWN_EVENT,
+                            serde_json::json!({
+                                "busySessionsCount": busy,
+                                "timeoutMs": sidecar_shutdown_timeout().as_millis() as u64,
+                            }),
+                        );
+                        let handle = app_handle.clone();
+                        std::thread::spawn(move || {
+                            shutdown_background_services(&handle);
+                            QUIT_SHUTDOWN_FINISHED.store(true, Ordering::SeqCst);
+                            handle.exit(0);
+                        });
+                    }
                 }
-                kill_sidecar(app_handle.clone());
             }
             tauri::RunEvent::Exit => {
-                if let Some(state) = app_handle.try_state::<DesktopSshManagerState>() {
-                    state.shutdown_all(app_handle);
+                shutdown_background_services(app_handle);
+                single_instance::release(&single_instance::instance_file_path());
+                if is_headless() {
+                    single_instance::release(&headless::control_file_path());
+                }
+            }
+            #[cfg(target_os = "macos")]
+            tauri::RunEvent::Opened { urls } => {
+                for url in urls
+                    .iter()
+                    .filter(|url| url.scheme() == deep_link::DEEP_LINK_SCHEME)
+                {
+                    handle_deep_link(app_handle, url.as_str());
                 }
-                kill_sidecar(app_handle.clone());
             }
             #[cfg(target_os = "macos")]
             tauri::RunEvent::Reopen {
@@ -4242,9 +5878,7 @@ fn main() {
             } => {
                 if !has_visible_windows {
                     let windows = app_handle.webview_windows();
-                    let hidden = windows
-                        .values()
-                        .find(|w| !w.is_visible().unwrap_or(true));
+                    let hidden = windows.values().find(|w| !w.is_visible().unwrap_or(true));
                     if let Some(w) = hidden {
                         let _ = w.show();
                         let _ = w.set_focus();
@@ -4725,4 +6359,55 @@ mod tests {
             "init script must set __OPENCHAMBER_DESKTOP_BOOT_OUTCOME__"
         );
     }
+
+    #[test]
+    fn sidecar_restart_policy_backs_off_then_gives_up() {
+        let mut policy = SidecarRestartPolicy::default();
+        let start = std::time::Instant::now();
+        let backoffs: Vec<_> = (0..SIDECAR_MAX_CRASHES)
+            .map(|i| policy.record_crash(start + Duration::from_secs(i as u64)))
+            .collect();
+        assert_eq!(backoffs[0], Some(SIDECAR_RESTART_INITIAL_BACKOFF));
+        assert_eq!(backoffs[1], Some(SIDECAR_RESTART_INITIAL_BACKOFF * 2));
+        assert!(backoffs
+            .iter()
+            .all(|backoff| backoff.is_some_and(|d| d <= SIDECAR_RESTART_MAX_BACKOFF)));
+        assert_eq!(policy.record_crash(start + Duration::from_secs(10)), None);
+    }
+
+    #[test]
+    fn sidecar_restart_policy_forgets_old_crashes() {
+        let mut policy = SidecarRestartPolicy::default();
+        let start = std::time::Instant::now();
+        for i in 0..SIDECAR_MAX_CRASHES {
+            policy.record_crash(start + Duration::from_secs(i as u64));
+        }
+        let later = start + SIDECAR_CRASH_WINDOW + Duration::from_secs(60);
+        assert_eq!(
+            policy.record_crash(later),
+            Some(SIDECAR_RESTART_INITIAL_BACKOFF)
+        );
+    }
+
+    #[test]
+    fn sidecar_shutdown_timeout_reads_and_clamps_the_setting() {
+        let timeout =
+            |value: serde_json::Value| sidecar_shutdown_timeout_from_settings(Some(&value));
+        assert_eq!(
+            sidecar_shutdown_timeout_from_settings(None),
+            DEFAULT_SIDECAR_SHUTDOWN_TIMEOUT
+        );
+        assert_eq!(
+            timeout(serde_json::json!({ "desktopShutdownTimeoutSeconds": 90 })),
+            Duration::from_secs(90)
+        );
+        assert_eq!(
+            timeout(serde_json::json!({ "desktopShutdownTimeoutSeconds": 86_400 })),
+            MAX_SIDECAR_SHUTDOWN_TIMEOUT
+        );
+        assert_eq!(
+            timeout(serde_json::json!({ "desktopShutdownTimeoutSeconds": 0 })),
+            DEFAULT_SIDECAR_SHUTDOWN_TIMEOUT
+        );
+    }
 }
@@ -0,0 +1,295 @@
+use crate::login_env::run_with_timeout;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+/// Oldest opencode release the bundled server supports. Tracks the
+/// `@opencode-ai/sdk` range in `packages/web/package.json`.
+pub const MIN_OPENCODE_VERSION: (u64, u64, u64) = (1, 4, 25);
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Env vars the desktop and server accept as an opencode override.
+pub const OPENCODE_BINARY_ENV_VARS: &[&str] = &[
+    "OPENCHAMBER_OPENCODE_PATH",
+    "OPENCHAMBER_OPENCODE_BIN",
+    "OPENCODE_PATH",
+    "OPENCODE_BINARY",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpencodeCandidate {
+    pub path: String,
+    /// Where the candidate was found: `settings`, `env`, `path` or
+    /// `installDir`.
+    pub sources: Vec<String>,
+    /// Parsed `x.y.z` version, if `--version` printed one.
+    pub version: Option<String>,
+    pub version_output: Option<String>,
+    pub compatible: bool,
+    /// True for the binary `opencodeBinary` currently points at.
+    pub configured: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpencodeDetection {
+    pub configured: Option<String>,
+    pub minimum_version: String,
+    pub candidates: Vec<OpencodeCandidate>,
+}
+
+fn binary_names() -> &'static [&'static str] {
+    if cfg!(windows) {
+        &["opencode.exe", "opencode.cmd"]
+    } else {
+        &["opencode"]
+    }
+}
+
+/// Resolve a settings or env value that may name the binary or its
+/// directory.
+pub fn resolve_binary_path(value: &str) -> Option<PathBuf> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let path = PathBuf::from(trimmed);
+    if path.is_dir() {
+        return Some(path.join(binary_names()[0]));
+    }
+    Some(path)
+}
+
+/// Install locations used by the opencode installer and common package
+/// managers.
+fn install_dirs(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(home) = home {
+        for dir in [
+            ".opencode/bin",
+            ".local/bin",
+            ".bun/bin",
+            ".npm-global/bin",
+            ".volta/bin",
+            ".local/share/pnpm",
+            "Library/pnpm",
+            "AppData/Roaming/npm",
+        ] {
+            dirs.push(home.join(dir));
+        }
+    }
+    if !cfg!(windows) {
+        for dir in ["/opt/homebrew/bin", "/usr/local/bin", "/usr/bin"] {
+            dirs.push(PathBuf::from(dir));
+        }
+    }
+    dirs
+}
+
+/// Every place an opencode binary could come from, in the order the
+/// server would prefer them, with duplicates merged.
+pub fn candidate_paths(
+    configured: Option<&Path>,
+    env_overrides: &[PathBuf],
+    path_dirs: &[PathBuf],
+    home: Option<&Path>,
+) -> Vec<(PathBuf, Vec<&'static str>)> {
+    let mut found: Vec<(PathBuf, Vec<&'static str>)> = Vec::new();
+    let mut add = |path: PathBuf, source: &'static str| {
+        if let Some((_, sources)) = found.iter_mut().find(|(existing, _)| *existing == path) {
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+            return;
+        }
+        found.push((path, vec![source]));
+    };
+
+    if let Some(configured) = configured {
+        add(configured.to_path_buf(), "settings");
+    }
+    for path in env_overrides {
+        add(path.clone(), "env");
+    }
+    for (dirs, source) in [
+        (path_dirs.to_vec(), "path"),
+        (install_dirs(home), "installDir"),
+    ] {
+        for dir in dirs {
+            for name in binary_names() {
+                let path = dir.join(name);
+                if path.is_file() {
+                    add(path, source);
+                }
+            }
+        }
+    }
+    found
+}
+
+/// First `x.y.z` in `--version` output, ignoring a leading `v`.
+pub fn parse_opencode_version(output: &str) -> Option<(u64, u64, u64)> {
+    output
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '(' || c == ')')
+        .map(|token| token.trim_start_matches('v'))
+        .find_map(|token| {
+            let mut parts = token.splitn(3, '.');
+            let major = parts.next()?.parse().ok()?;
+            let minor = parts.next()?.parse().ok()?;
+            let patch_raw = parts.next()?;
+            let patch_digits: String = patch_raw
+                .chars()
+                .take_while(|c| c.is_ascii_digit())
+                .collect();
+            Some((major, minor, patch_digits.parse().ok()?))
+        })
+}
+
+pub fn format_version(version: (u64, u64, u64)) -> String {
+    format!("{}.{}.{}", version.0, version.1, version.2)
+}
+
+/// Run `binary --version` with `path_env` as PATH (script wrappers need
+/// node or bun), killing it after a timeout.
+fn run_version(binary: &Path, path_env: &str) -> Result<String> {
+    let mut command = Command::new(binary);
+    command.arg("--version").env("PATH", path_env);
+    let (status, output) = run_with_timeout(command, VERSION_TIMEOUT)?;
+    if !status.success() {
+        bail!("--version exited with {status}");
+    }
+    Ok(String::from_utf8_lossy(&output).trim().to_string())
+}
+
+/// Probe one binary. Errors are reported on the candidate, never returned.
+pub fn probe_candidate(path: &Path, sources: Vec<String>, path_env: &str) -> OpencodeCandidate {
+    let mut candidate = OpencodeCandidate {
+        path: path.to_string_lossy().to_string(),
+        sources,
+        version: None,
+        version_output: None,
+        compatible: false,
+        configured: false,
+        error: None,
+    };
+    if !path.is_file() {
+        candidate.error = Some("File not found".to_string());
+        return candidate;
+    }
+    match run_version(path, path_env) {
+        Ok(output) => {
+            let version = parse_opencode_version(&output);
+            candidate.version = version.map(format_version);
+            candidate.compatible = version.is_some_and(|version| version >= MIN_OPENCODE_VERSION);
+            if version.is_none() {
+                candidate.error = Some("Unrecognized --version output".to_string());
+            } else if !candidate.compatible {
+                candidate.error = Some(format!(
+                    "Requires opencode {} or newer",
+                    format_version(MIN_OPENCODE_VERSION)
+                ));
+            }
+            candidate.version_output = Some(output);
+        }
+        Err(err) => candidate.error = Some(err.to_string()),
+    }
+    candidate
+}
+
+/// Find and probe every opencode binary. Blocking; run off the async
+/// runtime.
+pub fn detect_opencode(
+    configured: Option<PathBuf>,
+    path_env: &str,
+    home: Option<&Path>,
+) -> OpencodeDetection {
+    let env_overrides: Vec<PathBuf> = OPENCODE_BINARY_ENV_VARS
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .filter_map(|value| resolve_binary_path(&value))
+        .collect();
+    let mut seen = HashSet::new();
+    let path_dirs: Vec<PathBuf> = std::env::split_paths(path_env)
+        .filter(|dir| !dir.as_os_str().is_empty() && seen.insert(dir.clone()))
+        .collect();
+
+    let candidates = candidate_paths(configured.as_deref(), &env_overrides, &path_dirs, home)
+        .into_iter()
+        .map(|(path, sources)| {
+            let sources = sources.into_iter().map(str::to_string).collect();
+            let mut candidate = probe_candidate(&path, sources, path_env);
+            candidate.configured = configured.as_deref() == Some(path.as_path());
+            candidate
+        })
+        .collect();
+
+    OpencodeDetection {
+        configured: configured.map(|path| path.to_string_lossy().to_string()),
+        minimum_version: format_version(MIN_OPENCODE_VERSION),
+        candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn parse_opencode_version_reads_common_formats() {
+        assert_eq!(parse_opencode_version("1.4.25"), Some((1, 4, 25)));
+        assert_eq!(
+            parse_opencode_version("opencode v1.10.0\n"),
+            Some((1, 10, 0))
+        );
+        assert_eq!(
+            parse_opencode_version("opencode 0.15.3-beta.2 (darwin-arm64)"),
+            Some((0, 15, 3))
+        );
+        assert_eq!(parse_opencode_version("command not found"), None);
+        assert!((1, 4, 25) >= MIN_OPENCODE_VERSION);
+        assert!((1, 4, 24) < MIN_OPENCODE_VERSION);
+        assert!((0, 15, 3) < MIN_OPENCODE_VERSION);
+    }
+
+    #[test]
+    fn candidate_paths_merges_sources_for_the_same_binary() {
+        let root = std::env::temp_dir().join(format!(
+            "openchamber-opencode-detect-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        let bin_dir = root.join("bin");
+        let other_dir = root.join("other");
+        fs::create_dir_all(&bin_dir).expect("bin dir");
+        fs::create_dir_all(&other_dir).expect("other dir");
+        let name = binary_names()[0];
+        fs::write(bin_dir.join(name), "").expect("binary");
+        fs::write(other_dir.join(name), "").expect("binary");
+
+        let configured = bin_dir.join(name);
+        let found = candidate_paths(
+            Some(&configured),
+            &[],
+            &[bin_dir.clone(), other_dir.clone(), root.join("missing")],
+            None,
+        );
+        // Real install dirs on this machine may add more entries after these.
+        assert_eq!(
+            found[..2],
+            [
+                (configured.clone(), vec!["settings", "path"]),
+                (other_dir.join(name), vec!["path"]),
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
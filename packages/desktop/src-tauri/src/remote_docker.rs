@@ -0,0 +1,1084 @@
+use crate::docker_transport::{DockerCliRuntime, DockerRunSpec, DockerRuntime};
+use crate::instance_tracker::{InstanceTracker, ManagerEvents, MAX_LOG_LINES_PER_INSTANCE};
+use crate::remote_ssh::{
+    connect_host_for_bind, ensure_bind_host_available, ensure_host_id_unused,
+    is_loopback_bind_host, parse_version_token, read_settings_root, sanitize_bind_host,
+    settings_file_path, sync_desktop_hosts, trimmed_non_empty, update_ssh_host_url,
+    wait_local_forward_ready, write_settings_root, DesktopSshInstallMethod,
+    DesktopSshInstanceStatus, DesktopSshLocalForwardConfig, DesktopSshPhase, DesktopSshRemoteMode,
+    DesktopSshRemoteOpenchamberConfig, HostKind, DEFAULT_LOCAL_BIND_HOST, LOCAL_HOST_ID,
+};
+use crate::ssh_transport::bracket_host;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+use tauri::{AppHandle, State};
+
+const DOCKER_STATUS_EVENT: &str = "openchamber:docker-instance-status";
+const DOCKER_INSTANCES_KEY: &str = "desktopDockerInstances";
+/// Port the managed server listens on inside the container.
+const DEFAULT_CONTAINER_PORT: u16 = 3000;
+const INSTANCE_LABEL: &str = "dev.openchamber.instance";
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopDockerContainerConfig {
+    /// Container to attach to; created from `image` when it does not exist.
+    pub name: String,
+    #[serde(default)]
+    pub image: Option<String>,
+    /// `-v` specs applied when the container is created.
+    #[serde(default)]
+    pub volumes: Vec<String>,
+    /// `KEY=VALUE` pairs applied when the container is created.
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default)]
+    pub workdir: Option<String>,
+    /// Remove the container on disconnect when this app created it.
+    #[serde(default)]
+    pub remove_on_disconnect: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopDockerInstance {
+    pub id: String,
+    pub nickname: Option<String>,
+    /// `DOCKER_HOST` override, e.g. `unix:///run/user/1000/docker.sock`
+    /// or `ssh://user@build-box`.
+    #[serde(default)]
+    pub docker_host: Option<String>,
+    pub container: DesktopDockerContainerConfig,
+    #[serde(default)]
+    pub remote_openchamber: DesktopSshRemoteOpenchamberConfig,
+    #[serde(default)]
+    pub local_forward: DesktopSshLocalForwardConfig,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopDockerInstancesConfig {
+    pub instances: Vec<DesktopDockerInstance>,
+}
+
+struct DockerSession {
+    instance: DesktopDockerInstance,
+    local_url: String,
+    local_port: u16,
+    container_port: u16,
+    created_container: bool,
+    started_container: bool,
+    started_server: bool,
+}
+
+struct DesktopDockerManagerInner {
+    runtime: Arc<dyn DockerRuntime>,
+    settings_path: PathBuf,
+    tracker: InstanceTracker,
+    sessions: Mutex<HashMap<String, DockerSession>>,
+    connect_tasks: Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl Default for DesktopDockerManagerInner {
+    fn default() -> Self {
+        Self::with_runtime(Arc::new(DockerCliRuntime), settings_file_path())
+    }
+}
+
+#[derive(Default)]
+pub struct DesktopDockerManagerState {
+    inner: Arc<DesktopDockerManagerInner>,
+}
+
+fn build_display_label(instance: &DesktopDockerInstance) -> String {
+    trimmed_non_empty(instance.nickname.as_deref())
+        .unwrap_or_else(|| format!("docker:{}", instance.container.name))
+}
+
+/// Host of a `tcp://` or `ssh://` daemon address; `None` for local sockets.
+fn daemon_address_host(docker_host: Option<&str>) -> Option<String> {
+    let rest = docker_host?
+        .strip_prefix("tcp://")
+        .or_else(|| docker_host?.strip_prefix("ssh://"))?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed.split_once(']').map(|(host, _)| host)?
+    } else {
+        authority.split(':').next()?
+    };
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+fn is_valid_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|ch| ch.is_ascii_alphanumeric())
+        && chars.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-'))
+}
+
+fn container_port_for(instance: &DesktopDockerInstance) -> u16 {
+    instance
+        .remote_openchamber
+        .preferred_port
+        .unwrap_or(DEFAULT_CONTAINER_PORT)
+}
+
+fn sanitize_list(values: &[String]) -> Vec<String> {
+    values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn sanitize_docker_instance(mut instance: DesktopDockerInstance) -> Result<DesktopDockerInstance> {
+    instance.id = instance.id.trim().to_string();
+    if instance.id.is_empty() || instance.id == LOCAL_HOST_ID {
+        return Err(anyhow!("Docker instance id is required"));
+    }
+    instance.nickname = trimmed_non_empty(instance.nickname.as_deref());
+    instance.docker_host = trimmed_non_empty(instance.docker_host.as_deref());
+
+    let container = &mut instance.container;
+    container.name = container.name.trim().to_string();
+    if !is_valid_container_name(&container.name) {
+        return Err(anyhow!(
+            "Invalid container name '{}': use letters, digits, '_', '.' or '-'",
+            container.name
+        ));
+    }
+    container.image = trimmed_non_empty(container.image.as_deref());
+    container.volumes = sanitize_list(&container.volumes);
+    container.env = sanitize_list(&container.env);
+    if let Some(entry) = container.env.iter().find(|entry| !entry.contains('=')) {
+        return Err(anyhow!("Environment entry '{entry}' must be KEY=VALUE"));
+    }
+    container.workdir = trimmed_non_empty(container.workdir.as_deref());
+
+    // Whether a local bind host exists on this machine is checked at connect,
+    // so settings stay writable while an interface is down.
+    let bind_host = sanitize_bind_host(&instance.local_forward.bind_host)?;
+    if daemon_address_host(instance.docker_host.as_deref()).is_some()
+        && is_loopback_bind_host(&bind_host)
+    {
+        // The port is published on the daemon's machine, not this one.
+        return Err(anyhow!(
+            "Remote Docker daemons need a bind host reachable from this machine, such as 0.0.0.0"
+        ));
+    }
+    instance.local_forward.bind_host = bind_host;
+    Ok(instance)
+}
+
+/// Ids of Docker instances stored in a settings root.
+pub(crate) fn docker_instance_ids(root: &Value) -> HashSet<String> {
+    root.get(DOCKER_INSTANCES_KEY)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("id").and_then(Value::as_str))
+                .map(|id| id.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_docker_instances_from_path(path: &Path) -> DesktopDockerInstancesConfig {
+    let root = read_settings_root(path);
+    let Some(items) = root.get(DOCKER_INSTANCES_KEY).and_then(Value::as_array) else {
+        return DesktopDockerInstancesConfig::default();
+    };
+    let mut seen = HashSet::new();
+    let instances = items
+        .iter()
+        .filter_map(|item| serde_json::from_value::<DesktopDockerInstance>(item.clone()).ok())
+        .filter(|instance| {
+            let id = instance.id.trim();
+            !id.is_empty() && id != LOCAL_HOST_ID && seen.insert(id.to_string())
+        })
+        .collect();
+    DesktopDockerInstancesConfig { instances }
+}
+
+fn write_docker_instances_to_path(
+    path: &Path,
+    config: DesktopDockerInstancesConfig,
+) -> Result<DesktopDockerInstancesConfig> {
+    let mut root = read_settings_root(path);
+    let previous_ids = docker_instance_ids(&root);
+
+    let mut seen = HashSet::new();
+    let mut sanitized = Vec::new();
+    for instance in config.instances {
+        let normalized = sanitize_docker_instance(instance)?;
+        if !seen.insert(normalized.id.clone()) {
+            continue;
+        }
+        ensure_host_id_unused(&root, &normalized.id, HostKind::Docker)?;
+        sanitized.push(normalized);
+    }
+
+    let entries: Vec<(String, String)> = sanitized
+        .iter()
+        .map(|instance| (instance.id.clone(), build_display_label(instance)))
+        .collect();
+    sync_desktop_hosts(&mut root, &previous_ids, &entries);
+    root[DOCKER_INSTANCES_KEY] = serde_json::to_value(&sanitized)?;
+    write_settings_root(path, &root)?;
+    Ok(DesktopDockerInstancesConfig {
+        instances: sanitized,
+    })
+}
+
+/// `-p` value publishing `container_port` on `bind_host`. Docker wants an
+/// IP, and an empty host port lets the daemon pick one.
+fn publish_spec(bind_host: &str, host_port: Option<u16>, container_port: u16) -> String {
+    let host = if bind_host.eq_ignore_ascii_case("localhost") {
+        DEFAULT_LOCAL_BIND_HOST
+    } else {
+        bind_host
+    };
+    let host_port = host_port.map(|port| port.to_string()).unwrap_or_default();
+    format!("{}:{host_port}:{container_port}", bracket_host(host))
+}
+
+pub(crate) fn install_openchamber_script(
+    version: &str,
+    method: &DesktopSshInstallMethod,
+) -> String {
+    let bun = format!("bun add -g @openchamber/web@{version}");
+    let npm = format!("npm install -g @openchamber/web@{version}");
+    let (first, first_cmd, second, second_cmd) = match method {
+        DesktopSshInstallMethod::Npm => ("npm", npm, "bun", bun),
+        _ => ("bun", bun, "npm", npm),
+    };
+    format!(
+        "if command -v {first} >/dev/null 2>&1; then {first_cmd}; \
+         elif command -v {second} >/dev/null 2>&1; then {second_cmd}; \
+         else echo 'Neither bun nor npm is available' >&2; exit 127; fi"
+    )
+}
+
+pub(crate) fn server_health_script(port: u16) -> String {
+    format!(
+        "if command -v curl >/dev/null 2>&1; then curl -fsS --max-time 3 -o /dev/null http://127.0.0.1:{port}/health; \
+         else wget -qO- http://127.0.0.1:{port}/health >/dev/null; fi 2>/dev/null && echo yes || echo no"
+    )
+}
+
+fn start_server_script(port: u16) -> String {
+    // The published port reaches the container's external interface.
+    format!(
+        "OPENCHAMBER_RUNTIME=docker openchamber serve --daemon --hostname 0.0.0.0 --port {port}"
+    )
+}
+
+pub(crate) fn stop_server_script(port: u16) -> String {
+    format!(
+        "if command -v curl >/dev/null 2>&1; then curl -fsS -X POST http://127.0.0.1:{port}/api/system/shutdown >/dev/null 2>&1 || true; \
+         elif command -v wget >/dev/null 2>&1; then wget -qO- --method=POST http://127.0.0.1:{port}/api/system/shutdown >/dev/null 2>&1 || true; fi"
+    )
+}
+
+/// Make sure OpenChamber answers on `port` inside a container or pod,
+/// installing and starting it in managed mode. `exec` runs a script in the
+/// workload and `place` names it in messages. Returns whether the server
+/// was started here.
+pub(crate) fn ensure_workload_server(
+    exec: &dyn Fn(&str) -> Result<String>,
+    on_phase: &dyn Fn(DesktopSshPhase, String),
+    remote: &DesktopSshRemoteOpenchamberConfig,
+    app_version: &str,
+    port: u16,
+    start_script: &str,
+    place: &str,
+) -> Result<bool> {
+    let healthy = || exec(&server_health_script(port)).is_ok_and(|output| output.trim() == "yes");
+
+    if matches!(remote.mode, DesktopSshRemoteMode::External) {
+        on_phase(
+            DesktopSshPhase::ServerDetecting,
+            format!("Probing OpenChamber server in {place}"),
+        );
+        if !healthy() {
+            return Err(anyhow!(
+                "No OpenChamber server answers on port {port} inside the {place}"
+            ));
+        }
+        return Ok(false);
+    }
+
+    let installed = exec("openchamber --version 2>/dev/null || true")
+        .ok()
+        .and_then(|output| parse_version_token(&output));
+    if installed.as_deref() != Some(app_version) {
+        on_phase(
+            if installed.is_some() {
+                DesktopSshPhase::Updating
+            } else {
+                DesktopSshPhase::Installing
+            },
+            format!("Installing OpenChamber {app_version} in {place}"),
+        );
+        exec(&install_openchamber_script(
+            app_version,
+            &remote.install_method,
+        ))?;
+    }
+
+    on_phase(
+        DesktopSshPhase::ServerDetecting,
+        "Checking for a running OpenChamber server".to_string(),
+    );
+    if healthy() {
+        return Ok(false);
+    }
+
+    on_phase(
+        DesktopSshPhase::ServerStarting,
+        format!("Starting OpenChamber in {place}"),
+    );
+    exec(start_script)?;
+    Ok(true)
+}
+
+impl DesktopDockerManagerInner {
+    fn with_runtime(runtime: Arc<dyn DockerRuntime>, settings_path: PathBuf) -> Self {
+        Self {
+            runtime,
+            settings_path,
+            tracker: InstanceTracker::new(DOCKER_STATUS_EVENT),
+            sessions: Mutex::default(),
+            connect_tasks: Mutex::default(),
+        }
+    }
+
+    fn statuses_with_defaults(&self) -> Vec<DesktopSshInstanceStatus> {
+        let config = read_docker_instances_from_path(&self.settings_path);
+        self.tracker
+            .snapshots(config.instances.iter().map(|instance| instance.id.as_str()))
+    }
+
+    fn connect_blocking(
+        &self,
+        app: &dyn ManagerEvents,
+        instance: DesktopDockerInstance,
+    ) -> Result<()> {
+        let runtime = self.runtime.as_ref();
+        let id = instance.id.clone();
+        let host = instance.docker_host.as_deref();
+        let name = instance.container.name.clone();
+
+        self.tracker.set_phase(
+            app,
+            &id,
+            DesktopSshPhase::ConfigResolved,
+            "Checking Docker daemon",
+        );
+        if daemon_address_host(host).is_none() {
+            ensure_bind_host_available(&instance.local_forward.bind_host)?;
+        }
+        let version = runtime.server_version(host)?;
+        self.tracker
+            .append_log(&id, format!("Docker daemon {version}"));
+
+        self.tracker.set_phase(
+            app,
+            &id,
+            DesktopSshPhase::ContainerStarting,
+            format!("Starting container {name}"),
+        );
+        let container_port = container_port_for(&instance);
+        let mut created_container = false;
+        let mut started_container = false;
+        match runtime.inspect(host, &name)? {
+            Some(state) if state.running => {
+                self.tracker.append_log(
+                    &id,
+                    format!("Attached to running container {name} ({})", state.image),
+                );
+            }
+            Some(_) => {
+                runtime.start(host, &name)?;
+                started_container = true;
+                self.tracker
+                    .append_log(&id, format!("Started existing container {name}"));
+            }
+            None => {
+                let Some(image) = instance.container.image.clone() else {
+                    return Err(anyhow!(
+                        "Container {name} does not exist and no image is configured"
+                    ));
+                };
+                runtime.run_detached(
+                    host,
+                    &DockerRunSpec {
+                        name: name.clone(),
+                        image: image.clone(),
+                        publish: publish_spec(
+                            &instance.local_forward.bind_host,
+                            instance.local_forward.preferred_local_port,
+                            container_port,
+                        ),
+                        volumes: instance.container.volumes.clone(),
+                        env: instance.container.env.clone(),
+                        workdir: instance.container.workdir.clone(),
+                        labels: vec![(INSTANCE_LABEL.to_string(), id.clone())],
+                    },
+                )?;
+                created_container = true;
+                self.tracker
+                    .append_log(&id, format!("Created container {name} from {image}"));
+            }
+        }
+
+        let result = self.prepare_session(app, &instance, container_port);
+        let (local_url, local_port, started_server) = match result {
+            Ok(ready) => ready,
+            Err(err) => {
+                if created_container || started_container {
+                    runtime.stop(
+                        host,
+                        &name,
+                        created_container && instance.container.remove_on_disconnect,
+                    );
+                }
+                return Err(err);
+            }
+        };
+
+        let exposed = !is_loopback_bind_host(&instance.local_forward.bind_host)
+            || daemon_address_host(host).is_some();
+        if exposed {
+            self.tracker.append_log_with_level(
+                &id,
+                "WARN",
+                format!("Container port is published beyond loopback at {local_url}"),
+            );
+        }
+        let _ = update_ssh_host_url(
+            &self.settings_path,
+            &id,
+            &build_display_label(&instance),
+            &local_url,
+        );
+
+        self.sessions.lock().expect("docker sessions mutex").insert(
+            id.clone(),
+            DockerSession {
+                instance,
+                local_url: local_url.clone(),
+                local_port,
+                container_port,
+                created_container,
+                started_container,
+                started_server,
+            },
+        );
+        self.tracker.publish(
+            app,
+            DesktopSshInstanceStatus {
+                phase: DesktopSshPhase::Ready,
+                detail: Some("Docker instance is ready".to_string()),
+                local_url: Some(local_url),
+                local_port: Some(local_port),
+                remote_port: Some(container_port),
+                started_by_us: started_server,
+                exposed_beyond_loopback: exposed,
+                ..DesktopSshInstanceStatus::idle(&id)
+            },
+        );
+        Ok(())
+    }
+
+    /// Probe the container, bring the server up and wait for the published
+    /// port. Returns the local URL, host port and whether the server was
+    /// started here.
+    fn prepare_session(
+        &self,
+        app: &dyn ManagerEvents,
+        instance: &DesktopDockerInstance,
+        container_port: u16,
+    ) -> Result<(String, u16, bool)> {
+        let runtime = self.runtime.as_ref();
+        let host = instance.docker_host.as_deref();
+        let name = instance.container.name.as_str();
+
+        let local_port = runtime
+            .published_port(host, name, container_port)?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Container {name} does not publish port {container_port}; recreate it with -p or let OpenChamber create it"
+                )
+            })?;
+
+        self.tracker.set_phase(
+            app,
+            &instance.id,
+            DesktopSshPhase::RemoteProbe,
+            "Probing container platform",
+        );
+        let platform = runtime.exec(host, name, "uname -s")?;
+        let platform = platform.trim().to_ascii_lowercase();
+        if platform != "linux" {
+            return Err(anyhow!("Unsupported container platform: {platform}"));
+        }
+
+        let started_server = ensure_workload_server(
+            &|script| runtime.exec(host, name, script),
+            &|phase, detail| self.tracker.set_phase(app, &instance.id, phase, detail),
+            &instance.remote_openchamber,
+            &app.app_version(),
+            container_port,
+            &start_server_script(container_port),
+            "container",
+        )?;
+
+        self.tracker.set_phase(
+            app,
+            &instance.id,
+            DesktopSshPhase::Forwarding,
+            format!("Waiting for published port {local_port}"),
+        );
+        let connect_host = daemon_address_host(host)
+            .unwrap_or_else(|| connect_host_for_bind(&instance.local_forward.bind_host));
+        wait_local_forward_ready(&connect_host, local_port)?;
+        Ok((
+            format!("http://{}:{local_port}", bracket_host(&connect_host)),
+            local_port,
+            started_server,
+        ))
+    }
+
+    /// Drop the session and stop whatever this app started for it.
+    fn teardown_session(&self, id: &str) {
+        let Some(session) = self
+            .sessions
+            .lock()
+            .expect("docker sessions mutex")
+            .remove(id)
+        else {
+            return;
+        };
+        let runtime = self.runtime.as_ref();
+        let instance = &session.instance;
+        let host = instance.docker_host.as_deref();
+        let name = instance.container.name.as_str();
+        let keep_running = instance.remote_openchamber.keep_running;
+
+        if session.created_container && instance.container.remove_on_disconnect {
+            self.tracker
+                .append_log(id, format!("Removing container {name}"));
+            runtime.stop(host, name, true);
+        } else if !keep_running && (session.created_container || session.started_container) {
+            self.tracker
+                .append_log(id, format!("Stopping container {name}"));
+            runtime.stop(host, name, false);
+        } else if !keep_running && session.started_server {
+            let _ = runtime.exec(host, name, &stop_server_script(session.container_port));
+        }
+    }
+
+    fn cancel_connect_task(&self, id: &str) {
+        if let Some(handle) = self
+            .connect_tasks
+            .lock()
+            .expect("docker connect task mutex")
+            .remove(id)
+        {
+            handle.abort();
+        }
+    }
+
+    fn disconnect(&self, app: &dyn ManagerEvents, id: &str) {
+        self.cancel_connect_task(id);
+        self.teardown_session(id);
+        self.tracker
+            .publish(app, DesktopSshInstanceStatus::idle(id));
+    }
+
+    /// Re-check a live session and report a stopped container or server.
+    fn refresh_session(&self, app: &dyn ManagerEvents, id: &str) {
+        let target = {
+            let sessions = self.sessions.lock().expect("docker sessions mutex");
+            sessions.get(id).map(|session| {
+                (
+                    session.instance.docker_host.clone(),
+                    session.instance.container.name.clone(),
+                    session.local_url.clone(),
+                    session.local_port,
+                    session.container_port,
+                )
+            })
+        };
+        let Some((host, name, local_url, local_port, container_port)) = target else {
+            return;
+        };
+        let running = self
+            .runtime
+            .inspect(host.as_deref(), &name)
+            .ok()
+            .flatten()
+            .is_some_and(|state| state.running);
+        if !running {
+            self.sessions
+                .lock()
+                .expect("docker sessions mutex")
+                .remove(id);
+            self.tracker.set_phase(
+                app,
+                id,
+                DesktopSshPhase::Error,
+                format!("Container {name} is no longer running"),
+            );
+            return;
+        }
+        let healthy = self
+            .runtime
+            .exec(
+                host.as_deref(),
+                &name,
+                &server_health_script(container_port),
+            )
+            .is_ok_and(|output| output.trim() == "yes");
+        let previous = self.tracker.snapshot(id);
+        let phase = if healthy {
+            DesktopSshPhase::Ready
+        } else {
+            DesktopSshPhase::Degraded
+        };
+        if previous.phase != phase {
+            self.tracker.publish(
+                app,
+                DesktopSshInstanceStatus {
+                    phase,
+                    detail: Some(if healthy {
+                        "Docker instance is ready".to_string()
+                    } else {
+                        "OpenChamber server in container is not responding".to_string()
+                    }),
+                    local_url: Some(local_url),
+                    local_port: Some(local_port),
+                    remote_port: Some(container_port),
+                    ..previous
+                },
+            );
+        }
+    }
+
+    fn start_connect(
+        self: &Arc<Self>,
+        app: Arc<dyn ManagerEvents>,
+        id: String,
+    ) -> Result<(), String> {
+        let config = read_docker_instances_from_path(&self.settings_path);
+        let Some(instance) = config.instances.into_iter().find(|item| item.id == id) else {
+            return Err("Docker instance not found".to_string());
+        };
+        if self
+            .connect_tasks
+            .lock()
+            .expect("docker connect task mutex")
+            .contains_key(&id)
+        {
+            self.tracker
+                .append_log(&id, "Connection already in progress");
+            return Ok(());
+        }
+
+        self.teardown_session(&id);
+        self.tracker.append_log(&id, "Starting Docker instance");
+        let inner = Arc::clone(self);
+        let id_for_task = id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let result = tauri::async_runtime::spawn_blocking({
+                let inner = Arc::clone(&inner);
+                let app = Arc::clone(&app);
+                move || inner.connect_blocking(app.as_ref(), instance)
+            })
+            .await;
+            let failure = match result {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err),
+                Err(err) => Some(anyhow!("Docker task failed: {err}")),
+            };
+            inner
+                .connect_tasks
+                .lock()
+                .expect("docker connect task mutex")
+                .remove(&id_for_task);
+            if let Some(err) = failure {
+                inner.tracker.set_phase(
+                    app.as_ref(),
+                    &id_for_task,
+                    DesktopSshPhase::Error,
+                    err.to_string(),
+                );
+            }
+        });
+        self.connect_tasks
+            .lock()
+            .expect("docker connect task mutex")
+            .insert(id, handle);
+        Ok(())
+    }
+}
+
+impl DesktopDockerManagerState {
+    pub fn statuses(&self) -> Vec<DesktopSshInstanceStatus> {
+        self.inner.statuses_with_defaults()
+    }
+
+    pub fn has_instance(&self, id: &str) -> bool {
+        read_docker_instances_from_path(&self.inner.settings_path)
+            .instances
+            .iter()
+            .any(|instance| instance.id == id)
+    }
+
+    /// Connect `id`, or reuse its ready session, and return the local URL.
+    pub async fn connect_and_wait(&self, app: &AppHandle, id: &str) -> Result<String> {
+        if let Some(url) = self.inner.tracker.ready_url(id) {
+            return Ok(url);
+        }
+        self.inner
+            .start_connect(Arc::new(app.clone()), id.to_string())
+            .map_err(|err| anyhow!(err))?;
+        let connecting = || {
+            self.inner
+                .connect_tasks
+                .lock()
+                .expect("docker connect task mutex")
+                .contains_key(id)
+        };
+        self.inner
+            .tracker
+            .wait_for_ready_url(id, connecting, "Docker connection failed")
+            .await
+    }
+
+    pub fn shutdown_all(&self, app: &AppHandle) {
+        let ids: Vec<String> = self
+            .inner
+            .sessions
+            .lock()
+            .expect("docker sessions mutex")
+            .keys()
+            .cloned()
+            .collect();
+        for id in ids {
+            self.inner.disconnect(app, &id);
+        }
+        let connect_ids: Vec<String> = self
+            .inner
+            .connect_tasks
+            .lock()
+            .expect("docker connect task mutex")
+            .keys()
+            .cloned()
+            .collect();
+        for id in connect_ids {
+            self.inner.cancel_connect_task(&id);
+        }
+    }
+}
+
+fn required_id(id: &str) -> Result<String, String> {
+    let id = id.trim();
+    if id.is_empty() || id == LOCAL_HOST_ID {
+        return Err("Docker instance id is required".to_string());
+    }
+    Ok(id.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_docker_instances_get() -> Result<DesktopDockerInstancesConfig, String> {
+    Ok(read_docker_instances_from_path(&settings_file_path()))
+}
+
+#[tauri::command]
+pub fn desktop_docker_instances_set(config: DesktopDockerInstancesConfig) -> Result<(), String> {
+    write_docker_instances_to_path(&settings_file_path(), config)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_docker_connect(
+    app: AppHandle,
+    state: State<'_, DesktopDockerManagerState>,
+    id: String,
+) -> Result<(), String> {
+    let id = required_id(&id)?;
+    state.inner.start_connect(Arc::new(app), id)
+}
+
+#[tauri::command]
+pub fn desktop_docker_disconnect(
+    app: AppHandle,
+    state: State<'_, DesktopDockerManagerState>,
+    id: String,
+) -> Result<(), String> {
+    let id = required_id(&id)?;
+    state.inner.disconnect(&app, &id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn desktop_docker_status(
+    app: AppHandle,
+    state: State<'_, DesktopDockerManagerState>,
+    id: Option<String>,
+) -> Result<Vec<DesktopSshInstanceStatus>, String> {
+    let inner = Arc::clone(&state.inner);
+    let id = id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    tauri::async_runtime::spawn_blocking(move || match id {
+        Some(id) => {
+            inner.refresh_session(&app, &id);
+            vec![inner.tracker.snapshot(&id)]
+        }
+        None => inner.statuses_with_defaults(),
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_docker_logs(
+    state: State<'_, DesktopDockerManagerState>,
+    id: String,
+    limit: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let id = required_id(&id)?;
+    let cap = limit.unwrap_or(200).min(MAX_LOG_LINES_PER_INSTANCE);
+    Ok(state.inner.tracker.logs(&id, cap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker_transport::fake::FakeDockerRuntime;
+    use crate::instance_tracker::fake::{temp_settings_path, RecordingEvents};
+    use serde_json::json;
+
+    fn fake_manager(name: &str) -> (Arc<FakeDockerRuntime>, DesktopDockerManagerInner) {
+        let runtime = Arc::new(FakeDockerRuntime::new());
+        let manager = DesktopDockerManagerInner::with_runtime(
+            runtime.clone(),
+            temp_settings_path("docker", name),
+        );
+        (runtime, manager)
+    }
+
+    fn sample_instance(id: &str, container: &str) -> DesktopDockerInstance {
+        DesktopDockerInstance {
+            id: id.to_string(),
+            nickname: None,
+            docker_host: None,
+            container: DesktopDockerContainerConfig {
+                name: container.to_string(),
+                image: Some("node:22".to_string()),
+                volumes: Vec::new(),
+                env: Vec::new(),
+                workdir: None,
+                remove_on_disconnect: false,
+            },
+            remote_openchamber: DesktopSshRemoteOpenchamberConfig::default(),
+            local_forward: DesktopSshLocalForwardConfig::default(),
+        }
+    }
+
+    #[test]
+    fn sanitize_validates_container_and_daemon_settings() {
+        let mut bad_name = sample_instance("docker-a", "-oops");
+        bad_name.container.name = "-oops".to_string();
+        assert!(sanitize_docker_instance(bad_name).is_err());
+
+        let mut bad_env = sample_instance("docker-a", "agent");
+        bad_env.container.env = vec!["TOKEN".to_string()];
+        assert!(sanitize_docker_instance(bad_env).is_err());
+
+        let mut remote = sample_instance("docker-a", "agent");
+        remote.docker_host = Some("ssh://alice@build-box".to_string());
+        let err = sanitize_docker_instance(remote.clone()).expect_err("loopback on remote daemon");
+        assert!(err.to_string().contains("Remote Docker daemons"));
+        remote.local_forward.bind_host = "0.0.0.0".to_string();
+        assert!(sanitize_docker_instance(remote).is_ok());
+
+        let (runtime, manager) = fake_manager("bind-missing");
+        let mut unassigned = sample_instance("docker-a", "agent");
+        unassigned.local_forward.bind_host = "192.0.2.1".to_string();
+        let sanitized = sanitize_docker_instance(unassigned).expect("syntax is valid");
+        let err = manager
+            .connect_blocking(&RecordingEvents::default(), sanitized)
+            .expect_err("unassigned address");
+        assert!(err.to_string().contains("not available on this machine"));
+        assert!(runtime.runs().is_empty());
+    }
+
+    #[test]
+    fn daemon_address_host_reads_tcp_and_ssh_hosts() {
+        assert_eq!(
+            daemon_address_host(Some("ssh://alice@build-box:2222")),
+            Some("build-box".to_string())
+        );
+        assert_eq!(
+            daemon_address_host(Some("tcp://[fd00::2]:2376")),
+            Some("fd00::2".to_string())
+        );
+        assert_eq!(
+            daemon_address_host(Some("unix:///var/run/docker.sock")),
+            None
+        );
+        assert_eq!(daemon_address_host(None), None);
+    }
+
+    #[test]
+    fn publish_spec_brackets_ipv6_and_leaves_port_to_docker() {
+        assert_eq!(publish_spec("127.0.0.1", None, 3000), "127.0.0.1::3000");
+        assert_eq!(publish_spec("::1", Some(4100), 3000), "[::1]:4100:3000");
+        assert_eq!(
+            publish_spec("localhost", Some(4100), 3000),
+            "127.0.0.1:4100:3000"
+        );
+    }
+
+    #[test]
+    fn write_syncs_hosts_and_rejects_ssh_id_collisions() {
+        let path = temp_settings_path("docker", "write");
+        write_settings_root(
+            &path,
+            &json!({ "desktopSshInstances": [{ "id": "ssh-prod", "sshCommand": "ssh prod" }] }),
+        )
+        .expect("seed");
+
+        let err = write_docker_instances_to_path(
+            &path,
+            DesktopDockerInstancesConfig {
+                instances: vec![sample_instance("ssh-prod", "agent")],
+            },
+        )
+        .expect_err("collision");
+        assert!(err.to_string().contains("SSH instance"));
+
+        write_docker_instances_to_path(
+            &path,
+            DesktopDockerInstancesConfig {
+                instances: vec![sample_instance("docker-agent", "agent")],
+            },
+        )
+        .expect("write");
+        let root = read_settings_root(&path);
+        let hosts = root["desktopHosts"].as_array().expect("hosts");
+        assert!(hosts
+            .iter()
+            .any(|host| host["id"] == "docker-agent" && host["label"] == "docker:agent"));
+
+        write_docker_instances_to_path(&path, DesktopDockerInstancesConfig::default())
+            .expect("remove");
+        let root = read_settings_root(&path);
+        assert!(!root["desktopHosts"]
+            .as_array()
+            .expect("hosts")
+            .iter()
+            .any(|host| host["id"] == "docker-agent"));
+    }
+
+    #[test]
+    fn connect_creates_container_installs_and_starts_server() {
+        let (runtime, manager) = fake_manager("create");
+        runtime.respond("uname -s", Ok("Linux\n"));
+        let events = RecordingEvents::default();
+
+        manager
+            .connect_blocking(&events, sample_instance("docker-agent", "agent"))
+            .expect("connect");
+
+        let runs = runtime.runs();
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].publish, "127.0.0.1::3000");
+        assert_eq!(
+            runs[0].labels,
+            vec![(INSTANCE_LABEL.to_string(), "docker-agent".to_string())]
+        );
+        let execs = runtime.execs();
+        assert!(execs
+            .iter()
+            .any(|script| script.contains("bun add -g @openchamber/web@1.9.10")));
+        assert!(execs
+            .iter()
+            .any(|script| script.contains("--hostname 0.0.0.0 --port 3000")));
+
+        let phases = events.phases();
+        assert!(phases.contains(&DesktopSshPhase::ContainerStarting));
+        assert!(phases.contains(&DesktopSshPhase::Installing));
+        let ready = events.last();
+        assert_eq!(ready.phase, DesktopSshPhase::Ready);
+        assert_eq!(
+            ready.local_url,
+            Some(format!("http://127.0.0.1:{}", runtime.host_port))
+        );
+        assert!(ready.started_by_us);
+        assert!(!ready.exposed_beyond_loopback);
+    }
+
+    #[test]
+    fn connect_fails_early_when_daemon_is_unreachable() {
+        let (runtime, manager) = fake_manager("daemon-down");
+        runtime.set_daemon_down();
+        let events = RecordingEvents::default();
+
+        let err = manager
+            .connect_blocking(&events, sample_instance("docker-agent", "agent"))
+            .expect_err("daemon down");
+        assert!(err.to_string().contains("Docker daemon"));
+        assert_eq!(events.phases(), vec![DesktopSshPhase::ConfigResolved]);
+        assert!(runtime.runs().is_empty());
+    }
+
+    #[test]
+    fn connect_requires_published_port_on_existing_container() {
+        let (runtime, manager) = fake_manager("unpublished");
+        runtime.add_container("agent", false, None);
+        let events = RecordingEvents::default();
+
+        let err = manager
+            .connect_blocking(&events, sample_instance("docker-agent", "agent"))
+            .expect_err("no published port");
+        assert!(err.to_string().contains("does not publish port 3000"));
+        assert_eq!(runtime.stopped(), vec![("agent".to_string(), false)]);
+        assert!(runtime.runs().is_empty());
+    }
+
+    #[test]
+    fn disconnect_removes_containers_created_for_the_session() {
+        let (runtime, manager) = fake_manager("remove");
+        runtime.respond("uname -s", Ok("Linux\n"));
+        runtime.respond("openchamber --version", Ok("openchamber 1.9.10\n"));
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("docker-agent", "agent");
+        instance.container.remove_on_disconnect = true;
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        assert!(!events.phases().contains(&DesktopSshPhase::Installing));
+
+        manager.disconnect(&events, "docker-agent");
+        assert_eq!(runtime.stopped(), vec![("agent".to_string(), true)]);
+        assert!(!runtime.is_running("agent"));
+        assert_eq!(events.last().phase, DesktopSshPhase::Idle);
+    }
+}
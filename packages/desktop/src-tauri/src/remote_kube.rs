@@ -0,0 +1,1128 @@
+use crate::instance_tracker::{InstanceTracker, ManagerEvents, MAX_LOG_LINES_PER_INSTANCE};
+use crate::kube_transport::{KubeRuntime, KubeTarget, KubectlRuntime};
+use crate::remote_docker::{ensure_workload_server, server_health_script, stop_server_script};
+use crate::remote_ssh::{
+    connect_host_for_bind, ensure_bind_host_available, ensure_host_id_unused,
+    is_local_port_available, is_local_tunnel_reachable, is_loopback_bind_host, now_millis,
+    pick_unused_local_port, read_settings_root, sanitize_bind_host, sanitize_forward,
+    settings_file_path, sync_desktop_hosts, trimmed_non_empty, update_ssh_host_url,
+    wait_local_forward_ready, write_settings_root, DesktopSshInstanceStatus,
+    DesktopSshLocalForwardConfig, DesktopSshPhase, DesktopSshPortForward,
+    DesktopSshPortForwardType, DesktopSshRemoteOpenchamberConfig, HostKind,
+    DEFAULT_RECONNECT_MAX_ATTEMPTS, LOCAL_HOST_ID, MONITOR_INITIAL_POLL_SECS,
+    MONITOR_STABILIZE_TICKS, MONITOR_STEADY_POLL_SECS,
+};
+use crate::ssh_transport::{bracket_host, SshProcess};
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tauri::{AppHandle, State};
+
+const KUBE_STATUS_EVENT: &str = "openchamber:kube-instance-status";
+const KUBE_INSTANCES_KEY: &str = "desktopKubeInstances";
+/// Port the managed server listens on inside the pod.
+const DEFAULT_POD_PORT: u16 = 3000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopKubeInstance {
+    pub id: String,
+    pub nickname: Option<String>,
+    /// kubeconfig context; `None` uses the current one.
+    #[serde(default)]
+    pub context: Option<String>,
+    #[serde(default)]
+    pub namespace: Option<String>,
+    /// Fixed pod name. Exactly one of `pod` and `selector` is set.
+    #[serde(default)]
+    pub pod: Option<String>,
+    /// Label selector such as `app=devbox,owner=alice`; the first running
+    /// match is used and re-resolved on every reconnect.
+    #[serde(default)]
+    pub selector: Option<String>,
+    #[serde(default)]
+    pub container: Option<String>,
+    #[serde(default)]
+    pub remote_openchamber: DesktopSshRemoteOpenchamberConfig,
+    #[serde(default)]
+    pub local_forward: DesktopSshLocalForwardConfig,
+    /// Extra forwards; only `local` forwards to ports of the pod itself.
+    #[serde(default)]
+    pub port_forwards: Vec<DesktopSshPortForward>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopKubeInstancesConfig {
+    pub instances: Vec<DesktopKubeInstance>,
+}
+
+struct KubeSession {
+    instance: DesktopKubeInstance,
+    target: KubeTarget,
+    pod: String,
+    /// Address the desktop dials to reach the main forward.
+    local_host: String,
+    local_port: u16,
+    remote_port: u16,
+    started_by_us: bool,
+    main_forward: Box<dyn SshProcess>,
+    extra_forwards: Vec<(String, Box<dyn SshProcess>)>,
+}
+
+/// Outcome of a single monitor poll.
+enum MonitorTick {
+    Healthy,
+    Dropped(String),
+    SessionGone,
+}
+
+struct DesktopKubeManagerInner {
+    runtime: Arc<dyn KubeRuntime>,
+    settings_path: PathBuf,
+    tracker: InstanceTracker,
+    sessions: Mutex<HashMap<String, KubeSession>>,
+    connect_tasks: Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>,
+    monitor_tasks: Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>,
+    retry_attempts: Mutex<HashMap<String, u32>>,
+}
+
+impl Default for DesktopKubeManagerInner {
+    fn default() -> Self {
+        Self::with_runtime(Arc::new(KubectlRuntime), settings_file_path())
+    }
+}
+
+#[derive(Default)]
+pub struct DesktopKubeManagerState {
+    inner: Arc<DesktopKubeManagerInner>,
+}
+
+fn build_display_label(instance: &DesktopKubeInstance) -> String {
+    if let Some(nickname) = trimmed_non_empty(instance.nickname.as_deref()) {
+        return nickname;
+    }
+    let workload = instance
+        .pod
+        .clone()
+        .or_else(|| instance.selector.clone())
+        .unwrap_or_default();
+    match instance.namespace.as_deref() {
+        Some(namespace) => format!("k8s:{namespace}/{workload}"),
+        None => format!("k8s:{workload}"),
+    }
+}
+
+fn kube_target(instance: &DesktopKubeInstance) -> KubeTarget {
+    KubeTarget {
+        context: instance.context.clone(),
+        namespace: instance.namespace.clone(),
+        container: instance.container.clone(),
+    }
+}
+
+fn pod_port_for(instance: &DesktopKubeInstance) -> u16 {
+    instance
+        .remote_openchamber
+        .preferred_port
+        .unwrap_or(DEFAULT_POD_PORT)
+}
+
+fn is_loopback_target(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost") || is_loopback_bind_host(host)
+}
+
+fn sanitize_kube_forward(forward: &DesktopSshPortForward) -> Result<Option<DesktopSshPortForward>> {
+    let Some(normalized) = sanitize_forward(forward)? else {
+        return Ok(None);
+    };
+    if !matches!(normalized.forward_type, DesktopSshPortForwardType::Local) {
+        return Err(anyhow!(
+            "Port forward {}: Kubernetes instances only support local forwards",
+            normalized.id
+        ));
+    }
+    // `kubectl port-forward` can only reach ports of the pod itself.
+    if let Some(host) = normalized.remote_host.as_deref() {
+        if !is_loopback_target(host) {
+            return Err(anyhow!(
+                "Port forward {}: the target must be a port of the pod, not {host}",
+                normalized.id
+            ));
+        }
+    }
+    Ok(Some(normalized))
+}
+
+fn sanitize_kube_instance(mut instance: DesktopKubeInstance) -> Result<DesktopKubeInstance> {
+    instance.id = instance.id.trim().to_string();
+    if instance.id.is_empty() || instance.id == LOCAL_HOST_ID {
+        return Err(anyhow!("Kubernetes instance id is required"));
+    }
+    instance.nickname = trimmed_non_empty(instance.nickname.as_deref());
+    instance.context = trimmed_non_empty(instance.context.as_deref());
+    instance.namespace = trimmed_non_empty(instance.namespace.as_deref());
+    instance.pod = trimmed_non_empty(instance.pod.as_deref());
+    instance.selector = trimmed_non_empty(instance.selector.as_deref());
+    instance.container = trimmed_non_empty(instance.container.as_deref());
+
+    match (&instance.pod, &instance.selector) {
+        (Some(_), Some(_)) => {
+            return Err(anyhow!(
+                "Set either a pod name or a label selector, not both"
+            ))
+        }
+        (None, None) => return Err(anyhow!("A pod name or label selector is required")),
+        _ => {}
+    }
+    if let Some(pod) = instance.pod.as_deref() {
+        if pod.contains(char::is_whitespace) || pod.contains('/') {
+            return Err(anyhow!("Invalid pod name '{pod}'"));
+        }
+    }
+
+    // Availability on this machine is checked at connect.
+    instance.local_forward.bind_host = sanitize_bind_host(&instance.local_forward.bind_host)?;
+
+    let mut forwards = Vec::new();
+    for forward in &instance.port_forwards {
+        if let Some(normalized) = sanitize_kube_forward(forward)? {
+            forwards.push(normalized);
+        }
+    }
+    instance.port_forwards = forwards;
+    Ok(instance)
+}
+
+/// Ids of Kubernetes instances stored in a settings root.
+pub(crate) fn kube_instance_ids(root: &Value) -> HashSet<String> {
+    root.get(KUBE_INSTANCES_KEY)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("id").and_then(Value::as_str))
+                .map(|id| id.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn read_kube_instances_from_path(path: &Path) -> DesktopKubeInstancesConfig {
+    let root = read_settings_root(path);
+    let Some(items) = root.get(KUBE_INSTANCES_KEY).and_then(Value::as_array) else {
+        return DesktopKubeInstancesConfig::default();
+    };
+    let mut seen = HashSet::new();
+    let instances = items
+        .iter()
+        .filter_map(|item| serde_json::from_value::<DesktopKubeInstance>(item.clone()).ok())
+        .filter(|instance| {
+            let id = instance.id.trim();
+            !id.is_empty() && id != LOCAL_HOST_ID && seen.insert(id.to_string())
+        })
+        .collect();
+    DesktopKubeInstancesConfig { instances }
+}
+
+fn write_kube_instances_to_path(
+    path: &Path,
+    config: DesktopKubeInstancesConfig,
+) -> Result<DesktopKubeInstancesConfig> {
+    let mut root = read_settings_root(path);
+    let previous_ids = kube_instance_ids(&root);
+
+    let mut seen = HashSet::new();
+    let mut sanitized = Vec::new();
+    for instance in config.instances {
+        let normalized = sanitize_kube_instance(instance)?;
+        if !seen.insert(normalized.id.clone()) {
+            continue;
+        }
+        ensure_host_id_unused(&root, &normalized.id, HostKind::Kube)?;
+        sanitized.push(normalized);
+    }
+
+    let entries: Vec<(String, String)> = sanitized
+        .iter()
+        .map(|instance| (instance.id.clone(), build_display_label(instance)))
+        .collect();
+    sync_desktop_hosts(&mut root, &previous_ids, &entries);
+    root[KUBE_INSTANCES_KEY] = serde_json::to_value(&sanitized)?;
+    write_settings_root(path, &root)?;
+    Ok(DesktopKubeInstancesConfig {
+        instances: sanitized,
+    })
+}
+
+fn start_server_script(port: u16) -> String {
+    // `kubectl port-forward` dials the pod's loopback interface.
+    format!(
+        "OPENCHAMBER_RUNTIME=kubernetes openchamber serve --daemon --hostname 127.0.0.1 --port {port}"
+    )
+}
+
+impl DesktopKubeManagerInner {
+    fn with_runtime(runtime: Arc<dyn KubeRuntime>, settings_path: PathBuf) -> Self {
+        Self {
+            runtime,
+            settings_path,
+            tracker: InstanceTracker::new(KUBE_STATUS_EVENT),
+            sessions: Mutex::default(),
+            connect_tasks: Mutex::default(),
+            monitor_tasks: Mutex::default(),
+            retry_attempts: Mutex::default(),
+        }
+    }
+
+    fn set_phase(
+        &self,
+        app: &dyn ManagerEvents,
+        id: &str,
+        phase: DesktopSshPhase,
+        detail: impl Into<String>,
+    ) {
+        self.tracker.publish(
+            app,
+            DesktopSshInstanceStatus {
+                phase,
+                detail: Some(detail.into()),
+                retry_attempt: self.current_retry_attempt(id),
+                ..DesktopSshInstanceStatus::idle(id)
+            },
+        );
+    }
+
+    fn statuses_with_defaults(&self) -> Vec<DesktopSshInstanceStatus> {
+        let config = read_kube_instances_from_path(&self.settings_path);
+        self.tracker
+            .snapshots(config.instances.iter().map(|instance| instance.id.as_str()))
+    }
+
+    fn current_retry_attempt(&self, id: &str) -> u32 {
+        self.retry_attempts
+            .lock()
+            .expect("kube retry mutex")
+            .get(id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn next_retry_attempt(&self, id: &str) -> u32 {
+        let mut attempts = self.retry_attempts.lock().expect("kube retry mutex");
+        let attempt = attempts.entry(id.to_string()).or_insert(0);
+        *attempt = attempt.saturating_add(1);
+        *attempt
+    }
+
+    fn clear_retry_attempt(&self, id: &str) {
+        self.retry_attempts
+            .lock()
+            .expect("kube retry mutex")
+            .remove(id);
+    }
+
+    fn spawn_extra_forwards(
+        &self,
+        instance: &DesktopKubeInstance,
+        target: &KubeTarget,
+        pod: &str,
+    ) -> Result<Vec<(String, Box<dyn SshProcess>)>> {
+        let mut spawned: Vec<(String, Box<dyn SshProcess>)> = Vec::new();
+        for forward in instance
+            .port_forwards
+            .iter()
+            .filter(|forward| forward.enabled)
+        {
+            let (Some(local_port), Some(remote_port)) = (forward.local_port, forward.remote_port)
+            else {
+                continue;
+            };
+            let bind_host = forward.local_host.as_deref().unwrap_or("127.0.0.1");
+            match ensure_bind_host_available(bind_host).and_then(|()| {
+                self.runtime
+                    .spawn_port_forward(target, pod, bind_host, local_port, remote_port)
+            }) {
+                Ok(process) => {
+                    self.tracker.append_log(
+                        &instance.id,
+                        format!(
+                            "Forwarding {}:{local_port} to pod port {remote_port}",
+                            bracket_host(bind_host)
+                        ),
+                    );
+                    spawned.push((forward.id.clone(), process));
+                }
+                Err(err) => {
+                    for (_, mut process) in spawned {
+                        process.kill();
+                    }
+                    return Err(anyhow!("Port forward {}: {err}", forward.id));
+                }
+            }
+        }
+        Ok(spawned)
+    }
+
+    fn connect_blocking(
+        &self,
+        app: &dyn ManagerEvents,
+        instance: DesktopKubeInstance,
+    ) -> Result<()> {
+        let runtime = self.runtime.as_ref();
+        let id = instance.id.clone();
+        let target = kube_target(&instance);
+
+        self.set_phase(app, &id, DesktopSshPhase::ConfigResolved, "Resolving pod");
+        ensure_bind_host_available(&instance.local_forward.bind_host)?;
+        let pod = runtime.resolve_pod(
+            &target,
+            instance.pod.as_deref(),
+            instance.selector.as_deref(),
+        )?;
+        self.tracker.append_log(&id, format!("Using pod {pod}"));
+
+        self.set_phase(
+            app,
+            &id,
+            DesktopSshPhase::RemoteProbe,
+            "Probing pod platform",
+        );
+        let platform = runtime.exec(&target, &pod, "uname -s")?;
+        let platform = platform.trim().to_ascii_lowercase();
+        if platform != "linux" {
+            return Err(anyhow!("Unsupported pod platform: {platform}"));
+        }
+
+        let remote_port = pod_port_for(&instance);
+        let started_by_us = ensure_workload_server(
+            &|script| runtime.exec(&target, &pod, script),
+            &|phase, detail| self.set_phase(app, &id, phase, detail),
+            &instance.remote_openchamber,
+            &app.app_version(),
+            remote_port,
+            &start_server_script(remote_port),
+            "pod",
+        )?;
+
+        let bind_host = instance.local_forward.bind_host.clone();
+        let local_port = match instance.local_forward.preferred_local_port {
+            Some(port) if is_local_port_available(&bind_host, port) => port,
+            _ => pick_unused_local_port(&bind_host)?,
+        };
+        self.set_phase(
+            app,
+            &id,
+            DesktopSshPhase::Forwarding,
+            format!("Forwarding local port {local_port} to pod port {remote_port}"),
+        );
+        let mut main_forward =
+            runtime.spawn_port_forward(&target, &pod, &bind_host, local_port, remote_port)?;
+        let local_host = connect_host_for_bind(&bind_host);
+        if let Err(err) = wait_local_forward_ready(&local_host, local_port) {
+            let stderr = main_forward.take_stderr();
+            main_forward.kill();
+            return Err(if stderr.trim().is_empty() {
+                err
+            } else {
+                anyhow!("{err}: {}", stderr.trim())
+            });
+        }
+        let extra_forwards = match self.spawn_extra_forwards(&instance, &target, &pod) {
+            Ok(spawned) => spawned,
+            Err(err) => {
+                main_forward.kill();
+                return Err(err);
+            }
+        };
+
+        let exposed = !is_loopback_bind_host(&bind_host)
+            || instance
+                .port_forwards
+                .iter()
+                .filter(|forward| forward.enabled)
+                .any(|forward| {
+                    !is_loopback_bind_host(forward.local_host.as_deref().unwrap_or("127.0.0.1"))
+                });
+        let local_url = format!("http://{}:{local_port}", bracket_host(&local_host));
+        let _ = update_ssh_host_url(
+            &self.settings_path,
+            &id,
+            &build_display_label(&instance),
+            &local_url,
+        );
+
+        self.sessions.lock().expect("kube sessions mutex").insert(
+            id.clone(),
+            KubeSession {
+                instance,
+                target,
+                pod,
+                local_host,
+                local_port,
+                remote_port,
+                started_by_us,
+                main_forward,
+                extra_forwards,
+            },
+        );
+        self.clear_retry_attempt(&id);
+        self.tracker.publish(
+            app,
+            DesktopSshInstanceStatus {
+                phase: DesktopSshPhase::Ready,
+                detail: Some("Kubernetes instance is ready".to_string()),
+                local_url: Some(local_url),
+                local_port: Some(local_port),
+                remote_port: Some(remote_port),
+                started_by_us,
+                exposed_beyond_loopback: exposed,
+                ..DesktopSshInstanceStatus::idle(&id)
+            },
+        );
+        Ok(())
+    }
+
+    fn monitor_tick(&self, id: &str) -> MonitorTick {
+        let mut notices = Vec::new();
+        let dropped_reason = {
+            let mut sessions = self.sessions.lock().expect("kube sessions mutex");
+            let Some(session) = sessions.get_mut(id) else {
+                return MonitorTick::SessionGone;
+            };
+
+            session.extra_forwards.retain_mut(|(forward_id, process)| {
+                let Some(status) = process.try_wait() else {
+                    return true;
+                };
+                notices.push(format!(
+                    "Port forward {forward_id} exited ({status}) {}",
+                    process.take_stderr().trim()
+                ));
+                false
+            });
+
+            match session.main_forward.try_wait() {
+                Some(status) => {
+                    let stderr = session.main_forward.take_stderr();
+                    Some(if stderr.trim().is_empty() {
+                        format!("kubectl port-forward to {} exited ({status})", session.pod)
+                    } else {
+                        format!(
+                            "kubectl port-forward to {} exited ({status}): {}",
+                            session.pod,
+                            stderr.trim()
+                        )
+                    })
+                }
+                None if !is_local_tunnel_reachable(&session.local_host, session.local_port) => {
+                    Some("Local port-forward is not reachable".to_string())
+                }
+                None => None,
+            }
+        };
+
+        for notice in notices {
+            self.tracker
+                .append_log_with_level(id, "WARN", notice.trim_end());
+        }
+
+        match dropped_reason {
+            Some(reason) => MonitorTick::Dropped(reason),
+            None => MonitorTick::Healthy,
+        }
+    }
+
+    /// Tear down a dropped session and decide whether to retry. Returns the
+    /// backoff to wait before reconnecting, or `None` once the retry limit
+    /// is exhausted and the instance has been moved to `Error`.
+    fn begin_reconnect(&self, app: &dyn ManagerEvents, id: &str, reason: &str) -> Option<Duration> {
+        self.tracker.append_log_with_level(id, "WARN", reason);
+        self.teardown_session(id);
+        let attempt = self.next_retry_attempt(id);
+
+        if attempt > DEFAULT_RECONNECT_MAX_ATTEMPTS {
+            self.tracker.publish(
+                app,
+                DesktopSshInstanceStatus {
+                    phase: DesktopSshPhase::Error,
+                    detail: Some(format!("{reason}. Retry limit reached")),
+                    retry_attempt: attempt,
+                    requires_user_action: true,
+                    ..DesktopSshInstanceStatus::idle(id)
+                },
+            );
+            return None;
+        }
+
+        self.set_phase(
+            app,
+            id,
+            DesktopSshPhase::Degraded,
+            format!("{reason}. Reconnecting"),
+        );
+
+        let delay_ms = (2u64.saturating_pow(attempt.saturating_sub(1))).saturating_mul(1000);
+        let jitter = (now_millis() % 700).saturating_add(100);
+        Some(Duration::from_millis(
+            delay_ms.min(30_000).saturating_add(jitter),
+        ))
+    }
+
+    /// Stop every forward owned by the session and, when this app started
+    /// the server and should not keep it, the server itself.
+    fn teardown_session(&self, id: &str) {
+        let Some(mut session) = self
+            .sessions
+            .lock()
+            .expect("kube sessions mutex")
+            .remove(id)
+        else {
+            return;
+        };
+        for (_, process) in session.extra_forwards.iter_mut() {
+            process.kill();
+        }
+        session.main_forward.kill();
+        if session.started_by_us && !session.instance.remote_openchamber.keep_running {
+            let _ = self.runtime.exec(
+                &session.target,
+                &session.pod,
+                &stop_server_script(session.remote_port),
+            );
+        }
+    }
+
+    fn cancel_task(tasks: &Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>, id: &str) {
+        if let Some(handle) = tasks.lock().expect("kube task mutex").remove(id) {
+            handle.abort();
+        }
+    }
+
+    fn disconnect(&self, app: &dyn ManagerEvents, id: &str) {
+        Self::cancel_task(&self.connect_tasks, id);
+        Self::cancel_task(&self.monitor_tasks, id);
+        self.teardown_session(id);
+        self.clear_retry_attempt(id);
+        self.tracker
+            .publish(app, DesktopSshInstanceStatus::idle(id));
+    }
+
+    /// Re-check the server inside the pod for an explicit status request.
+    fn refresh_session(&self, app: &dyn ManagerEvents, id: &str) {
+        let target = {
+            let sessions = self.sessions.lock().expect("kube sessions mutex");
+            sessions.get(id).map(|session| {
+                (
+                    session.target.clone(),
+                    session.pod.clone(),
+                    session.remote_port,
+                )
+            })
+        };
+        let Some((target, pod, remote_port)) = target else {
+            return;
+        };
+        let healthy = self
+            .runtime
+            .exec(&target, &pod, &server_health_script(remote_port))
+            .is_ok_and(|output| output.trim() == "yes");
+        let previous = self.tracker.snapshot(id);
+        if !healthy && previous.phase == DesktopSshPhase::Ready {
+            self.tracker.publish(
+                app,
+                DesktopSshInstanceStatus {
+                    phase: DesktopSshPhase::Degraded,
+                    detail: Some("OpenChamber server in pod is not responding".to_string()),
+                    updated_at_ms: now_millis(),
+                    ..previous
+                },
+            );
+        }
+    }
+
+    fn spawn_monitor(self: &Arc<Self>, app: Arc<dyn ManagerEvents>, id: String) {
+        Self::cancel_task(&self.monitor_tasks, &id);
+        let inner = Arc::clone(self);
+        let id_for_task = id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let mut healthy_ticks: u32 = 0;
+            loop {
+                let poll_secs = if healthy_ticks >= MONITOR_STABILIZE_TICKS {
+                    MONITOR_STEADY_POLL_SECS
+                } else {
+                    MONITOR_INITIAL_POLL_SECS
+                };
+                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
+
+                let tick = tauri::async_runtime::spawn_blocking({
+                    let inner = Arc::clone(&inner);
+                    let id = id_for_task.clone();
+                    move || inner.monitor_tick(&id)
+                })
+                .await
+                .unwrap_or(MonitorTick::Healthy);
+
+                let reason = match tick {
+                    MonitorTick::Healthy => {
+                        healthy_ticks = healthy_ticks.saturating_add(1);
+                        continue;
+                    }
+                    MonitorTick::SessionGone => break,
+                    MonitorTick::Dropped(reason) => reason,
+                };
+
+                inner
+                    .monitor_tasks
+                    .lock()
+                    .expect("kube task mutex")
+                    .remove(&id_for_task);
+                let Some(delay) = inner.begin_reconnect(app.as_ref(), &id_for_task, &reason) else {
+                    break;
+                };
+                tokio::time::sleep(delay).await;
+                if let Err(err) = inner.start_connect(Arc::clone(&app), id_for_task.clone()) {
+                    inner.set_phase(app.as_ref(), &id_for_task, DesktopSshPhase::Error, err);
+                }
+                break;
+            }
+        });
+        self.monitor_tasks
+            .lock()
+            .expect("kube task mutex")
+            .insert(id, handle);
+    }
+
+    fn start_connect(
+        self: &Arc<Self>,
+        app: Arc<dyn ManagerEvents>,
+        id: String,
+    ) -> Result<(), String> {
+        let config = read_kube_instances_from_path(&self.settings_path);
+        let Some(instance) = config.instances.into_iter().find(|item| item.id == id) else {
+            return Err("Kubernetes instance not found".to_string());
+        };
+        if self
+            .connect_tasks
+            .lock()
+            .expect("kube task mutex")
+            .contains_key(&id)
+        {
+            self.tracker
+                .append_log(&id, "Connection already in progress");
+            return Ok(());
+        }
+
+        self.tracker
+            .append_log(&id, "Starting Kubernetes connection");
+        // Keep the retry counter: reconnects go through here and must still
+        // reach the retry limit.
+        Self::cancel_task(&self.monitor_tasks, &id);
+        self.teardown_session(&id);
+
+        let inner = Arc::clone(self);
+        let id_for_task = id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let result = tauri::async_runtime::spawn_blocking({
+                let inner = Arc::clone(&inner);
+                let app = Arc::clone(&app);
+                move || inner.connect_blocking(app.as_ref(), instance)
+            })
+            .await;
+            let failure = match result {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err),
+                Err(err) => Some(anyhow!("Kubernetes task failed: {err}")),
+            };
+            inner
+                .connect_tasks
+                .lock()
+                .expect("kube task mutex")
+                .remove(&id_for_task);
+            match failure {
+                None => inner.spawn_monitor(app, id_for_task),
+                Some(err) => {
+                    inner.set_phase(
+                        app.as_ref(),
+                        &id_for_task,
+                        DesktopSshPhase::Error,
+                        err.to_string(),
+                    );
+                    inner.teardown_session(&id_for_task);
+                }
+            }
+        });
+        self.connect_tasks
+            .lock()
+            .expect("kube task mutex")
+            .insert(id, handle);
+        Ok(())
+    }
+}
+
+impl DesktopKubeManagerState {
+    pub fn statuses(&self) -> Vec<DesktopSshInstanceStatus> {
+        self.inner.statuses_with_defaults()
+    }
+
+    pub fn has_instance(&self, id: &str) -> bool {
+        read_kube_instances_from_path(&self.inner.settings_path)
+            .instances
+            .iter()
+            .any(|instance| instance.id == id)
+    }
+
+    /// Connect `id`, or reuse its ready session, and return the local URL.
+    pub async fn connect_and_wait(&self, app: &AppHandle, id: &str) -> Result<String> {
+        if let Some(url) = self.inner.tracker.ready_url(id) {
+            return Ok(url);
+        }
+        self.inner.clear_retry_attempt(id);
+        self.inner
+            .start_connect(Arc::new(app.clone()), id.to_string())
+            .map_err(|err| anyhow!(err))?;
+        let connecting = || {
+            self.inner
+                .connect_tasks
+                .lock()
+                .expect("kube task mutex")
+                .contains_key(id)
+        };
+        self.inner
+            .tracker
+            .wait_for_ready_url(id, connecting, "Kubernetes connection failed")
+            .await
+    }
+
+    pub fn shutdown_all(&self, app: &AppHandle) {
+        let ids: HashSet<String> = {
+            let sessions = self.inner.sessions.lock().expect("kube sessions mutex");
+            let connecting = self.inner.connect_tasks.lock().expect("kube task mutex");
+            sessions.keys().chain(connecting.keys()).cloned().collect()
+        };
+        for id in ids {
+            self.inner.disconnect(app, &id);
+        }
+    }
+}
+
+fn required_id(id: &str) -> Result<String, String> {
+    let id = id.trim();
+    if id.is_empty() || id == LOCAL_HOST_ID {
+        return Err("Kubernetes instance id is required".to_string());
+    }
+    Ok(id.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_kube_instances_get() -> Result<DesktopKubeInstancesConfig, String> {
+    Ok(read_kube_instances_from_path(&settings_file_path()))
+}
+
+#[tauri::command]
+pub fn desktop_kube_instances_set(config: DesktopKubeInstancesConfig) -> Result<(), String> {
+    write_kube_instances_to_path(&settings_file_path(), config)
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_kube_connect(
+    app: AppHandle,
+    state: State<'_, DesktopKubeManagerState>,
+    id: String,
+) -> Result<(), String> {
+    let id = required_id(&id)?;
+    state.inner.clear_retry_attempt(&id);
+    state.inner.start_connect(Arc::new(app), id)
+}
+
+#[tauri::command]
+pub fn desktop_kube_disconnect(
+    app: AppHandle,
+    state: State<'_, DesktopKubeManagerState>,
+    id: String,
+) -> Result<(), String> {
+    let id = required_id(&id)?;
+    state.inner.disconnect(&app, &id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn desktop_kube_status(
+    app: AppHandle,
+    state: State<'_, DesktopKubeManagerState>,
+    id: Option<String>,
+) -> Result<Vec<DesktopSshInstanceStatus>, String> {
+    let inner = Arc::clone(&state.inner);
+    let id = id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    tauri::async_runtime::spawn_blocking(move || match id {
+        Some(id) => {
+            inner.refresh_session(&app, &id);
+            vec![inner.tracker.snapshot(&id)]
+        }
+        None => inner.statuses_with_defaults(),
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+pub fn desktop_kube_logs(
+    state: State<'_, DesktopKubeManagerState>,
+    id: String,
+    limit: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let id = required_id(&id)?;
+    let cap = limit.unwrap_or(200).min(MAX_LOG_LINES_PER_INSTANCE);
+    Ok(state.inner.tracker.logs(&id, cap))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instance_tracker::fake::{temp_settings_path, RecordingEvents};
+    use crate::kube_transport::fake::FakeKubeRuntime;
+    use serde_json::json;
+
+    fn fake_manager(name: &str) -> (Arc<FakeKubeRuntime>, DesktopKubeManagerInner) {
+        let runtime = Arc::new(FakeKubeRuntime::default());
+        runtime.respond("uname -s", Ok("Linux\n"));
+        let manager = DesktopKubeManagerInner::with_runtime(
+            runtime.clone(),
+            temp_settings_path("kube", name),
+        );
+        (runtime, manager)
+    }
+
+    fn sample_instance(id: &str, selector: &str) -> DesktopKubeInstance {
+        DesktopKubeInstance {
+            id: id.to_string(),
+            nickname: None,
+            context: Some("dev".to_string()),
+            namespace: Some("team-a".to_string()),
+            pod: None,
+            selector: Some(selector.to_string()),
+            container: None,
+            remote_openchamber: DesktopSshRemoteOpenchamberConfig::default(),
+            local_forward: DesktopSshLocalForwardConfig::default(),
+            port_forwards: Vec::new(),
+        }
+    }
+
+    fn pod_forward(id: &str, remote_host: Option<&str>) -> DesktopSshPortForward {
+        DesktopSshPortForward {
+            id: id.to_string(),
+            enabled: true,
+            forward_type: DesktopSshPortForwardType::Local,
+            local_host: None,
+            local_port: Some(pick_unused_local_port("127.0.0.1").expect("port")),
+            remote_host: remote_host.map(str::to_string),
+            remote_port: Some(5432),
+        }
+    }
+
+    #[test]
+    fn sanitize_requires_one_pod_source_and_pod_local_forwards() {
+        let mut neither = sample_instance("kube-a", "app=devbox");
+        neither.selector = None;
+        assert!(sanitize_kube_instance(neither).is_err());
+
+        let mut both = sample_instance("kube-a", "app=devbox");
+        both.pod = Some("devbox-0".to_string());
+        assert!(sanitize_kube_instance(both).is_err());
+
+        let mut elsewhere = sample_instance("kube-a", "app=devbox");
+        elsewhere.port_forwards = vec![pod_forward("db", Some("db.internal"))];
+        let err = sanitize_kube_instance(elsewhere).expect_err("non-pod target");
+        assert!(err.to_string().contains("port of the pod"));
+
+        let mut dynamic = sample_instance("kube-a", "app=devbox");
+        let mut forward = pod_forward("socks", None);
+        forward.forward_type = DesktopSshPortForwardType::Dynamic;
+        dynamic.port_forwards = vec![forward];
+        assert!(sanitize_kube_instance(dynamic).is_err());
+
+        let mut valid = sample_instance("kube-a", " app=devbox ");
+        valid.port_forwards = vec![pod_forward("db", None)];
+        let sanitized = sanitize_kube_instance(valid).expect("valid");
+        assert_eq!(sanitized.selector.as_deref(), Some("app=devbox"));
+        assert_eq!(
+            sanitized.port_forwards[0].remote_host.as_deref(),
+            Some("127.0.0.1")
+        );
+
+        let (runtime, manager) = fake_manager("bind-missing");
+        let mut unassigned = sample_instance("kube-a", "app=devbox");
+        unassigned.local_forward.bind_host = "192.0.2.1".to_string();
+        let sanitized = sanitize_kube_instance(unassigned).expect("syntax is valid");
+        let err = manager
+            .connect_blocking(&RecordingEvents::default(), sanitized)
+            .expect_err("unassigned address");
+        assert!(err.to_string().contains("not available on this machine"));
+        assert!(runtime.forwards().is_empty());
+    }
+
+    #[test]
+    fn write_syncs_hosts_and_rejects_other_instance_ids() {
+        let path = temp_settings_path("kube", "write");
+        write_settings_root(
+            &path,
+            &json!({ "desktopDockerInstances": [{ "id": "shared", "container": { "name": "x" } }] }),
+        )
+        .expect("seed");
+
+        let err = write_kube_instances_to_path(
+            &path,
+            DesktopKubeInstancesConfig {
+                instances: vec![sample_instance("shared", "app=devbox")],
+            },
+        )
+        .expect_err("collision");
+        assert!(err.to_string().contains("Docker instance"));
+
+        write_kube_instances_to_path(
+            &path,
+            DesktopKubeInstancesConfig {
+                instances: vec![sample_instance("kube-devbox", "app=devbox")],
+            },
+        )
+        .expect("write");
+        let root = read_settings_root(&path);
+        assert!(root["desktopHosts"]
+            .as_array()
+            .expect("hosts")
+            .iter()
+            .any(|host| host["id"] == "kube-devbox" && host["label"] == "k8s:team-a/app=devbox"));
+        assert!(kube_instance_ids(&root).contains("kube-devbox"));
+    }
+
+    #[test]
+    fn connect_resolves_selector_installs_and_forwards() {
+        let (runtime, manager) = fake_manager("connect");
+        runtime.add_pod("devbox-7f9c", &["app=devbox"]);
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("kube-devbox", "app=devbox");
+        instance.port_forwards = vec![pod_forward("db", Some("127.0.0.1"))];
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+
+        assert!(runtime
+            .execs()
+            .iter()
+            .any(|(pod, script)| pod == "devbox-7f9c"
+                && script.contains("bun add -g @openchamber/web@1.9.10")));
+        assert!(runtime
+            .execs()
+            .iter()
+            .any(|(_, script)| script.contains("--hostname 127.0.0.1 --port 3000")));
+        let forwards = runtime.forwards();
+        assert_eq!(forwards.len(), 2);
+        assert!(forwards[0].starts_with("devbox-7f9c ") && forwards[0].ends_with(":3000"));
+        assert!(forwards[1].ends_with(":5432"));
+
+        let phases = events.phases();
+        assert_eq!(phases.first(), Some(&DesktopSshPhase::ConfigResolved));
+        assert!(phases.contains(&DesktopSshPhase::Installing));
+        let ready = events.last();
+        assert_eq!(ready.phase, DesktopSshPhase::Ready);
+        assert!(ready.started_by_us);
+        assert_eq!(ready.remote_port, Some(3000));
+        assert!(matches!(
+            manager.monitor_tick("kube-devbox"),
+            MonitorTick::Healthy
+        ));
+    }
+
+    #[test]
+    fn dropped_forward_reconnects_to_rescheduled_pod() {
+        let (runtime, manager) = fake_manager("reconnect");
+        runtime.add_pod("devbox-old", &["app=devbox"]);
+        runtime.respond("openchamber --version", Ok("openchamber 1.9.10\n"));
+        let events = RecordingEvents::default();
+        let instance = sample_instance("kube-devbox", "app=devbox");
+
+        manager
+            .connect_blocking(&events, instance.clone())
+            .expect("connect");
+        runtime.drop_forwards();
+        runtime.remove_pod("devbox-old");
+        runtime.add_pod("devbox-new", &["app=devbox"]);
+
+        let MonitorTick::Dropped(reason) = manager.monitor_tick("kube-devbox") else {
+            panic!("expected dropped tick");
+        };
+        assert!(reason.contains("devbox-old"));
+        assert!(manager
+            .begin_reconnect(&events, "kube-devbox", &reason)
+            .is_some());
+        let degraded = events.last();
+        assert_eq!(degraded.phase, DesktopSshPhase::Degraded);
+        assert_eq!(degraded.retry_attempt, 1);
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("reconnect");
+        assert!(runtime
+            .forwards()
+            .last()
+            .expect("forward")
+            .starts_with("devbox-new "));
+        let ready = events.last();
+        assert_eq!(ready.phase, DesktopSshPhase::Ready);
+        assert_eq!(ready.retry_attempt, 0);
+    }
+
+    #[test]
+    fn reconnect_gives_up_at_retry_limit() {
+        let (_runtime, manager) = fake_manager("limit");
+        let events = RecordingEvents::default();
+        for _ in 0..DEFAULT_RECONNECT_MAX_ATTEMPTS {
+            assert!(manager
+                .begin_reconnect(&events, "kube-devbox", "Pod deleted")
+                .is_some());
+        }
+        assert!(manager
+            .begin_reconnect(&events, "kube-devbox", "Pod deleted")
+            .is_none());
+        let last = events.last();
+        assert_eq!(last.phase, DesktopSshPhase::Error);
+        assert!(last.requires_user_action);
+    }
+
+    #[test]
+    fn disconnect_stops_server_started_for_the_session() {
+        let (runtime, manager) = fake_manager("disconnect");
+        runtime.add_pod("devbox-0", &[]);
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("kube-devbox", "");
+        instance.selector = None;
+        instance.pod = Some("devbox-0".to_string());
+        instance.remote_openchamber.keep_running = false;
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        manager.disconnect(&events, "kube-devbox");
+
+        assert!(runtime
+            .execs()
+            .last()
+            .is_some_and(|(_, script)| script.contains("/api/system/shutdown")));
+        assert!(matches!(
+            manager.monitor_tick("kube-devbox"),
+            MonitorTick::SessionGone
+        ));
+        assert_eq!(events.last().phase, DesktopSshPhase::Idle);
+    }
+}
@@ -1,43 +1,71 @@
-use anyhow::{anyhow, Context, Result};
+use crate::local_profiles::local_profile_ids;
+use crate::remote_docker::docker_instance_ids;
+use crate::remote_kube::kube_instance_ids;
+use crate::ssh_transport::{
+    bracket_host, shell_quote, ExternalMaster, OpenSshTransport, SshMasterOptions, SshProcess,
+    SshTransport, DEFAULT_CONTROL_PERSIST_SEC,
+};
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::{
     collections::{HashMap, HashSet},
     fs,
-    io::Read,
-    net::{TcpListener, TcpStream},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
     path::{Path, PathBuf},
-    process::{Child, Command, Stdio},
     sync::{Arc, Mutex},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tauri::{AppHandle, Emitter, State};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
-const LOCAL_HOST_ID: &str = "local";
+pub(crate) const LOCAL_HOST_ID: &str = "local";
 const SSH_STATUS_EVENT: &str = "openchamber:ssh-instance-status";
+const SSH_METRICS_EVENT: &str = "openchamber:ssh-instance-metrics";
 const DEFAULT_CONNECTION_TIMEOUT_SEC: u16 = 60;
-const DEFAULT_LOCAL_BIND_HOST: &str = "127.0.0.1";
-const DEFAULT_CONTROL_PERSIST_SEC: u16 = 300;
+pub(crate) const DEFAULT_LOCAL_BIND_HOST: &str = "127.0.0.1";
 const DEFAULT_READY_TIMEOUT_SEC: u64 = 30;
-const DEFAULT_RECONNECT_MAX_ATTEMPTS: u32 = 5;
+pub(crate) const DEFAULT_RECONNECT_MAX_ATTEMPTS: u32 = 5;
 const MAX_LOG_LINES_PER_INSTANCE: usize = 1200;
+const JUMP_HOST_ALIAS_PREFIX: &str = "openchamber-jump-";
+const JUMP_HOSTS_CONFIG_FILE: &str = "jump_hosts.conf";
+const AGENT_IDENTITY_FILE: &str = "agent_identity.pub";
+const AUTO_CONNECT_GROUPS_KEY: &str = "desktopSshAutoConnectGroups";
+const DEFAULT_BULK_CONCURRENCY: usize = 4;
+const MAX_BULK_CONCURRENCY: usize = 16;
+const INSTANCES_BUNDLE_FORMAT: &str = "openchamber-ssh-instances";
+const INSTANCES_BUNDLE_VERSION: u32 = 1;
 
 /// Monitor starts with fast polling and relaxes to steady-state after stabilization.
-const MONITOR_INITIAL_POLL_SECS: u64 = 2;
-const MONITOR_STEADY_POLL_SECS: u64 = 10;
+pub(crate) const MONITOR_INITIAL_POLL_SECS: u64 = 2;
+pub(crate) const MONITOR_STEADY_POLL_SECS: u64 = 10;
 /// Number of healthy ticks before switching from initial to steady-state polling.
-const MONITOR_STABILIZE_TICKS: u32 = 5;
+pub(crate) const MONITOR_STABILIZE_TICKS: u32 = 5;
+/// Remote metrics are sampled on healthy ticks at most this often.
+const METRICS_POLL_SECS: u64 = 60;
+/// Idle-stop leases are refreshed this often; well under the one-minute
+/// granularity of the remote watchdog.
+const IDLE_LEASE_REFRESH_SECS: u64 = 20;
+const MAX_IDLE_STOP_MIN: u32 = 7 * 24 * 60;
+const MAX_CONTROL_PERSIST_SEC: u32 = 24 * 60 * 60;
+/// Free space on the remote home volume below this is flagged as low.
+const LOW_DISK_FREE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DesktopSshInstancesConfig {
     pub instances: Vec<DesktopSshInstance>,
+    /// Groups connected automatically at app startup. `None` on write keeps
+    /// the stored list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_connect_groups: Option<Vec<String>>,
 }
 
 impl Default for DesktopSshInstancesConfig {
     fn default() -> Self {
         Self {
             instances: Vec::new(),
+            auto_connect_groups: None,
         }
     }
 }
@@ -89,6 +117,10 @@ pub struct DesktopSshRemoteOpenchamberConfig {
     pub install_method: DesktopSshInstallMethod,
     #[serde(default)]
     pub upload_bundle_over_ssh: bool,
+    /// With `keep_running`, stop the server once no desktop has been
+    /// connected for this many minutes and no scheduled tasks are active.
+    #[serde(default)]
+    pub idle_stop_after_min: Option<u32>,
 }
 
 impl Default for DesktopSshRemoteOpenchamberConfig {
@@ -99,6 +131,7 @@ impl Default for DesktopSshRemoteOpenchamberConfig {
             preferred_port: None,
             install_method: DesktopSshInstallMethod::Bun,
             upload_bundle_over_ssh: false,
+            idle_stop_after_min: None,
         }
     }
 }
@@ -120,6 +153,45 @@ impl Default for DesktopSshLocalForwardConfig {
     }
 }
 
+/// SSH connection multiplexing for an instance.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshMultiplexConfig {
+    /// Seconds the ControlMaster lingers after the last client; `0` disables
+    /// backgrounding.
+    #[serde(default = "default_control_persist_sec")]
+    pub control_persist_sec: u32,
+    #[serde(default)]
+    pub keep_alive_interval_sec: Option<u16>,
+    #[serde(default)]
+    pub compression: bool,
+    /// Attach to the ControlMaster from the user's SSH config when one is
+    /// already running for the destination instead of opening a new one.
+    #[serde(default)]
+    pub reuse_user_master: bool,
+}
+
+impl Default for DesktopSshMultiplexConfig {
+    fn default() -> Self {
+        Self {
+            control_persist_sec: default_control_persist_sec(),
+            keep_alive_interval_sec: None,
+            compression: false,
+            reuse_user_master: false,
+        }
+    }
+}
+
+impl DesktopSshMultiplexConfig {
+    fn master_options(&self) -> SshMasterOptions {
+        SshMasterOptions {
+            persist_sec: self.control_persist_sec,
+            keep_alive_interval_sec: self.keep_alive_interval_sec,
+            compression: self.compression,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DesktopSshSecretStore {
@@ -172,6 +244,54 @@ pub struct DesktopSshPortForward {
     pub remote_port: Option<u16>,
 }
 
+/// One bastion hop, in connection order. Rendered into a generated ssh
+/// config so each hop can carry its own user, port and identity.
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshJumpHost {
+    pub host: String,
+    pub user: Option<String>,
+    pub port: Option<u16>,
+    pub identity_file: Option<String>,
+    pub ssh_password: Option<DesktopSshStoredSecret>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopSshIdentitySource {
+    Agent,
+    File,
+}
+
+/// Key an instance authenticates with: an agent key by fingerprint or a
+/// private key path.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshIdentityChoice {
+    pub source: DesktopSshIdentitySource,
+    pub value: String,
+    #[serde(default = "default_true")]
+    pub identities_only: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshIdentityCandidate {
+    pub source: DesktopSshIdentitySource,
+    pub value: String,
+    pub label: String,
+    pub key_type: Option<String>,
+    pub bits: Option<u32>,
+    pub available: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshIdentityList {
+    pub identities: Vec<DesktopSshIdentityCandidate>,
+    pub agent_error: Option<String>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DesktopSshInstance {
@@ -189,6 +309,57 @@ pub struct DesktopSshInstance {
     pub auth: DesktopSshAuthConfig,
     #[serde(default)]
     pub port_forwards: Vec<DesktopSshPortForward>,
+    #[serde(default)]
+    pub jump_hosts: Vec<DesktopSshJumpHost>,
+    #[serde(default)]
+    pub identity: Option<DesktopSshIdentityChoice>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub multiplex: DesktopSshMultiplexConfig,
+}
+
+/// Picks instances for bulk operations. Set fields must all match.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshSelector {
+    pub tag: Option<String>,
+    pub group: Option<String>,
+}
+
+impl DesktopSshSelector {
+    fn normalized(&self) -> Option<Self> {
+        let selector = Self {
+            tag: trimmed_non_empty(self.tag.as_deref()),
+            group: trimmed_non_empty(self.group.as_deref()),
+        };
+        (selector.tag.is_some() || selector.group.is_some()).then_some(selector)
+    }
+
+    fn matches(&self, instance: &DesktopSshInstance) -> bool {
+        let tag_matches = self.tag.as_deref().is_none_or(|tag| {
+            instance
+                .tags
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(tag))
+        });
+        let group_matches = self.group.as_deref().is_none_or(|group| {
+            instance
+                .group
+                .as_deref()
+                .is_some_and(|candidate| candidate.eq_ignore_ascii_case(group))
+        });
+        tag_matches && group_matches
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshBulkStatus {
+    pub status: DesktopSshInstanceStatus,
+    pub session_alive: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -203,6 +374,8 @@ pub enum DesktopSshPhase {
     Updating,
     ServerDetecting,
     ServerStarting,
+    /// Docker instances: creating or starting the container.
+    ContainerStarting,
     Forwarding,
     Ready,
     Degraded,
@@ -224,11 +397,17 @@ pub struct DesktopSshInstanceStatus {
     pub retry_attempt: u32,
     #[serde(default)]
     pub requires_user_action: bool,
+    /// Index into `jump_hosts` of the hop that failed, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_jump_host: Option<usize>,
+    /// Set while any forward of the session listens beyond loopback.
+    #[serde(default)]
+    pub exposed_beyond_loopback: bool,
     pub updated_at_ms: u64,
 }
 
 impl DesktopSshInstanceStatus {
-    fn idle(id: impl Into<String>) -> Self {
+    pub(crate) fn idle(id: impl Into<String>) -> Self {
         Self {
             id: id.into(),
             phase: DesktopSshPhase::Idle,
@@ -239,11 +418,52 @@ impl DesktopSshInstanceStatus {
             started_by_us: false,
             retry_attempt: 0,
             requires_user_action: false,
+            failed_jump_host: None,
+            exposed_beyond_loopback: false,
             updated_at_ms: now_millis(),
         }
     }
 }
 
+/// Portable, secret-free set of instance definitions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshInstancesBundle {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at_ms: u64,
+    #[serde(default)]
+    pub app_version: Option<String>,
+    pub instances: Vec<DesktopSshInstance>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DesktopSshImportAction {
+    Add,
+    Rename,
+    Skip,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshImportEntry {
+    pub source_id: String,
+    pub id: String,
+    pub label: String,
+    pub action: DesktopSshImportAction,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshImportPreview {
+    pub version: u32,
+    pub entries: Vec<DesktopSshImportEntry>,
+    pub applied: bool,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DesktopSshImportCandidate {
@@ -253,9 +473,13 @@ pub struct DesktopSshImportCandidate {
     pub ssh_command: String,
 }
 
-#[derive(Default)]
 struct DesktopSshManagerInner {
+    transport: Arc<dyn SshTransport>,
+    settings_path: PathBuf,
     statuses: Mutex<HashMap<String, DesktopSshInstanceStatus>>,
+    metrics: Mutex<HashMap<String, DesktopSshRemoteMetrics>>,
+    /// Listeners of live sessions that are reachable beyond loopback.
+    exposures: Mutex<HashMap<String, Vec<String>>>,
     logs: Mutex<HashMap<String, Vec<String>>>,
     sessions: Mutex<HashMap<String, SshSession>>,
     connect_tasks: Mutex<HashMap<String, tauri::async_runtime::JoinHandle<()>>>,
@@ -264,19 +488,59 @@ struct DesktopSshManagerInner {
     connect_attempts: Mutex<HashMap<String, u32>>,
 }
 
+impl Default for DesktopSshManagerInner {
+    fn default() -> Self {
+        Self::with_transport(Arc::new(OpenSshTransport), settings_file_path())
+    }
+}
+
 struct SshSession {
     instance: DesktopSshInstance,
     parsed: DesktopSshParsedCommand,
     session_dir: PathBuf,
     control_path: PathBuf,
+    /// Address the desktop dials to reach the main forward.
+    local_host: String,
     local_port: u16,
     remote_port: u16,
     started_by_us: bool,
-    master: Child,
+    master: Box<dyn SshProcess>,
     master_detached: bool,
-    main_forward: Child,
+    /// False when attached to a ControlMaster the user started.
+    owns_master: bool,
+    main_forward: Box<dyn SshProcess>,
     main_forward_detached: bool,
-    extra_forwards: Vec<Child>,
+    extra_forwards: Vec<DesktopSshPortForward>,
+    /// Lease this desktop keeps fresh while the idle-stop watchdog runs.
+    idle_lease: Option<String>,
+}
+
+/// Outcome of a single monitor poll.
+enum MonitorTick {
+    Healthy,
+    Dropped(String),
+    SessionGone,
+}
+
+/// Sink for manager side effects that would otherwise need a live `AppHandle`.
+pub trait SshManagerEvents: Send + Sync {
+    fn app_version(&self) -> String;
+    fn emit_status(&self, status: &DesktopSshInstanceStatus);
+    fn emit_metrics(&self, metrics: &DesktopSshRemoteMetrics);
+}
+
+impl SshManagerEvents for AppHandle {
+    fn app_version(&self) -> String {
+        self.package_info().version.to_string()
+    }
+
+    fn emit_status(&self, status: &DesktopSshInstanceStatus) {
+        let _ = self.emit(SSH_STATUS_EVENT, status);
+    }
+
+    fn emit_metrics(&self, metrics: &DesktopSshRemoteMetrics) {
+        let _ = self.emit(SSH_METRICS_EVENT, metrics);
+    }
 }
 
 #[derive(Default)]
@@ -293,7 +557,53 @@ struct RemoteSystemInfo {
     started_at: Option<String>,
 }
 
-fn default_true() -> bool {
+/// Lightweight sample of the remote host and its OpenChamber server.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DesktopSshRemoteMetrics {
+    pub id: String,
+    pub collected_at_ms: u64,
+    pub load_average: Option<[f64; 3]>,
+    pub mem_total_bytes: Option<u64>,
+    pub mem_available_bytes: Option<u64>,
+    pub disk_mount: Option<String>,
+    pub disk_total_bytes: Option<u64>,
+    pub disk_free_bytes: Option<u64>,
+    pub disk_low: bool,
+    pub server_pid: Option<u64>,
+    pub server_rss_bytes: Option<u64>,
+    pub server_started_at: Option<String>,
+    pub server_uptime_sec: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Connect failure attributed to a specific jump host.
+#[derive(Debug)]
+struct JumpHostFailure {
+    index: usize,
+    host: String,
+    message: String,
+}
+
+impl std::fmt::Display for JumpHostFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Jump host {} ({}) failed: {}",
+            self.index + 1,
+            self.host,
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for JumpHostFailure {}
+
+fn default_control_persist_sec() -> u32 {
+    DEFAULT_CONTROL_PERSIST_SEC
+}
+
+pub(crate) fn default_true() -> bool {
     true
 }
 
@@ -305,14 +615,14 @@ fn default_local_bind_host() -> String {
     DEFAULT_LOCAL_BIND_HOST.to_string()
 }
 
-fn now_millis() -> u64 {
+pub(crate) fn now_millis() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map(|d| d.as_millis() as u64)
         .unwrap_or(0)
 }
 
-fn settings_file_path() -> PathBuf {
+pub(crate) fn settings_file_path() -> PathBuf {
     if let Ok(dir) = std::env::var("OPENCHAMBER_DATA_DIR") {
         if !dir.trim().is_empty() {
             return PathBuf::from(dir.trim()).join("settings.json");
@@ -325,7 +635,7 @@ fn settings_file_path() -> PathBuf {
         .join("settings.json")
 }
 
-fn read_settings_root(path: &Path) -> Value {
+pub(crate) fn read_settings_root(path: &Path) -> Value {
     let raw = fs::read_to_string(path).unwrap_or_default();
     let parsed = serde_json::from_str::<Value>(&raw).unwrap_or_else(|_| json!({}));
     if parsed.is_object() {
@@ -335,7 +645,7 @@ fn read_settings_root(path: &Path) -> Value {
     }
 }
 
-fn write_settings_root(path: &Path, root: &Value) -> Result<()> {
+pub(crate) fn write_settings_root(path: &Path, root: &Value) -> Result<()> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
@@ -361,7 +671,87 @@ fn build_display_label(instance: &DesktopSshInstance) -> String {
     instance.id.clone()
 }
 
-fn read_desktop_ssh_instances_from_path(path: &Path) -> DesktopSshInstancesConfig {
+/// Host kinds whose ids share one namespace in the desktop hosts list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HostKind {
+    Ssh,
+    Docker,
+    Kube,
+    LocalProfile,
+}
+
+impl HostKind {
+    const ALL: [HostKind; 4] = [Self::Ssh, Self::Docker, Self::Kube, Self::LocalProfile];
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::Ssh => "an SSH instance",
+            Self::Docker => "a Docker instance",
+            Self::Kube => "a Kubernetes instance",
+            Self::LocalProfile => "a local profile",
+        }
+    }
+
+    /// Short name printed by `--list-hosts`.
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Ssh => "ssh",
+            Self::Docker => "docker",
+            Self::Kube => "kube",
+            Self::LocalProfile => "profile",
+        }
+    }
+
+    /// Kind of the host stored in `root` under `id`, if any.
+    pub(crate) fn of(root: &Value, id: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.ids(root).contains(id))
+    }
+
+    /// Ids of hosts of every kind stored in `root`.
+    pub(crate) fn all_ids(root: &Value) -> HashSet<String> {
+        Self::ALL
+            .into_iter()
+            .flat_map(|kind| kind.ids(root))
+            .collect()
+    }
+
+    fn ids(self, root: &Value) -> HashSet<String> {
+        match self {
+            Self::Ssh => root
+                .get("desktopSshInstances")
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(|item| item.get("id").and_then(Value::as_str))
+                        .map(|id| id.trim().to_string())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            Self::Docker => docker_instance_ids(root),
+            Self::Kube => kube_instance_ids(root),
+            Self::LocalProfile => local_profile_ids(root),
+        }
+    }
+}
+
+/// Fail when `id` is stored in `root` for a host of any kind but `kind`.
+pub(crate) fn ensure_host_id_unused(root: &Value, id: &str, kind: HostKind) -> Result<()> {
+    match HostKind::ALL
+        .into_iter()
+        .find(|other| *other != kind && other.ids(root).contains(id))
+    {
+        Some(other) => Err(anyhow!(
+            "Instance id {id} is already used by {}",
+            other.description()
+        )),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn read_desktop_ssh_instances_from_path(path: &Path) -> DesktopSshInstancesConfig {
     let root = read_settings_root(path);
     let Some(items) = root
         .get("desktopSshInstances")
@@ -400,42 +790,161 @@ fn read_desktop_ssh_instances_from_path(path: &Path) -> DesktopSshInstancesConfi
         instances.push(instance);
     }
 
-    DesktopSshInstancesConfig { instances }
+    DesktopSshInstancesConfig {
+        instances,
+        auto_connect_groups: Some(read_auto_connect_groups(&root)),
+    }
+}
+
+fn read_auto_connect_groups(root: &Value) -> Vec<String> {
+    let groups: Vec<String> = root
+        .get(AUTO_CONNECT_GROUPS_KEY)
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    sanitize_labels(&groups)
+}
+
+/// Trim, drop blanks and dedupe case-insensitively, keeping first spelling.
+fn sanitize_labels(values: &[String]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    values
+        .iter()
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty() && seen.insert(value.to_ascii_lowercase()))
+        .map(str::to_string)
+        .collect()
 }
 
 fn read_desktop_ssh_instances_from_disk() -> DesktopSshInstancesConfig {
     read_desktop_ssh_instances_from_path(&settings_file_path())
 }
 
-fn sanitize_bind_host(raw: &str) -> String {
+/// Host with surrounding whitespace and IPv6 brackets removed.
+fn unbracketed_host(raw: &str) -> Option<String> {
     let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return DEFAULT_LOCAL_BIND_HOST.to_string();
+    let host = trimmed
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+        .unwrap_or(trimmed)
+        .trim();
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+/// Normalize a local listen address: `localhost` or an IPv4/IPv6 literal.
+pub(crate) fn sanitize_bind_host(raw: &str) -> Result<String> {
+    let Some(host) = unbracketed_host(raw) else {
+        return Ok(DEFAULT_LOCAL_BIND_HOST.to_string());
+    };
+    if host.eq_ignore_ascii_case("localhost") {
+        return Ok("localhost".to_string());
+    }
+    host.parse::<IpAddr>()
+        .map(|ip| ip.to_string())
+        .map_err(|_| {
+            anyhow!(
+                "Invalid bind host '{}': expected localhost or an IP address",
+                raw.trim()
+            )
+        })
+}
+
+/// Fail unless `host` is a wildcard or assigned to one of this machine's
+/// interfaces. Probing with a throwaway listener covers every platform.
+pub(crate) fn ensure_bind_host_available(host: &str) -> Result<()> {
+    let Ok(ip) = host.parse::<IpAddr>() else {
+        return Ok(());
+    };
+    if ip.is_unspecified() {
+        return Ok(());
+    }
+    TcpListener::bind((ip, 0))
+        .map(|_| ())
+        .map_err(|err| anyhow!("Bind address {host} is not available on this machine: {err}"))
+}
+
+pub(crate) fn is_loopback_bind_host(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Address to dial for a listener bound to `bind_host`.
+pub(crate) fn connect_host_for_bind(bind_host: &str) -> String {
+    match bind_host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) if ip.is_unspecified() => Ipv4Addr::LOCALHOST.to_string(),
+        Ok(IpAddr::V6(ip)) if ip.is_unspecified() => Ipv6Addr::LOCALHOST.to_string(),
+        Ok(_) => bind_host.to_string(),
+        Err(_) => DEFAULT_LOCAL_BIND_HOST.to_string(),
+    }
+}
+
+/// Listeners of `instance` that accept connections from beyond loopback.
+fn exposed_listeners(instance: &DesktopSshInstance, main_port: u16) -> Vec<String> {
+    let mut exposed = Vec::new();
+    let bind_host = &instance.local_forward.bind_host;
+    if !is_loopback_bind_host(bind_host) {
+        exposed.push(format!(
+            "main tunnel on {}:{main_port}",
+            bracket_host(bind_host)
+        ));
     }
-    match trimmed {
-        "127.0.0.1" | "localhost" | "0.0.0.0" => trimmed.to_string(),
-        _ => DEFAULT_LOCAL_BIND_HOST.to_string(),
+    for forward in instance
+        .port_forwards
+        .iter()
+        .filter(|forward| forward.enabled)
+    {
+        let (side, host, port) = match forward.forward_type {
+            DesktopSshPortForwardType::Remote => (
+                "remote",
+                forward.remote_host.as_deref(),
+                forward.remote_port,
+            ),
+            _ => ("local", forward.local_host.as_deref(), forward.local_port),
+        };
+        let host = host.unwrap_or(DEFAULT_LOCAL_BIND_HOST);
+        if !is_loopback_bind_host(host) {
+            exposed.push(format!(
+                "{} ({side}) on {}:{}",
+                forward.id,
+                bracket_host(host),
+                port.unwrap_or_default()
+            ));
+        }
     }
+    exposed
 }
 
-fn sanitize_forward(forward: &DesktopSshPortForward) -> Option<DesktopSshPortForward> {
+pub(crate) fn sanitize_forward(
+    forward: &DesktopSshPortForward,
+) -> Result<Option<DesktopSshPortForward>> {
     let id = forward.id.trim().to_string();
     if id.is_empty() {
-        return None;
+        return Ok(None);
     }
 
     let mut normalized = forward.clone();
     normalized.id = id;
-    normalized.local_host = normalized
-        .local_host
-        .as_ref()
-        .map(|v| sanitize_bind_host(v))
-        .or_else(|| Some(DEFAULT_LOCAL_BIND_HOST.to_string()));
+    normalized.remote_host = normalized.remote_host.as_deref().and_then(unbracketed_host);
+    normalized.local_host = match normalized.forward_type {
+        // For `-R` the local side is a connect target, not a listener.
+        DesktopSshPortForwardType::Remote => {
+            normalized.local_host.as_deref().and_then(unbracketed_host)
+        }
+        _ => Some(sanitize_bind_host(
+            normalized.local_host.as_deref().unwrap_or(""),
+        )?),
+    };
 
     match normalized.forward_type {
         DesktopSshPortForwardType::Local => {
             if normalized.local_port.is_none() || normalized.remote_port.is_none() {
-                return None;
+                return Ok(None);
             }
             if normalized
                 .remote_host
@@ -449,7 +958,7 @@ fn sanitize_forward(forward: &DesktopSshPortForward) -> Option<DesktopSshPortFor
         }
         DesktopSshPortForwardType::Remote => {
             if normalized.local_port.is_none() || normalized.remote_port.is_none() {
-                return None;
+                return Ok(None);
             }
             if normalized
                 .remote_host
@@ -472,94 +981,531 @@ fn sanitize_forward(forward: &DesktopSshPortForward) -> Option<DesktopSshPortFor
         }
         DesktopSshPortForwardType::Dynamic => {
             if normalized.local_port.is_none() {
-                return None;
+                return Ok(None);
             }
             normalized.remote_host = None;
             normalized.remote_port = None;
         }
     }
 
-    Some(normalized)
+    Ok(Some(normalized))
 }
 
-fn sanitize_instance(mut instance: DesktopSshInstance) -> Result<DesktopSshInstance> {
-    instance.id = instance.id.trim().to_string();
-    if instance.id.is_empty() || instance.id == LOCAL_HOST_ID {
-        return Err(anyhow!("SSH instance id is required"));
+pub(crate) fn trimmed_non_empty(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Normalize a hop. Blank hosts are dropped; values that would break the
+/// generated config or the `-J` list are rejected.
+fn sanitize_jump_host(hop: &DesktopSshJumpHost) -> Result<Option<DesktopSshJumpHost>> {
+    let host = hop.host.trim();
+    if host.is_empty() {
+        return Ok(None);
     }
-    instance.ssh_command = instance.ssh_command.trim().to_string();
-    if instance.ssh_command.is_empty() {
-        return Err(anyhow!("SSH command is required"));
+    let user = trimmed_non_empty(hop.user.as_deref());
+    let identity_file = trimmed_non_empty(hop.identity_file.as_deref());
+
+    let unsafe_value = |value: &str| {
+        value.starts_with('-')
+            || value
+                .chars()
+                .any(|ch| ch.is_whitespace() || ch.is_control() || ch == ',' || ch == '"')
+    };
+    if unsafe_value(host) || user.as_deref().is_some_and(unsafe_value) {
+        return Err(anyhow!("Invalid jump host: {host}"));
     }
-    if instance.connection_timeout_sec == 0 {
-        instance.connection_timeout_sec = DEFAULT_CONNECTION_TIMEOUT_SEC;
+    if identity_file
+        .as_deref()
+        .is_some_and(|value| value.chars().any(|ch| ch.is_control() || ch == '"'))
+    {
+        return Err(anyhow!("Invalid identity file for jump host {host}"));
     }
-    instance.local_forward.bind_host = sanitize_bind_host(&instance.local_forward.bind_host);
-    let parsed = parse_ssh_command(&instance.ssh_command)?;
-    instance.ssh_parsed = Some(parsed);
 
-    let mut seen = HashSet::new();
-    let mut forwards = Vec::new();
-    for forward in &instance.port_forwards {
-        let Some(normalized) = sanitize_forward(forward) else {
-            continue;
-        };
-        if seen.contains(&normalized.id) {
-            continue;
+    Ok(Some(DesktopSshJumpHost {
+        host: host.to_string(),
+        user,
+        port: hop.port.filter(|port| *port > 0),
+        identity_file,
+        ssh_password: hop.ssh_password.clone(),
+    }))
+}
+
+fn has_inline_jump_option(args: &[String]) -> bool {
+    let is_proxy_jump = |value: &str| value.trim().to_ascii_lowercase().starts_with("proxyjump");
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("-J") {
+            return true;
+        }
+        if arg == "-o" {
+            if iter.next().is_some_and(|value| is_proxy_jump(value)) {
+                return true;
+            }
+        } else if let Some(value) = arg.strip_prefix("-o") {
+            if is_proxy_jump(value) {
+                return true;
+            }
         }
-        seen.insert(normalized.id.clone());
-        forwards.push(normalized);
     }
-    instance.port_forwards = forwards;
+    false
+}
 
-    Ok(instance)
+fn jump_host_alias(index: usize) -> String {
+    format!("{JUMP_HOST_ALIAS_PREFIX}{}", index + 1)
 }
 
-fn sync_desktop_hosts_for_ssh(
-    root: &mut Value,
-    previous_ids: &HashSet<String>,
-    instances: &[DesktopSshInstance],
-) {
-    let next_ids: HashSet<String> = instances.iter().map(|item| item.id.clone()).collect();
+/// Render hops as `Host` aliases followed by the configs ssh would have
+/// read without `-F`, so the rest of the user's setup still applies.
+fn render_jump_hosts_config(hops: &[DesktopSshJumpHost], base_config: Option<&str>) -> String {
+    let mut out = String::from("# Generated by OpenChamber for SSH jump hosts.\n");
+    for (index, hop) in hops.iter().enumerate() {
+        out.push_str(&format!("\nHost {}\n", jump_host_alias(index)));
+        out.push_str(&format!("  HostName {}\n", hop.host));
+        if let Some(user) = hop.user.as_deref() {
+            out.push_str(&format!("  User {user}\n"));
+        }
+        if let Some(port) = hop.port {
+            out.push_str(&format!("  Port {port}\n"));
+        }
+        if let Some(identity) = hop.identity_file.as_deref() {
+            out.push_str(&format!("  IdentityFile \"{identity}\"\n"));
+            out.push_str("  IdentitiesOnly yes\n");
+        }
+        if index > 0 {
+            out.push_str(&format!("  ProxyJump {}\n", jump_host_alias(index - 1)));
+        }
+    }
 
-    let mut hosts = root
-        .get("desktopHosts")
-        .and_then(Value::as_array)
-        .cloned()
-        .unwrap_or_default();
+    out.push_str("\nMatch all\n");
+    match base_config {
+        Some(path) => out.push_str(&format!("  Include \"{path}\"\n")),
+        None => {
+            out.push_str("  Include ~/.ssh/config\n");
+            out.push_str("  Include /etc/ssh/ssh_config\n");
+        }
+    }
+    out
+}
 
-    hosts.retain(|entry| {
-        let id = entry
-            .get("id")
-            .and_then(Value::as_str)
-            .map(|value| value.trim())
-            .unwrap_or("");
-        if id.is_empty() {
-            return false;
+/// Split a user-supplied `-F` out of the parsed args.
+fn take_config_file_arg(args: &[String]) -> (Option<String>, Vec<String>) {
+    let mut config = None;
+    let mut rest = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "-F" {
+            config = iter.next().cloned();
+        } else if let Some(value) = arg.strip_prefix("-F") {
+            config = Some(value.to_string());
+        } else {
+            rest.push(arg.clone());
         }
-        !(previous_ids.contains(id) && !next_ids.contains(id))
-    });
+    }
+    (config, rest)
+}
 
-    for instance in instances {
-        let label = build_display_label(instance);
-        let mut found = false;
-        for host in &mut hosts {
-            let host_id = host
-                .get("id")
-                .and_then(Value::as_str)
-                .map(|value| value.trim())
-                .unwrap_or("");
-            if host_id != instance.id {
-                continue;
-            }
-            if let Some(obj) = host.as_object_mut() {
-                obj.insert("id".to_string(), Value::String(instance.id.clone()));
-                obj.insert("label".to_string(), Value::String(label.clone()));
-                let should_set_default_url = obj
-                    .get("url")
-                    .and_then(Value::as_str)
-                    .map(|value| value.trim().is_empty())
-                    .unwrap_or(true);
+/// Parsed command with the instance's jump hosts applied. Writes the
+/// generated config into the session directory when there are hops.
+fn apply_jump_hosts(
+    parsed: &DesktopSshParsedCommand,
+    hops: &[DesktopSshJumpHost],
+    session_dir: &Path,
+) -> Result<DesktopSshParsedCommand> {
+    if hops.is_empty() {
+        return Ok(parsed.clone());
+    }
+
+    let (base_config, rest) = take_config_file_arg(&parsed.args);
+    let config_path = session_dir.join(JUMP_HOSTS_CONFIG_FILE);
+    fs::write(
+        &config_path,
+        render_jump_hosts_config(hops, base_config.as_deref()),
+    )?;
+
+    // The last hop already chains through the earlier ones via ProxyJump.
+    let mut args = vec![
+        "-F".to_string(),
+        config_path.display().to_string(),
+        "-J".to_string(),
+        jump_host_alias(hops.len() - 1),
+    ];
+    args.extend(rest);
+    Ok(DesktopSshParsedCommand {
+        destination: parsed.destination.clone(),
+        args,
+    })
+}
+
+fn mentions_host(message: &str, host: &str) -> bool {
+    let is_host_char = |ch: char| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-' || ch == '_';
+    message.match_indices(host).any(|(start, _)| {
+        let before = message[..start].chars().next_back();
+        let after = message[start + host.len()..].chars().next();
+        !before.is_some_and(is_host_char) && !after.is_some_and(is_host_char)
+    })
+}
+
+/// Best-effort attribution of an ssh error to a hop. Later hops win since
+/// their errors often echo the earlier hops they were reached through.
+fn failed_jump_host_index(message: &str, hops: &[DesktopSshJumpHost]) -> Option<usize> {
+    hops.iter().enumerate().rev().find_map(|(index, hop)| {
+        (mentions_host(message, &hop.host) || mentions_host(message, &jump_host_alias(index)))
+            .then_some(index)
+    })
+}
+
+fn attribute_jump_host_failure(err: anyhow::Error, hops: &[DesktopSshJumpHost]) -> anyhow::Error {
+    let message = err.to_string();
+    match failed_jump_host_index(&message, hops) {
+        Some(index) => anyhow::Error::new(JumpHostFailure {
+            index,
+            host: hops[index].host.clone(),
+            message,
+        }),
+        None => err,
+    }
+}
+
+fn enabled_secret_value(secret: Option<&DesktopSshStoredSecret>) -> Option<&str> {
+    secret
+        .filter(|secret| secret.enabled)
+        .and_then(|secret| secret.value.as_deref())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Host name ssh shows in password prompts for `destination`.
+fn destination_prompt_host(destination: &str) -> &str {
+    let host = destination
+        .rsplit_once('@')
+        .map_or(destination, |(_, host)| host);
+    host.trim_start_matches('[').trim_end_matches(']')
+}
+
+/// Environment consumed by the askpass helper: the target's secret plus one
+/// entry per hop, matched by host name or key path in the prompt. With jump
+/// hosts the target's secret is matched the same way.
+fn askpass_env_for_instance(
+    instance: &DesktopSshInstance,
+    parsed: &DesktopSshParsedCommand,
+) -> Vec<(String, String)> {
+    let mut env = Vec::new();
+    if let Some(secret) = enabled_secret_value(instance.auth.ssh_password.as_ref()) {
+        env.push((
+            "OPENCHAMBER_SSH_ASKPASS_VALUE".to_string(),
+            secret.to_string(),
+        ));
+    }
+    if instance.jump_hosts.is_empty() {
+        return env;
+    }
+
+    env.push((
+        "OPENCHAMBER_SSH_ASKPASS_HOP_COUNT".to_string(),
+        instance.jump_hosts.len().to_string(),
+    ));
+    env.push((
+        "OPENCHAMBER_SSH_ASKPASS_TARGET_HOST".to_string(),
+        destination_prompt_host(&parsed.destination).to_string(),
+    ));
+    if let Some(identity) = instance
+        .identity
+        .as_ref()
+        .filter(|identity| matches!(identity.source, DesktopSshIdentitySource::File))
+    {
+        env.push((
+            "OPENCHAMBER_SSH_ASKPASS_TARGET_KEY".to_string(),
+            identity.value.trim_start_matches('~').to_string(),
+        ));
+    }
+    for (index, hop) in instance.jump_hosts.iter().enumerate() {
+        let Some(secret) = enabled_secret_value(hop.ssh_password.as_ref()) else {
+            continue;
+        };
+        let prefix = format!("OPENCHAMBER_SSH_ASKPASS_HOP_{}", index + 1);
+        env.push((format!("{prefix}_HOST"), hop.host.clone()));
+        if let Some(identity) = hop.identity_file.as_deref() {
+            // Prompts show the expanded path, so match on the part after `~`.
+            env.push((
+                format!("{prefix}_KEY"),
+                identity.trim_start_matches('~').to_string(),
+            ));
+        }
+        env.push((format!("{prefix}_VALUE"), secret.to_string()));
+    }
+    env
+}
+
+fn expand_home_path(value: &str) -> PathBuf {
+    match value.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+        None if value == "~" => PathBuf::from(std::env::var("HOME").unwrap_or_default()),
+        None => PathBuf::from(value),
+    }
+}
+
+/// ControlPath from `ssh -G` output, if it names a concrete socket.
+fn resolved_control_path(resolved: &HashMap<String, Vec<String>>) -> Option<PathBuf> {
+    let value = resolved.get("controlpath")?.first()?.trim();
+    if value.is_empty() || value.eq_ignore_ascii_case("none") || value.contains('%') {
+        return None;
+    }
+    Some(expand_home_path(value))
+}
+
+fn has_inline_identity_option(args: &[String]) -> bool {
+    let is_identity_file = |value: &str| {
+        value
+            .trim()
+            .to_ascii_lowercase()
+            .starts_with("identityfile")
+    };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg.starts_with("-i") {
+            return true;
+        }
+        if arg == "-o" {
+            if iter.next().is_some_and(|value| is_identity_file(value)) {
+                return true;
+            }
+        } else if let Some(value) = arg.strip_prefix("-o") {
+            if is_identity_file(value) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn sanitize_identity(
+    identity: Option<DesktopSshIdentityChoice>,
+) -> Result<Option<DesktopSshIdentityChoice>> {
+    let Some(mut identity) = identity else {
+        return Ok(None);
+    };
+    identity.value = identity.value.trim().to_string();
+    if identity.value.is_empty() {
+        return Ok(None);
+    }
+    if identity.value.chars().any(|ch| ch.is_control()) {
+        return Err(anyhow!("Invalid SSH identity: {}", identity.value));
+    }
+    Ok(Some(identity))
+}
+
+/// Parsed command with the chosen identity pinned via `-i`. Agent keys are
+/// selected by writing their public half into the session directory, which
+/// ssh then matches against the agent.
+fn apply_identity(
+    transport: &dyn SshTransport,
+    parsed: &DesktopSshParsedCommand,
+    identity: Option<&DesktopSshIdentityChoice>,
+    session_dir: &Path,
+) -> Result<DesktopSshParsedCommand> {
+    let Some(identity) = identity else {
+        return Ok(parsed.clone());
+    };
+
+    let identity_path = match identity.source {
+        DesktopSshIdentitySource::File => {
+            let path = expand_home_path(&identity.value);
+            if !path.is_file() {
+                return Err(anyhow!("SSH identity file not found: {}", identity.value));
+            }
+            path
+        }
+        DesktopSshIdentitySource::Agent => {
+            let key = transport
+                .agent_identities()
+                .map_err(|err| anyhow!("Failed to list ssh-agent keys: {err}"))?
+                .into_iter()
+                .find(|key| key.fingerprint == identity.value)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "SSH agent key {} is not loaded in ssh-agent",
+                        identity.value
+                    )
+                })?;
+            if key.public_key.is_empty() {
+                return Err(anyhow!(
+                    "SSH agent did not return a public key for {}",
+                    identity.value
+                ));
+            }
+            let path = session_dir.join(AGENT_IDENTITY_FILE);
+            fs::write(&path, format!("{}\n", key.public_key))?;
+            path
+        }
+    };
+
+    let mut args = parsed.args.clone();
+    args.push("-i".to_string());
+    args.push(identity_path.display().to_string());
+    if identity.identities_only {
+        args.push("-o".to_string());
+        args.push("IdentitiesOnly=yes".to_string());
+    }
+    Ok(DesktopSshParsedCommand {
+        destination: parsed.destination.clone(),
+        args,
+    })
+}
+
+/// Turn a master failure into the most specific error available: the hop
+/// that failed, or the chosen identity being rejected by the target.
+fn explain_master_failure(err: anyhow::Error, instance: &DesktopSshInstance) -> anyhow::Error {
+    let err = attribute_jump_host_failure(err, &instance.jump_hosts);
+    if err.downcast_ref::<JumpHostFailure>().is_some() {
+        return err;
+    }
+    let Some(identity) = instance.identity.as_ref() else {
+        return err;
+    };
+    let message = err.to_string();
+    if !(message.contains("Permission denied") && message.contains("publickey")) {
+        return err;
+    }
+    let chosen = match identity.source {
+        DesktopSshIdentitySource::Agent => format!("agent key {}", identity.value),
+        DesktopSshIdentitySource::File => format!("identity file {}", identity.value),
+    };
+    let destination = instance
+        .ssh_parsed
+        .as_ref()
+        .map(|parsed| parsed.destination.as_str())
+        .unwrap_or(instance.ssh_command.as_str());
+    anyhow!("SSH identity rejected: {chosen} was not accepted by {destination}: {message}")
+}
+
+fn sanitize_instance(mut instance: DesktopSshInstance) -> Result<DesktopSshInstance> {
+    instance.id = instance.id.trim().to_string();
+    if instance.id.is_empty() || instance.id == LOCAL_HOST_ID {
+        return Err(anyhow!("SSH instance id is required"));
+    }
+    instance.ssh_command = instance.ssh_command.trim().to_string();
+    if instance.ssh_command.is_empty() {
+        return Err(anyhow!("SSH command is required"));
+    }
+    if instance.connection_timeout_sec == 0 {
+        instance.connection_timeout_sec = DEFAULT_CONNECTION_TIMEOUT_SEC;
+    }
+    // Only the syntax is checked here: an address that is missing right now
+    // (a VPN or network that is down) must not block saving; connect checks
+    // that it is available.
+    instance.local_forward.bind_host = sanitize_bind_host(&instance.local_forward.bind_host)?;
+    let parsed = parse_ssh_command(&instance.ssh_command)?;
+    instance.jump_hosts = instance
+        .jump_hosts
+        .iter()
+        .filter_map(|hop| sanitize_jump_host(hop).transpose())
+        .collect::<Result<Vec<_>>>()?;
+    if !instance.jump_hosts.is_empty() && has_inline_jump_option(&parsed.args) {
+        return Err(anyhow!(
+            "SSH command already sets -J/ProxyJump; remove it or clear the jump host list"
+        ));
+    }
+    instance.identity = sanitize_identity(instance.identity.take())?;
+    instance.tags = sanitize_labels(&instance.tags);
+    instance.remote_openchamber.idle_stop_after_min = instance
+        .remote_openchamber
+        .idle_stop_after_min
+        .filter(|minutes| *minutes > 0)
+        .map(|minutes| minutes.min(MAX_IDLE_STOP_MIN));
+    instance.group = trimmed_non_empty(instance.group.as_deref());
+    instance.multiplex.control_persist_sec = instance
+        .multiplex
+        .control_persist_sec
+        .min(MAX_CONTROL_PERSIST_SEC);
+    instance.multiplex.keep_alive_interval_sec = instance
+        .multiplex
+        .keep_alive_interval_sec
+        .filter(|interval| *interval > 0);
+    if instance.identity.is_some() && has_inline_identity_option(&parsed.args) {
+        return Err(anyhow!(
+            "SSH command already sets -i; remove it or clear the identity choice"
+        ));
+    }
+    instance.ssh_parsed = Some(parsed);
+
+    let mut seen = HashSet::new();
+    let mut forwards = Vec::new();
+    for forward in &instance.port_forwards {
+        let Some(normalized) = sanitize_forward(forward)? else {
+            continue;
+        };
+        if seen.contains(&normalized.id) {
+            continue;
+        }
+        seen.insert(normalized.id.clone());
+        forwards.push(normalized);
+    }
+    instance.port_forwards = forwards;
+
+    Ok(instance)
+}
+
+fn sync_desktop_hosts_for_ssh(
+    root: &mut Value,
+    previous_ids: &HashSet<String>,
+    instances: &[DesktopSshInstance],
+) {
+    let entries: Vec<(String, String)> = instances
+        .iter()
+        .map(|instance| (instance.id.clone(), build_display_label(instance)))
+        .collect();
+    sync_desktop_hosts(root, previous_ids, &entries);
+}
+
+/// Mirror managed instances into `desktopHosts`. `entries` are `(id, label)`
+/// pairs; hosts that were in `previous_ids` but are gone get removed.
+pub(crate) fn sync_desktop_hosts(
+    root: &mut Value,
+    previous_ids: &HashSet<String>,
+    entries: &[(String, String)],
+) {
+    let next_ids: HashSet<String> = entries.iter().map(|(id, _)| id.clone()).collect();
+
+    let mut hosts = root
+        .get("desktopHosts")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    hosts.retain(|entry| {
+        let id = entry
+            .get("id")
+            .and_then(Value::as_str)
+            .map(|value| value.trim())
+            .unwrap_or("");
+        if id.is_empty() {
+            return false;
+        }
+        !(previous_ids.contains(id) && !next_ids.contains(id))
+    });
+
+    for (id, label) in entries {
+        let mut found = false;
+        for host in &mut hosts {
+            let host_id = host
+                .get("id")
+                .and_then(Value::as_str)
+                .map(|value| value.trim())
+                .unwrap_or("");
+            if host_id != id {
+                continue;
+            }
+            if let Some(obj) = host.as_object_mut() {
+                obj.insert("id".to_string(), Value::String(id.clone()));
+                obj.insert("label".to_string(), Value::String(label.clone()));
+                let should_set_default_url = obj
+                    .get("url")
+                    .and_then(Value::as_str)
+                    .map(|value| value.trim().is_empty())
+                    .unwrap_or(true);
                 if should_set_default_url {
                     obj.insert(
                         "url".to_string(),
@@ -573,7 +1519,7 @@ fn sync_desktop_hosts_for_ssh(
 
         if !found {
             hosts.push(json!({
-                "id": instance.id,
+                "id": id,
                 "label": label,
                 "url": "http://127.0.0.1/"
             }));
@@ -615,22 +1561,195 @@ fn write_desktop_ssh_instances_to_path(
         if seen.contains(&normalized.id) {
             continue;
         }
+        ensure_host_id_unused(&root, &normalized.id, HostKind::Ssh)?;
         seen.insert(normalized.id.clone());
         sanitized.push(normalized);
     }
 
+    let auto_connect_groups = match config.auto_connect_groups {
+        Some(groups) => sanitize_labels(&groups),
+        None => read_auto_connect_groups(&root),
+    };
+
     sync_desktop_hosts_for_ssh(&mut root, &previous_ids, &sanitized);
     root["desktopSshInstances"] = serde_json::to_value(&sanitized)?;
+    root[AUTO_CONNECT_GROUPS_KEY] = serde_json::to_value(&auto_connect_groups)?;
     write_settings_root(path, &root)?;
 
     Ok(DesktopSshInstancesConfig {
         instances: sanitized,
+        auto_connect_groups: Some(auto_connect_groups),
+    })
+}
+
+fn strip_secret(secret: Option<DesktopSshStoredSecret>) -> Option<DesktopSshStoredSecret> {
+    // Keep `enabled` so the recipient knows a password is expected.
+    secret.map(|secret| DesktopSshStoredSecret {
+        enabled: secret.enabled,
+        value: None,
+        store: DesktopSshSecretStore::Never,
     })
 }
 
-fn update_ssh_host_url(instance_id: &str, label: &str, local_url: &str) -> Result<()> {
-    let path = settings_file_path();
-    let mut root = read_settings_root(&path);
+fn strip_instance_secrets(mut instance: DesktopSshInstance) -> DesktopSshInstance {
+    instance.auth.ssh_password = strip_secret(instance.auth.ssh_password.take());
+    instance.auth.openchamber_password = strip_secret(instance.auth.openchamber_password.take());
+    for hop in &mut instance.jump_hosts {
+        hop.ssh_password = strip_secret(hop.ssh_password.take());
+    }
+    instance
+}
+
+/// Bundle for the given ids, or every instance when `ids` is `None`.
+fn build_instances_bundle(
+    config: DesktopSshInstancesConfig,
+    ids: Option<&[String]>,
+    app_version: &str,
+) -> DesktopSshInstancesBundle {
+    let instances = config
+        .instances
+        .into_iter()
+        .filter(|instance| ids.is_none_or(|ids| ids.contains(&instance.id)))
+        .map(strip_instance_secrets)
+        .collect();
+    DesktopSshInstancesBundle {
+        format: INSTANCES_BUNDLE_FORMAT.to_string(),
+        version: INSTANCES_BUNDLE_VERSION,
+        exported_at_ms: now_millis(),
+        app_version: Some(app_version.to_string()),
+        instances,
+    }
+}
+
+fn export_instances_to_path(
+    settings_path: &Path,
+    bundle_path: &Path,
+    ids: Option<&[String]>,
+    app_version: &str,
+) -> Result<usize> {
+    let bundle = build_instances_bundle(
+        read_desktop_ssh_instances_from_path(settings_path),
+        ids,
+        app_version,
+    );
+    let count = bundle.instances.len();
+    let mut raw = serde_json::to_string_pretty(&bundle)?;
+    raw.push('\n');
+    fs::write(bundle_path, raw)?;
+    Ok(count)
+}
+
+fn parse_instances_bundle(raw: &str) -> Result<DesktopSshInstancesBundle> {
+    let bundle: DesktopSshInstancesBundle =
+        serde_json::from_str(raw).map_err(|err| anyhow!("Invalid instances bundle: {err}"))?;
+    if bundle.format != INSTANCES_BUNDLE_FORMAT {
+        return Err(anyhow!("Not an OpenChamber SSH instances bundle"));
+    }
+    if bundle.version == 0 || bundle.version > INSTANCES_BUNDLE_VERSION {
+        return Err(anyhow!(
+            "Unsupported instances bundle version {} (supported: {INSTANCES_BUNDLE_VERSION})",
+            bundle.version
+        ));
+    }
+    Ok(bundle)
+}
+
+fn unique_instance_id(base: &str, taken: &HashSet<String>) -> String {
+    (2..)
+        .map(|suffix| format!("{base}-{suffix}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("unbounded suffix range")
+}
+
+/// Validate bundle instances against the existing set. Colliding ids are
+/// remapped; invalid entries are reported and left out.
+/// `taken` holds the ids of every configured host, whatever its kind, since
+/// they all share one namespace.
+fn plan_instances_import(
+    mut taken: HashSet<String>,
+    bundle: DesktopSshInstancesBundle,
+) -> (DesktopSshImportPreview, Vec<DesktopSshInstance>) {
+    let mut entries = Vec::new();
+    let mut accepted = Vec::new();
+
+    for instance in bundle.instances {
+        let source_id = instance.id.trim().to_string();
+        let label = build_display_label(&instance);
+        let mut instance = strip_instance_secrets(instance);
+        let action = if taken.contains(&source_id) || source_id == LOCAL_HOST_ID {
+            instance.id = unique_instance_id(&source_id, &taken);
+            DesktopSshImportAction::Rename
+        } else {
+            DesktopSshImportAction::Add
+        };
+
+        match sanitize_instance(instance) {
+            Ok(sanitized) => {
+                taken.insert(sanitized.id.clone());
+                entries.push(DesktopSshImportEntry {
+                    source_id,
+                    id: sanitized.id.clone(),
+                    label,
+                    action,
+                    error: None,
+                });
+                accepted.push(sanitized);
+            }
+            Err(err) => entries.push(DesktopSshImportEntry {
+                id: source_id.clone(),
+                source_id,
+                label,
+                action: DesktopSshImportAction::Skip,
+                error: Some(err.to_string()),
+            }),
+        }
+    }
+
+    (
+        DesktopSshImportPreview {
+            version: bundle.version,
+            entries,
+            applied: false,
+        },
+        accepted,
+    )
+}
+
+fn import_instances_from_path(
+    settings_path: &Path,
+    bundle_path: &Path,
+    apply: bool,
+) -> Result<DesktopSshImportPreview> {
+    let raw = fs::read_to_string(bundle_path)
+        .map_err(|err| anyhow!("Failed to read {}: {err}", bundle_path.display()))?;
+    let bundle = parse_instances_bundle(&raw)?;
+    let existing = read_desktop_ssh_instances_from_path(settings_path);
+    let taken = HostKind::all_ids(&read_settings_root(settings_path));
+    let (mut preview, accepted) = plan_instances_import(taken, bundle);
+    if !apply || accepted.is_empty() {
+        return Ok(preview);
+    }
+
+    let mut instances = existing.instances;
+    instances.extend(accepted);
+    write_desktop_ssh_instances_to_path(
+        settings_path,
+        DesktopSshInstancesConfig {
+            instances,
+            auto_connect_groups: None,
+        },
+    )?;
+    preview.applied = true;
+    Ok(preview)
+}
+
+pub(crate) fn update_ssh_host_url(
+    path: &Path,
+    instance_id: &str,
+    label: &str,
+    local_url: &str,
+) -> Result<()> {
+    let mut root = read_settings_root(path);
     let mut hosts = root
         .get("desktopHosts")
         .and_then(Value::as_array)
@@ -665,12 +1784,11 @@ fn update_ssh_host_url(instance_id: &str, label: &str, local_url: &str) -> Resul
     }
 
     root["desktopHosts"] = Value::Array(hosts);
-    write_settings_root(&path, &root)
+    write_settings_root(path, &root)
 }
 
-fn persist_local_port_for_instance(instance_id: &str, local_port: u16) -> Result<()> {
-    let path = settings_file_path();
-    let mut root = read_settings_root(&path);
+fn persist_local_port_for_instance(path: &Path, instance_id: &str, local_port: u16) -> Result<()> {
+    let mut root = read_settings_root(path);
     let mut changed = false;
 
     if let Some(items) = root
@@ -698,7 +1816,7 @@ fn persist_local_port_for_instance(instance_id: &str, local_port: u16) -> Result
     }
 
     if changed {
-        write_settings_root(&path, &root)?;
+        write_settings_root(path, &root)?;
     }
 
     Ok(())
@@ -859,65 +1977,8 @@ fn parse_ssh_command(raw: &str) -> Result<DesktopSshParsedCommand> {
     Ok(DesktopSshParsedCommand { destination, args })
 }
 
-fn shell_quote(value: &str) -> String {
-    let escaped = value.replace('\'', "'\\''");
-    format!("'{escaped}'")
-}
-
-fn run_output(command: &mut Command) -> Result<(i32, String, String)> {
-    let output = command
-        .output()
-        .with_context(|| format!("failed to execute command: {:?}", command))?;
-
-    let code = output.status.code().unwrap_or(-1);
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-    Ok((code, stdout, stderr))
-}
-
-fn build_ssh_command(
-    parsed: &DesktopSshParsedCommand,
-    pre_destination_args: &[String],
-    remote_command: Option<&str>,
-) -> Command {
-    let mut command = Command::new("ssh");
-    command
-        .args(&parsed.args)
-        .args(pre_destination_args)
-        .arg(&parsed.destination);
-    if let Some(remote) = remote_command {
-        command.arg(remote);
-    }
-    command
-}
-
-fn resolve_ssh_config(parsed: &DesktopSshParsedCommand) -> Result<HashMap<String, String>> {
-    let args = vec!["-G".to_string()];
-    let mut command = build_ssh_command(parsed, &args, None);
-    let (code, stdout, stderr) = run_output(&mut command)?;
-    if code != 0 {
-        return Err(anyhow!(stderr.trim().to_string()));
-    }
-
-    let mut resolved = HashMap::new();
-    for line in stdout.lines() {
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-        let mut parts = trimmed.splitn(2, ' ');
-        let key = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
-        let value = parts.next().unwrap_or_default().trim();
-        if key.is_empty() || value.is_empty() {
-            continue;
-        }
-        resolved.insert(key, value.to_string());
-    }
-    Ok(resolved)
-}
-
-fn ensure_session_dir(instance_id: &str) -> Result<PathBuf> {
-    let base = settings_file_path()
+fn ensure_session_dir(settings_path: &Path, instance_id: &str) -> Result<PathBuf> {
+    let base = settings_path
         .parent()
         .map(Path::to_path_buf)
         .unwrap_or_else(|| PathBuf::from("/tmp"))
@@ -941,11 +2002,37 @@ fn askpass_script_content() -> String {
     let script = r#"#!/bin/bash
 PROMPT="$1"
 
-if [[ -n "$OPENCHAMBER_SSH_ASKPASS_VALUE" ]]; then
-  if [[ "$PROMPT" == *"assword"* || "$PROMPT" == *"passphrase"* ]]; then
+HOP_COUNT="${OPENCHAMBER_SSH_ASKPASS_HOP_COUNT:-0}"
+for ((i = 1; i <= HOP_COUNT; i++)); do
+  HOP_VALUE_VAR="OPENCHAMBER_SSH_ASKPASS_HOP_${i}_VALUE"
+  HOP_HOST_VAR="OPENCHAMBER_SSH_ASKPASS_HOP_${i}_HOST"
+  HOP_KEY_VAR="OPENCHAMBER_SSH_ASKPASS_HOP_${i}_KEY"
+  HOP_VALUE="${!HOP_VALUE_VAR}"
+  HOP_HOST="${!HOP_HOST_VAR}"
+  HOP_KEY="${!HOP_KEY_VAR}"
+  if [[ -z "$HOP_VALUE" ]]; then
+    continue
+  fi
+  if [[ -n "$HOP_HOST" && "$PROMPT" == *"@$HOP_HOST's password"* ]] || [[ -n "$HOP_KEY" && "$PROMPT" == *"$HOP_KEY"* ]]; then
+    printf '%s\n' "$HOP_VALUE"
+    exit 0
+  fi
+done
+
+if [[ -n "$OPENCHAMBER_SSH_ASKPASS_VALUE" ]] && [[ "$PROMPT" == *"assword"* || "$PROMPT" == *"passphrase"* ]]; then
+  if [[ "$HOP_COUNT" -eq 0 ]]; then
     printf '%s\n' "$OPENCHAMBER_SSH_ASKPASS_VALUE"
     exit 0
   fi
+  # Behind jump hosts the destination's secret only answers prompts that
+  # name the destination; a hop without its own secret gets nothing.
+  TARGET_HOST="$OPENCHAMBER_SSH_ASKPASS_TARGET_HOST"
+  TARGET_KEY="$OPENCHAMBER_SSH_ASKPASS_TARGET_KEY"
+  if [[ -n "$TARGET_HOST" && "$PROMPT" == *"@$TARGET_HOST's password"* ]] || [[ -n "$TARGET_KEY" && "$PROMPT" == *"$TARGET_KEY"* ]]; then
+    printf '%s\n' "$OPENCHAMBER_SSH_ASKPASS_VALUE"
+    exit 0
+  fi
+  exit 1
 fi
 
 DEFAULT_ANSWER=""
@@ -990,71 +2077,22 @@ fn write_askpass_script(path: &Path) -> Result<()> {
     Ok(())
 }
 
-fn spawn_master_process(
-    parsed: &DesktopSshParsedCommand,
-    control_path: &Path,
-    askpass_path: &Path,
-    ssh_password: Option<&str>,
-) -> Result<Child> {
-    let args = vec![
-        "-o".to_string(),
-        "ControlMaster=yes".to_string(),
-        "-o".to_string(),
-        format!("ControlPath={}", control_path.display()),
-        "-o".to_string(),
-        format!("ControlPersist={DEFAULT_CONTROL_PERSIST_SEC}"),
-        "-N".to_string(),
-    ];
-    let mut command = build_ssh_command(parsed, &args, None);
-    command
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .env("SSH_ASKPASS_REQUIRE", "force")
-        .env("SSH_ASKPASS", askpass_path)
-        .env("DISPLAY", "1");
-
-    if let Some(secret) = ssh_password.filter(|value| !value.trim().is_empty()) {
-        command.env("OPENCHAMBER_SSH_ASKPASS_VALUE", secret.trim());
-    }
-
-    command.spawn().with_context(|| {
-        format!(
-            "failed to start SSH ControlMaster for {}",
-            parsed.destination
-        )
-    })
-}
-
 fn wait_for_master_ready(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     timeout_sec: u16,
-    master: &mut Child,
+    master: &mut dyn SshProcess,
 ) -> Result<()> {
     let deadline = std::time::Instant::now() + Duration::from_secs(timeout_sec as u64);
     let mut poll_ms: u64 = 250;
     while std::time::Instant::now() < deadline {
-        let args = vec![
-            "-o".to_string(),
-            "ControlMaster=no".to_string(),
-            "-o".to_string(),
-            format!("ControlPath={}", control_path.display()),
-            "-O".to_string(),
-            "check".to_string(),
-        ];
-
-        let mut check = build_ssh_command(parsed, &args, None);
-        let (code, _stdout, _stderr) = run_output(&mut check)?;
-        if code == 0 {
+        if transport.check_master(parsed, control_path) {
             return Ok(());
         }
 
-        if let Some(status) = master.try_wait().ok().flatten() {
-            let mut stderr = String::new();
-            if let Some(mut stream) = master.stderr.take() {
-                let _ = stream.read_to_string(&mut stderr);
-            }
+        if let Some(status) = master.try_wait() {
+            let stderr = master.take_stderr();
             if stderr.trim().is_empty() {
                 return Err(anyhow!(format!(
                     "SSH master process exited before ready (status: {status})"
@@ -1070,83 +2108,27 @@ fn wait_for_master_ready(
     Err(anyhow!("SSH ControlMaster connection timed out"))
 }
 
-fn control_master_operation(
+fn remote_command_exists(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
-    op: &str,
-) -> Result<(i32, String, String)> {
-    let args = vec![
-        "-o".to_string(),
-        "ControlMaster=no".to_string(),
-        "-o".to_string(),
-        format!("ControlPath={}", control_path.display()),
-        "-o".to_string(),
-        "BatchMode=yes".to_string(),
-        "-o".to_string(),
-        "ConnectTimeout=3".to_string(),
-        "-O".to_string(),
-        op.to_string(),
-    ];
-    let mut command = build_ssh_command(parsed, &args, None);
-    run_output(&mut command)
-}
-
-fn is_control_master_alive(parsed: &DesktopSshParsedCommand, control_path: &Path) -> bool {
-    control_master_operation(parsed, control_path, "check")
-        .map(|(code, _, _)| code == 0)
+    command_name: &str,
+) -> bool {
+    transport
+        .run_command(
+            parsed,
+            control_path,
+            &format!(
+                "command -v {} >/dev/null 2>&1 && echo yes || echo no",
+                command_name
+            ),
+            DEFAULT_CONNECTION_TIMEOUT_SEC,
+        )
+        .map(|output| output.trim() == "yes")
         .unwrap_or(false)
 }
 
-fn stop_control_master_best_effort(parsed: &DesktopSshParsedCommand, control_path: &Path) {
-    let _ = control_master_operation(parsed, control_path, "exit");
-}
-
-fn run_remote_command(
-    parsed: &DesktopSshParsedCommand,
-    control_path: &Path,
-    script: &str,
-    timeout_sec: u16,
-) -> Result<String> {
-    let args = vec![
-        "-o".to_string(),
-        "ControlMaster=no".to_string(),
-        "-o".to_string(),
-        format!("ControlPath={}", control_path.display()),
-        "-o".to_string(),
-        format!("ConnectTimeout={timeout_sec}"),
-        "-T".to_string(),
-    ];
-    let remote = format!("sh -lc {}", shell_quote(script));
-    let mut command = build_ssh_command(parsed, &args, Some(&remote));
-    let (code, stdout, stderr) = run_output(&mut command)?;
-    if code != 0 {
-        if stderr.trim().is_empty() {
-            return Err(anyhow!("Remote command failed"));
-        }
-        return Err(anyhow!(stderr.trim().to_string()));
-    }
-    Ok(stdout)
-}
-
-fn remote_command_exists(
-    parsed: &DesktopSshParsedCommand,
-    control_path: &Path,
-    command_name: &str,
-) -> bool {
-    run_remote_command(
-        parsed,
-        control_path,
-        &format!(
-            "command -v {} >/dev/null 2>&1 && echo yes || echo no",
-            command_name
-        ),
-        DEFAULT_CONNECTION_TIMEOUT_SEC,
-    )
-    .map(|output| output.trim() == "yes")
-    .unwrap_or(false)
-}
-
-fn parse_version_token(raw: &str) -> Option<String> {
+pub(crate) fn parse_version_token(raw: &str) -> Option<String> {
     for token in raw.split_whitespace() {
         let mut candidate = token.trim().trim_start_matches('v').to_string();
         while candidate.ends_with(',') || candidate.ends_with(')') || candidate.ends_with('(') {
@@ -1167,27 +2149,30 @@ fn parse_version_token(raw: &str) -> Option<String> {
 }
 
 fn current_remote_openchamber_version(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
 ) -> Option<String> {
-    run_remote_command(
-        parsed,
-        control_path,
-        "openchamber --version 2>/dev/null || true",
-        DEFAULT_CONNECTION_TIMEOUT_SEC,
-    )
-    .ok()
-    .and_then(|value| parse_version_token(&value))
+    transport
+        .run_command(
+            parsed,
+            control_path,
+            "openchamber --version 2>/dev/null || true",
+            DEFAULT_CONNECTION_TIMEOUT_SEC,
+        )
+        .ok()
+        .and_then(|value| parse_version_token(&value))
 }
 
 fn install_openchamber_managed(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     version: &str,
     preferred: &DesktopSshInstallMethod,
 ) -> Result<()> {
-    let has_bun = remote_command_exists(parsed, control_path, "bun");
-    let has_npm = remote_command_exists(parsed, control_path, "npm");
+    let has_bun = remote_command_exists(transport, parsed, control_path, "bun");
+    let has_npm = remote_command_exists(transport, parsed, control_path, "npm");
 
     let mut commands = Vec::new();
 
@@ -1224,7 +2209,7 @@ fn install_openchamber_managed(
 
     let mut last_error: Option<anyhow::Error> = None;
     for command in commands {
-        match run_remote_command(
+        match transport.run_command(
             parsed,
             control_path,
             &command,
@@ -1270,6 +2255,7 @@ fn configured_openchamber_password(instance: &DesktopSshInstance) -> Option<&str
 }
 
 fn probe_remote_system_info(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     port: u16,
@@ -1290,7 +2276,7 @@ fn probe_remote_system_info(
         "AUTH_STATUS=0; INFO_STATUS=0; HEALTH_STATUS=0; BODY_FILE=\"$(mktemp)\"; COOKIE_FILE=\"$(mktemp)\"; cleanup() {{ rm -f \"$BODY_FILE\" \"$COOKIE_FILE\"; }}; trap cleanup EXIT; if command -v curl >/dev/null 2>&1; then if [ \"{auth_enabled}\" = \"1\" ]; then AUTH_STATUS=\"$(curl -sS --max-time 3 -o /dev/null -w '%{{http_code}}' -c \"$COOKIE_FILE\" -H 'content-type: application/json' --data {auth_payload} http://127.0.0.1:{port}/auth/session || true)\"; if [ \"$AUTH_STATUS\" = \"200\" ]; then INFO_STATUS=\"$(curl -sS --max-time 3 -b \"$COOKIE_FILE\" -o \"$BODY_FILE\" -w '%{{http_code}}' http://127.0.0.1:{port}/api/system/info || true)\"; else INFO_STATUS=\"$(curl -sS --max-time 3 -o \"$BODY_FILE\" -w '%{{http_code}}' http://127.0.0.1:{port}/api/system/info || true)\"; fi; else INFO_STATUS=\"$(curl -sS --max-time 3 -o \"$BODY_FILE\" -w '%{{http_code}}' http://127.0.0.1:{port}/api/system/info || true)\"; fi; HEALTH_STATUS=\"$(curl -sS --max-time 3 -o /dev/null -w '%{{http_code}}' http://127.0.0.1:{port}/health || true)\"; elif command -v wget >/dev/null 2>&1; then wget -qO \"$BODY_FILE\" http://127.0.0.1:{port}/api/system/info >/dev/null 2>&1; if [ $? -eq 0 ]; then INFO_STATUS=200; fi; wget -qO- http://127.0.0.1:{port}/health >/dev/null 2>&1; if [ $? -eq 0 ]; then HEALTH_STATUS=200; fi; else exit 127; fi; printf 'INFO_STATUS=%s\\nAUTH_STATUS=%s\\nHEALTH_STATUS=%s\\n' \"$INFO_STATUS\" \"$AUTH_STATUS\" \"$HEALTH_STATUS\"; cat \"$BODY_FILE\" 2>/dev/null || true",
         auth_payload = shell_quote(&auth_payload),
     );
-    let output = run_remote_command(
+    let output = transport.run_command(
         parsed,
         control_path,
         &script,
@@ -1348,13 +2334,152 @@ fn probe_remote_system_info(
     Ok(info)
 }
 
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Seconds since the epoch for a UTC `YYYY-MM-DDTHH:MM:SS[.fff]Z` timestamp,
+/// as produced by `Date.prototype.toISOString`.
+fn parse_iso_utc_secs(value: &str) -> Option<i64> {
+    let value = value.trim().strip_suffix('Z')?;
+    let (date, time) = value.split_once('T')?;
+    let mut date_parts = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (
+        date_parts.next()??,
+        date_parts.next()??,
+        date_parts.next()??,
+    );
+    let time = time.split('.').next()?;
+    let mut time_parts = time.split(':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (
+        time_parts.next()??,
+        time_parts.next()??,
+        time_parts.next()??,
+    );
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    Some(days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second)
+}
+
+/// Host-level metrics script. Prints `KEY=value` lines; works on Linux
+/// (`/proc`) and falls back to `sysctl` on macOS.
+fn remote_metrics_script(server_pid: Option<u64>) -> String {
+    let mut script = String::from(
+        "printf 'NOW=%s\\n' \"$(date +%s)\"; \
+         if [ -r /proc/loadavg ]; then printf 'LOAD=%s\\n' \"$(cut -d' ' -f1-3 /proc/loadavg)\"; \
+         else printf 'LOAD=%s\\n' \"$(sysctl -n vm.loadavg 2>/dev/null | tr -d '{}')\"; fi; \
+         if [ -r /proc/meminfo ]; then awk '/^MemTotal:/ {print \"MEM_TOTAL_KB=\" $2} /^MemAvailable:/ {print \"MEM_AVAILABLE_KB=\" $2}' /proc/meminfo; \
+         else printf 'MEM_TOTAL_KB=%s\\n' \"$(( $(sysctl -n hw.memsize 2>/dev/null || echo 0) / 1024 ))\"; fi; \
+         df -Pk \"$HOME\" 2>/dev/null | awk 'NR==2 {print \"DISK=\" $2 \" \" $4 \" \" $6}'",
+    );
+    if let Some(pid) = server_pid {
+        script.push_str(&format!(
+            "; printf 'RSS_KB=%s\\n' \"$(ps -o rss= -p {pid} 2>/dev/null | tr -d ' ')\""
+        ));
+    }
+    script
+}
+
+/// Fill host fields from the metrics script output. Returns the remote
+/// clock (`NOW`) so uptime does not depend on local clock skew.
+fn apply_remote_metrics_output(metrics: &mut DesktopSshRemoteMetrics, output: &str) -> Option<i64> {
+    let mut remote_now = None;
+    let kib = |value: &str| value.trim().parse::<u64>().ok().map(|kb| kb * 1024);
+    for line in output.lines() {
+        let Some((key, value)) = line.trim().split_once('=') else {
+            continue;
+        };
+        match key {
+            "NOW" => remote_now = value.trim().parse::<i64>().ok(),
+            "LOAD" => {
+                let loads: Vec<f64> = value
+                    .split_whitespace()
+                    .filter_map(|part| part.parse::<f64>().ok())
+                    .collect();
+                if let [one, five, fifteen] = loads[..] {
+                    metrics.load_average = Some([one, five, fifteen]);
+                }
+            }
+            "MEM_TOTAL_KB" => metrics.mem_total_bytes = kib(value).filter(|bytes| *bytes > 0),
+            "MEM_AVAILABLE_KB" => metrics.mem_available_bytes = kib(value),
+            "DISK" => {
+                let mut parts = value.splitn(3, ' ');
+                metrics.disk_total_bytes = parts.next().and_then(kib);
+                metrics.disk_free_bytes = parts.next().and_then(kib);
+                metrics.disk_mount = trimmed_non_empty(parts.next());
+            }
+            "RSS_KB" => metrics.server_rss_bytes = kib(value),
+            _ => {}
+        }
+    }
+    metrics.disk_low = metrics
+        .disk_free_bytes
+        .is_some_and(|free| free < LOW_DISK_FREE_BYTES);
+    remote_now
+}
+
+fn collect_remote_metrics(
+    transport: &dyn SshTransport,
+    instance: &DesktopSshInstance,
+    parsed: &DesktopSshParsedCommand,
+    control_path: &Path,
+    remote_port: u16,
+) -> DesktopSshRemoteMetrics {
+    let mut metrics = DesktopSshRemoteMetrics {
+        id: instance.id.clone(),
+        collected_at_ms: now_millis(),
+        ..DesktopSshRemoteMetrics::default()
+    };
+
+    // The server may sit behind UI auth or be unreachable; host metrics are
+    // still worth reporting.
+    if let Ok(info) = probe_remote_system_info(
+        transport,
+        parsed,
+        control_path,
+        remote_port,
+        configured_openchamber_password(instance),
+    ) {
+        metrics.server_pid = info.pid;
+        metrics.server_started_at = info.started_at;
+    }
+
+    match transport.run_command(
+        parsed,
+        control_path,
+        &remote_metrics_script(metrics.server_pid),
+        DEFAULT_CONNECTION_TIMEOUT_SEC,
+    ) {
+        Ok(output) => {
+            let remote_now = apply_remote_metrics_output(&mut metrics, &output);
+            let started = metrics
+                .server_started_at
+                .as_deref()
+                .and_then(parse_iso_utc_secs);
+            if let (Some(now), Some(started)) = (remote_now, started) {
+                metrics.server_uptime_sec = u64::try_from(now - started).ok();
+            }
+        }
+        Err(err) => metrics.error = Some(err.to_string()),
+    }
+    metrics
+}
+
 fn remote_server_running(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     port: u16,
     openchamber_password: Option<&str>,
 ) -> bool {
-    probe_remote_system_info(parsed, control_path, port, openchamber_password).is_ok()
+    probe_remote_system_info(transport, parsed, control_path, port, openchamber_password).is_ok()
 }
 
 fn random_port_candidate(seed: &str) -> u16 {
@@ -1369,6 +2494,7 @@ fn random_port_candidate(seed: &str) -> u16 {
 }
 
 fn start_remote_server_managed(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     instance: &DesktopSshInstance,
@@ -1390,7 +2516,7 @@ fn start_remote_server_managed(
     let script = format!(
         "{env_prefix} openchamber serve --daemon --hostname 127.0.0.1 --port {desired_port}"
     );
-    let output = run_remote_command(
+    let output = transport.run_command(
         parsed,
         control_path,
         &script,
@@ -1406,7 +2532,72 @@ fn start_remote_server_managed(
     Ok(desired_port)
 }
 
+/// Runs detached on the remote host. Stops the server once every desktop
+/// lease is older than the grace period, unless scheduled tasks are
+/// enabled or running. Exits quietly when the server is already gone.
+const IDLE_WATCHDOG_SCRIPT: &str = r#"DIR="$1"; PORT="$2"; INTERVAL="${3:-30}"
+http_get() { if command -v curl >/dev/null 2>&1; then curl -fsS --max-time 5 "$1" 2>/dev/null; else wget -qO- "$1" 2>/dev/null; fi; }
+http_post() { if command -v curl >/dev/null 2>&1; then curl -fsS --max-time 5 -X POST "$1" >/dev/null 2>&1; else wget -qO- --post-data='' "$1" >/dev/null 2>&1; fi; }
+finish() { rm -f "$DIR"/*.lease "$DIR/watchdog.pid"; exit 0; }
+while sleep "$INTERVAL"; do
+  http_get "http://127.0.0.1:$PORT/health" >/dev/null || finish
+  GRACE="$(cat "$DIR/grace" 2>/dev/null)"
+  [ -n "$(find "$DIR" -name '*.lease' -mmin -"${GRACE:-30}" 2>/dev/null)" ] && continue
+  # Only an explicit "no tasks" answer counts as idle; an error status (such
+  # as a 401 behind a UI password) or an unexpected body keeps it running.
+  STATUS="$(http_get "http://127.0.0.1:$PORT/api/openchamber/scheduled-tasks/status")" || continue
+  case "$STATUS" in *'"hasEnabledScheduledTasks":false'*) ;; *) continue ;; esac
+  case "$STATUS" in *'"hasRunningScheduledTasks":false'*) ;; *) continue ;; esac
+  http_post "http://127.0.0.1:$PORT/api/system/shutdown"
+  finish
+done
+"#;
+
+/// Grace period when idle-stop applies: managed servers kept running
+/// across disconnects.
+fn idle_stop_minutes(instance: &DesktopSshInstance) -> Option<u32> {
+    let remote = &instance.remote_openchamber;
+    if !matches!(remote.mode, DesktopSshRemoteMode::Managed) || !remote.keep_running {
+        return None;
+    }
+    remote.idle_stop_after_min.filter(|minutes| *minutes > 0)
+}
+
+fn idle_lease_name(instance_id: &str) -> String {
+    let safe: String = instance_id
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{safe}-{}", now_millis())
+}
+
+fn idle_stop_dir(remote_port: u16) -> String {
+    format!("\"$HOME/.config/openchamber/desktop-idle/{remote_port}\"")
+}
+
+fn arm_idle_stop_script(remote_port: u16, grace_min: u32, lease: &str) -> String {
+    let dir = idle_stop_dir(remote_port);
+    format!(
+        "DIR={dir}; mkdir -p \"$DIR\" && touch \"$DIR/{lease}.lease\" && printf '%s\\n' {grace_min} > \"$DIR/grace\" && cat > \"$DIR/watchdog.sh\" <<'OPENCHAMBER_WATCHDOG'\n{IDLE_WATCHDOG_SCRIPT}OPENCHAMBER_WATCHDOG\n\
+         if [ -f \"$DIR/watchdog.pid\" ] && kill -0 \"$(cat \"$DIR/watchdog.pid\")\" 2>/dev/null; then exit 0; fi\n\
+         nohup sh \"$DIR/watchdog.sh\" \"$DIR\" {remote_port} >/dev/null 2>&1 </dev/null &\n\
+         echo $! > \"$DIR/watchdog.pid\""
+    )
+}
+
+fn refresh_idle_lease_script(remote_port: u16, lease: &str) -> String {
+    let dir = idle_stop_dir(remote_port);
+    format!("DIR={dir}; mkdir -p \"$DIR\" && touch \"$DIR/{lease}.lease\"")
+}
+
 fn stop_remote_server_best_effort(
+    transport: &dyn SshTransport,
     parsed: &DesktopSshParsedCommand,
     control_path: &Path,
     remote_port: u16,
@@ -1414,7 +2605,7 @@ fn stop_remote_server_best_effort(
     let script = format!(
         "if command -v curl >/dev/null 2>&1; then curl -fsS -X POST http://127.0.0.1:{remote_port}/api/system/shutdown >/dev/null 2>&1 || true; elif command -v wget >/dev/null 2>&1; then wget -qO- --method=POST http://127.0.0.1:{remote_port}/api/system/shutdown >/dev/null 2>&1 || true; fi"
     );
-    let _ = run_remote_command(
+    let _ = transport.run_command(
         parsed,
         control_path,
         &script,
@@ -1422,146 +2613,30 @@ fn stop_remote_server_best_effort(
     );
 }
 
-fn spawn_main_forward(
-    parsed: &DesktopSshParsedCommand,
-    control_path: &Path,
-    bind_host: &str,
-    local_port: u16,
-    remote_port: u16,
-) -> Result<Child> {
-    let args = vec![
-        "-o".to_string(),
-        "ControlMaster=no".to_string(),
-        "-o".to_string(),
-        format!("ControlPath={}", control_path.display()),
-        "-N".to_string(),
-        "-L".to_string(),
-        format!("{bind_host}:{local_port}:127.0.0.1:{remote_port}"),
-    ];
-    let mut command = build_ssh_command(parsed, &args, None);
-    command
-        .stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::piped())
-        .spawn()
-        .with_context(|| format!("Failed to start main SSH forward on local port {local_port}"))
-}
-
-fn spawn_extra_forward(
-    parsed: &DesktopSshParsedCommand,
-    control_path: &Path,
-    forward: &DesktopSshPortForward,
-) -> Result<()> {
-    let mut args = vec![
-        "-o".to_string(),
-        "ControlMaster=no".to_string(),
-        "-o".to_string(),
-        format!("ControlPath={}", control_path.display()),
-        "-O".to_string(),
-        "forward".to_string(),
-    ];
-
-    match forward.forward_type {
-        DesktopSshPortForwardType::Local => {
-            let local_host = forward
-                .local_host
-                .as_deref()
-                .filter(|v| !v.trim().is_empty())
-                .unwrap_or("127.0.0.1");
-            let local_port = forward
-                .local_port
-                .ok_or_else(|| anyhow!("Missing local port"))?;
-            let remote_host = forward
-                .remote_host
-                .as_deref()
-                .filter(|v| !v.trim().is_empty())
-                .unwrap_or("127.0.0.1");
-            let remote_port = forward
-                .remote_port
-                .ok_or_else(|| anyhow!("Missing remote port"))?;
-            args.push("-L".to_string());
-            args.push(format!(
-                "{local_host}:{local_port}:{remote_host}:{remote_port}"
-            ));
-        }
-        DesktopSshPortForwardType::Remote => {
-            let remote_host = forward
-                .remote_host
-                .as_deref()
-                .filter(|v| !v.trim().is_empty())
-                .unwrap_or("127.0.0.1");
-            let remote_port = forward
-                .remote_port
-                .ok_or_else(|| anyhow!("Missing remote port"))?;
-            let local_host = forward
-                .local_host
-                .as_deref()
-                .filter(|v| !v.trim().is_empty())
-                .unwrap_or("127.0.0.1");
-            let local_port = forward
-                .local_port
-                .ok_or_else(|| anyhow!("Missing local port"))?;
-            args.push("-R".to_string());
-            args.push(format!(
-                "{remote_host}:{remote_port}:{local_host}:{local_port}"
-            ));
-        }
-        DesktopSshPortForwardType::Dynamic => {
-            let local_host = forward
-                .local_host
-                .as_deref()
-                .filter(|v| !v.trim().is_empty())
-                .unwrap_or("127.0.0.1");
-            let local_port = forward
-                .local_port
-                .ok_or_else(|| anyhow!("Missing local port"))?;
-            args.push("-D".to_string());
-            args.push(format!("{local_host}:{local_port}"));
-        }
-    }
-
-    let mut command = build_ssh_command(parsed, &args, None);
-    let (code, stdout, stderr) = run_output(&mut command)
-        .with_context(|| format!("Failed to configure extra SSH forward {}", forward.id))?;
-    if code != 0 {
-        let detail = if stderr.trim().is_empty() {
-            stdout.trim()
-        } else {
-            stderr.trim()
-        };
-        return Err(anyhow!(format!(
-            "Failed to configure extra SSH forward {}: {}",
-            forward.id,
-            if detail.is_empty() {
-                "unknown error"
-            } else {
-                detail
-            }
-        )));
-    }
-    Ok(())
+pub(crate) fn is_local_port_available(bind_host: &str, port: u16) -> bool {
+    TcpListener::bind((bind_host, port)).is_ok()
 }
 
-fn is_local_port_available(bind_host: &str, port: u16) -> bool {
-    TcpListener::bind(format!("{bind_host}:{port}")).is_ok()
+pub(crate) fn pick_unused_local_port(bind_host: &str) -> Result<u16> {
+    let listener = TcpListener::bind((bind_host, 0))?;
+    Ok(listener.local_addr()?.port())
 }
 
-fn pick_unused_local_port() -> Result<u16> {
-    let listener = TcpListener::bind("127.0.0.1:0")?;
-    Ok(listener.local_addr()?.port())
+fn local_socket_addr(host: &str, port: u16) -> Option<SocketAddr> {
+    (host, port).to_socket_addrs().ok()?.next()
 }
 
-fn is_local_tunnel_reachable(local_port: u16) -> bool {
-    let addr = format!("127.0.0.1:{local_port}");
-    let Ok(parsed) = addr.parse() else {
+pub(crate) fn is_local_tunnel_reachable(host: &str, local_port: u16) -> bool {
+    let Some(parsed) = local_socket_addr(host, local_port) else {
         return false;
     };
     TcpStream::connect_timeout(&parsed, Duration::from_millis(500)).is_ok()
 }
 
-fn wait_local_forward_ready(local_port: u16) -> Result<()> {
+pub(crate) fn wait_local_forward_ready(local_host: &str, local_port: u16) -> Result<()> {
     let deadline = std::time::Instant::now() + Duration::from_secs(DEFAULT_READY_TIMEOUT_SEC);
-    let addr: std::net::SocketAddr = format!("127.0.0.1:{local_port}").parse()?;
+    let addr = local_socket_addr(local_host, local_port)
+        .ok_or_else(|| anyhow!("Invalid local forward address {local_host}"))?;
     let mut poll_ms: u64 = 250;
     while std::time::Instant::now() < deadline {
         if let Ok(mut stream) =
@@ -1571,7 +2646,8 @@ fn wait_local_forward_ready(local_port: u16) -> Result<()> {
             let _ = stream.set_read_timeout(Some(Duration::from_millis(1000)));
             let _ = stream.set_write_timeout(Some(Duration::from_millis(1000)));
             let request = format!(
-                "GET /health HTTP/1.1\r\nHost: 127.0.0.1:{local_port}\r\nConnection: close\r\n\r\n"
+                "GET /health HTTP/1.1\r\nHost: {}:{local_port}\r\nConnection: close\r\n\r\n",
+                bracket_host(local_host)
             );
             if stream.write_all(request.as_bytes()).is_ok() {
                 let mut buf = [0u8; 32];
@@ -1594,11 +2670,6 @@ fn wait_local_forward_ready(local_port: u16) -> Result<()> {
     ))
 }
 
-fn kill_child(child: &mut Child) {
-    let _ = child.kill();
-    let _ = child.wait();
-}
-
 fn parse_ssh_config_candidates(path: &Path, source: &str) -> Vec<DesktopSshImportCandidate> {
     let Ok(content) = fs::read_to_string(path) else {
         return Vec::new();
@@ -1642,6 +2713,22 @@ fn parse_ssh_config_candidates(path: &Path, source: &str) -> Vec<DesktopSshImpor
 }
 
 impl DesktopSshManagerInner {
+    fn with_transport(transport: Arc<dyn SshTransport>, settings_path: PathBuf) -> Self {
+        Self {
+            transport,
+            settings_path,
+            statuses: Mutex::default(),
+            metrics: Mutex::default(),
+            exposures: Mutex::default(),
+            logs: Mutex::default(),
+            sessions: Mutex::default(),
+            connect_tasks: Mutex::default(),
+            monitor_tasks: Mutex::default(),
+            reconnect_attempts: Mutex::default(),
+            connect_attempts: Mutex::default(),
+        }
+    }
+
     fn append_log_with_level(&self, id: &str, level: &str, message: impl Into<String>) {
         let line = format!("[{}] [{}] {}", now_millis(), level, message.into());
         let mut logs = self.logs.lock().expect("ssh logs mutex");
@@ -1695,7 +2782,7 @@ impl DesktopSshManagerInner {
 
     fn set_status(
         &self,
-        app: &AppHandle,
+        app: &dyn SshManagerEvents,
         id: &str,
         phase: DesktopSshPhase,
         detail: Option<String>,
@@ -1706,44 +2793,68 @@ impl DesktopSshManagerInner {
         retry_attempt: u32,
         requires_user_action: bool,
     ) {
-        let level = if matches!(&phase, DesktopSshPhase::Error) {
+        self.publish_status(
+            app,
+            DesktopSshInstanceStatus {
+                id: id.to_string(),
+                phase,
+                detail,
+                local_url,
+                local_port,
+                remote_port,
+                started_by_us,
+                retry_attempt,
+                requires_user_action,
+                failed_jump_host: None,
+                exposed_beyond_loopback: self
+                    .exposures
+                    .lock()
+                    .expect("ssh exposures mutex")
+                    .contains_key(id),
+                updated_at_ms: now_millis(),
+            },
+        );
+    }
+
+    fn publish_status(&self, app: &dyn SshManagerEvents, status: DesktopSshInstanceStatus) {
+        let level = if matches!(&status.phase, DesktopSshPhase::Error) {
             "ERROR"
-        } else if matches!(&phase, DesktopSshPhase::Degraded) {
+        } else if matches!(&status.phase, DesktopSshPhase::Degraded) {
             "WARN"
         } else {
             "INFO"
         };
 
         self.append_log_with_level(
-            id,
+            &status.id,
             level,
             format!(
                 "phase={} detail={} retry={} requires_user_action={}",
-                serde_json::to_string(&phase).unwrap_or_else(|_| "\"unknown\"".to_string()),
-                detail.as_deref().unwrap_or(""),
-                retry_attempt,
-                requires_user_action
+                serde_json::to_string(&status.phase).unwrap_or_else(|_| "\"unknown\"".to_string()),
+                status.detail.as_deref().unwrap_or(""),
+                status.retry_attempt,
+                status.requires_user_action
             ),
         );
 
-        let status = DesktopSshInstanceStatus {
-            id: id.to_string(),
-            phase,
-            detail,
-            local_url,
-            local_port,
-            remote_port,
-            started_by_us,
-            retry_attempt,
-            requires_user_action,
-            updated_at_ms: now_millis(),
-        };
-
         self.statuses
             .lock()
             .expect("ssh status mutex")
-            .insert(id.to_string(), status.clone());
-        let _ = app.emit(SSH_STATUS_EVENT, status);
+            .insert(status.id.clone(), status.clone());
+        app.emit_status(&status);
+    }
+
+    /// Move an instance to `Error` after a failed connect attempt, keeping
+    /// the failing jump host when the error could be attributed to one.
+    fn report_connect_failure(&self, app: &dyn SshManagerEvents, id: &str, err: &anyhow::Error) {
+        let mut status = DesktopSshInstanceStatus::idle(id);
+        status.phase = DesktopSshPhase::Error;
+        status.detail = Some(err.to_string());
+        status.requires_user_action = true;
+        status.failed_jump_host = err
+            .downcast_ref::<JumpHostFailure>()
+            .map(|failure| failure.index);
+        self.publish_status(app, status);
     }
 
     fn clear_retry_attempt(&self, id: &str) {
@@ -1801,6 +2912,16 @@ impl DesktopSshManagerInner {
         }
     }
 
+    /// Forget the monitor handle without aborting it. Used by the monitor
+    /// task itself before it tears down or hands off to a reconnect, so it
+    /// doesn't cancel its own pending work.
+    fn detach_monitor_task(&self, id: &str) {
+        self.monitor_tasks
+            .lock()
+            .expect("ssh monitor task mutex")
+            .remove(id);
+    }
+
     fn session_is_alive(&self, id: &str) -> bool {
         let mut sessions = self.sessions.lock().expect("ssh sessions mutex");
         let Some(session) = sessions.get_mut(id) else {
@@ -1810,8 +2931,8 @@ impl DesktopSshManagerInner {
         let mut main_anchor_alive = false;
 
         if !session.main_forward_detached {
-            if let Some(status) = session.main_forward.try_wait().ok().flatten() {
-                if status.success() {
+            if let Some(status) = session.main_forward.try_wait() {
+                if status.success {
                     session.main_forward_detached = true;
                     self.append_log_with_level(
                         id,
@@ -1819,10 +2940,7 @@ impl DesktopSshManagerInner {
                         "Main tunnel helper exited after ControlMaster handoff",
                     );
                 } else {
-                    let mut stderr = String::new();
-                    if let Some(mut stream) = session.main_forward.stderr.take() {
-                        let _ = stream.read_to_string(&mut stderr);
-                    }
+                    let stderr = session.main_forward.take_stderr();
                     self.append_log_with_level(
                         id,
                         "WARN",
@@ -1847,8 +2965,11 @@ impl DesktopSshManagerInner {
         }
 
         if session.master_detached {
-            if !is_control_master_alive(&session.parsed, &session.control_path) {
-                if is_local_tunnel_reachable(session.local_port) {
+            if !self
+                .transport
+                .check_master(&session.parsed, &session.control_path)
+            {
+                if is_local_tunnel_reachable(&session.local_host, session.local_port) {
                     self.append_log_with_level(
                         id,
                         "WARN",
@@ -1863,8 +2984,12 @@ impl DesktopSshManagerInner {
                 );
                 return false;
             }
-        } else if let Some(status) = session.master.try_wait().ok().flatten() {
-            if status.success() && is_control_master_alive(&session.parsed, &session.control_path) {
+        } else if let Some(status) = session.master.try_wait() {
+            if status.success
+                && self
+                    .transport
+                    .check_master(&session.parsed, &session.control_path)
+            {
                 session.master_detached = true;
                 self.append_log_with_level(
                     id,
@@ -1872,10 +2997,7 @@ impl DesktopSshManagerInner {
                     "SSH ControlMaster transitioned to detached background mode",
                 );
             } else {
-                let mut stderr = String::new();
-                if let Some(mut stream) = session.master.stderr.take() {
-                    let _ = stream.read_to_string(&mut stderr);
-                }
+                let stderr = session.master.take_stderr();
                 self.append_log_with_level(
                     id,
                     "WARN",
@@ -1895,10 +3017,15 @@ impl DesktopSshManagerInner {
         true
     }
 
-    fn disconnect_internal(&self, app: &AppHandle, id: &str, report_idle: bool) {
-        self.cancel_connect_task(id);
-        self.cancel_monitor_task(id);
-
+    /// Stop every process owned by the session without touching tasks,
+    /// retry counters or the published status.
+    fn teardown_session(&self, id: &str) {
+        let transport = self.transport.as_ref();
+        self.metrics.lock().expect("ssh metrics mutex").remove(id);
+        self.exposures
+            .lock()
+            .expect("ssh exposures mutex")
+            .remove(id);
         if let Some(mut session) = self.sessions.lock().expect("ssh sessions mutex").remove(id) {
             if session.started_by_us
                 && matches!(
@@ -1908,24 +3035,43 @@ impl DesktopSshManagerInner {
                 && !session.instance.remote_openchamber.keep_running
             {
                 stop_remote_server_best_effort(
+                    transport,
                     &session.parsed,
                     &session.control_path,
                     session.remote_port,
                 );
+            } else if let Some(lease) = session.idle_lease.as_deref() {
+                // The grace period starts from this last touch.
+                let _ = transport.run_command(
+                    &session.parsed,
+                    &session.control_path,
+                    &refresh_idle_lease_script(session.remote_port, lease),
+                    DEFAULT_CONNECTION_TIMEOUT_SEC,
+                );
             }
 
-            stop_control_master_best_effort(&session.parsed, &session.control_path);
-
-            kill_child(&mut session.main_forward);
-            for child in &mut session.extra_forwards {
-                kill_child(child);
+            for forward in &session.extra_forwards {
+                let _ = transport.cancel_forward(&session.parsed, &session.control_path, forward);
             }
-            kill_child(&mut session.master);
+            if session.owns_master {
+                transport.stop_master(&session.parsed, &session.control_path);
+            }
+
+            session.main_forward.kill();
+            session.master.kill();
 
-            let _ = fs::remove_file(&session.control_path);
+            if session.owns_master {
+                let _ = fs::remove_file(&session.control_path);
+            }
             let _ = fs::remove_file(session.session_dir.join("askpass.sh"));
+            let _ = fs::remove_file(session.session_dir.join(AGENT_IDENTITY_FILE));
         }
+    }
 
+    fn disconnect_internal(&self, app: &dyn SshManagerEvents, id: &str, report_idle: bool) {
+        self.cancel_connect_task(id);
+        self.cancel_monitor_task(id);
+        self.teardown_session(id);
         self.clear_retry_attempt(id);
 
         if report_idle {
@@ -1946,12 +3092,13 @@ impl DesktopSshManagerInner {
 
     fn ensure_remote_server(
         &self,
-        app: &AppHandle,
+        app: &dyn SshManagerEvents,
         instance: &DesktopSshInstance,
         parsed: &DesktopSshParsedCommand,
         control_path: &Path,
     ) -> Result<(u16, bool)> {
-        let app_version = app.package_info().version.to_string();
+        let transport = self.transport.as_ref();
+        let app_version = app.app_version();
 
         match instance.remote_openchamber.mode {
             DesktopSshRemoteMode::External => {
@@ -1973,6 +3120,7 @@ impl DesktopSshManagerInner {
                     false,
                 );
                 probe_remote_system_info(
+                    transport,
                     parsed,
                     control_path,
                     port,
@@ -1999,7 +3147,8 @@ impl DesktopSshManagerInner {
                     false,
                 );
 
-                let installed_version = current_remote_openchamber_version(parsed, control_path);
+                let installed_version =
+                    current_remote_openchamber_version(transport, parsed, control_path);
                 if installed_version.is_none() {
                     self.set_status(
                         app,
@@ -2014,6 +3163,7 @@ impl DesktopSshManagerInner {
                         false,
                     );
                     install_openchamber_managed(
+                        transport,
                         parsed,
                         control_path,
                         &app_version,
@@ -2039,6 +3189,7 @@ impl DesktopSshManagerInner {
                         false,
                     );
                     install_openchamber_managed(
+                        transport,
                         parsed,
                         control_path,
                         &app_version,
@@ -2064,6 +3215,7 @@ impl DesktopSshManagerInner {
 
                 if let Some(port) = remote_port {
                     if !remote_server_running(
+                        transport,
                         parsed,
                         control_path,
                         port,
@@ -2090,8 +3242,13 @@ impl DesktopSshManagerInner {
                         .remote_openchamber
                         .preferred_port
                         .unwrap_or_else(|| random_port_candidate(&instance.id));
-                    let started_port =
-                        start_remote_server_managed(parsed, control_path, instance, desired_port)?;
+                    let started_port = start_remote_server_managed(
+                        transport,
+                        parsed,
+                        control_path,
+                        instance,
+                        desired_port,
+                    )?;
                     remote_port = Some(started_port);
                     started_by_us = true;
                 }
@@ -2101,6 +3258,7 @@ impl DesktopSshManagerInner {
                 };
 
                 if !remote_server_running(
+                    transport,
                     parsed,
                     control_path,
                     port,
@@ -2116,11 +3274,65 @@ impl DesktopSshManagerInner {
         }
     }
 
+    /// Start a ControlMaster private to this instance.
+    fn spawn_own_master(
+        &self,
+        app: &dyn SshManagerEvents,
+        instance: &DesktopSshInstance,
+        parsed: &DesktopSshParsedCommand,
+        session_dir: &Path,
+    ) -> Result<(PathBuf, Box<dyn SshProcess>)> {
+        let transport = self.transport.as_ref();
+        let control_path = control_path_for_instance(session_dir, &instance.id);
+        let _ = fs::remove_file(&control_path);
+        let askpass_path = session_dir.join("askpass.sh");
+        write_askpass_script(&askpass_path)?;
+
+        self.set_status(
+            app,
+            &instance.id,
+            DesktopSshPhase::MasterConnecting,
+            Some(match instance.jump_hosts.len() {
+                0 => "Establishing SSH ControlMaster".to_string(),
+                1 => "Establishing SSH ControlMaster via 1 jump host".to_string(),
+                hops => format!("Establishing SSH ControlMaster via {hops} jump hosts"),
+            }),
+            None,
+            None,
+            None,
+            false,
+            0,
+            false,
+        );
+
+        let mut master = transport.spawn_master(
+            parsed,
+            &control_path,
+            &askpass_path,
+            &askpass_env_for_instance(instance, parsed),
+            &instance.multiplex.master_options(),
+        )?;
+
+        if let Err(err) = wait_for_master_ready(
+            transport,
+            parsed,
+            &control_path,
+            instance.connection_timeout_sec,
+            master.as_mut(),
+        ) {
+            master.kill();
+            return Err(explain_master_failure(err, instance));
+        }
+
+        Ok((control_path, master))
+    }
+
     fn connect_blocking(
-        self: &Arc<Self>,
-        app: &AppHandle,
+        &self,
+        app: &dyn SshManagerEvents,
         instance: DesktopSshInstance,
     ) -> Result<()> {
+        let transport = self.transport.as_ref();
         let id = instance.id.clone();
         self.set_status(
             app,
@@ -2141,7 +3353,34 @@ impl DesktopSshManagerInner {
             .or_else(|| parse_ssh_command(&instance.ssh_command).ok())
             .ok_or_else(|| anyhow!("Invalid SSH command"))?;
 
-        let _resolved = resolve_ssh_config(&parsed)?;
+        let session_dir = ensure_session_dir(&self.settings_path, &id)?;
+        let parsed = apply_jump_hosts(&parsed, &instance.jump_hosts, &session_dir)?;
+        let parsed = apply_identity(transport, &parsed, instance.identity.as_ref(), &session_dir)?;
+        if !instance.jump_hosts.is_empty() {
+            let route: Vec<&str> = instance
+                .jump_hosts
+                .iter()
+                .map(|hop| hop.host.as_str())
+                .collect();
+            self.append_log(
+                &id,
+                format!("Routing through jump hosts: {}", route.join(" -> ")),
+            );
+        }
+
+        let resolved = transport.resolve_config(&parsed)?;
+        let user_master = if instance.multiplex.reuse_user_master {
+            let candidate = resolved_control_path(&resolved);
+            if candidate.is_none() {
+                self.append_log(
+                    &id,
+                    "No ControlPath in SSH config; opening a new ControlMaster",
+                );
+            }
+            candidate.filter(|path| transport.check_master(&parsed, path))
+        } else {
+            None
+        };
 
         self.set_status(
             app,
@@ -2156,17 +3395,34 @@ impl DesktopSshManagerInner {
             false,
         );
 
-        let session_dir = ensure_session_dir(&id)?;
-        let control_path = control_path_for_instance(&session_dir, &id);
-        let _ = fs::remove_file(&control_path);
-        let askpass_path = session_dir.join("askpass.sh");
-        write_askpass_script(&askpass_path)?;
+        let (control_path, mut master, owns_master) = if let Some(path) = user_master {
+            self.append_log(
+                &id,
+                format!("Attached to existing ControlMaster at {}", path.display()),
+            );
+            self.set_status(
+                app,
+                &id,
+                DesktopSshPhase::MasterConnecting,
+                Some("Attached to existing SSH ControlMaster".to_string()),
+                None,
+                None,
+                None,
+                false,
+                0,
+                false,
+            );
+            (path, Box::new(ExternalMaster) as Box<dyn SshProcess>, false)
+        } else {
+            let (path, master) = self.spawn_own_master(app, &instance, &parsed, &session_dir)?;
+            (path, master, true)
+        };
 
         self.set_status(
             app,
             &id,
-            DesktopSshPhase::MasterConnecting,
-            Some("Establishing SSH ControlMaster".to_string()),
+            DesktopSshPhase::RemoteProbe,
+            Some("Probing remote platform".to_string()),
             None,
             None,
             None,
@@ -2175,52 +3431,22 @@ impl DesktopSshManagerInner {
             false,
         );
 
-        let mut master = spawn_master_process(
-            &parsed,
-            &control_path,
-            &askpass_path,
-            instance.auth.ssh_password.as_ref().and_then(|secret| {
-                if secret.enabled {
-                    secret.value.as_deref()
-                } else {
-                    None
-                }
-            }),
-        )?;
-
-        if let Err(err) = wait_for_master_ready(
-            &parsed,
-            &control_path,
-            instance.connection_timeout_sec,
-            &mut master,
-        ) {
-            kill_child(&mut master);
-            return Err(err);
-        }
-
-        self.set_status(
-            app,
-            &id,
-            DesktopSshPhase::RemoteProbe,
-            Some("Probing remote platform".to_string()),
-            None,
-            None,
-            None,
-            false,
-            0,
-            false,
-        );
-
-        let remote_os = run_remote_command(
+        let remote_os = match transport.run_command(
             &parsed,
             &control_path,
             "uname -s",
             instance.connection_timeout_sec,
-        )?;
+        ) {
+            Ok(output) => output,
+            Err(err) => {
+                master.kill();
+                return Err(err);
+            }
+        };
 
         let remote_os = remote_os.trim().to_ascii_lowercase();
         if remote_os != "linux" && remote_os != "darwin" {
-            kill_child(&mut master);
+            master.kill();
             return Err(anyhow!("Unsupported remote OS: {remote_os}"));
         }
 
@@ -2228,11 +3454,48 @@ impl DesktopSshManagerInner {
             match self.ensure_remote_server(app, &instance, &parsed, &control_path) {
                 Ok(result) => result,
                 Err(err) => {
-                    kill_child(&mut master);
+                    master.kill();
                     return Err(err);
                 }
             };
 
+        // The watchdog has no credentials, so behind a UI password its status
+        // check is always refused and it would never stop the server.
+        let idle_warning = idle_stop_minutes(&instance)
+            .filter(|_| configured_openchamber_password(&instance).is_some())
+            .map(|_| {
+                let warning =
+                    "Idle-stop is off because the remote server requires a UI password".to_string();
+                self.append_log_with_level(&id, "WARN", warning.clone());
+                warning
+            });
+        let idle_minutes = idle_stop_minutes(&instance).filter(|_| idle_warning.is_none());
+        let idle_lease = idle_minutes.and_then(|grace_min| {
+            let lease = idle_lease_name(&id);
+            match transport.run_command(
+                &parsed,
+                &control_path,
+                &arm_idle_stop_script(remote_port, grace_min, &lease),
+                DEFAULT_CONNECTION_TIMEOUT_SEC,
+            ) {
+                Ok(_) => {
+                    self.append_log(
+                        &id,
+                        format!("Remote server will stop after {grace_min} min without a desktop"),
+                    );
+                    Some(lease)
+                }
+                Err(err) => {
+                    self.append_log_with_level(
+                        &id,
+                        "WARN",
+                        format!("Failed to arm remote idle-stop: {err}"),
+                    );
+                    None
+                }
+            }
+        });
+
         self.set_status(
             app,
             &id,
@@ -2246,28 +3509,45 @@ impl DesktopSshManagerInner {
             false,
         );
 
-        let bind_host = sanitize_bind_host(&instance.local_forward.bind_host);
+        let bind_host = match sanitize_bind_host(&instance.local_forward.bind_host)
+            .and_then(|host| ensure_bind_host_available(&host).map(|()| host))
+        {
+            Ok(host) => host,
+            Err(err) => {
+                master.kill();
+                return Err(err);
+            }
+        };
+        let local_host = connect_host_for_bind(&bind_host);
         let mut local_port = instance.local_forward.preferred_local_port.unwrap_or(0);
-        if local_port == 0 {
-            local_port = pick_unused_local_port()?;
-        }
-        if !is_local_port_available(&bind_host, local_port) {
-            local_port = pick_unused_local_port()?;
-        }
-
-        let mut main_forward =
-            match spawn_main_forward(&parsed, &control_path, &bind_host, local_port, remote_port) {
-                Ok(child) => child,
+        if local_port == 0 || !is_local_port_available(&bind_host, local_port) {
+            local_port = match pick_unused_local_port(&bind_host) {
+                Ok(port) => port,
                 Err(err) => {
-                    kill_child(&mut master);
+                    master.kill();
                     return Err(err);
                 }
             };
+        }
+
+        let mut main_forward = match transport.spawn_main_forward(
+            &parsed,
+            &control_path,
+            &bind_host,
+            local_port,
+            remote_port,
+        ) {
+            Ok(child) => child,
+            Err(err) => {
+                master.kill();
+                return Err(err);
+            }
+        };
         let mut main_forward_detached = false;
 
         std::thread::sleep(Duration::from_millis(250));
-        if let Some(status) = main_forward.try_wait().ok().flatten() {
-            if status.success() {
+        if let Some(status) = main_forward.try_wait() {
+            if status.success {
                 main_forward_detached = true;
                 self.append_log_with_level(
                     &id,
@@ -2275,11 +3555,8 @@ impl DesktopSshManagerInner {
                     "Main tunnel helper exited after ControlMaster handoff",
                 );
             } else {
-                let mut stderr = String::new();
-                if let Some(mut stream) = main_forward.stderr.take() {
-                    let _ = stream.read_to_string(&mut stderr);
-                }
-                kill_child(&mut master);
+                let stderr = main_forward.take_stderr();
+                master.kill();
                 return Err(anyhow!(format!(
                     "Failed to start main port forward (status: {status}): {}",
                     stderr.trim()
@@ -2294,15 +3571,32 @@ impl DesktopSshManagerInner {
             .iter()
             .filter(|forward| forward.enabled)
         {
-            match spawn_extra_forward(&parsed, &control_path, forward) {
+            let local_bind = forward
+                .local_host
+                .as_deref()
+                .filter(|_| !matches!(forward.forward_type, DesktopSshPortForwardType::Remote));
+            if let Some(Err(err)) = local_bind.map(ensure_bind_host_available) {
+                extra_errors.push(format!("{}: {err}", forward.id));
+                continue;
+            }
+            match transport.add_forward(&parsed, &control_path, forward) {
                 Ok(()) => {
+                    extra_forwards.push(forward.clone());
                     if matches!(forward.forward_type, DesktopSshPortForwardType::Local) {
                         if let Some(local_port) = forward.local_port {
+                            let host = connect_host_for_bind(
+                                forward
+                                    .local_host
+                                    .as_deref()
+                                    .unwrap_or(DEFAULT_LOCAL_BIND_HOST),
+                            );
                             std::thread::sleep(Duration::from_millis(100));
-                            if !is_local_tunnel_reachable(local_port) {
+                            if !is_local_tunnel_reachable(&host, local_port) {
                                 extra_errors.push(format!(
-                                    "{}: local listener 127.0.0.1:{} is not reachable",
-                                    forward.id, local_port
+                                    "{}: local listener {}:{} is not reachable",
+                                    forward.id,
+                                    bracket_host(&host),
+                                    local_port
                                 ));
                             }
                         }
@@ -2312,20 +3606,32 @@ impl DesktopSshManagerInner {
             }
         }
 
-        if let Err(err) = wait_local_forward_ready(local_port) {
-            kill_child(&mut main_forward);
-            for child in &mut extra_forwards {
-                kill_child(child);
+        if let Err(err) = wait_local_forward_ready(&local_host, local_port) {
+            main_forward.kill();
+            for forward in &extra_forwards {
+                let _ = transport.cancel_forward(&parsed, &control_path, forward);
             }
-            kill_child(&mut master);
+            master.kill();
             return Err(err);
         }
 
-        let local_url = format!("http://127.0.0.1:{local_port}");
+        let local_url = format!("http://{}:{local_port}", bracket_host(&local_host));
+        let exposed = exposed_listeners(&instance, local_port);
+        if !exposed.is_empty() {
+            self.append_log_with_level(
+                &id,
+                "WARN",
+                format!("Forwards reachable beyond loopback: {}", exposed.join(", ")),
+            );
+            self.exposures
+                .lock()
+                .expect("ssh exposures mutex")
+                .insert(id.clone(), exposed);
+        }
         let label = build_display_label(&instance);
-        let _ = update_ssh_host_url(&id, &label, &local_url);
+        let _ = update_ssh_host_url(&self.settings_path, &id, &label, &local_url);
         if instance.local_forward.preferred_local_port != Some(local_port) {
-            let _ = persist_local_port_for_instance(&id, local_port);
+            let _ = persist_local_port_for_instance(&self.settings_path, &id, local_port);
         }
 
         self.sessions.lock().expect("ssh sessions mutex").insert(
@@ -2335,30 +3641,37 @@ impl DesktopSshManagerInner {
                 parsed,
                 session_dir,
                 control_path,
+                local_host,
                 local_port,
                 remote_port,
                 started_by_us,
                 master,
-                master_detached: false,
+                master_detached: !owns_master,
+                owns_master,
                 main_forward,
                 main_forward_detached,
                 extra_forwards,
+                idle_lease,
             },
         );
 
         self.clear_retry_attempt(&id);
+        let mut detail = if extra_errors.is_empty() {
+            "SSH instance is ready".to_string()
+        } else {
+            format!(
+                "SSH instance is ready with forward warnings: {}",
+                extra_errors.join("; ")
+            )
+        };
+        if let Some(warning) = idle_warning {
+            detail = format!("{detail}. {warning}");
+        }
         self.set_status(
             app,
             &id,
             DesktopSshPhase::Ready,
-            if extra_errors.is_empty() {
-                Some("SSH instance is ready".to_string())
-            } else {
-                Some(format!(
-                    "SSH instance is ready with forward warnings: {}",
-                    extra_errors.join("; ")
-                ))
-            },
+            Some(detail),
             Some(local_url),
             Some(local_port),
             Some(remote_port),
@@ -2367,112 +3680,51 @@ impl DesktopSshManagerInner {
             false,
         );
 
-        self.spawn_monitor(app.clone(), id);
         Ok(())
     }
 
-    fn spawn_monitor(self: &Arc<Self>, app: AppHandle, id: String) {
-        self.cancel_monitor_task(&id);
-        let inner = Arc::clone(self);
-        let id_for_task = id.clone();
-        let handle = tauri::async_runtime::spawn(async move {
-            let mut healthy_ticks: u32 = 0;
-            loop {
-                let poll_secs = if healthy_ticks >= MONITOR_STABILIZE_TICKS {
-                    MONITOR_STEADY_POLL_SECS
-                } else {
-                    MONITOR_INITIAL_POLL_SECS
-                };
-                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
-
-                let mut dropped_reason: Option<String> = None;
-                let mut detached_notice: Option<String> = None;
-                {
-                    let mut sessions = inner.sessions.lock().expect("ssh sessions mutex");
-                    let Some(session) = sessions.get_mut(&id_for_task) else {
-                        break;
-                    };
+    /// Inspect the live session once. Detach transitions are logged here;
+    /// anything that warrants a reconnect is returned as `Dropped`.
+    fn monitor_tick(&self, id: &str) -> MonitorTick {
+        let mut dropped_reason: Option<String> = None;
+        let mut detached_notice: Option<String> = None;
+        {
+            let mut sessions = self.sessions.lock().expect("ssh sessions mutex");
+            let Some(session) = sessions.get_mut(id) else {
+                return MonitorTick::SessionGone;
+            };
 
-                    let mut main_anchor_alive = false;
+            let mut main_anchor_alive = false;
 
-                    if !session.main_forward_detached {
-                        if let Some(status) = session.main_forward.try_wait().ok().flatten() {
-                            if status.success() {
-                                session.main_forward_detached = true;
-                                detached_notice = Some(
-                                    "Main tunnel helper exited after ControlMaster handoff"
-                                        .to_string(),
-                                );
-                            } else {
-                                let mut stderr = String::new();
-                                if let Some(mut stream) = session.main_forward.stderr.take() {
-                                    let _ = stream.read_to_string(&mut stderr);
-                                }
-                                dropped_reason = Some(if stderr.trim().is_empty() {
-                                    format!("Main SSH forward exited ({status})")
-                                } else {
-                                    format!("Main SSH forward exited ({status}): {}", stderr.trim())
-                                });
-                            }
+            if !session.main_forward_detached {
+                if let Some(status) = session.main_forward.try_wait() {
+                    if status.success {
+                        session.main_forward_detached = true;
+                        detached_notice = Some(
+                            "Main tunnel helper exited after ControlMaster handoff".to_string(),
+                        );
+                    } else {
+                        let stderr = session.main_forward.take_stderr();
+                        dropped_reason = Some(if stderr.trim().is_empty() {
+                            format!("Main SSH forward exited ({status})")
                         } else {
-                            main_anchor_alive = true;
-                        }
+                            format!("Main SSH forward exited ({status}): {}", stderr.trim())
+                        });
                     }
+                } else {
+                    main_anchor_alive = true;
+                }
+            }
 
-                    if dropped_reason.is_none() {
-                        if main_anchor_alive {
-                            if !session.master_detached {
-                                if let Some(status) = session.master.try_wait().ok().flatten() {
-                                    if status.success()
-                                        && is_control_master_alive(
-                                            &session.parsed,
-                                            &session.control_path,
-                                        )
-                                    {
-                                        session.master_detached = true;
-                                        if detached_notice.is_none() {
-                                            detached_notice = Some(
-                                                "SSH ControlMaster transitioned to detached background mode"
-                                                    .to_string(),
-                                            );
-                                        }
-                                    } else {
-                                        detached_notice = Some(
-                                            "SSH ControlMaster exited while main tunnel is still active"
-                                                .to_string(),
-                                        );
-                                    }
-                                }
-                            } else if !is_control_master_alive(
-                                &session.parsed,
-                                &session.control_path,
-                            ) {
-                                detached_notice = Some(
-                                    "SSH ControlMaster is not reachable; main tunnel remains active"
-                                        .to_string(),
-                                );
-                            }
-                        } else if session.master_detached {
-                            // Fast path: check local tunnel first (cheap TCP probe)
-                            // before spawning an SSH subprocess for control master check.
-                            if is_local_tunnel_reachable(session.local_port) {
-                                // Tunnel is alive — skip the expensive SSH check entirely.
-                            } else if !is_control_master_alive(
-                                &session.parsed,
-                                &session.control_path,
-                            ) {
-                                dropped_reason =
-                                    Some("SSH ControlMaster is not reachable".to_string());
-                            } else {
-                                detached_notice = Some(
-                                    "Local tunnel unreachable but ControlMaster is alive"
-                                        .to_string(),
-                                );
-                            }
-                        } else if let Some(status) = session.master.try_wait().ok().flatten() {
-                            if status.success()
-                                && is_control_master_alive(&session.parsed, &session.control_path)
-                            {
+            if dropped_reason.is_none() {
+                let master_alive = || {
+                    self.transport
+                        .check_master(&session.parsed, &session.control_path)
+                };
+                if main_anchor_alive {
+                    if !session.master_detached {
+                        if let Some(status) = session.master.try_wait() {
+                            if status.success && master_alive() {
                                 session.master_detached = true;
                                 if detached_notice.is_none() {
                                     detached_notice = Some(
@@ -2481,79 +3733,180 @@ impl DesktopSshManagerInner {
                                     );
                                 }
                             } else {
-                                let mut stderr = String::new();
-                                if let Some(mut stream) = session.master.stderr.take() {
-                                    let _ = stream.read_to_string(&mut stderr);
-                                }
-                                dropped_reason = Some(if stderr.trim().is_empty() {
-                                    format!("SSH ControlMaster exited ({status})")
-                                } else {
-                                    format!(
-                                        "SSH ControlMaster exited ({status}): {}",
-                                        stderr.trim()
-                                    )
-                                });
+                                detached_notice = Some(
+                                    "SSH ControlMaster exited while main tunnel is still active"
+                                        .to_string(),
+                                );
                             }
                         }
+                    } else if !master_alive() {
+                        detached_notice = Some(
+                            "SSH ControlMaster is not reachable; main tunnel remains active"
+                                .to_string(),
+                        );
+                    }
+                } else if session.master_detached {
+                    // Fast path: check local tunnel first (cheap TCP probe)
+                    // before spawning an SSH subprocess for control master check.
+                    if is_local_tunnel_reachable(&session.local_host, session.local_port) {
+                        // Tunnel is alive — skip the expensive SSH check entirely.
+                    } else if !master_alive() {
+                        dropped_reason = Some("SSH ControlMaster is not reachable".to_string());
+                    } else {
+                        detached_notice =
+                            Some("Local tunnel unreachable but ControlMaster is alive".to_string());
+                    }
+                } else if let Some(status) = session.master.try_wait() {
+                    if status.success && master_alive() {
+                        session.master_detached = true;
+                        if detached_notice.is_none() {
+                            detached_notice = Some(
+                                "SSH ControlMaster transitioned to detached background mode"
+                                    .to_string(),
+                            );
+                        }
+                    } else {
+                        let stderr = session.master.take_stderr();
+                        dropped_reason = Some(if stderr.trim().is_empty() {
+                            format!("SSH ControlMaster exited ({status})")
+                        } else {
+                            format!("SSH ControlMaster exited ({status}): {}", stderr.trim())
+                        });
                     }
                 }
+            }
+        }
 
-                if let Some(message) = detached_notice {
-                    inner.append_log_with_level(&id_for_task, "INFO", message);
-                }
+        if let Some(message) = detached_notice {
+            self.append_log_with_level(id, "INFO", message);
+        }
 
-                if dropped_reason.is_none() {
-                    healthy_ticks = healthy_ticks.saturating_add(1);
-                    continue;
-                }
+        match dropped_reason {
+            Some(reason) => MonitorTick::Dropped(reason),
+            None => MonitorTick::Healthy,
+        }
+    }
+
+    /// Tear down a dropped session and decide whether to retry. Returns the
+    /// backoff to wait before reconnecting, or `None` once the retry limit
+    /// is exhausted and the instance has been moved to `Error`.
+    fn begin_reconnect(
+        &self,
+        app: &dyn SshManagerEvents,
+        id: &str,
+        reason: &str,
+    ) -> Option<Duration> {
+        self.append_log_with_level(id, "WARN", reason);
+        self.teardown_session(id);
+        let attempt = self.next_retry_attempt(id);
 
-                let dropped_reason =
-                    dropped_reason.unwrap_or_else(|| "SSH connection dropped".to_string());
-                inner.append_log_with_level(&id_for_task, "WARN", dropped_reason.clone());
+        if attempt > DEFAULT_RECONNECT_MAX_ATTEMPTS {
+            self.set_status(
+                app,
+                id,
+                DesktopSshPhase::Error,
+                Some(format!("{reason}. Retry limit reached")),
+                None,
+                None,
+                None,
+                false,
+                attempt,
+                true,
+            );
+            return None;
+        }
 
-                inner.disconnect_internal(&app, &id_for_task, false);
-                let attempt = inner.next_retry_attempt(&id_for_task);
+        self.set_status(
+            app,
+            id,
+            DesktopSshPhase::Degraded,
+            Some(format!("{reason}. Reconnecting")),
+            None,
+            None,
+            None,
+            false,
+            attempt,
+            false,
+        );
 
-                if attempt > DEFAULT_RECONNECT_MAX_ATTEMPTS {
-                    inner.set_status(
-                        &app,
-                        &id_for_task,
-                        DesktopSshPhase::Error,
-                        Some(format!("{dropped_reason}. Retry limit reached")),
-                        None,
-                        None,
-                        None,
-                        false,
-                        attempt,
-                        true,
-                    );
-                    break;
-                }
+        let delay_ms = (2u64.saturating_pow(attempt.saturating_sub(1))).saturating_mul(1000);
+        let jitter = (now_millis() % 700).saturating_add(100);
+        Some(Duration::from_millis(
+            delay_ms.min(30_000).saturating_add(jitter),
+        ))
+    }
 
-                inner.set_status(
-                    &app,
-                    &id_for_task,
-                    DesktopSshPhase::Degraded,
-                    Some(format!("{dropped_reason}. Reconnecting")),
-                    None,
-                    None,
-                    None,
-                    false,
-                    attempt,
-                    false,
-                );
+    fn spawn_monitor(self: &Arc<Self>, app: Arc<dyn SshManagerEvents>, id: String) {
+        self.cancel_monitor_task(&id);
+        let inner = Arc::clone(self);
+        let id_for_task = id.clone();
+        let handle = tauri::async_runtime::spawn(async move {
+            let mut healthy_ticks: u32 = 0;
+            let mut last_metrics: Option<Instant> = None;
+            let mut last_lease: Option<Instant> = None;
+            loop {
+                let poll_secs = if healthy_ticks >= MONITOR_STABILIZE_TICKS {
+                    MONITOR_STEADY_POLL_SECS
+                } else {
+                    MONITOR_INITIAL_POLL_SECS
+                };
+                tokio::time::sleep(Duration::from_secs(poll_secs)).await;
 
-                let delay_ms =
-                    (2u64.saturating_pow(attempt.saturating_sub(1))).saturating_mul(1000);
-                let jitter = (now_millis() % 700).saturating_add(100);
-                tokio::time::sleep(Duration::from_millis(
-                    delay_ms.min(30_000).saturating_add(jitter),
-                ))
-                .await;
+                let tick = tauri::async_runtime::spawn_blocking({
+                    let inner = Arc::clone(&inner);
+                    let id = id_for_task.clone();
+                    move || inner.monitor_tick(&id)
+                })
+                .await
+                .unwrap_or(MonitorTick::Healthy);
+
+                let reason = match tick {
+                    MonitorTick::Healthy => {
+                        healthy_ticks = healthy_ticks.saturating_add(1);
+                        let lease_due = last_lease.is_none_or(|at| {
+                            at.elapsed() >= Duration::from_secs(IDLE_LEASE_REFRESH_SECS)
+                        });
+                        if lease_due {
+                            last_lease = Some(Instant::now());
+                            let _ = tauri::async_runtime::spawn_blocking({
+                                let inner = Arc::clone(&inner);
+                                let id = id_for_task.clone();
+                                move || inner.refresh_idle_lease(&id)
+                            })
+                            .await;
+                        }
+                        let metrics_due = last_metrics.is_none_or(|at| {
+                            at.elapsed() >= Duration::from_secs(METRICS_POLL_SECS)
+                        });
+                        if metrics_due {
+                            last_metrics = Some(Instant::now());
+                            let _ = tauri::async_runtime::spawn_blocking({
+                                let inner = Arc::clone(&inner);
+                                let app = Arc::clone(&app);
+                                let id = id_for_task.clone();
+                                move || inner.refresh_metrics(app.as_ref(), &id)
+                            })
+                            .await;
+                        }
+                        continue;
+                    }
+                    MonitorTick::SessionGone => {
+                        inner.detach_monitor_task(&id_for_task);
+                        break;
+                    }
+                    MonitorTick::Dropped(reason) => reason,
+                };
+
+                inner.detach_monitor_task(&id_for_task);
+                let Some(delay) = inner.begin_reconnect(app.as_ref(), &id_for_task, &reason) else {
+                    break;
+                };
+                tokio::time::sleep(delay).await;
 
-                if let Err(err) = inner.start_connect(app.clone(), id_for_task.clone()) {
+                if let Err(err) = inner.start_connect(Arc::clone(&app), id_for_task.clone()) {
+                    let attempt = inner.current_retry_attempt(&id_for_task);
                     inner.set_status(
-                        &app,
+                        app.as_ref(),
                         &id_for_task,
                         DesktopSshPhase::Error,
                         Some(err),
@@ -2567,12 +3920,6 @@ impl DesktopSshManagerInner {
                 }
                 break;
             }
-
-            inner
-                .monitor_tasks
-                .lock()
-                .expect("ssh monitor task mutex")
-                .remove(&id_for_task);
         });
         self.monitor_tasks
             .lock()
@@ -2580,8 +3927,23 @@ impl DesktopSshManagerInner {
             .insert(id, handle);
     }
 
-    fn start_connect(self: &Arc<Self>, app: AppHandle, id: String) -> Result<(), String> {
-        let config = read_desktop_ssh_instances_from_disk();
+    fn start_connect(
+        self: &Arc<Self>,
+        app: Arc<dyn SshManagerEvents>,
+        id: String,
+    ) -> Result<(), String> {
+        self.start_connect_with_permit(app, id, None)
+    }
+
+    /// Like `start_connect`, holding `permit` until the attempt settles so
+    /// bulk connects can cap how many run at once.
+    fn start_connect_with_permit(
+        self: &Arc<Self>,
+        app: Arc<dyn SshManagerEvents>,
+        id: String,
+        permit: Option<OwnedSemaphorePermit>,
+    ) -> Result<(), String> {
+        let config = read_desktop_ssh_instances_from_path(&self.settings_path);
         let Some(instance) = config.instances.into_iter().find(|item| item.id == id) else {
             return Err("SSH instance not found".to_string());
         };
@@ -2599,7 +3961,7 @@ impl DesktopSshManagerInner {
         if self.session_is_alive(&id) {
             let snapshot = self.status_snapshot_for_instance(&id);
             self.set_status(
-                &app,
+                app.as_ref(),
                 &id,
                 DesktopSshPhase::Ready,
                 Some("SSH session already active".to_string()),
@@ -2622,59 +3984,42 @@ impl DesktopSshManagerInner {
         let connect_attempt = self.next_connect_attempt(&id);
         self.append_attempt_separator(&id, connect_attempt, retry_attempt);
         self.append_log(&id, "Starting SSH connection");
-        self.disconnect_internal(&app, &id, false);
+        // Keep the retry counter: reconnects go through here and must still
+        // reach the retry limit.
+        self.cancel_monitor_task(&id);
+        self.teardown_session(&id);
 
         let id_for_task = id.clone();
         let inner = Arc::clone(self);
-        let app_for_task = app.clone();
         let handle = tauri::async_runtime::spawn(async move {
             let result = tauri::async_runtime::spawn_blocking({
                 let inner = Arc::clone(&inner);
-                let app = app_for_task.clone();
+                let app = Arc::clone(&app);
                 let instance = instance.clone();
-                move || inner.connect_blocking(&app, instance)
+                move || inner.connect_blocking(app.as_ref(), instance)
             })
             .await;
 
-            match result {
-                Ok(Ok(())) => {}
-                Ok(Err(err)) => {
-                    inner.set_status(
-                        &app_for_task,
-                        &id_for_task,
-                        DesktopSshPhase::Error,
-                        Some(err.to_string()),
-                        None,
-                        None,
-                        None,
-                        false,
-                        0,
-                        true,
-                    );
-                    inner.disconnect_internal(&app_for_task, &id_for_task, false);
-                }
-                Err(err) => {
-                    inner.set_status(
-                        &app_for_task,
-                        &id_for_task,
-                        DesktopSshPhase::Error,
-                        Some(format!("SSH task failed: {err}")),
-                        None,
-                        None,
-                        None,
-                        false,
-                        0,
-                        true,
-                    );
-                    inner.disconnect_internal(&app_for_task, &id_for_task, false);
-                }
-            }
+            let failure = match result {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => Some(err),
+                Err(err) => Some(anyhow!("SSH task failed: {err}")),
+            };
+            drop(permit);
 
             inner
                 .connect_tasks
                 .lock()
                 .expect("ssh connect task mutex")
                 .remove(&id_for_task);
+
+            match failure {
+                None => inner.spawn_monitor(app, id_for_task),
+                Some(err) => {
+                    inner.report_connect_failure(app.as_ref(), &id_for_task, &err);
+                    inner.teardown_session(&id_for_task);
+                }
+            }
         });
 
         self.connect_tasks
@@ -2685,33 +4030,280 @@ impl DesktopSshManagerInner {
         Ok(())
     }
 
-    fn statuses_with_defaults(&self) -> Vec<DesktopSshInstanceStatus> {
-        let config = read_desktop_ssh_instances_from_disk();
-        let statuses = self.statuses.lock().expect("ssh status mutex");
-        let mut result = Vec::new();
-
-        for instance in config.instances {
-            result.push(
-                statuses
-                    .get(&instance.id)
-                    .cloned()
-                    .unwrap_or_else(|| DesktopSshInstanceStatus::idle(instance.id)),
+    /// Keep this desktop's idle-stop lease fresh on the remote host.
+    fn refresh_idle_lease(&self, id: &str) {
+        let target = {
+            let sessions = self.sessions.lock().expect("ssh sessions mutex");
+            sessions.get(id).and_then(|session| {
+                session.idle_lease.clone().map(|lease| {
+                    (
+                        lease,
+                        session.parsed.clone(),
+                        session.control_path.clone(),
+                        session.remote_port,
+                    )
+                })
+            })
+        };
+        let Some((lease, parsed, control_path, remote_port)) = target else {
+            return;
+        };
+        if let Err(err) = self.transport.run_command(
+            &parsed,
+            &control_path,
+            &refresh_idle_lease_script(remote_port, &lease),
+            DEFAULT_CONNECTION_TIMEOUT_SEC,
+        ) {
+            self.append_log_with_level(
+                id,
+                "WARN",
+                format!("Failed to refresh idle-stop lease: {err}"),
             );
         }
-
-        result.sort_by(|a, b| a.id.cmp(&b.id));
-        result
     }
-}
 
-#[tauri::command]
-pub fn desktop_ssh_logs(
-    state: State<'_, DesktopSshManagerState>,
-    id: String,
-    limit: Option<usize>,
-) -> Result<Vec<String>, String> {
-    let id = id.trim().to_string();
-    if id.is_empty() || id == LOCAL_HOST_ID {
+    /// Sample remote metrics for a live session and publish them.
+    fn refresh_metrics(&self, app: &dyn SshManagerEvents, id: &str) {
+        let target = {
+            let sessions = self.sessions.lock().expect("ssh sessions mutex");
+            sessions.get(id).map(|session| {
+                (
+                    session.instance.clone(),
+                    session.parsed.clone(),
+                    session.control_path.clone(),
+                    session.remote_port,
+                )
+            })
+        };
+        let Some((instance, parsed, control_path, remote_port)) = target else {
+            return;
+        };
+
+        let metrics = collect_remote_metrics(
+            self.transport.as_ref(),
+            &instance,
+            &parsed,
+            &control_path,
+            remote_port,
+        );
+        let previous = self
+            .metrics
+            .lock()
+            .expect("ssh metrics mutex")
+            .insert(id.to_string(), metrics.clone());
+        if metrics.disk_low && !previous.is_some_and(|previous| previous.disk_low) {
+            self.append_log_with_level(
+                id,
+                "WARN",
+                format!(
+                    "Remote disk is low: {} MiB free on {}",
+                    metrics.disk_free_bytes.unwrap_or(0) / (1024 * 1024),
+                    metrics.disk_mount.as_deref().unwrap_or("home volume")
+                ),
+            );
+        }
+        app.emit_metrics(&metrics);
+    }
+
+    fn metrics_snapshot(&self, id: Option<&str>) -> Vec<DesktopSshRemoteMetrics> {
+        let metrics = self.metrics.lock().expect("ssh metrics mutex");
+        let mut result: Vec<_> = metrics
+            .values()
+            .filter(|entry| id.is_none_or(|id| entry.id == id))
+            .cloned()
+            .collect();
+        result.sort_by(|a, b| a.id.cmp(&b.id));
+        result
+    }
+
+    fn matching_instance_ids(&self, selector: &DesktopSshSelector) -> Vec<String> {
+        read_desktop_ssh_instances_from_path(&self.settings_path)
+            .instances
+            .into_iter()
+            .filter(|instance| selector.matches(instance))
+            .map(|instance| instance.id)
+            .collect()
+    }
+
+    /// Connect `ids` with at most `concurrency` attempts in flight and wait
+    /// for every attempt to settle.
+    async fn connect_many(
+        self: &Arc<Self>,
+        app: Arc<dyn SshManagerEvents>,
+        ids: &[String],
+        concurrency: usize,
+    ) -> Vec<DesktopSshInstanceStatus> {
+        let limit = concurrency.clamp(1, MAX_BULK_CONCURRENCY);
+        let semaphore = Arc::new(Semaphore::new(limit));
+        for id in ids {
+            let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                break;
+            };
+            self.clear_retry_attempt(id);
+            if let Err(err) =
+                self.start_connect_with_permit(Arc::clone(&app), id.clone(), Some(permit))
+            {
+                self.append_log_with_level(id, "ERROR", err);
+            }
+        }
+        // Every permit comes back once the last attempt has settled.
+        let _ = semaphore.acquire_many(limit as u32).await;
+        ids.iter()
+            .map(|id| self.status_snapshot_for_instance(id))
+            .collect()
+    }
+
+    /// Run blocking `work` for each id on the blocking pool, at most
+    /// `concurrency` at a time. Results keep the order of `ids`.
+    async fn run_many<T, F>(self: &Arc<Self>, ids: &[String], concurrency: usize, work: F) -> Vec<T>
+    where
+        T: Send + 'static,
+        F: Fn(&DesktopSshManagerInner, &str) -> T + Send + Sync + 'static,
+    {
+        let semaphore = Arc::new(Semaphore::new(concurrency.clamp(1, MAX_BULK_CONCURRENCY)));
+        let work = Arc::new(work);
+        let mut handles = Vec::new();
+        for id in ids {
+            let Ok(permit) = Arc::clone(&semaphore).acquire_owned().await else {
+                break;
+            };
+            let inner = Arc::clone(self);
+            let work = Arc::clone(&work);
+            let id = id.clone();
+            handles.push(tauri::async_runtime::spawn_blocking(move || {
+                let result = work(inner.as_ref(), &id);
+                drop(permit);
+                result
+            }));
+        }
+
+        let mut results = Vec::new();
+        for handle in handles {
+            if let Ok(result) = handle.await {
+                results.push(result);
+            }
+        }
+        results
+    }
+
+    async fn disconnect_many(
+        self: &Arc<Self>,
+        app: Arc<dyn SshManagerEvents>,
+        ids: &[String],
+        concurrency: usize,
+    ) -> Vec<DesktopSshInstanceStatus> {
+        self.run_many(ids, concurrency, move |inner, id| {
+            inner.disconnect_internal(app.as_ref(), id, true);
+            inner.status_snapshot_for_instance(id)
+        })
+        .await
+    }
+
+    /// Status snapshots plus a live ControlMaster check per instance.
+    async fn check_many(
+        self: &Arc<Self>,
+        ids: &[String],
+        concurrency: usize,
+    ) -> Vec<DesktopSshBulkStatus> {
+        self.run_many(ids, concurrency, |inner, id| DesktopSshBulkStatus {
+            session_alive: inner.session_is_alive(id),
+            status: inner.status_snapshot_for_instance(id),
+        })
+        .await
+    }
+
+    fn statuses_with_defaults(&self) -> Vec<DesktopSshInstanceStatus> {
+        let config = read_desktop_ssh_instances_from_path(&self.settings_path);
+        let statuses = self.statuses.lock().expect("ssh status mutex");
+        let mut result = Vec::new();
+
+        for instance in config.instances {
+            result.push(
+                statuses
+                    .get(&instance.id)
+                    .cloned()
+                    .unwrap_or_else(|| DesktopSshInstanceStatus::idle(instance.id)),
+            );
+        }
+
+        result.sort_by(|a, b| a.id.cmp(&b.id));
+        result
+    }
+
+    /// Agent keys plus the IdentityFile candidates `ssh -G` resolves for a
+    /// saved instance or an unsaved command.
+    fn list_identities(
+        &self,
+        id: Option<&str>,
+        ssh_command: Option<&str>,
+    ) -> Result<DesktopSshIdentityList> {
+        let parsed = match (ssh_command, id) {
+            (Some(command), _) => parse_ssh_command(command)?,
+            (None, Some(id)) => {
+                let config = read_desktop_ssh_instances_from_path(&self.settings_path);
+                let instance = config
+                    .instances
+                    .into_iter()
+                    .find(|item| item.id == id)
+                    .ok_or_else(|| anyhow!("SSH instance not found"))?;
+                match instance.ssh_parsed {
+                    Some(parsed) => parsed,
+                    None => parse_ssh_command(&instance.ssh_command)?,
+                }
+            }
+            (None, None) => DesktopSshParsedCommand {
+                destination: "localhost".to_string(),
+                args: Vec::new(),
+            },
+        };
+
+        let mut list = DesktopSshIdentityList::default();
+        match self.transport.agent_identities() {
+            Ok(keys) => {
+                list.identities
+                    .extend(keys.into_iter().map(|key| DesktopSshIdentityCandidate {
+                        source: DesktopSshIdentitySource::Agent,
+                        label: if key.comment.is_empty() {
+                            key.fingerprint.clone()
+                        } else {
+                            key.comment
+                        },
+                        value: key.fingerprint,
+                        key_type: key.key_type,
+                        bits: key.bits,
+                        available: true,
+                    }))
+            }
+            Err(err) => list.agent_error = Some(err.to_string()),
+        }
+
+        let resolved = self.transport.resolve_config(&parsed)?;
+        let mut seen = HashSet::new();
+        for file in resolved.get("identityfile").into_iter().flatten() {
+            if !seen.insert(file.clone()) {
+                continue;
+            }
+            list.identities.push(DesktopSshIdentityCandidate {
+                source: DesktopSshIdentitySource::File,
+                value: file.clone(),
+                label: file.clone(),
+                key_type: None,
+                bits: None,
+                available: expand_home_path(file).is_file(),
+            });
+        }
+        Ok(list)
+    }
+}
+
+#[tauri::command]
+pub fn desktop_ssh_logs(
+    state: State<'_, DesktopSshManagerState>,
+    id: String,
+    limit: Option<usize>,
+) -> Result<Vec<String>, String> {
+    let id = id.trim().to_string();
+    if id.is_empty() || id == LOCAL_HOST_ID {
         return Err("SSH instance id is required".to_string());
     }
     let cap = limit.unwrap_or(200).min(MAX_LOG_LINES_PER_INSTANCE);
@@ -2731,7 +4323,103 @@ pub fn desktop_ssh_logs_clear(
     Ok(())
 }
 
+/// `ssh-add` and `ssh -G` can hang on a stuck agent, so the probes run on a
+/// blocking thread.
+#[tauri::command]
+pub async fn desktop_ssh_list_identities(
+    state: State<'_, DesktopSshManagerState>,
+    id: Option<String>,
+    ssh_command: Option<String>,
+) -> Result<DesktopSshIdentityList, String> {
+    let id = id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let ssh_command = ssh_command
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    let inner = Arc::clone(&state.inner);
+    tauri::async_runtime::spawn_blocking(move || {
+        inner.list_identities(id.as_deref(), ssh_command.as_deref())
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())
+}
+
 impl DesktopSshManagerState {
+    /// Connect every instance in the configured auto-connect groups.
+    pub fn auto_connect_groups(&self, app: &AppHandle) {
+        let config = read_desktop_ssh_instances_from_path(&self.inner.settings_path);
+        let groups = config.auto_connect_groups.unwrap_or_default();
+        let ids: Vec<String> = config
+            .instances
+            .into_iter()
+            .filter(|instance| {
+                instance.group.as_deref().is_some_and(|group| {
+                    groups
+                        .iter()
+                        .any(|candidate| candidate.eq_ignore_ascii_case(group))
+                })
+            })
+            .map(|instance| instance.id)
+            .collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        log::info!(
+            "[desktop:ssh] auto-connecting {} instance(s) from groups: {}",
+            ids.len(),
+            groups.join(", ")
+        );
+        let inner = Arc::clone(&self.inner);
+        let app: Arc<dyn SshManagerEvents> = Arc::new(app.clone());
+        tauri::async_runtime::spawn(async move {
+            inner
+                .connect_many(app, &ids, DEFAULT_BULK_CONCURRENCY)
+                .await;
+        });
+    }
+
+    pub fn statuses(&self) -> Vec<DesktopSshInstanceStatus> {
+        self.inner.statuses_with_defaults()
+    }
+
+    pub fn has_instance(&self, id: &str) -> bool {
+        read_desktop_ssh_instances_from_path(&self.inner.settings_path)
+            .instances
+            .iter()
+            .any(|instance| instance.id == id)
+    }
+
+    /// Connect `id`, or reuse its ready session, and return the local URL.
+    pub async fn connect_and_wait(&self, app: &AppHandle, id: &str) -> Result<String> {
+        if !self.has_instance(id) {
+            return Err(anyhow!("SSH instance not found"));
+        }
+        let current = self.inner.status_snapshot_for_instance(id);
+        if matches!(current.phase, DesktopSshPhase::Ready) {
+            if let Some(url) = current.local_url {
+                return Ok(url);
+            }
+        }
+
+        let inner = Arc::clone(&self.inner);
+        let events: Arc<dyn SshManagerEvents> = Arc::new(app.clone());
+        let status = inner
+            .connect_many(events, &[id.to_string()], 1)
+            .await
+            .into_iter()
+            .next()
+            .unwrap_or_else(|| DesktopSshInstanceStatus::idle(id));
+        match (status.phase, status.local_url) {
+            (DesktopSshPhase::Ready, Some(url)) => Ok(url),
+            _ => Err(anyhow!(status
+                .detail
+                .unwrap_or_else(|| "SSH connection failed".to_string()))),
+        }
+    }
+
     pub fn shutdown_all(&self, app: &AppHandle) {
         let ids: Vec<String> = self
             .inner
@@ -2783,6 +4471,43 @@ pub fn desktop_ssh_instances_set(config: DesktopSshInstancesConfig) -> Result<()
         .map_err(|err| err.to_string())
 }
 
+#[tauri::command]
+pub fn desktop_ssh_instances_export(
+    app: AppHandle,
+    path: String,
+    ids: Option<Vec<String>>,
+) -> Result<usize, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Export path is required".to_string());
+    }
+    export_instances_to_path(
+        &settings_file_path(),
+        Path::new(path),
+        ids.as_deref(),
+        &app.app_version(),
+    )
+    .map_err(|err| err.to_string())
+}
+
+/// Preview an import; pass `apply` to write the accepted instances.
+#[tauri::command]
+pub fn desktop_ssh_instances_import(
+    path: String,
+    apply: Option<bool>,
+) -> Result<DesktopSshImportPreview, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Import path is required".to_string());
+    }
+    import_instances_from_path(
+        &settings_file_path(),
+        Path::new(path),
+        apply.unwrap_or(false),
+    )
+    .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub fn desktop_ssh_import_hosts() -> Result<Vec<DesktopSshImportCandidate>, String> {
     let mut candidates = Vec::new();
@@ -2812,7 +4537,8 @@ pub fn desktop_ssh_connect(
     if id.is_empty() || id == LOCAL_HOST_ID {
         return Err("SSH instance id is required".to_string());
     }
-    state.inner.start_connect(app, id)
+    state.inner.clear_retry_attempt(&id);
+    state.inner.start_connect(Arc::new(app), id)
 }
 
 #[tauri::command]
@@ -2829,6 +4555,17 @@ pub fn desktop_ssh_disconnect(
     Ok(())
 }
 
+#[tauri::command]
+pub fn desktop_ssh_metrics(
+    state: State<'_, DesktopSshManagerState>,
+    id: Option<String>,
+) -> Result<Vec<DesktopSshRemoteMetrics>, String> {
+    let id = id
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty());
+    Ok(state.inner.metrics_snapshot(id.as_deref()))
+}
+
 #[tauri::command]
 pub fn desktop_ssh_status(
     state: State<'_, DesktopSshManagerState>,
@@ -2844,9 +4581,126 @@ pub fn desktop_ssh_status(
     Ok(state.inner.statuses_with_defaults())
 }
 
+fn bulk_targets(
+    state: &DesktopSshManagerState,
+    selector: &DesktopSshSelector,
+) -> Result<Vec<String>, String> {
+    let selector = selector
+        .normalized()
+        .ok_or_else(|| "A tag or group is required".to_string())?;
+    Ok(state.inner.matching_instance_ids(&selector))
+}
+
+#[tauri::command]
+pub async fn desktop_ssh_bulk_connect(
+    app: AppHandle,
+    state: State<'_, DesktopSshManagerState>,
+    selector: DesktopSshSelector,
+    concurrency: Option<usize>,
+) -> Result<Vec<DesktopSshInstanceStatus>, String> {
+    let ids = bulk_targets(&state, &selector)?;
+    Ok(state
+        .inner
+        .connect_many(
+            Arc::new(app),
+            &ids,
+            concurrency.unwrap_or(DEFAULT_BULK_CONCURRENCY),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn desktop_ssh_bulk_disconnect(
+    app: AppHandle,
+    state: State<'_, DesktopSshManagerState>,
+    selector: DesktopSshSelector,
+    concurrency: Option<usize>,
+) -> Result<Vec<DesktopSshInstanceStatus>, String> {
+    let ids = bulk_targets(&state, &selector)?;
+    Ok(state
+        .inner
+        .disconnect_many(
+            Arc::new(app),
+            &ids,
+            concurrency.unwrap_or(DEFAULT_BULK_CONCURRENCY),
+        )
+        .await)
+}
+
+#[tauri::command]
+pub async fn desktop_ssh_bulk_status(
+    state: State<'_, DesktopSshManagerState>,
+    selector: DesktopSshSelector,
+    concurrency: Option<usize>,
+) -> Result<Vec<DesktopSshBulkStatus>, String> {
+    let ids = bulk_targets(&state, &selector)?;
+    Ok(state
+        .inner
+        .check_many(&ids, concurrency.unwrap_or(DEFAULT_BULK_CONCURRENCY))
+        .await)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ssh_transport::{fake::FakeSshTransport, SshAgentIdentity};
+
+    #[derive(Default)]
+    struct RecordingEvents {
+        statuses: Mutex<Vec<DesktopSshInstanceStatus>>,
+        metrics: Mutex<Vec<DesktopSshRemoteMetrics>>,
+    }
+
+    impl RecordingEvents {
+        fn phases(&self) -> Vec<DesktopSshPhase> {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .iter()
+                .map(|status| status.phase.clone())
+                .collect()
+        }
+
+        fn last(&self) -> DesktopSshInstanceStatus {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .last()
+                .cloned()
+                .expect("status emitted")
+        }
+    }
+
+    impl SshManagerEvents for RecordingEvents {
+        fn app_version(&self) -> String {
+            "1.9.10".to_string()
+        }
+
+        fn emit_status(&self, status: &DesktopSshInstanceStatus) {
+            self.statuses
+                .lock()
+                .expect("events mutex")
+                .push(status.clone());
+        }
+
+        fn emit_metrics(&self, metrics: &DesktopSshRemoteMetrics) {
+            self.metrics
+                .lock()
+                .expect("events mutex")
+                .push(metrics.clone());
+        }
+    }
+
+    fn fake_manager(name: &str) -> (Arc<FakeSshTransport>, DesktopSshManagerInner) {
+        let dir = std::env::temp_dir().join(format!("openchamber-ssh-{name}-{}", now_millis()));
+        fs::create_dir_all(&dir).expect("create temp settings dir");
+        let transport = Arc::new(FakeSshTransport::default());
+        let manager = DesktopSshManagerInner::with_transport(
+            Arc::clone(&transport) as Arc<dyn SshTransport>,
+            dir.join("settings.json"),
+        );
+        (transport, manager)
+    }
 
     fn sample_instance(id: &str, command: &str) -> DesktopSshInstance {
         DesktopSshInstance {
@@ -2859,6 +4713,18 @@ mod tests {
             local_forward: DesktopSshLocalForwardConfig::default(),
             auth: DesktopSshAuthConfig::default(),
             port_forwards: Vec::new(),
+            jump_hosts: Vec::new(),
+            identity: None,
+            tags: Vec::new(),
+            group: None,
+            multiplex: DesktopSshMultiplexConfig::default(),
+        }
+    }
+
+    fn jump_host(host: &str) -> DesktopSshJumpHost {
+        DesktopSshJumpHost {
+            host: host.to_string(),
+            ..DesktopSshJumpHost::default()
         }
     }
 
@@ -2999,4 +4865,1201 @@ mod tests {
         assert!(!is_liveness_http_status(500));
         assert!(!is_liveness_http_status(0));
     }
+
+    #[test]
+    fn connect_over_fake_transport_reaches_ready() {
+        let (transport, manager) = fake_manager("ready");
+        transport.respond("openchamber --version", Ok("openchamber 1.9.10\n"));
+        let events = RecordingEvents::default();
+
+        manager
+            .connect_blocking(
+                &events,
+                sample_instance("ssh-ready", "ssh user@example.com"),
+            )
+            .expect("connect");
+
+        assert_eq!(
+            events.phases(),
+            vec![
+                DesktopSshPhase::ConfigResolved,
+                DesktopSshPhase::AuthCheck,
+                DesktopSshPhase::MasterConnecting,
+                DesktopSshPhase::RemoteProbe,
+                DesktopSshPhase::RemoteProbe,
+                DesktopSshPhase::ServerDetecting,
+                DesktopSshPhase::ServerStarting,
+                DesktopSshPhase::Forwarding,
+                DesktopSshPhase::Ready,
+            ]
+        );
+        let ready = events.last();
+        assert!(ready.started_by_us);
+        assert!(ready.local_url.is_some());
+        assert!(transport
+            .commands()
+            .iter()
+            .any(|command| command.contains("openchamber serve --daemon")));
+        assert!(manager.session_is_alive("ssh-ready"));
+
+        manager.teardown_session("ssh-ready");
+        assert!(!manager.session_is_alive("ssh-ready"));
+    }
+
+    #[test]
+    fn connect_installs_openchamber_when_missing() {
+        let (transport, manager) = fake_manager("install");
+        let events = RecordingEvents::default();
+
+        manager
+            .connect_blocking(
+                &events,
+                sample_instance("ssh-install", "ssh user@example.com"),
+            )
+            .expect("connect");
+
+        assert!(events.phases().contains(&DesktopSshPhase::Installing));
+        assert!(transport
+            .commands()
+            .iter()
+            .any(|command| command == "bun add -g @openchamber/web@1.9.10"));
+        manager.teardown_session("ssh-install");
+    }
+
+    #[test]
+    fn connect_reports_master_rejection() {
+        let (transport, manager) = fake_manager("rejected");
+        transport.fail_master("user@example.com: Permission denied (publickey).");
+        let events = RecordingEvents::default();
+
+        let err = manager
+            .connect_blocking(
+                &events,
+                sample_instance("ssh-rejected", "ssh user@example.com"),
+            )
+            .expect_err("master should fail");
+
+        assert!(err.to_string().contains("Permission denied"));
+        assert_eq!(events.last().phase, DesktopSshPhase::MasterConnecting);
+        assert!(transport.commands().is_empty());
+    }
+
+    #[test]
+    fn monitor_drop_degrades_then_errors_at_retry_limit() {
+        let (transport, manager) = fake_manager("monitor");
+        transport.respond("openchamber --version", Ok("openchamber 1.9.10\n"));
+        let events = RecordingEvents::default();
+        manager
+            .connect_blocking(
+                &events,
+                sample_instance("ssh-monitor", "ssh user@example.com"),
+            )
+            .expect("connect");
+        assert!(matches!(
+            manager.monitor_tick("ssh-monitor"),
+            MonitorTick::Healthy
+        ));
+
+        transport.drop_main_forward();
+        let MonitorTick::Dropped(reason) = manager.monitor_tick("ssh-monitor") else {
+            panic!("expected dropped tick");
+        };
+        assert!(reason.starts_with("Main SSH forward exited"));
+
+        assert!(manager
+            .begin_reconnect(&events, "ssh-monitor", &reason)
+            .is_some());
+        let degraded = events.last();
+        assert_eq!(degraded.phase, DesktopSshPhase::Degraded);
+        assert_eq!(degraded.retry_attempt, 1);
+        assert!(matches!(
+            manager.monitor_tick("ssh-monitor"),
+            MonitorTick::SessionGone
+        ));
+
+        manager
+            .reconnect_attempts
+            .lock()
+            .expect("ssh retry mutex")
+            .insert("ssh-monitor".to_string(), DEFAULT_RECONNECT_MAX_ATTEMPTS);
+        assert!(manager
+            .begin_reconnect(&events, "ssh-monitor", &reason)
+            .is_none());
+        let failed = events.last();
+        assert_eq!(failed.phase, DesktopSshPhase::Error);
+        assert!(failed.requires_user_action);
+    }
+
+    #[test]
+    fn teardown_cancels_extra_forwards() {
+        let (transport, manager) = fake_manager("forwards");
+        transport.respond("openchamber --version", Ok("openchamber 1.9.10\n"));
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-forwards", "ssh user@example.com");
+        instance.port_forwards.push(DesktopSshPortForward {
+            id: "socks".to_string(),
+            enabled: true,
+            forward_type: DesktopSshPortForwardType::Dynamic,
+            local_host: None,
+            local_port: Some(1080),
+            remote_host: None,
+            remote_port: None,
+        });
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        assert!(transport
+            .forwards()
+            .contains(&"-D 127.0.0.1:1080".to_string()));
+
+        manager.teardown_session("ssh-forwards");
+        assert_eq!(
+            transport.cancelled_forwards(),
+            vec!["-D 127.0.0.1:1080".to_string()]
+        );
+    }
+
+    #[test]
+    fn jump_hosts_render_chained_aliases() {
+        let hops = vec![
+            DesktopSshJumpHost {
+                host: "bastion1.example.com".to_string(),
+                user: Some("alice".to_string()),
+                port: Some(2222),
+                identity_file: Some("~/.ssh/bastion".to_string()),
+                ssh_password: None,
+            },
+            jump_host("bastion2.internal"),
+        ];
+
+        let config = render_jump_hosts_config(&hops, None);
+        assert!(config.contains(
+            "Host openchamber-jump-1\n  HostName bastion1.example.com\n  User alice\n  Port 2222\n  IdentityFile \"~/.ssh/bastion\"\n  IdentitiesOnly yes\n"
+        ));
+        assert!(config.contains(
+            "Host openchamber-jump-2\n  HostName bastion2.internal\n  ProxyJump openchamber-jump-1\n"
+        ));
+        assert!(
+            config.ends_with("Match all\n  Include ~/.ssh/config\n  Include /etc/ssh/ssh_config\n")
+        );
+
+        let dir = std::env::temp_dir().join(format!("openchamber-ssh-jump-{}", now_millis()));
+        fs::create_dir_all(&dir).expect("create session dir");
+        let parsed =
+            parse_ssh_command("ssh -F /tmp/custom.conf -p 2200 user@target").expect("parsed");
+        let applied = apply_jump_hosts(&parsed, &hops, &dir).expect("applied");
+        assert_eq!(
+            applied.args,
+            vec![
+                "-F".to_string(),
+                dir.join(JUMP_HOSTS_CONFIG_FILE).display().to_string(),
+                "-J".to_string(),
+                "openchamber-jump-2".to_string(),
+                "-p".to_string(),
+                "2200".to_string(),
+            ]
+        );
+        let written = fs::read_to_string(dir.join(JUMP_HOSTS_CONFIG_FILE)).expect("config");
+        assert!(written.contains("Include \"/tmp/custom.conf\""));
+    }
+
+    #[test]
+    fn sanitize_instance_validates_jump_hosts() {
+        let mut instance = sample_instance("ssh-jump", "ssh user@target");
+        instance.jump_hosts = vec![jump_host("  bastion1  "), jump_host(" ")];
+        let sanitized = sanitize_instance(instance).expect("sanitized");
+        assert_eq!(sanitized.jump_hosts.len(), 1);
+        assert_eq!(sanitized.jump_hosts[0].host, "bastion1");
+
+        let mut inline = sample_instance("ssh-jump", "ssh -J other user@target");
+        inline.jump_hosts = vec![jump_host("bastion1")];
+        assert!(sanitize_instance(inline).is_err());
+
+        let mut injected = sample_instance("ssh-jump", "ssh user@target");
+        injected.jump_hosts = vec![jump_host("bastion1,evil")];
+        assert!(sanitize_instance(injected).is_err());
+    }
+
+    #[test]
+    fn failed_jump_host_index_prefers_later_hops() {
+        let hops = vec![jump_host("bastion1"), jump_host("bastion10")];
+        assert_eq!(
+            failed_jump_host_index("alice@bastion10: Permission denied (publickey).", &hops),
+            Some(1)
+        );
+        assert_eq!(
+            failed_jump_host_index(
+                "ssh: Could not resolve hostname bastion1: Name or service not known",
+                &hops
+            ),
+            Some(0)
+        );
+        assert_eq!(
+            failed_jump_host_index("user@target: Permission denied (publickey).", &hops),
+            None
+        );
+    }
+
+    #[test]
+    fn connect_failure_reports_failing_jump_host() {
+        let (transport, manager) = fake_manager("jump-failure");
+        transport.fail_master("bob@bastion2: Permission denied (publickey).");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-jump-failure", "ssh user@target");
+        instance.jump_hosts = vec![jump_host("bastion1"), jump_host("bastion2")];
+
+        let err = manager
+            .connect_blocking(&events, instance)
+            .expect_err("master should fail");
+        manager.report_connect_failure(&events, "ssh-jump-failure", &err);
+
+        let status = events.last();
+        assert_eq!(status.phase, DesktopSshPhase::Error);
+        assert_eq!(status.failed_jump_host, Some(1));
+        assert!(status
+            .detail
+            .unwrap_or_default()
+            .starts_with("Jump host 2 (bastion2) failed"));
+    }
+
+    #[test]
+    fn askpass_env_includes_per_hop_secrets() {
+        let mut instance = sample_instance("ssh-askpass", "ssh user@target");
+        instance.jump_hosts = vec![
+            jump_host("bastion1"),
+            DesktopSshJumpHost {
+                host: "bastion2".to_string(),
+                identity_file: Some("~/.ssh/bastion2".to_string()),
+                ssh_password: Some(DesktopSshStoredSecret {
+                    enabled: true,
+                    value: Some("hunter2".to_string()),
+                    store: DesktopSshSecretStore::Settings,
+                }),
+                ..DesktopSshJumpHost::default()
+            },
+        ];
+
+        let parsed = parse_ssh_command(&instance.ssh_command).expect("parse");
+        let env: HashMap<String, String> = askpass_env_for_instance(&instance, &parsed)
+            .into_iter()
+            .collect();
+        assert_eq!(
+            env.get("OPENCHAMBER_SSH_ASKPASS_HOP_COUNT")
+                .map(String::as_str),
+            Some("2")
+        );
+        assert!(!env.contains_key("OPENCHAMBER_SSH_ASKPASS_HOP_1_VALUE"));
+        assert_eq!(
+            env.get("OPENCHAMBER_SSH_ASKPASS_HOP_2_HOST")
+                .map(String::as_str),
+            Some("bastion2")
+        );
+        assert_eq!(
+            env.get("OPENCHAMBER_SSH_ASKPASS_HOP_2_KEY")
+                .map(String::as_str),
+            Some("/.ssh/bastion2")
+        );
+        assert_eq!(
+            env.get("OPENCHAMBER_SSH_ASKPASS_HOP_2_VALUE")
+                .map(String::as_str),
+            Some("hunter2")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn askpass_script_keeps_target_secret_from_hops_without_one() {
+        let dir = std::env::temp_dir().join(format!("openchamber-askpass-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir");
+        let script = dir.join("askpass.sh");
+        write_askpass_script(&script).expect("script");
+
+        let mut instance = sample_instance("ssh-askpass-hop", "ssh user@target");
+        instance.jump_hosts = vec![jump_host("bastion1")];
+        instance.auth.ssh_password = Some(DesktopSshStoredSecret {
+            enabled: true,
+            value: Some("target-secret".to_string()),
+            store: DesktopSshSecretStore::Settings,
+        });
+        let parsed = parse_ssh_command(&instance.ssh_command).expect("parse");
+        let env = askpass_env_for_instance(&instance, &parsed);
+        let answer = |prompt: &str| {
+            let output = std::process::Command::new("bash")
+                .arg(&script)
+                .arg(prompt)
+                .env_clear()
+                .envs(env.iter().cloned())
+                .output()
+                .expect("run askpass");
+            String::from_utf8_lossy(&output.stdout).to_string()
+        };
+
+        assert_eq!(answer("user@bastion1's password: "), "");
+        assert_eq!(
+            answer("Enter passphrase for key '/home/user/.ssh/id_ed25519': "),
+            ""
+        );
+        assert_eq!(answer("user@target's password: "), "target-secret\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn agent_key(fingerprint: &str, comment: &str) -> SshAgentIdentity {
+        SshAgentIdentity {
+            fingerprint: fingerprint.to_string(),
+            bits: Some(256),
+            key_type: Some("ED25519".to_string()),
+            comment: comment.to_string(),
+            public_key: format!("ssh-ed25519 AAAAC3Nza {comment}"),
+        }
+    }
+
+    #[test]
+    fn list_identities_merges_agent_keys_and_config_files() {
+        let (transport, manager) = fake_manager("list-identities");
+        transport.set_agent_identities(vec![agent_key("SHA256:abc", "me@laptop")]);
+        let existing = manager
+            .settings_path
+            .parent()
+            .expect("settings dir")
+            .join("id_test");
+        fs::write(&existing, "key").expect("write key");
+        let existing = existing.display().to_string();
+        transport.set_identity_files(&[&existing, "/nonexistent/id_rsa", &existing]);
+
+        let list = manager
+            .list_identities(None, Some("ssh user@example.com"))
+            .expect("identities");
+        assert_eq!(list.agent_error, None);
+        assert_eq!(list.identities.len(), 3);
+        assert_eq!(list.identities[0].source, DesktopSshIdentitySource::Agent);
+        assert_eq!(list.identities[0].value, "SHA256:abc");
+        assert_eq!(list.identities[0].label, "me@laptop");
+        assert_eq!(list.identities[1].source, DesktopSshIdentitySource::File);
+        assert_eq!(list.identities[1].value, existing);
+        assert!(list.identities[1].available);
+        assert!(!list.identities[2].available);
+    }
+
+    #[test]
+    fn apply_identity_pins_agent_key_and_file() {
+        let (transport, manager) = fake_manager("apply-identity");
+        transport.set_agent_identities(vec![agent_key("SHA256:abc", "me@laptop")]);
+        let dir = manager
+            .settings_path
+            .parent()
+            .expect("settings dir")
+            .to_path_buf();
+        let parsed = parse_ssh_command("ssh user@example.com").expect("parsed");
+
+        let agent = DesktopSshIdentityChoice {
+            source: DesktopSshIdentitySource::Agent,
+            value: "SHA256:abc".to_string(),
+            identities_only: true,
+        };
+        let applied =
+            apply_identity(transport.as_ref(), &parsed, Some(&agent), &dir).expect("agent");
+        let pub_path = dir.join(AGENT_IDENTITY_FILE);
+        assert_eq!(
+            applied.args,
+            vec![
+                "-i".to_string(),
+                pub_path.display().to_string(),
+                "-o".to_string(),
+                "IdentitiesOnly=yes".to_string(),
+            ]
+        );
+        assert_eq!(
+            fs::read_to_string(&pub_path).expect("pub key"),
+            "ssh-ed25519 AAAAC3Nza me@laptop\n"
+        );
+
+        let missing = DesktopSshIdentityChoice {
+            value: "SHA256:other".to_string(),
+            ..agent
+        };
+        assert!(apply_identity(transport.as_ref(), &parsed, Some(&missing), &dir).is_err());
+
+        let key_path = dir.join("id_file");
+        fs::write(&key_path, "key").expect("write key");
+        let file = DesktopSshIdentityChoice {
+            source: DesktopSshIdentitySource::File,
+            value: key_path.display().to_string(),
+            identities_only: false,
+        };
+        let applied = apply_identity(transport.as_ref(), &parsed, Some(&file), &dir).expect("file");
+        assert_eq!(
+            applied.args,
+            vec!["-i".to_string(), key_path.display().to_string()]
+        );
+    }
+
+    #[test]
+    fn sanitize_instance_rejects_inline_identity_with_choice() {
+        let mut instance = sample_instance("ssh-identity", "ssh -i ~/.ssh/id_rsa user@example.com");
+        instance.identity = Some(DesktopSshIdentityChoice {
+            source: DesktopSshIdentitySource::File,
+            value: "~/.ssh/id_ed25519".to_string(),
+            identities_only: true,
+        });
+        assert!(sanitize_instance(instance.clone()).is_err());
+
+        instance.ssh_command = "ssh user@example.com".to_string();
+        let sanitized = sanitize_instance(instance.clone()).expect("sanitized");
+        assert!(sanitized.identity.is_some());
+
+        instance.identity.as_mut().expect("identity").value = "  ".to_string();
+        let sanitized = sanitize_instance(instance).expect("sanitized");
+        assert!(sanitized.identity.is_none());
+    }
+
+    #[test]
+    fn connect_failure_names_rejected_identity() {
+        let (transport, manager) = fake_manager("identity-rejected");
+        transport.set_agent_identities(vec![agent_key("SHA256:abc", "me@laptop")]);
+        transport.fail_master("user@example.com: Permission denied (publickey).");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-identity-rejected", "ssh user@example.com");
+        instance.identity = Some(DesktopSshIdentityChoice {
+            source: DesktopSshIdentitySource::Agent,
+            value: "SHA256:abc".to_string(),
+            identities_only: true,
+        });
+
+        let err = manager
+            .connect_blocking(&events, instance)
+            .expect_err("master should fail");
+        assert!(err
+            .to_string()
+            .starts_with("SSH identity rejected: agent key SHA256:abc was not accepted by"));
+    }
+
+    #[test]
+    fn selector_matches_tags_and_group_case_insensitively() {
+        let mut instance = sample_instance("ssh-gpu-1", "ssh user@gpu1");
+        instance.tags = vec!["GPU".to_string(), "cuda".to_string()];
+        instance.group = Some("gpu-boxes".to_string());
+
+        let by_tag = DesktopSshSelector {
+            tag: Some(" gpu ".to_string()),
+            group: None,
+        }
+        .normalized()
+        .expect("selector");
+        assert!(by_tag.matches(&instance));
+
+        let both = DesktopSshSelector {
+            tag: Some("cuda".to_string()),
+            group: Some("staging".to_string()),
+        };
+        assert!(!both.matches(&instance));
+
+        assert!(DesktopSshSelector {
+            tag: Some("  ".to_string()),
+            group: None,
+        }
+        .normalized()
+        .is_none());
+    }
+
+    #[test]
+    fn instances_write_keeps_auto_connect_groups_when_omitted() {
+        let (_transport, manager) = fake_manager("auto-connect-groups");
+        let mut instance = sample_instance("ssh-staging", "ssh user@staging");
+        instance.tags = vec![" web ".to_string(), "WEB".to_string(), String::new()];
+        instance.group = Some(" staging ".to_string());
+
+        let written = write_desktop_ssh_instances_to_path(
+            &manager.settings_path,
+            DesktopSshInstancesConfig {
+                instances: vec![instance.clone()],
+                auto_connect_groups: Some(vec!["staging".to_string(), "Staging".to_string()]),
+            },
+        )
+        .expect("write");
+        assert_eq!(written.instances[0].tags, vec!["web".to_string()]);
+        assert_eq!(written.instances[0].group.as_deref(), Some("staging"));
+        assert_eq!(
+            written.auto_connect_groups,
+            Some(vec!["staging".to_string()])
+        );
+
+        write_desktop_ssh_instances_to_path(
+            &manager.settings_path,
+            DesktopSshInstancesConfig {
+                instances: vec![instance],
+                auto_connect_groups: None,
+            },
+        )
+        .expect("rewrite");
+        let read = read_desktop_ssh_instances_from_path(&manager.settings_path);
+        assert_eq!(read.auto_connect_groups, Some(vec!["staging".to_string()]));
+    }
+
+    #[test]
+    fn bulk_connect_and_disconnect_follow_selector() {
+        let (_transport, manager) = fake_manager("bulk-connect");
+        let manager = Arc::new(manager);
+        let mut instances = Vec::new();
+        for (id, group) in [("ssh-a", "staging"), ("ssh-b", "staging"), ("ssh-c", "gpu")] {
+            let mut instance = sample_instance(id, &format!("ssh user@{id}"));
+            instance.group = Some(group.to_string());
+            instances.push(instance);
+        }
+        write_desktop_ssh_instances_to_path(
+            &manager.settings_path,
+            DesktopSshInstancesConfig {
+                instances,
+                auto_connect_groups: None,
+            },
+        )
+        .expect("write");
+
+        let selector = DesktopSshSelector {
+            tag: None,
+            group: Some("staging".to_string()),
+        };
+        let ids = manager.matching_instance_ids(&selector);
+        assert_eq!(ids, vec!["ssh-a".to_string(), "ssh-b".to_string()]);
+
+        let events: Arc<dyn SshManagerEvents> = Arc::new(RecordingEvents::default());
+        let connected =
+            tauri::async_runtime::block_on(manager.connect_many(Arc::clone(&events), &ids, 1));
+        assert!(connected
+            .iter()
+            .all(|status| status.phase == DesktopSshPhase::Ready));
+
+        let checked = tauri::async_runtime::block_on(manager.check_many(&ids, 2));
+        assert!(checked.iter().all(|entry| entry.session_alive));
+
+        let disconnected = tauri::async_runtime::block_on(manager.disconnect_many(events, &ids, 2));
+        assert!(disconnected
+            .iter()
+            .all(|status| status.phase == DesktopSshPhase::Idle));
+        assert_eq!(
+            manager.status_snapshot_for_instance("ssh-c").phase,
+            DesktopSshPhase::Idle
+        );
+    }
+
+    fn secret(value: &str) -> Option<DesktopSshStoredSecret> {
+        Some(DesktopSshStoredSecret {
+            enabled: true,
+            value: Some(value.to_string()),
+            store: DesktopSshSecretStore::Settings,
+        })
+    }
+
+    #[test]
+    fn export_bundle_strips_secrets() {
+        let (_transport, manager) = fake_manager("export-bundle");
+        let mut instance = sample_instance("ssh-team", "ssh user@team");
+        instance.auth.ssh_password = secret("hunter2");
+        instance.jump_hosts = vec![DesktopSshJumpHost {
+            ssh_password: secret("bastion-secret"),
+            ..jump_host("bastion")
+        }];
+        let other = sample_instance("ssh-private", "ssh user@private");
+        write_desktop_ssh_instances_to_path(
+            &manager.settings_path,
+            DesktopSshInstancesConfig {
+                instances: vec![instance, other],
+                auto_connect_groups: None,
+            },
+        )
+        .expect("write");
+
+        let bundle_path = manager.settings_path.with_file_name("bundle.json");
+        let count = export_instances_to_path(
+            &manager.settings_path,
+            &bundle_path,
+            Some(&["ssh-team".to_string()]),
+            "1.9.10",
+        )
+        .expect("export");
+        assert_eq!(count, 1);
+
+        let raw = fs::read_to_string(&bundle_path).expect("bundle");
+        assert!(!raw.contains("hunter2"));
+        assert!(!raw.contains("bastion-secret"));
+        let bundle = parse_instances_bundle(&raw).expect("parse");
+        assert_eq!(bundle.version, INSTANCES_BUNDLE_VERSION);
+        let auth = bundle.instances[0]
+            .auth
+            .ssh_password
+            .as_ref()
+            .expect("auth");
+        assert!(auth.enabled);
+        assert_eq!(auth.value, None);
+    }
+
+    #[test]
+    fn import_renames_ids_used_by_other_host_kinds() {
+        let (_transport, manager) = fake_manager("import-cross-kind");
+        fs::write(
+            &manager.settings_path,
+            serde_json::to_string(&json!({
+                "desktopDockerInstances": [{ "id": "box" }],
+                "desktopLocalProfiles": [{ "id": "work" }],
+            }))
+            .expect("json"),
+        )
+        .expect("settings");
+
+        let bundle = DesktopSshInstancesBundle {
+            format: INSTANCES_BUNDLE_FORMAT.to_string(),
+            version: INSTANCES_BUNDLE_VERSION,
+            exported_at_ms: 0,
+            app_version: None,
+            instances: vec![
+                sample_instance("box", "ssh user@box"),
+                sample_instance("work", "ssh user@work"),
+            ],
+        };
+        let bundle_path = manager.settings_path.with_file_name("import.json");
+        fs::write(&bundle_path, serde_json::to_string(&bundle).expect("json")).expect("bundle");
+
+        let preview = import_instances_from_path(&manager.settings_path, &bundle_path, false)
+            .expect("preview");
+        let actions: Vec<_> = preview
+            .entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.action.clone()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("box-2", DesktopSshImportAction::Rename),
+                ("work-2", DesktopSshImportAction::Rename),
+            ]
+        );
+        let applied =
+            import_instances_from_path(&manager.settings_path, &bundle_path, true).expect("apply");
+        assert!(applied.applied);
+    }
+
+    #[test]
+    fn import_remaps_colliding_ids_and_skips_invalid_entries() {
+        let (_transport, manager) = fake_manager("import-bundle");
+        write_desktop_ssh_instances_to_path(
+            &manager.settings_path,
+            DesktopSshInstancesConfig {
+                instances: vec![sample_instance("ssh-team", "ssh user@mine")],
+                auto_connect_groups: None,
+            },
+        )
+        .expect("write");
+
+        let mut leaked = sample_instance("ssh-new", "ssh user@new");
+        leaked.auth.ssh_password = secret("hunter2");
+        let bundle = DesktopSshInstancesBundle {
+            format: INSTANCES_BUNDLE_FORMAT.to_string(),
+            version: INSTANCES_BUNDLE_VERSION,
+            exported_at_ms: 0,
+            app_version: None,
+            instances: vec![
+                sample_instance("ssh-team", "ssh user@team"),
+                leaked,
+                sample_instance("ssh-bad", "ssh -o ProxyCommand=evil user@bad"),
+            ],
+        };
+        let bundle_path = manager.settings_path.with_file_name("import.json");
+        fs::write(&bundle_path, serde_json::to_string(&bundle).expect("json")).expect("bundle");
+
+        let preview = import_instances_from_path(&manager.settings_path, &bundle_path, false)
+            .expect("preview");
+        assert!(!preview.applied);
+        let actions: Vec<_> = preview
+            .entries
+            .iter()
+            .map(|entry| (entry.id.as_str(), entry.action.clone()))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("ssh-team-2", DesktopSshImportAction::Rename),
+                ("ssh-new", DesktopSshImportAction::Add),
+                ("ssh-bad", DesktopSshImportAction::Skip),
+            ]
+        );
+        assert!(preview.entries[2].error.is_some());
+        assert_eq!(
+            read_desktop_ssh_instances_from_path(&manager.settings_path)
+                .instances
+                .len(),
+            1
+        );
+
+        let applied =
+            import_instances_from_path(&manager.settings_path, &bundle_path, true).expect("apply");
+        assert!(applied.applied);
+        let config = read_desktop_ssh_instances_from_path(&manager.settings_path);
+        let ids: Vec<&str> = config
+            .instances
+            .iter()
+            .map(|instance| instance.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["ssh-team", "ssh-team-2", "ssh-new"]);
+        assert_eq!(
+            config.instances[2]
+                .auth
+                .ssh_password
+                .as_ref()
+                .and_then(|s| s.value.clone()),
+            None
+        );
+    }
+
+    #[test]
+    fn parse_bundle_rejects_unknown_format_and_newer_versions() {
+        assert!(parse_instances_bundle("{}").is_err());
+        let newer = json!({
+            "format": INSTANCES_BUNDLE_FORMAT,
+            "version": INSTANCES_BUNDLE_VERSION + 1,
+            "instances": [],
+        });
+        let err = parse_instances_bundle(&newer.to_string()).expect_err("newer version");
+        assert!(err
+            .to_string()
+            .contains("Unsupported instances bundle version"));
+    }
+
+    #[test]
+    fn parse_iso_utc_secs_handles_js_timestamps() {
+        assert_eq!(parse_iso_utc_secs("1970-01-01T00:00:00.000Z"), Some(0));
+        assert_eq!(
+            parse_iso_utc_secs("2024-02-29T12:30:15.250Z"),
+            Some(1_709_209_815)
+        );
+        assert_eq!(parse_iso_utc_secs("2024-02-29 12:30:15"), None);
+    }
+
+    #[test]
+    fn apply_remote_metrics_output_parses_linux_and_macos_lines() {
+        let mut metrics = DesktopSshRemoteMetrics::default();
+        let now = apply_remote_metrics_output(
+            &mut metrics,
+            "NOW=1700000000\nLOAD=0.52 0.40 0.33\nMEM_TOTAL_KB=16384000\nMEM_AVAILABLE_KB=8192000\nDISK=104857600 1048576 /home\nRSS_KB=204800\n",
+        );
+        assert_eq!(now, Some(1_700_000_000));
+        assert_eq!(metrics.load_average, Some([0.52, 0.40, 0.33]));
+        assert_eq!(metrics.mem_available_bytes, Some(8_192_000 * 1024));
+        assert_eq!(metrics.disk_mount.as_deref(), Some("/home"));
+        assert_eq!(metrics.disk_free_bytes, Some(1024 * 1024 * 1024));
+        assert!(metrics.disk_low);
+        assert_eq!(metrics.server_rss_bytes, Some(200 * 1024 * 1024));
+
+        let mut mac = DesktopSshRemoteMetrics::default();
+        apply_remote_metrics_output(&mut mac, "LOAD= 1.10 1.20 1.30 \nMEM_TOTAL_KB=0\n");
+        assert_eq!(mac.load_average, Some([1.10, 1.20, 1.30]));
+        assert_eq!(mac.mem_total_bytes, None);
+        assert!(!mac.disk_low);
+    }
+
+    #[test]
+    fn refresh_metrics_publishes_sample_for_live_session() {
+        let (transport, manager) = fake_manager("metrics");
+        let events = RecordingEvents::default();
+        manager
+            .connect_blocking(&events, sample_instance("ssh-metrics", "ssh user@metrics"))
+            .expect("connect");
+        transport.respond(
+            "INFO_STATUS",
+            Ok("INFO_STATUS=200\nAUTH_STATUS=0\nHEALTH_STATUS=200\n{\"pid\":4242,\"startedAt\":\"2023-11-14T22:00:00.000Z\"}"),
+        );
+        transport.respond(
+            "MEM_TOTAL_KB",
+            Ok("NOW=1700000000\nLOAD=0.10 0.20 0.30\nDISK=1000 500 /\nRSS_KB=1024\n"),
+        );
+
+        manager.refresh_metrics(&events, "ssh-metrics");
+
+        let snapshot = manager.metrics_snapshot(Some("ssh-metrics"));
+        assert_eq!(snapshot.len(), 1);
+        let metrics = &snapshot[0];
+        assert_eq!(metrics.server_pid, Some(4242));
+        assert_eq!(metrics.server_rss_bytes, Some(1024 * 1024));
+        assert_eq!(metrics.server_uptime_sec, Some(800));
+        assert!(metrics.disk_low);
+        assert!(transport
+            .commands()
+            .iter()
+            .any(|command| command.contains("ps -o rss= -p 4242")));
+        assert_eq!(events.metrics.lock().expect("events mutex").len(), 1);
+
+        manager.teardown_session("ssh-metrics");
+        assert!(manager.metrics_snapshot(None).is_empty());
+    }
+
+    #[test]
+    fn idle_stop_applies_only_to_kept_managed_servers() {
+        let mut instance = sample_instance("ssh-idle", "ssh user@gpu");
+        instance.remote_openchamber.idle_stop_after_min = Some(30);
+        assert_eq!(idle_stop_minutes(&instance), Some(30));
+
+        instance.remote_openchamber.keep_running = false;
+        assert_eq!(idle_stop_minutes(&instance), None);
+
+        instance.remote_openchamber.keep_running = true;
+        instance.remote_openchamber.mode = DesktopSshRemoteMode::External;
+        assert_eq!(idle_stop_minutes(&instance), None);
+
+        let mut huge = sample_instance("ssh-idle-huge", "ssh user@gpu");
+        huge.remote_openchamber.idle_stop_after_min = Some(u32::MAX);
+        let sanitized = sanitize_instance(huge).expect("sanitized");
+        assert_eq!(
+            sanitized.remote_openchamber.idle_stop_after_min,
+            Some(MAX_IDLE_STOP_MIN)
+        );
+    }
+
+    #[test]
+    fn connect_arms_idle_stop_and_touches_lease_on_teardown() {
+        let (transport, manager) = fake_manager("idle-stop");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-idle-stop", "ssh user@gpu");
+        instance.remote_openchamber.idle_stop_after_min = Some(15);
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        let armed: Vec<String> = transport
+            .commands()
+            .into_iter()
+            .filter(|command| command.contains("watchdog.sh"))
+            .collect();
+        assert_eq!(armed.len(), 1);
+        assert!(armed[0].contains("printf '%s\\n' 15 > \"$DIR/grace\""));
+        assert!(armed[0].contains("scheduled-tasks/status"));
+
+        manager.teardown_session("ssh-idle-stop");
+        let commands = transport.commands();
+        let last_touch = commands
+            .iter()
+            .rev()
+            .find(|command| command.contains(".lease"))
+            .expect("lease touch");
+        assert!(!last_touch.contains("watchdog.sh"));
+        assert!(!commands.iter().any(
+            |command| command.contains("/api/system/shutdown") && !command.contains("watchdog")
+        ));
+    }
+
+    #[test]
+    fn idle_stop_is_not_armed_behind_a_ui_password() {
+        let (transport, manager) = fake_manager("idle-stop-password");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-idle-password", "ssh user@gpu");
+        instance.remote_openchamber.idle_stop_after_min = Some(15);
+        instance.auth.openchamber_password = secret("ui-secret");
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        assert!(!transport
+            .commands()
+            .iter()
+            .any(|command| command.contains("watchdog.sh")));
+        let ready = events.last();
+        assert_eq!(ready.phase, DesktopSshPhase::Ready);
+        assert!(ready
+            .detail
+            .unwrap_or_default()
+            .ends_with("Idle-stop is off because the remote server requires a UI password"));
+    }
+
+    /// Run the watchdog `transport` was asked to install against a local
+    /// server whose scheduled-tasks route answers `status_reply`. Returns
+    /// whether it asked the server to shut down.
+    #[cfg(unix)]
+    fn run_installed_watchdog(
+        transport: &FakeSshTransport,
+        status_reply: (&'static str, &'static str),
+    ) -> bool {
+        use std::io::{BufRead, BufReader, Write};
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let armed = transport
+            .commands()
+            .into_iter()
+            .find(|command| command.contains("watchdog.sh"))
+            .expect("watchdog installed");
+        let script = armed
+            .split("<<'OPENCHAMBER_WATCHDOG'\n")
+            .nth(1)
+            .and_then(|rest| rest.split("OPENCHAMBER_WATCHDOG\n").next())
+            .expect("watchdog script")
+            .to_string();
+
+        let dir = std::env::temp_dir().join(format!("openchamber-watchdog-{}", now_millis()));
+        fs::create_dir_all(&dir).expect("watchdog dir");
+        fs::write(dir.join("watchdog.sh"), script).expect("write script");
+        fs::write(dir.join("grace"), "0\n").expect("write grace");
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+        let status_hits = Arc::new(AtomicUsize::new(0));
+        let shutdown_hit = Arc::new(AtomicBool::new(false));
+        {
+            let status_hits = Arc::clone(&status_hits);
+            let shutdown_hit = Arc::clone(&shutdown_hit);
+            std::thread::spawn(move || {
+                for mut stream in listener.incoming().flatten() {
+                    let mut line = String::new();
+                    let _ = BufReader::new(&stream).read_line(&mut line);
+                    let reply = if line.contains("/api/system/shutdown") {
+                        shutdown_hit.store(true, Ordering::SeqCst);
+                        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}".to_string()
+                    } else if line.contains("scheduled-tasks/status") {
+                        status_hits.fetch_add(1, Ordering::SeqCst);
+                        let (status_line, body) = status_reply;
+                        format!(
+                            "HTTP/1.1 {status_line}\r\nContent-Length: {}\r\n\r\n{body}",
+                            body.len()
+                        )
+                    } else if status_hits.load(Ordering::SeqCst) >= 2 {
+                        // Let the watchdog exit once it has checked twice.
+                        "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\n\r\n".to_string()
+                    } else {
+                        "HTTP/1.1 200 OK\r\nContent-Length: 2\r\n\r\n{}".to_string()
+                    };
+                    let _ = stream.write_all(reply.as_bytes());
+                }
+            });
+        }
+
+        let status = std::process::Command::new("sh")
+            .arg(dir.join("watchdog.sh"))
+            .arg(&dir)
+            .arg(port.to_string())
+            .arg("0.1")
+            .status()
+            .expect("run watchdog");
+        assert!(status.success());
+        let _ = fs::remove_dir_all(&dir);
+        shutdown_hit.load(Ordering::SeqCst)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn idle_watchdog_keeps_password_protected_servers_running() {
+        let (transport, manager) = fake_manager("idle-password");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-idle-password", "ssh user@gpu");
+        instance.remote_openchamber.idle_stop_after_min = Some(15);
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+
+        let unauthorized = ("401 Unauthorized", r#"{"error":"Unauthorized"}"#);
+        assert!(!run_installed_watchdog(&transport, unauthorized));
+        assert!(!run_installed_watchdog(
+            &transport,
+            ("200 OK", "<html></html>")
+        ));
+        let idle = (
+            "200 OK",
+            r#"{"hasEnabledScheduledTasks":false,"hasRunningScheduledTasks":false}"#,
+        );
+        assert!(run_installed_watchdog(&transport, idle));
+    }
+
+    #[test]
+    fn sanitize_instance_clamps_multiplex_settings() {
+        let mut instance = sample_instance("ssh-mux", "ssh user@example.com");
+        instance.multiplex.control_persist_sec = u32::MAX;
+        instance.multiplex.keep_alive_interval_sec = Some(0);
+
+        let sanitized = sanitize_instance(instance).expect("sanitized");
+        assert_eq!(
+            sanitized.multiplex.control_persist_sec,
+            MAX_CONTROL_PERSIST_SEC
+        );
+        assert_eq!(sanitized.multiplex.keep_alive_interval_sec, None);
+    }
+
+    #[test]
+    fn connect_passes_multiplex_options_to_master() {
+        let (transport, manager) = fake_manager("mux-options");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-mux-options", "ssh user@example.com");
+        instance.multiplex = DesktopSshMultiplexConfig {
+            control_persist_sec: 0,
+            keep_alive_interval_sec: Some(30),
+            compression: true,
+            reuse_user_master: true,
+        };
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        assert_eq!(
+            transport.master_options(),
+            vec![SshMasterOptions {
+                persist_sec: 0,
+                keep_alive_interval_sec: Some(30),
+                compression: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn connect_attaches_to_running_user_master() {
+        let (transport, manager) = fake_manager("mux-reuse");
+        let user_socket = std::env::temp_dir().join("openchamber-user-master.sock");
+        transport.set_user_master(&user_socket);
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-mux-reuse", "ssh user@example.com");
+        instance.multiplex.reuse_user_master = true;
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        assert!(transport.master_options().is_empty());
+        assert!(events
+            .statuses
+            .lock()
+            .expect("events mutex")
+            .iter()
+            .any(
+                |status| status.detail.as_deref() == Some("Attached to existing SSH ControlMaster")
+            ));
+        {
+            let sessions = manager.sessions.lock().expect("ssh sessions mutex");
+            let session = sessions.get("ssh-mux-reuse").expect("session");
+            assert_eq!(session.control_path, user_socket);
+            assert!(!session.owns_master);
+        }
+
+        manager.teardown_session("ssh-mux-reuse");
+        assert!(transport.stopped_masters().is_empty());
+    }
+
+    #[test]
+    fn host_ids_are_unique_across_kinds() {
+        let root = json!({
+            "desktopSshInstances": [{ "id": " prod " }],
+            "desktopDockerInstances": [{ "id": "box" }],
+            "desktopKubeInstances": [{ "id": "cluster" }],
+            "desktopLocalProfiles": [{ "id": "work" }],
+        });
+        ensure_host_id_unused(&root, "prod", HostKind::Ssh).expect("same kind");
+        ensure_host_id_unused(&root, "fresh", HostKind::Docker).expect("unused");
+        for (id, kind, owner) in [
+            ("prod", HostKind::Docker, "an SSH instance"),
+            ("box", HostKind::Kube, "a Docker instance"),
+            ("cluster", HostKind::LocalProfile, "a Kubernetes instance"),
+            ("work", HostKind::Ssh, "a local profile"),
+        ] {
+            let err = ensure_host_id_unused(&root, id, kind).expect_err("collision");
+            assert_eq!(
+                err.to_string(),
+                format!("Instance id {id} is already used by {owner}")
+            );
+        }
+        let labels: Vec<Option<&str>> = ["prod", "box", "cluster", "work", "plain"]
+            .into_iter()
+            .map(|id| HostKind::of(&root, id).map(HostKind::label))
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                Some("ssh"),
+                Some("docker"),
+                Some("kube"),
+                Some("profile"),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn sanitize_bind_host_accepts_ip_literals() {
+        assert_eq!(sanitize_bind_host("").expect("default"), "127.0.0.1");
+        assert_eq!(sanitize_bind_host(" [::1] ").expect("ipv6"), "::1");
+        assert_eq!(sanitize_bind_host("LocalHost").expect("name"), "localhost");
+        assert_eq!(sanitize_bind_host("10.8.0.2").expect("ipv4"), "10.8.0.2");
+        assert!(sanitize_bind_host("eth0; rm -rf /").is_err());
+    }
+
+    #[test]
+    fn bind_host_not_on_this_machine_is_saved_but_fails_connect() {
+        let (_transport, manager) = fake_manager("bind-missing");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-bind", "ssh user@example.com");
+        instance.local_forward.bind_host = "192.0.2.1".to_string();
+        let sanitized = sanitize_instance(instance).expect("syntax is valid");
+        let err = manager
+            .connect_blocking(&events, sanitized)
+            .expect_err("unassigned address");
+        assert!(err.to_string().contains("not available on this machine"));
+
+        let mut wildcard = sample_instance("ssh-bind", "ssh user@example.com");
+        wildcard.local_forward.bind_host = "[::]".to_string();
+        let sanitized = sanitize_instance(wildcard).expect("wildcard");
+        assert_eq!(sanitized.local_forward.bind_host, "::");
+        assert_eq!(connect_host_for_bind("::"), "::1");
+        assert_eq!(connect_host_for_bind("0.0.0.0"), "127.0.0.1");
+    }
+
+    #[test]
+    fn exposed_listeners_flag_non_loopback_binds() {
+        let mut instance = sample_instance("ssh-exposed", "ssh user@example.com");
+        instance.local_forward.bind_host = "0.0.0.0".to_string();
+        instance.port_forwards = vec![
+            DesktopSshPortForward {
+                id: "socks".to_string(),
+                enabled: true,
+                forward_type: DesktopSshPortForwardType::Dynamic,
+                local_host: Some("::1".to_string()),
+                local_port: Some(1080),
+                remote_host: None,
+                remote_port: None,
+            },
+            DesktopSshPortForward {
+                id: "callback".to_string(),
+                enabled: true,
+                forward_type: DesktopSshPortForwardType::Remote,
+                local_host: Some("127.0.0.1".to_string()),
+                local_port: Some(3000),
+                remote_host: Some("::".to_string()),
+                remote_port: Some(9000),
+            },
+        ];
+
+        assert_eq!(
+            exposed_listeners(&instance, 4100),
+            vec![
+                "main tunnel on 0.0.0.0:4100".to_string(),
+                "callback (remote) on [::]:9000".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn status_reports_exposure_while_session_is_live() {
+        let (_transport, manager) = fake_manager("exposed");
+        let events = RecordingEvents::default();
+        let mut instance = sample_instance("ssh-exposed", "ssh user@example.com");
+        instance.local_forward.bind_host = "0.0.0.0".to_string();
+
+        manager
+            .connect_blocking(&events, instance)
+            .expect("connect");
+        let ready = events.last();
+        assert_eq!(ready.phase, DesktopSshPhase::Ready);
+        assert!(ready.exposed_beyond_loopback);
+        assert!(ready
+            .local_url
+            .as_deref()
+            .is_some_and(|url| url.starts_with("http://127.0.0.1:")));
+
+        manager.teardown_session("ssh-exposed");
+        assert!(manager
+            .exposures
+            .lock()
+            .expect("ssh exposures mutex")
+            .is_empty());
+    }
 }
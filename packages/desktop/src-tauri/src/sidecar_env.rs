@@ -0,0 +1,242 @@
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+pub const SIDECAR_ENV_SETTINGS_KEY: &str = "sidecarEnv";
+pub const SIDECAR_ARGS_SETTINGS_KEY: &str = "sidecarArgs";
+
+/// Variables the desktop sets itself; a user value would break the launch
+/// or the auth handshake.
+const RESERVED_ENV_NAMES: &[&str] = &[
+    "HOME",
+    "PATH",
+    "OPENCHAMBER_HOST",
+    "OPENCHAMBER_DIST_DIR",
+    "OPENCHAMBER_RUNTIME",
+    "OPENCHAMBER_DESKTOP_NOTIFY",
+    "OPENCHAMBER_DESKTOP_PROTOCOL",
+    "OPENCHAMBER_DESKTOP_TOKEN",
+];
+
+/// Server options the desktop controls (`--port`, and `--host` which would
+/// bypass the LAN access setting).
+const RESERVED_ARGS: &[&str] = &["port", "p", "host"];
+
+/// One user-defined variable as stored in `settings.json`. Secret values
+/// are stored the same way but are never sent back to the UI or logged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarEnvEntry {
+    pub name: String,
+    pub value: String,
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// A variable as exchanged with the UI. `value` is `None` for secrets when
+/// reading, and means "keep the stored value" when writing.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarEnvEntryEdit {
+    pub name: String,
+    #[serde(default)]
+    pub value: Option<String>,
+    #[serde(default)]
+    pub secret: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarEnvSettings {
+    pub env: Vec<SidecarEnvEntryEdit>,
+    pub args: Vec<String>,
+}
+
+pub fn validate_env_name(name: &str) -> Result<()> {
+    let mut chars = name.chars();
+    let valid = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !valid {
+        bail!("Invalid environment variable name: {name:?}");
+    }
+    if RESERVED_ENV_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    {
+        bail!("{name} is managed by OpenChamber and cannot be overridden");
+    }
+    Ok(())
+}
+
+pub fn validate_args(args: &[String]) -> Result<()> {
+    for arg in args {
+        if arg.contains('\0') {
+            bail!("Server arguments cannot contain NUL bytes");
+        }
+        let option = arg
+            .strip_prefix("--")
+            .or_else(|| arg.strip_prefix('-'))
+            .map(|option| option.split('=').next().unwrap_or(option));
+        if let Some(option) = option {
+            if RESERVED_ARGS.contains(&option) {
+                bail!("{arg} is managed by OpenChamber and cannot be overridden");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Stored variables and arguments. Invalid entries are skipped with a
+/// warning so a hand-edited settings file never blocks the launch.
+pub fn read_from_settings(
+    settings: Option<&serde_json::Value>,
+) -> (Vec<SidecarEnvEntry>, Vec<String>) {
+    let env: Vec<SidecarEnvEntry> = settings
+        .and_then(|settings| settings.get(SIDECAR_ENV_SETTINGS_KEY))
+        .and_then(|value| value.as_array())
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| serde_json::from_value::<SidecarEnvEntry>(entry.clone()).ok())
+                .filter(|entry| match validate_env_name(&entry.name) {
+                    Ok(()) => true,
+                    Err(err) => {
+                        log::warn!("[sidecar] ignoring {SIDECAR_ENV_SETTINGS_KEY} entry: {err}");
+                        false
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let args: Vec<String> = settings
+        .and_then(|settings| settings.get(SIDECAR_ARGS_SETTINGS_KEY))
+        .and_then(|value| value.as_array())
+        .map(|args| {
+            args.iter()
+                .filter_map(|arg| arg.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let args = match validate_args(&args) {
+        Ok(()) => args,
+        Err(err) => {
+            log::warn!("[sidecar] ignoring {SIDECAR_ARGS_SETTINGS_KEY}: {err}");
+            Vec::new()
+        }
+    };
+
+    (env, args)
+}
+
+/// What the UI may see: secret values are withheld.
+pub fn to_settings_view(env: &[SidecarEnvEntry], args: &[String]) -> SidecarEnvSettings {
+    SidecarEnvSettings {
+        env: env
+            .iter()
+            .map(|entry| SidecarEnvEntryEdit {
+                name: entry.name.clone(),
+                value: (!entry.secret).then(|| entry.value.clone()),
+                secret: entry.secret,
+            })
+            .collect(),
+        args: args.to_vec(),
+    }
+}
+
+/// Validate an edit from the UI against the stored entries, keeping secret
+/// values the UI did not resend.
+pub fn apply_edits(
+    existing: &[SidecarEnvEntry],
+    edits: Vec<SidecarEnvEntryEdit>,
+) -> Result<Vec<SidecarEnvEntry>> {
+    let mut seen = HashSet::new();
+    let mut entries = Vec::with_capacity(edits.len());
+    for edit in edits {
+        let name = edit.name.trim().to_string();
+        validate_env_name(&name)?;
+        if !seen.insert(name.clone()) {
+            bail!("{name} is defined more than once");
+        }
+        let value = match edit.value {
+            Some(value) => value,
+            None => existing
+                .iter()
+                .find(|entry| entry.name == name && entry.secret)
+                .map(|entry| entry.value.clone())
+                .ok_or_else(|| anyhow::anyhow!("{name} needs a value"))?,
+        };
+        if value.contains('\0') {
+            bail!("{name} cannot contain NUL bytes");
+        }
+        entries.push(SidecarEnvEntry {
+            name,
+            value,
+            secret: edit.secret,
+        });
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, value: &str, secret: bool) -> SidecarEnvEntry {
+        SidecarEnvEntry {
+            name: name.to_string(),
+            value: value.to_string(),
+            secret,
+        }
+    }
+
+    #[test]
+    fn secrets_are_hidden_and_kept_across_edits() {
+        let stored = vec![
+            entry("HTTPS_PROXY", "http://proxy:3128", false),
+            entry("API_KEY", "s3cret", true),
+        ];
+        let view = to_settings_view(&stored, &[]);
+        assert_eq!(view.env[0].value.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(view.env[1].value, None);
+
+        let updated = apply_edits(&stored, view.env).expect("apply edits");
+        assert_eq!(updated, stored);
+
+        let missing = vec![SidecarEnvEntryEdit {
+            name: "NEW_SECRET".to_string(),
+            value: None,
+            secret: true,
+        }];
+        assert!(apply_edits(&stored, missing).is_err());
+    }
+
+    #[test]
+    fn reserved_names_and_args_are_rejected() {
+        assert!(validate_env_name("FEATURE_FLAG_1").is_ok());
+        assert!(validate_env_name("path").is_err());
+        assert!(validate_env_name("OPENCHAMBER_DESKTOP_TOKEN").is_err());
+        assert!(validate_env_name("1BAD").is_err());
+        assert!(validate_env_name("WITH-DASH").is_err());
+
+        let args = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        assert!(validate_args(&args(&["--verbose", "--log-level", "debug"])).is_ok());
+        assert!(validate_args(&args(&["--port", "1"])).is_err());
+        assert!(validate_args(&args(&["--host=0.0.0.0"])).is_err());
+        assert!(validate_args(&args(&["-p"])).is_err());
+
+        let settings = serde_json::json!({
+            "sidecarEnv": [
+                { "name": "OK", "value": "1" },
+                { "name": "PATH", "value": "/tmp" },
+                { "name": "NO_VALUE" }
+            ],
+            "sidecarArgs": ["--host", "0.0.0.0"]
+        });
+        let (env, args) = read_from_settings(Some(&settings));
+        assert_eq!(env, vec![entry("OK", "1", false)]);
+        assert!(args.is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+use crate::remote_ssh::settings_file_path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Executable name of the bundled sidecar (without the Windows extension).
+pub const SIDECAR_PROCESS_NAME: &str = "openchamber-server";
+/// Allowed drift between the recorded spawn time and the start time the OS
+/// reports before a PID is treated as reused by an unrelated process.
+const START_TIME_TOLERANCE_SECS: u64 = 10;
+const TERMINATE_GRACE: Duration = Duration::from_secs(2);
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Ownership record for a sidecar spawned by this desktop build. One file
+/// per sidecar so several shells sharing a data dir never touch each
+/// other's processes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarLock {
+    pub pid: u32,
+    pub port: u16,
+    /// Unix seconds when the desktop spawned the process.
+    pub started_at: u64,
+    /// Desktop shell process that owns the sidecar.
+    pub shell_pid: u32,
+    pub app_version: String,
+    pub bind_host: String,
+    /// Auth token the sidecar was launched with, needed to reuse it.
+    pub token: String,
+    /// Local profile id; `None` for the main sidecar. Profile sidecars are
+    /// never adopted as the main one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub name: String,
+    /// Unix seconds; `None` when the platform does not report it.
+    pub started_at: Option<u64>,
+}
+
+/// `sidecar-locks/` next to `settings.json` in the data directory.
+pub fn locks_dir() -> PathBuf {
+    settings_file_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+        .join("sidecar-locks")
+}
+
+pub fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn lock_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("{pid}.json"))
+}
+
+pub fn write_lock_in(dir: &Path, lock: &SidecarLock) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = lock_path(dir, lock.pid);
+    // The lock holds the sidecar token, so it is created private rather than
+    // restricted after writing. A stale file from a reused pid is replaced.
+    let _ = fs::remove_file(&path);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(&path)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    file.write_all(serde_json::to_string_pretty(lock)?.as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+pub fn remove_lock_in(dir: &Path, pid: u32) {
+    let _ = fs::remove_file(lock_path(dir, pid));
+}
+
+/// Readable lock files, newest first. Unparseable files are removed.
+pub fn read_locks_in(dir: &Path) -> Vec<SidecarLock> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut locks: Vec<SidecarLock> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+        .filter_map(|path| {
+            let lock = fs::read_to_string(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<SidecarLock>(&raw).ok());
+            if lock.is_none() {
+                let _ = fs::remove_file(&path);
+            }
+            lock
+        })
+        .collect();
+    locks.sort_by_key(|lock| std::cmp::Reverse(lock.started_at));
+    locks
+}
+
+/// Parse `ps -o etime` output (`[[dd-]hh:]mm:ss`) into seconds.
+pub fn parse_ps_etime(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    let (days, clock) = match raw.split_once('-') {
+        Some((days, clock)) => (days.parse::<u64>().ok()?, clock),
+        None => (0, raw),
+    };
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [minutes, seconds] => (0, *minutes, *seconds),
+        [hours, minutes, seconds] => (*hours, *minutes, *seconds),
+        _ => return None,
+    };
+    Some(((days * 24 + hours) * 60 + minutes) * 60 + seconds)
+}
+
+/// Name and start time of a running process, or `None` if it is gone.
+pub fn process_info(pid: u32) -> Option<ProcessInfo> {
+    if cfg!(windows) {
+        let output = Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let name = stdout.lines().next()?.split(',').next()?.trim_matches('"');
+        if name.is_empty() || name.starts_with("INFO:") {
+            return None;
+        }
+        return Some(ProcessInfo {
+            name: name.to_string(),
+            started_at: None,
+        });
+    }
+
+    let output = Command::new("ps")
+        .args(["-o", "etime=", "-o", "comm=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (etime, comm) = stdout.trim().split_once(char::is_whitespace)?;
+    let elapsed = parse_ps_etime(etime)?;
+    // Linux truncates `comm` to 15 bytes; the exe link has the full name.
+    let name = fs::read_link(format!("/proc/{pid}/exe"))
+        .ok()
+        .and_then(|exe| {
+            exe.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| comm.trim().to_string());
+    Some(ProcessInfo {
+        name,
+        started_at: Some(now_unix_secs().saturating_sub(elapsed)),
+    })
+}
+
+fn process_base_name(name: &str) -> &str {
+    let base = name.rsplit(['/', '\\']).next().unwrap_or(name);
+    base.strip_suffix(".exe").unwrap_or(base)
+}
+
+/// True when `info` describes the process `lock` was written for, not a
+/// different program that has since been given the same PID. Without a
+/// start time (Windows) the name alone proves nothing, so this is false and
+/// the caller must confirm ownership another way.
+pub fn is_owned_process(lock: &SidecarLock, info: &ProcessInfo) -> bool {
+    if process_base_name(&info.name) != SIDECAR_PROCESS_NAME {
+        return false;
+    }
+    info.started_at
+        .is_some_and(|started_at| started_at.abs_diff(lock.started_at) <= START_TIME_TOLERANCE_SECS)
+}
+
+/// True when `shell_pid` is a live desktop shell other than this one.
+pub fn is_other_shell_alive(shell_pid: u32) -> bool {
+    if shell_pid == std::process::id() {
+        return false;
+    }
+    let Some(info) = process_info(shell_pid) else {
+        return false;
+    };
+    let current = std::env::current_exe().ok();
+    let current_name = current
+        .as_deref()
+        .and_then(|path| path.file_name())
+        .and_then(|name| name.to_str())
+        .map(process_base_name)
+        .unwrap_or_default();
+    !current_name.is_empty() && process_base_name(&info.name) == current_name
+}
+
+/// Poll until `pid` is gone. Returns `false` if it is still running when
+/// `timeout` elapses.
+pub fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if process_info(pid).is_none() {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+}
+
+/// Ask `pid` to exit, then force it after a short grace period.
+pub fn terminate_process(pid: u32) {
+    if process_info(pid).is_none() {
+        return;
+    }
+    if cfg!(windows) {
+        log::warn!("[sidecar] force-stopping pid {pid}");
+        let _ = Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .output();
+        return;
+    }
+
+    log::warn!("[sidecar] sending SIGTERM to pid {pid}");
+    let _ = Command::new("kill")
+        .args(["-TERM", &pid.to_string()])
+        .output();
+    if wait_for_exit(pid, TERMINATE_GRACE) {
+        log::info!("[sidecar] pid {pid} exited after SIGTERM");
+        return;
+    }
+    log::warn!("[sidecar] pid {pid} ignored SIGTERM; sending SIGKILL");
+    let _ = Command::new("kill")
+        .args(["-KILL", &pid.to_string()])
+        .output();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock(pid: u32, started_at: u64) -> SidecarLock {
+        SidecarLock {
+            pid,
+            port: 57123,
+            started_at,
+            shell_pid: 4242,
+            app_version: "1.9.10".to_string(),
+            bind_host: "127.0.0.1".to_string(),
+            token: "abc".to_string(),
+            profile: None,
+        }
+    }
+
+    #[test]
+    fn parse_ps_etime_accepts_all_ps_formats() {
+        assert_eq!(parse_ps_etime("00:07"), Some(7));
+        assert_eq!(parse_ps_etime(" 12:34 "), Some(754));
+        assert_eq!(parse_ps_etime("01:02:03"), Some(3723));
+        assert_eq!(parse_ps_etime("2-00:00:01"), Some(172_801));
+        assert_eq!(parse_ps_etime("soon"), None);
+    }
+
+    #[test]
+    fn is_owned_process_rejects_reused_and_unverifiable_pids() {
+        let lock = lock(100, 1_000);
+        let sidecar = |name: &str, started_at| ProcessInfo {
+            name: name.to_string(),
+            started_at,
+        };
+
+        assert!(is_owned_process(
+            &lock,
+            &sidecar("openchamber-server", Some(1_003))
+        ));
+        assert!(is_owned_process(
+            &lock,
+            &sidecar(
+                "/Applications/OpenChamber.app/Contents/MacOS/openchamber-server",
+                Some(998)
+            )
+        ));
+        assert!(!is_owned_process(
+            &lock,
+            &sidecar("openchamber-server.exe", None)
+        ));
+        assert!(!is_owned_process(
+            &lock,
+            &sidecar("openchamber-server", Some(5_000))
+        ));
+        assert!(!is_owned_process(&lock, &sidecar("node", Some(1_000))));
+    }
+
+    #[test]
+    fn locks_round_trip_newest_first() {
+        let dir =
+            std::env::temp_dir().join(format!("openchamber-sidecar-locks-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        write_lock_in(&dir, &lock(1, 10)).expect("write lock");
+        fs::write(dir.join("2.json"), "stale").expect("write stale lock");
+        write_lock_in(&dir, &lock(2, 20)).expect("write lock");
+        fs::write(dir.join("3.json"), "{broken").expect("write junk");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(dir.join("2.json"))
+                .expect("lock")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let locks = read_locks_in(&dir);
+        assert_eq!(
+            locks.iter().map(|lock| lock.pid).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+        assert!(!dir.join("3.json").exists());
+
+        remove_lock_in(&dir, 2);
+        assert_eq!(read_locks_in(&dir), vec![lock(1, 10)]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn terminate_process_stops_a_running_process() {
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .expect("spawn sleep");
+        let pid = child.id();
+        // Reap in the background so the exited process does not linger as a zombie.
+        let reaper = std::thread::spawn(move || child.wait());
+
+        assert!(!wait_for_exit(pid, Duration::from_millis(200)));
+        terminate_process(pid);
+        assert!(wait_for_exit(pid, Duration::from_secs(2)));
+        let _ = reaper.join();
+    }
+}
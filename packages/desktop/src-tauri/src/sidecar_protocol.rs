@@ -0,0 +1,403 @@
+use serde::{Deserialize, Serialize};
+
+/// Prefix of protocol lines on the sidecar's stdout.
+pub const SIDECAR_PROTOCOL_PREFIX: &str = "[OpenChamberDesktop] ";
+/// Prefix of the original one-way notification lines, still accepted from
+/// sidecars that predate the protocol.
+pub const SIDECAR_NOTIFY_PREFIX: &str = "[OpenChamberDesktopNotify] ";
+/// Highest protocol version this shell understands.
+pub const SIDECAR_PROTOCOL_VERSION: u32 = 1;
+
+/// Env var carrying the per-launch auth token to the sidecar.
+pub const SIDECAR_AUTH_TOKEN_ENV: &str = "OPENCHAMBER_DESKTOP_TOKEN";
+/// Header carrying the token on HTTP calls from the shell and its
+/// local-origin windows.
+pub const SIDECAR_AUTH_HEADER: &str = "X-OpenChamber-Desktop-Token";
+/// Window global through which local-origin pages receive the token.
+pub const SIDECAR_AUTH_WINDOW_GLOBAL: &str = "__OPENCHAMBER_DESKTOP_TOKEN__";
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarNotifyPayload {
+    pub title: Option<String>,
+    pub body: Option<String>,
+    pub tag: Option<String>,
+    pub require_hidden: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SidecarQuitRisk {
+    #[serde(default)]
+    pub has_active_tunnel: bool,
+    #[serde(default)]
+    pub has_enabled_scheduled_tasks: bool,
+    #[serde(default)]
+    pub has_running_scheduled_tasks: bool,
+    #[serde(default)]
+    pub enabled_scheduled_tasks_count: u32,
+    #[serde(default)]
+    pub running_scheduled_tasks_count: u32,
+    /// Sessions with an agent still responding.
+    #[serde(default)]
+    pub busy_sessions_count: u32,
+}
+
+impl SidecarQuitRisk {
+    /// Build the snapshot from the HTTP status endpoints, for sidecars whose
+    /// stdio belongs to an earlier shell: `scheduled-tasks/status`,
+    /// `tunnel/status` and `session-activity`.
+    pub fn from_http_status(
+        scheduled: serde_json::Value,
+        tunnel: &serde_json::Value,
+        activity: &serde_json::Value,
+    ) -> Self {
+        let mut risk: Self = serde_json::from_value(scheduled).unwrap_or_default();
+        risk.has_enabled_scheduled_tasks |= risk.enabled_scheduled_tasks_count > 0;
+        risk.has_running_scheduled_tasks |= risk.running_scheduled_tasks_count > 0;
+        risk.has_active_tunnel = tunnel.get("active").and_then(|v| v.as_bool()) == Some(true);
+        risk.busy_sessions_count = activity
+            .as_object()
+            .map(|sessions| {
+                sessions
+                    .values()
+                    .filter(|session| session.get("type").and_then(|v| v.as_str()) == Some("busy"))
+                    .count() as u32
+            })
+            .unwrap_or(0);
+        risk
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SidecarProgressState {
+    #[default]
+    Normal,
+    Indeterminate,
+    Paused,
+    Error,
+    None,
+}
+
+/// Desktop actions the sidecar can ask for.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum SidecarMessage {
+    Hello {
+        protocol: u32,
+    },
+    Notify(SidecarNotifyPayload),
+    OpenWindow {
+        url: String,
+    },
+    SetBadge {
+        #[serde(default)]
+        count: Option<i64>,
+    },
+    RequestAttention {
+        #[serde(default)]
+        critical: bool,
+    },
+    QuitRisk(SidecarQuitRisk),
+    Progress {
+        /// Fraction in `0..=1`; `None` clears the progress bar.
+        #[serde(default)]
+        value: Option<f64>,
+        #[serde(default)]
+        state: Option<SidecarProgressState>,
+    },
+}
+
+/// One parsed stdout line. `id` is set for requests that expect a reply.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SidecarRequest {
+    pub id: Option<String>,
+    /// Unknown or malformed messages keep their error so a request can
+    /// still be answered.
+    pub message: Result<SidecarMessage, String>,
+}
+
+/// Parse a sidecar stdout line. Returns `None` for ordinary output.
+pub fn parse_sidecar_line(line: &str) -> Option<SidecarRequest> {
+    let line = line.trim_end_matches(['\r', '\n']);
+    if let Some(rest) = line.strip_prefix(SIDECAR_NOTIFY_PREFIX) {
+        return Some(SidecarRequest {
+            id: None,
+            message: serde_json::from_str::<SidecarNotifyPayload>(rest.trim())
+                .map(SidecarMessage::Notify)
+                .map_err(|err| format!("Invalid notification: {err}")),
+        });
+    }
+
+    let rest = line.strip_prefix(SIDECAR_PROTOCOL_PREFIX)?;
+    let value = match serde_json::from_str::<serde_json::Value>(rest.trim()) {
+        Ok(value) => value,
+        Err(err) => {
+            return Some(SidecarRequest {
+                id: None,
+                message: Err(format!("Invalid message: {err}")),
+            })
+        }
+    };
+    let id = value
+        .get("id")
+        .and_then(|id| id.as_str())
+        .map(str::to_string);
+    let version = value.get("v").and_then(|v| v.as_u64()).unwrap_or(0);
+    let message = if version == 0 || version > u64::from(SIDECAR_PROTOCOL_VERSION) {
+        Err(format!("Unsupported protocol version: {version}"))
+    } else {
+        let kind = value
+            .get("type")
+            .and_then(|kind| kind.as_str())
+            .unwrap_or_default()
+            .to_string();
+        serde_json::from_value::<SidecarMessage>(value)
+            .map_err(|err| format!("Unsupported message '{kind}': {err}"))
+    };
+    Some(SidecarRequest { id, message })
+}
+
+/// Messages written to the sidecar's stdin.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DesktopMessage {
+    #[serde(rename_all = "camelCase")]
+    Hello { protocol: u32, app_version: String },
+    Reply {
+        id: String,
+        ok: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        result: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Event {
+        event: String,
+        data: serde_json::Value,
+    },
+}
+
+impl DesktopMessage {
+    pub fn reply(id: String, result: Result<serde_json::Value, String>) -> Self {
+        match result {
+            Ok(value) => DesktopMessage::Reply {
+                id,
+                ok: true,
+                result: (!value.is_null()).then_some(value),
+                error: None,
+            },
+            Err(error) => DesktopMessage::Reply {
+                id,
+                ok: false,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+
+    pub fn event(event: &str, data: serde_json::Value) -> Self {
+        DesktopMessage::Event {
+            event: event.to_string(),
+            data,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct DesktopEnvelope<'a> {
+    v: u32,
+    #[serde(flatten)]
+    message: &'a DesktopMessage,
+}
+
+/// Encode `message` as one newline-terminated stdin line.
+pub fn encode_desktop_message(message: &DesktopMessage) -> String {
+    let envelope = DesktopEnvelope {
+        v: SIDECAR_PROTOCOL_VERSION,
+        message,
+    };
+    let mut line = serde_json::to_string(&envelope).unwrap_or_default();
+    line.push('\n');
+    line
+}
+
+/// Generate a fresh auth token (32 random bytes, hex encoded).
+pub fn generate_sidecar_auth_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|err| format!("Failed to generate auth token: {err}"))?;
+    Ok(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// Init-script snippet that hands `token` to pages served from
+/// `local_origin` as a read-only window global; the UI sends it as a header.
+/// A cookie would also reach other ports on the same loopback host, such as
+/// SSH and tunnel forwards opened in the same window.
+pub fn sidecar_auth_token_script(local_origin: &str, token: &str) -> String {
+    if local_origin.is_empty() || token.is_empty() {
+        return String::new();
+    }
+    let origin_json = serde_json::to_string(local_origin).unwrap_or_else(|_| "\"\"".into());
+    let token_json = serde_json::to_string(token).unwrap_or_else(|_| "\"\"".into());
+    format!(
+        "try{{if(window.location.origin==={origin_json}){{Object.defineProperty(window,'{SIDECAR_AUTH_WINDOW_GLOBAL}',{{value:{token_json}}});}}}}catch(_e){{}}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sidecar_line_reads_protocol_and_legacy_lines() {
+        assert_eq!(parse_sidecar_line("Listening on 3000"), None);
+
+        let legacy = parse_sidecar_line(
+            "[OpenChamberDesktopNotify] {\"title\":\"Done\",\"requireHidden\":true}\n",
+        )
+        .expect("legacy line");
+        assert_eq!(legacy.id, None);
+        assert_eq!(
+            legacy.message,
+            Ok(SidecarMessage::Notify(SidecarNotifyPayload {
+                title: Some("Done".to_string()),
+                require_hidden: Some(true),
+                ..Default::default()
+            }))
+        );
+
+        let open = parse_sidecar_line(
+            "[OpenChamberDesktop] {\"v\":1,\"id\":\"s1\",\"type\":\"openWindow\",\"url\":\"http://127.0.0.1:3000\"}",
+        )
+        .expect("protocol line");
+        assert_eq!(open.id.as_deref(), Some("s1"));
+        assert_eq!(
+            open.message,
+            Ok(SidecarMessage::OpenWindow {
+                url: "http://127.0.0.1:3000".to_string()
+            })
+        );
+
+        let risk = parse_sidecar_line(
+            "[OpenChamberDesktop] {\"v\":1,\"type\":\"quitRisk\",\"hasActiveTunnel\":true,\"runningScheduledTasksCount\":2,\"busySessionsCount\":1}",
+        )
+        .expect("quit risk line");
+        assert_eq!(
+            risk.message,
+            Ok(SidecarMessage::QuitRisk(SidecarQuitRisk {
+                has_active_tunnel: true,
+                running_scheduled_tasks_count: 2,
+                busy_sessions_count: 1,
+                ..Default::default()
+            }))
+        );
+    }
+
+    #[test]
+    fn parse_sidecar_line_keeps_request_ids_for_unsupported_messages() {
+        let unknown = parse_sidecar_line(
+            "[OpenChamberDesktop] {\"v\":1,\"id\":\"s7\",\"type\":\"launchRocket\"}",
+        )
+        .expect("protocol line");
+        assert_eq!(unknown.id.as_deref(), Some("s7"));
+        assert!(unknown
+            .message
+            .expect_err("unknown type")
+            .contains("launchRocket"));
+
+        let newer = parse_sidecar_line(
+            "[OpenChamberDesktop] {\"v\":2,\"id\":\"s8\",\"type\":\"setBadge\"}",
+        )
+        .expect("protocol line");
+        assert_eq!(
+            newer.message,
+            Err("Unsupported protocol version: 2".to_string())
+        );
+
+        let malformed = parse_sidecar_line("[OpenChamberDesktop] {nope").expect("protocol line");
+        assert!(malformed.message.is_err());
+    }
+
+    #[test]
+    fn encode_desktop_message_writes_versioned_lines() {
+        let reply = encode_desktop_message(&DesktopMessage::reply(
+            "s1".to_string(),
+            Err("Unsupported URL scheme: file".to_string()),
+        ));
+        assert!(reply.ends_with('\n'));
+        let value: serde_json::Value = serde_json::from_str(reply.trim()).expect("json");
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "v": 1,
+                "type": "reply",
+                "id": "s1",
+                "ok": false,
+                "error": "Unsupported URL scheme: file",
+            })
+        );
+
+        let hello = encode_desktop_message(&DesktopMessage::Hello {
+            protocol: SIDECAR_PROTOCOL_VERSION,
+            app_version: "1.9.10".to_string(),
+        });
+        assert!(hello.contains("\"appVersion\":\"1.9.10\""));
+
+        let event = encode_desktop_message(&DesktopMessage::event(
+            "focusChanged",
+            serde_json::json!({ "anyFocused": true }),
+        ));
+        assert!(event.contains("\"event\":\"focusChanged\""));
+    }
+
+    #[test]
+    fn quit_risk_from_http_status_counts_busy_sessions() {
+        let risk = SidecarQuitRisk::from_http_status(
+            serde_json::json!({ "hasEnabledScheduledTasks": false, "enabledScheduledTasksCount": 2 }),
+            &serde_json::json!({ "active": true, "mode": "quick" }),
+            &serde_json::json!({
+                "ses_a": { "type": "busy" },
+                "ses_b": { "type": "idle" },
+                "ses_c": { "type": "busy" },
+            }),
+        );
+        assert_eq!(
+            risk,
+            SidecarQuitRisk {
+                has_active_tunnel: true,
+                has_enabled_scheduled_tasks: true,
+                enabled_scheduled_tasks_count: 2,
+                busy_sessions_count: 2,
+                ..SidecarQuitRisk::default()
+            }
+        );
+        assert_eq!(
+            SidecarQuitRisk::from_http_status(
+                serde_json::Value::Null,
+                &serde_json::Value::Null,
+                &serde_json::Value::Null
+            ),
+            SidecarQuitRisk::default()
+        );
+    }
+
+    #[test]
+    fn sidecar_auth_token_script_is_scoped_to_the_local_origin() {
+        let token = generate_sidecar_auth_token().expect("token");
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(token, generate_sidecar_auth_token().expect("token"));
+
+        let script = sidecar_auth_token_script("http://127.0.0.1:3000", &token);
+        assert!(script.starts_with(
+            "try{if(window.location.origin===\"http://127.0.0.1:3000\"){Object.defineProperty("
+        ));
+        assert!(script.contains(&format!(
+            "'__OPENCHAMBER_DESKTOP_TOKEN__',{{value:\"{token}\"}}"
+        )));
+        assert!(!script.contains("cookie"));
+
+        assert_eq!(sidecar_auth_token_script("", &token), "");
+    }
+}
@@ -0,0 +1,441 @@
+use crate::remote_ssh::settings_file_path;
+use crate::sidecar_protocol::generate_sidecar_auth_token;
+use crate::{cli, deep_link};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    io::{BufRead, BufReader, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+const INSTANCE_FILE_NAME: &str = "desktop-instance.json";
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const IO_TIMEOUT: Duration = Duration::from_secs(2);
+/// How long to wait for a concurrently starting instance to finish writing
+/// its record.
+const RECORD_WAIT_ATTEMPTS: usize = 20;
+const RECORD_WAIT_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_MESSAGE_BYTES: u64 = 64 * 1024;
+
+/// Where a loopback listener of this process accepts requests: the running
+/// instance, or the headless control API. Only readable by the user.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenerRecord {
+    pub pid: u32,
+    pub port: u16,
+    pub token: String,
+}
+
+/// Arguments of a launch that was handed to the running instance.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardedLaunch {
+    pub args: Vec<String>,
+    /// Working directory of the forwarding process, for relative paths.
+    #[serde(default)]
+    pub cwd: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForwardMessage {
+    token: String,
+    /// Only check that the instance answers; nothing is opened.
+    #[serde(default)]
+    probe: bool,
+    #[serde(flatten)]
+    launch: ForwardedLaunch,
+}
+
+pub enum Acquired {
+    /// This process is the running instance and should call [`serve`].
+    Primary(PrimaryInstance),
+    /// Another instance accepted the launch; this process should exit.
+    Forwarded,
+    /// Another instance answered a probe; nothing was handed to it.
+    Running,
+}
+
+pub struct PrimaryInstance {
+    listener: TcpListener,
+    token: String,
+}
+
+/// What a launch asks the running instance to show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchTarget {
+    /// No argument: bring an existing window forward.
+    Focus,
+    Url(String),
+    /// An `openchamber://` link.
+    DeepLink(String),
+    Project(PathBuf),
+    HostId(String),
+    SshConnect(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LaunchRequest {
+    pub target: LaunchTarget,
+    /// `--new-window`: open the target in a new window rather than reusing one.
+    pub new_window: bool,
+}
+
+pub fn instance_file_path() -> PathBuf {
+    settings_file_path()
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+        .join(INSTANCE_FILE_NAME)
+}
+
+impl ForwardedLaunch {
+    pub fn from_env() -> Self {
+        Self {
+            args: std::env::args().skip(1).collect(),
+            cwd: std::env::current_dir()
+                .ok()
+                .map(|dir| dir.to_string_lossy().to_string()),
+        }
+    }
+}
+
+/// Become the running instance, or hand `launch` to the one already
+/// running so a second shell never starts next to it. Without a launch the
+/// running instance is only probed. A record whose instance does not answer
+/// is treated as stale.
+pub fn acquire(path: &Path, launch: Option<&ForwardedLaunch>) -> Result<Acquired> {
+    for _ in 0..3 {
+        match publish_record(path) {
+            Ok((listener, record)) => {
+                return Ok(Acquired::Primary(PrimaryInstance {
+                    listener,
+                    token: record.token,
+                }));
+            }
+            Err(err)
+                if err
+                    .downcast_ref::<std::io::Error>()
+                    .is_some_and(|err| err.kind() == ErrorKind::AlreadyExists) =>
+            {
+                if let Some(record) = wait_for_record(path) {
+                    match forward(&record, launch) {
+                        Ok(()) if launch.is_some() => return Ok(Acquired::Forwarded),
+                        Ok(()) => return Ok(Acquired::Running),
+                        Err(err) => log::info!(
+                            "[desktop] previous instance pid {} did not answer ({err}); taking over",
+                            record.pid
+                        ),
+                    }
+                }
+                let _ = fs::remove_file(path);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Err(anyhow!("Could not acquire {}", path.display()))
+}
+
+/// Bind a loopback port and publish it with a fresh token in a new file at
+/// `path`, created private to the user. Fails with
+/// [`ErrorKind::AlreadyExists`] when a record is already there.
+pub(crate) fn publish_record(path: &Path) -> Result<(TcpListener, ListenerRecord)> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let record = ListenerRecord {
+        pid: std::process::id(),
+        port: listener.local_addr()?.port(),
+        token: generate_sidecar_auth_token().map_err(|err| anyhow!(err))?,
+    };
+    file.write_all(serde_json::to_string(&record)?.as_bytes())?;
+    Ok((listener, record))
+}
+
+fn wait_for_record(path: &Path) -> Option<ListenerRecord> {
+    for _ in 0..RECORD_WAIT_ATTEMPTS {
+        let record = fs::read_to_string(path)
+            .ok()
+            .and_then(|raw| serde_json::from_str::<ListenerRecord>(&raw).ok());
+        if record.is_some() {
+            return record;
+        }
+        std::thread::sleep(RECORD_WAIT_INTERVAL);
+    }
+    None
+}
+
+fn forward(record: &ListenerRecord, launch: Option<&ForwardedLaunch>) -> Result<()> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], record.port));
+    let mut stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)?;
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let message = ForwardMessage {
+        token: record.token.clone(),
+        probe: launch.is_none(),
+        launch: launch.cloned().unwrap_or_default(),
+    };
+    let mut line = serde_json::to_string(&message)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes())?;
+
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    if reply.trim() != "ok" {
+        return Err(anyhow!("unexpected reply {:?}", reply.trim()));
+    }
+    Ok(())
+}
+
+/// Accept forwarded launches on a background thread until the process
+/// exits. Messages without the instance token are dropped; probes are
+/// answered without calling `on_launch`.
+pub fn serve(instance: PrimaryInstance, on_launch: impl Fn(ForwardedLaunch) + Send + 'static) {
+    let PrimaryInstance { listener, token } = instance;
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            match read_message(&stream, &token) {
+                Ok(launch) => {
+                    let _ = (&stream).write_all(b"ok\n");
+                    if let Some(launch) = launch {
+                        on_launch(launch);
+                    }
+                }
+                Err(err) => log::warn!("[desktop] rejected forwarded launch: {err}"),
+            }
+        }
+    });
+}
+
+fn read_message(stream: &TcpStream, token: &str) -> Result<Option<ForwardedLaunch>> {
+    stream.set_read_timeout(Some(IO_TIMEOUT))?;
+    stream.set_write_timeout(Some(IO_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(stream.take(MAX_MESSAGE_BYTES)).read_line(&mut line)?;
+    let message: ForwardMessage = serde_json::from_str(line.trim())?;
+    if message.token != token {
+        return Err(anyhow!("token mismatch"));
+    }
+    Ok((!message.probe).then_some(message.launch))
+}
+
+/// Remove the record at `path` if it still belongs to this process.
+pub fn release(path: &Path) {
+    let owned = fs::read_to_string(path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<ListenerRecord>(&raw).ok())
+        .is_some_and(|record| record.pid == std::process::id());
+    if owned {
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// Interpret launch arguments. `--ssh-connect`, `--project` and `--host`
+/// win in that order; otherwise the positional argument is an
+/// `openchamber://` link, an http(s) URL, an existing directory (a file
+/// opens its directory), or else a host id.
+pub fn parse_request(launch: &ForwardedLaunch) -> Result<LaunchRequest> {
+    let args = cli::parse(&launch.args).map_err(|err| anyhow!(err))?;
+    let cwd = launch.cwd.as_deref().map(Path::new);
+    let target = if let Some(id) = args.ssh_connect {
+        LaunchTarget::SshConnect(id)
+    } else if let Some(project) = args.project {
+        let path = cli::absolute_path(Path::new(&project), cwd);
+        LaunchTarget::Project(fs::canonicalize(&path).unwrap_or(path))
+    } else if let Some(host) = args.host {
+        http_url(&host)
+            .map(LaunchTarget::Url)
+            .unwrap_or(LaunchTarget::HostId(host))
+    } else if let Some(arg) = args.positional {
+        positional_target(&arg, cwd)
+    } else {
+        LaunchTarget::Focus
+    };
+    Ok(LaunchRequest {
+        target,
+        new_window: args.new_window,
+    })
+}
+
+fn http_url(raw: &str) -> Option<String> {
+    url::Url::parse(raw)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .map(|url| url.to_string())
+}
+
+fn positional_target(arg: &str, cwd: Option<&Path>) -> LaunchTarget {
+    if deep_link::is_deep_link(arg) {
+        return LaunchTarget::DeepLink(arg.to_string());
+    }
+    if let Some(url) = http_url(arg) {
+        return LaunchTarget::Url(url);
+    }
+
+    let mut path = cli::absolute_path(Path::new(arg), cwd);
+    if path.is_file() {
+        if let Some(parent) = path.parent() {
+            path = parent.to_path_buf();
+        }
+    }
+    if path.is_dir() {
+        return LaunchTarget::Project(fs::canonicalize(&path).unwrap_or(path));
+    }
+
+    LaunchTarget::HostId(arg.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "openchamber-single-instance-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("temp dir");
+        dir
+    }
+
+    fn launch(args: &[&str], cwd: Option<&Path>) -> ForwardedLaunch {
+        ForwardedLaunch {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            cwd: cwd.map(|dir| dir.to_string_lossy().to_string()),
+        }
+    }
+
+    #[test]
+    fn second_launch_is_forwarded_and_stale_records_are_replaced() {
+        let dir = temp_dir("forward");
+        let path = dir.join(INSTANCE_FILE_NAME);
+
+        // A record from a crashed instance nobody listens on.
+        let stale = ListenerRecord {
+            pid: 0,
+            port: TcpListener::bind("127.0.0.1:0")
+                .and_then(|listener| listener.local_addr())
+                .map(|addr| addr.port())
+                .expect("port"),
+            token: "stale".to_string(),
+        };
+        fs::write(&path, serde_json::to_string(&stale).expect("json")).expect("write");
+
+        let Acquired::Primary(primary) = acquire(&path, Some(&launch(&[], None))).expect("acquire")
+        else {
+            panic!("expected to become the running instance");
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).expect("record").permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let (tx, rx) = mpsc::channel();
+        serve(primary, move |forwarded| {
+            let _ = tx.send(forwarded);
+        });
+
+        // A headless launch only probes, so the running instance opens nothing.
+        assert!(matches!(
+            acquire(&path, None).expect("probe"),
+            Acquired::Running
+        ));
+        let second = launch(&["https://example.test"], Some(&dir));
+        assert!(matches!(
+            acquire(&path, Some(&second)).expect("forward"),
+            Acquired::Forwarded
+        ));
+        assert_eq!(rx.recv_timeout(IO_TIMEOUT).expect("forwarded"), second);
+        assert!(rx.try_recv().is_err());
+
+        release(&path);
+        assert!(!path.exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    fn target(args: &[&str], cwd: Option<&Path>) -> LaunchTarget {
+        parse_request(&launch(args, cwd)).expect("parse").target
+    }
+
+    #[test]
+    fn parse_request_resolves_urls_projects_and_host_ids() {
+        let dir = temp_dir("target");
+        fs::create_dir_all(dir.join("project")).expect("project dir");
+        fs::write(dir.join("project/README.md"), "").expect("file");
+        let project = fs::canonicalize(dir.join("project")).expect("canonical");
+
+        assert_eq!(target(&[], None), LaunchTarget::Focus);
+        assert_eq!(target(&["-psn_0_12345"], None), LaunchTarget::Focus);
+        assert_eq!(
+            target(&["https://example.test/x"], None),
+            LaunchTarget::Url("https://example.test/x".to_string())
+        );
+        assert_eq!(
+            target(&["project"], Some(&dir)),
+            LaunchTarget::Project(project.clone())
+        );
+        assert_eq!(
+            target(&["project/README.md"], Some(&dir)),
+            LaunchTarget::Project(project.clone())
+        );
+        assert_eq!(
+            target(&["openchamber://ssh/connect/gpu-box"], None),
+            LaunchTarget::DeepLink("openchamber://ssh/connect/gpu-box".to_string())
+        );
+        assert_eq!(
+            target(&["gpu-box"], Some(&dir)),
+            LaunchTarget::HostId("gpu-box".to_string())
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn parse_request_prefers_flags_over_positional() {
+        let dir = temp_dir("flags");
+        fs::create_dir_all(dir.join("project")).expect("project dir");
+        let project = fs::canonicalize(dir.join("project")).expect("canonical");
+
+        assert_eq!(
+            parse_request(&launch(&["--new-window", "--host", "gpu-box"], None)).expect("host"),
+            LaunchRequest {
+                target: LaunchTarget::HostId("gpu-box".to_string()),
+                new_window: true,
+            }
+        );
+        assert_eq!(
+            target(&["--host=http://10.0.0.5:3000", "other"], None),
+            LaunchTarget::Url("http://10.0.0.5:3000/".to_string())
+        );
+        assert_eq!(
+            target(&["--project", "project", "--host", "gpu-box"], Some(&dir)),
+            LaunchTarget::Project(project)
+        );
+        assert_eq!(
+            target(
+                &["--ssh-connect", "prod", "--project", "project"],
+                Some(&dir)
+            ),
+            LaunchTarget::SshConnect("prod".to_string())
+        );
+        assert!(parse_request(&launch(&["--bogus"], None)).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,987 @@
+use crate::remote_ssh::{
+    DesktopSshParsedCommand, DesktopSshPortForward, DesktopSshPortForwardType,
+};
+use anyhow::{anyhow, Context, Result};
+use std::{
+    collections::HashMap,
+    fmt,
+    io::Read,
+    path::Path,
+    process::{Child, Command, Stdio},
+};
+
+pub const DEFAULT_CONTROL_PERSIST_SEC: u32 = 300;
+
+/// Exit state of a transport-owned process. Kept independent of
+/// `std::process::ExitStatus` so fakes can report exits without spawning.
+#[derive(Clone, Debug)]
+pub struct SshProcessExit {
+    pub success: bool,
+    pub detail: String,
+}
+
+impl fmt::Display for SshProcessExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.detail)
+    }
+}
+
+/// Long-lived process owned by an SSH session (ControlMaster or main forward).
+pub trait SshProcess: Send {
+    fn try_wait(&mut self) -> Option<SshProcessExit>;
+    fn take_stderr(&mut self) -> String;
+    fn kill(&mut self);
+}
+
+/// Stand-in for a ControlMaster the session attached to but does not own.
+/// Its liveness is only observable through `check_master`.
+pub struct ExternalMaster;
+
+impl SshProcess for ExternalMaster {
+    fn try_wait(&mut self) -> Option<SshProcessExit> {
+        None
+    }
+
+    fn take_stderr(&mut self) -> String {
+        String::new()
+    }
+
+    fn kill(&mut self) {}
+}
+
+/// ControlMaster tuning for masters this app spawns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SshMasterOptions {
+    /// `0` keeps the master in the foreground (`ControlPersist=no`).
+    pub persist_sec: u32,
+    pub keep_alive_interval_sec: Option<u16>,
+    pub compression: bool,
+}
+
+impl Default for SshMasterOptions {
+    fn default() -> Self {
+        Self {
+            persist_sec: DEFAULT_CONTROL_PERSIST_SEC,
+            keep_alive_interval_sec: None,
+            compression: false,
+        }
+    }
+}
+
+impl SshProcess for Child {
+    fn try_wait(&mut self) -> Option<SshProcessExit> {
+        Child::try_wait(self)
+            .ok()
+            .flatten()
+            .map(|status| SshProcessExit {
+                success: status.success(),
+                detail: status.to_string(),
+            })
+    }
+
+    fn take_stderr(&mut self) -> String {
+        let mut stderr = String::new();
+        if let Some(mut stream) = self.stderr.take() {
+            let _ = stream.read_to_string(&mut stderr);
+        }
+        stderr
+    }
+
+    fn kill(&mut self) {
+        let _ = Child::kill(self);
+        let _ = self.wait();
+    }
+}
+
+/// Everything the SSH manager needs from the wire. The manager owns the
+/// state machine (phases, retries, monitoring); the transport only knows
+/// how to talk to a host through a ControlMaster socket.
+pub trait SshTransport: Send + Sync {
+    /// Resolve the effective client configuration (`ssh -G`). Keys are
+    /// lowercased; repeated keys such as `identityfile` keep every value.
+    fn resolve_config(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+    ) -> Result<HashMap<String, Vec<String>>>;
+
+    /// Keys currently loaded in the local `ssh-agent`.
+    fn agent_identities(&self) -> Result<Vec<SshAgentIdentity>>;
+
+    fn spawn_master(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        askpass_path: &Path,
+        askpass_env: &[(String, String)],
+        options: &SshMasterOptions,
+    ) -> Result<Box<dyn SshProcess>>;
+
+    fn check_master(&self, parsed: &DesktopSshParsedCommand, control_path: &Path) -> bool;
+
+    fn stop_master(&self, parsed: &DesktopSshParsedCommand, control_path: &Path);
+
+    /// Run `script` through `sh -lc` on the remote host and return stdout.
+    fn run_command(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        script: &str,
+        timeout_sec: u16,
+    ) -> Result<String>;
+
+    /// Start the forward that anchors the main OpenChamber tunnel.
+    fn spawn_main_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        bind_host: &str,
+        local_port: u16,
+        remote_port: u16,
+    ) -> Result<Box<dyn SshProcess>>;
+
+    /// Add a user-configured forward to the running ControlMaster.
+    fn add_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        forward: &DesktopSshPortForward,
+    ) -> Result<()>;
+
+    fn cancel_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        forward: &DesktopSshPortForward,
+    ) -> Result<()>;
+}
+
+/// One key loaded in `ssh-agent`, as reported by `ssh-add -l` and `-L`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SshAgentIdentity {
+    pub fingerprint: String,
+    pub bits: Option<u32>,
+    pub key_type: Option<String>,
+    pub comment: String,
+    pub public_key: String,
+}
+
+pub fn shell_quote(value: &str) -> String {
+    let escaped = value.replace('\'', "'\\''");
+    format!("'{escaped}'")
+}
+
+/// Wrap IPv6 literals in brackets so they can be joined with a port.
+pub fn bracket_host(host: &str) -> String {
+    if host.contains(':') && !host.starts_with('[') {
+        format!("[{host}]")
+    } else {
+        host.to_string()
+    }
+}
+
+fn run_output(command: &mut Command) -> Result<(i32, String, String)> {
+    let output = command
+        .output()
+        .with_context(|| format!("failed to execute command: {:?}", command))?;
+
+    let code = output.status.code().unwrap_or(-1);
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    Ok((code, stdout, stderr))
+}
+
+fn build_ssh_command(
+    parsed: &DesktopSshParsedCommand,
+    pre_destination_args: &[String],
+    remote_command: Option<&str>,
+) -> Command {
+    let mut command = Command::new("ssh");
+    command
+        .args(&parsed.args)
+        .args(pre_destination_args)
+        .arg(&parsed.destination);
+    if let Some(remote) = remote_command {
+        command.arg(remote);
+    }
+    command
+}
+
+/// Pair `ssh-add -l -E sha256` lines with `ssh-add -L` lines. Both list the
+/// agent's keys in the same order.
+pub fn parse_agent_identities(listing: &str, public_keys: &str) -> Vec<SshAgentIdentity> {
+    let public_keys: Vec<&str> = public_keys
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    listing
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .filter_map(|(index, line)| {
+            // "256 SHA256:abc... comment words (ED25519)"
+            let mut parts = line.splitn(3, ' ');
+            let bits = parts.next()?.parse::<u32>().ok();
+            let fingerprint = parts.next()?.to_string();
+            if !fingerprint.contains(':') {
+                return None;
+            }
+            let rest = parts.next().unwrap_or_default().trim();
+            let (comment, key_type) = match rest.rfind(" (") {
+                Some(pos) if rest.ends_with(')') => (
+                    rest[..pos].trim().to_string(),
+                    Some(rest[pos + 2..rest.len() - 1].to_string()),
+                ),
+                _ => (rest.to_string(), None),
+            };
+            Some(SshAgentIdentity {
+                fingerprint,
+                bits,
+                key_type,
+                comment,
+                public_key: public_keys
+                    .get(index)
+                    .copied()
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect()
+}
+
+fn master_args(control_path: &Path, options: &SshMasterOptions) -> Vec<String> {
+    let persist = match options.persist_sec {
+        0 => "no".to_string(),
+        secs => secs.to_string(),
+    };
+    let mut args = vec![
+        "-o".to_string(),
+        "ControlMaster=yes".to_string(),
+        "-o".to_string(),
+        format!("ControlPath={}", control_path.display()),
+        "-o".to_string(),
+        format!("ControlPersist={persist}"),
+    ];
+    if let Some(interval) = options.keep_alive_interval_sec {
+        args.push("-o".to_string());
+        args.push(format!("ServerAliveInterval={interval}"));
+    }
+    if options.compression {
+        args.push("-o".to_string());
+        args.push("Compression=yes".to_string());
+    }
+    args.push("-N".to_string());
+    args
+}
+
+fn control_args(control_path: &Path) -> Vec<String> {
+    vec![
+        "-o".to_string(),
+        "ControlMaster=no".to_string(),
+        "-o".to_string(),
+        format!("ControlPath={}", control_path.display()),
+    ]
+}
+
+/// Render a user forward into its `-L`/`-R`/`-D` flag and spec.
+fn forward_spec(forward: &DesktopSshPortForward) -> Result<(&'static str, String)> {
+    let host_or_loopback = |value: &Option<String>| -> String {
+        value
+            .as_deref()
+            .filter(|v| !v.trim().is_empty())
+            .map(bracket_host)
+            .unwrap_or_else(|| "127.0.0.1".to_string())
+    };
+
+    match forward.forward_type {
+        DesktopSshPortForwardType::Local => {
+            let local_host = host_or_loopback(&forward.local_host);
+            let local_port = forward
+                .local_port
+                .ok_or_else(|| anyhow!("Missing local port"))?;
+            let remote_host = host_or_loopback(&forward.remote_host);
+            let remote_port = forward
+                .remote_port
+                .ok_or_else(|| anyhow!("Missing remote port"))?;
+            Ok((
+                "-L",
+                format!("{local_host}:{local_port}:{remote_host}:{remote_port}"),
+            ))
+        }
+        DesktopSshPortForwardType::Remote => {
+            let remote_host = host_or_loopback(&forward.remote_host);
+            let remote_port = forward
+                .remote_port
+                .ok_or_else(|| anyhow!("Missing remote port"))?;
+            let local_host = host_or_loopback(&forward.local_host);
+            let local_port = forward
+                .local_port
+                .ok_or_else(|| anyhow!("Missing local port"))?;
+            Ok((
+                "-R",
+                format!("{remote_host}:{remote_port}:{local_host}:{local_port}"),
+            ))
+        }
+        DesktopSshPortForwardType::Dynamic => {
+            let local_host = host_or_loopback(&forward.local_host);
+            let local_port = forward
+                .local_port
+                .ok_or_else(|| anyhow!("Missing local port"))?;
+            Ok(("-D", format!("{local_host}:{local_port}")))
+        }
+    }
+}
+
+/// Transport backed by the system `ssh` binary and OpenSSH multiplexing.
+#[derive(Default)]
+pub struct OpenSshTransport;
+
+impl OpenSshTransport {
+    fn forward_operation(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        forward: &DesktopSshPortForward,
+        op: &str,
+    ) -> Result<()> {
+        let (flag, spec) = forward_spec(forward)?;
+        let mut args = control_args(control_path);
+        args.push("-O".to_string());
+        args.push(op.to_string());
+        args.push(flag.to_string());
+        args.push(spec);
+
+        let mut command = build_ssh_command(parsed, &args, None);
+        let (code, stdout, stderr) = run_output(&mut command)
+            .with_context(|| format!("Failed to {op} extra SSH forward {}", forward.id))?;
+        if code != 0 {
+            let detail = if stderr.trim().is_empty() {
+                stdout.trim()
+            } else {
+                stderr.trim()
+            };
+            return Err(anyhow!(format!(
+                "Failed to {op} extra SSH forward {}: {}",
+                forward.id,
+                if detail.is_empty() {
+                    "unknown error"
+                } else {
+                    detail
+                }
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl SshTransport for OpenSshTransport {
+    fn resolve_config(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+    ) -> Result<HashMap<String, Vec<String>>> {
+        let args = vec!["-G".to_string()];
+        let mut command = build_ssh_command(parsed, &args, None);
+        let (code, stdout, stderr) = run_output(&mut command)?;
+        if code != 0 {
+            return Err(anyhow!(stderr.trim().to_string()));
+        }
+
+        let mut resolved: HashMap<String, Vec<String>> = HashMap::new();
+        for line in stdout.lines() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let mut parts = trimmed.splitn(2, ' ');
+            let key = parts.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let value = parts.next().unwrap_or_default().trim();
+            if key.is_empty() || value.is_empty() {
+                continue;
+            }
+            resolved.entry(key).or_default().push(value.to_string());
+        }
+        Ok(resolved)
+    }
+
+    fn agent_identities(&self) -> Result<Vec<SshAgentIdentity>> {
+        let (code, listing, stderr) =
+            run_output(Command::new("ssh-add").args(["-l", "-E", "sha256"]))?;
+        // 1 means the agent has no identities; 2 means no agent is reachable.
+        match code {
+            0 => {}
+            1 => return Ok(Vec::new()),
+            _ => {
+                let detail = stderr.trim();
+                return Err(anyhow!(if detail.is_empty() {
+                    "ssh-agent is not available".to_string()
+                } else {
+                    detail.to_string()
+                }));
+            }
+        }
+        let (_, public_keys, _) = run_output(Command::new("ssh-add").arg("-L"))?;
+        Ok(parse_agent_identities(&listing, &public_keys))
+    }
+
+    fn spawn_master(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        askpass_path: &Path,
+        askpass_env: &[(String, String)],
+        options: &SshMasterOptions,
+    ) -> Result<Box<dyn SshProcess>> {
+        let args = master_args(control_path, options);
+        let mut command = build_ssh_command(parsed, &args, None);
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .env("SSH_ASKPASS_REQUIRE", "force")
+            .env("SSH_ASKPASS", askpass_path)
+            .env("DISPLAY", "1");
+
+        for (key, value) in askpass_env {
+            command.env(key, value);
+        }
+
+        let child = command.spawn().with_context(|| {
+            format!(
+                "failed to start SSH ControlMaster for {}",
+                parsed.destination
+            )
+        })?;
+        Ok(Box::new(child))
+    }
+
+    fn check_master(&self, parsed: &DesktopSshParsedCommand, control_path: &Path) -> bool {
+        let mut args = control_args(control_path);
+        args.extend([
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            "ConnectTimeout=3".to_string(),
+            "-O".to_string(),
+            "check".to_string(),
+        ]);
+        let mut command = build_ssh_command(parsed, &args, None);
+        run_output(&mut command)
+            .map(|(code, _, _)| code == 0)
+            .unwrap_or(false)
+    }
+
+    fn stop_master(&self, parsed: &DesktopSshParsedCommand, control_path: &Path) {
+        let mut args = control_args(control_path);
+        args.extend([
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+            "-o".to_string(),
+            "ConnectTimeout=3".to_string(),
+            "-O".to_string(),
+            "exit".to_string(),
+        ]);
+        let mut command = build_ssh_command(parsed, &args, None);
+        let _ = run_output(&mut command);
+    }
+
+    fn run_command(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        script: &str,
+        timeout_sec: u16,
+    ) -> Result<String> {
+        let mut args = control_args(control_path);
+        args.extend([
+            "-o".to_string(),
+            format!("ConnectTimeout={timeout_sec}"),
+            "-T".to_string(),
+        ]);
+        let remote = format!("sh -lc {}", shell_quote(script));
+        let mut command = build_ssh_command(parsed, &args, Some(&remote));
+        let (code, stdout, stderr) = run_output(&mut command)?;
+        if code != 0 {
+            if stderr.trim().is_empty() {
+                return Err(anyhow!("Remote command failed"));
+            }
+            return Err(anyhow!(stderr.trim().to_string()));
+        }
+        Ok(stdout)
+    }
+
+    fn spawn_main_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        bind_host: &str,
+        local_port: u16,
+        remote_port: u16,
+    ) -> Result<Box<dyn SshProcess>> {
+        let mut args = control_args(control_path);
+        args.extend([
+            "-N".to_string(),
+            "-L".to_string(),
+            format!(
+                "{}:{local_port}:127.0.0.1:{remote_port}",
+                bracket_host(bind_host)
+            ),
+        ]);
+        let mut command = build_ssh_command(parsed, &args, None);
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| {
+                format!("Failed to start main SSH forward on local port {local_port}")
+            })?;
+        Ok(Box::new(child))
+    }
+
+    fn add_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        forward: &DesktopSshPortForward,
+    ) -> Result<()> {
+        self.forward_operation(parsed, control_path, forward, "forward")
+    }
+
+    fn cancel_forward(
+        &self,
+        parsed: &DesktopSshParsedCommand,
+        control_path: &Path,
+        forward: &DesktopSshPortForward,
+    ) -> Result<()> {
+        self.forward_operation(parsed, control_path, forward, "cancel")
+    }
+}
+
+/// Scriptable in-process transport for driving the SSH manager in tests.
+///
+/// Remote commands are answered from an ordered list of `(needle, reply)`
+/// rules matched by substring; the main forward binds a real loopback
+/// listener that answers every request with `200 OK` so the manager's
+/// readiness and reachability probes work unchanged.
+#[cfg(test)]
+pub mod fake {
+    use super::*;
+    use std::{
+        io::Write,
+        net::TcpListener,
+        path::PathBuf,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    pub type ExitCell = Arc<Mutex<Option<SshProcessExit>>>;
+
+    /// Process whose exit is driven through `exit`; killing it also sets
+    /// `stop` so any listener it backs goes away.
+    pub struct FakeProcess {
+        pub exit: ExitCell,
+        pub stderr: String,
+        pub stop: Option<Arc<AtomicBool>>,
+    }
+
+    impl SshProcess for FakeProcess {
+        fn try_wait(&mut self) -> Option<SshProcessExit> {
+            self.exit.lock().expect("fake exit mutex").clone()
+        }
+
+        fn take_stderr(&mut self) -> String {
+            std::mem::take(&mut self.stderr)
+        }
+
+        fn kill(&mut self) {
+            if let Some(stop) = self.stop.as_ref() {
+                stop.store(true, Ordering::SeqCst);
+            }
+            let mut exit = self.exit.lock().expect("fake exit mutex");
+            if exit.is_none() {
+                *exit = Some(SshProcessExit {
+                    success: false,
+                    detail: "signal: 9 (SIGKILL)".to_string(),
+                });
+            }
+        }
+    }
+
+    #[derive(Default)]
+    struct FakeState {
+        master_error: Option<String>,
+        master_alive: bool,
+        user_master: Option<PathBuf>,
+        master_options: Vec<SshMasterOptions>,
+        stopped_masters: Vec<PathBuf>,
+        forward_exit: Option<ExitCell>,
+        forward_stop: Option<Arc<AtomicBool>>,
+        rules: Vec<(String, Result<String, String>)>,
+        agent_identities: Vec<SshAgentIdentity>,
+        identity_files: Vec<String>,
+        commands: Vec<String>,
+        forwards: Vec<String>,
+        cancelled_forwards: Vec<String>,
+    }
+
+    pub struct FakeSshTransport {
+        state: Mutex<FakeState>,
+    }
+
+    impl Default for FakeSshTransport {
+        fn default() -> Self {
+            let rules = vec![
+                (
+                    "INFO_STATUS".to_string(),
+                    Ok("INFO_STATUS=200\nAUTH_STATUS=0\nHEALTH_STATUS=200\n{}".to_string()),
+                ),
+                ("uname -s".to_string(), Ok("Linux\n".to_string())),
+                ("command -v".to_string(), Ok("yes\n".to_string())),
+            ];
+            Self {
+                state: Mutex::new(FakeState {
+                    rules,
+                    ..FakeState::default()
+                }),
+            }
+        }
+    }
+
+    impl FakeSshTransport {
+        /// Answer remote commands containing `needle`. Later rules win.
+        pub fn respond(&self, needle: &str, reply: Result<&str, &str>) {
+            let reply = reply.map(str::to_string).map_err(str::to_string);
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .rules
+                .insert(0, (needle.to_string(), reply));
+        }
+
+        /// Make the next ControlMaster exit immediately with `stderr`.
+        pub fn fail_master(&self, stderr: &str) {
+            self.state.lock().expect("fake state mutex").master_error = Some(stderr.to_string());
+        }
+
+        /// Simulate the main forward exiting with an error and its local
+        /// listener going away.
+        pub fn drop_main_forward(&self) {
+            let state = self.state.lock().expect("fake state mutex");
+            if let Some(stop) = state.forward_stop.as_ref() {
+                stop.store(true, Ordering::SeqCst);
+            }
+            if let Some(exit) = state.forward_exit.as_ref() {
+                *exit.lock().expect("fake exit mutex") = Some(SshProcessExit {
+                    success: false,
+                    detail: "exit status: 255".to_string(),
+                });
+            }
+        }
+
+        /// Pretend the user already runs a ControlMaster at `path`, as
+        /// reported by `resolve_config`.
+        pub fn set_user_master(&self, path: &Path) {
+            self.state.lock().expect("fake state mutex").user_master = Some(path.to_path_buf());
+        }
+
+        /// Options of every ControlMaster spawned so far.
+        pub fn master_options(&self) -> Vec<SshMasterOptions> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .master_options
+                .clone()
+        }
+
+        pub fn stopped_masters(&self) -> Vec<PathBuf> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .stopped_masters
+                .clone()
+        }
+
+        pub fn set_agent_identities(&self, identities: Vec<SshAgentIdentity>) {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .agent_identities = identities;
+        }
+
+        /// IdentityFile values reported by `resolve_config`.
+        pub fn set_identity_files(&self, files: &[&str]) {
+            self.state.lock().expect("fake state mutex").identity_files =
+                files.iter().map(|file| file.to_string()).collect();
+        }
+
+        pub fn commands(&self) -> Vec<String> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .commands
+                .clone()
+        }
+
+        pub fn forwards(&self) -> Vec<String> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .forwards
+                .clone()
+        }
+
+        pub fn cancelled_forwards(&self) -> Vec<String> {
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .cancelled_forwards
+                .clone()
+        }
+    }
+
+    /// Answer every HTTP request with `200 OK` until `stop` is set.
+    pub fn serve_ok(listener: TcpListener, stop: Arc<AtomicBool>) {
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((mut stream, _)) => {
+                        let _ = stream.set_nonblocking(false);
+                        let _ = stream.set_read_timeout(Some(Duration::from_millis(200)));
+                        let mut buf = [0u8; 1024];
+                        let _ = stream.read(&mut buf);
+                        let _ = stream.write_all(
+                            b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                        );
+                    }
+                    Err(_) => std::thread::sleep(Duration::from_millis(10)),
+                }
+            }
+        });
+    }
+
+    impl SshTransport for FakeSshTransport {
+        fn resolve_config(
+            &self,
+            parsed: &DesktopSshParsedCommand,
+        ) -> Result<HashMap<String, Vec<String>>> {
+            let mut resolved = HashMap::new();
+            resolved.insert("hostname".to_string(), vec![parsed.destination.clone()]);
+            let state = self.state.lock().expect("fake state mutex");
+            if !state.identity_files.is_empty() {
+                resolved.insert("identityfile".to_string(), state.identity_files.clone());
+            }
+            if let Some(path) = state.user_master.as_ref() {
+                resolved.insert("controlpath".to_string(), vec![path.display().to_string()]);
+            }
+            Ok(resolved)
+        }
+
+        fn agent_identities(&self) -> Result<Vec<SshAgentIdentity>> {
+            Ok(self
+                .state
+                .lock()
+                .expect("fake state mutex")
+                .agent_identities
+                .clone())
+        }
+
+        fn spawn_master(
+            &self,
+            _parsed: &DesktopSshParsedCommand,
+            _control_path: &Path,
+            _askpass_path: &Path,
+            _askpass_env: &[(String, String)],
+            options: &SshMasterOptions,
+        ) -> Result<Box<dyn SshProcess>> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            state.master_options.push(options.clone());
+            let exit: ExitCell = Arc::new(Mutex::new(None));
+            let mut stderr = String::new();
+            if let Some(error) = state.master_error.take() {
+                *exit.lock().expect("fake exit mutex") = Some(SshProcessExit {
+                    success: false,
+                    detail: "exit status: 255".to_string(),
+                });
+                stderr = error;
+                state.master_alive = false;
+            } else {
+                state.master_alive = true;
+            }
+            Ok(Box::new(FakeProcess {
+                exit,
+                stderr,
+                stop: None,
+            }))
+        }
+
+        fn check_master(&self, _parsed: &DesktopSshParsedCommand, control_path: &Path) -> bool {
+            let state = self.state.lock().expect("fake state mutex");
+            state.master_alive || state.user_master.as_deref() == Some(control_path)
+        }
+
+        fn stop_master(&self, _parsed: &DesktopSshParsedCommand, control_path: &Path) {
+            let mut state = self.state.lock().expect("fake state mutex");
+            state.stopped_masters.push(control_path.to_path_buf());
+            if state.user_master.as_deref() == Some(control_path) {
+                state.user_master = None;
+            } else {
+                state.master_alive = false;
+            }
+        }
+
+        fn run_command(
+            &self,
+            _parsed: &DesktopSshParsedCommand,
+            control_path: &Path,
+            script: &str,
+            _timeout_sec: u16,
+        ) -> Result<String> {
+            let mut state = self.state.lock().expect("fake state mutex");
+            if !state.master_alive && state.user_master.as_deref() != Some(control_path) {
+                return Err(anyhow!("Control socket connect: Connection refused"));
+            }
+            state.commands.push(script.to_string());
+            let reply = state
+                .rules
+                .iter()
+                .find(|(needle, _)| script.contains(needle.as_str()))
+                .map(|(_, reply)| reply.clone())
+                .unwrap_or_else(|| Ok(String::new()));
+            reply.map_err(|err| anyhow!(err))
+        }
+
+        fn spawn_main_forward(
+            &self,
+            _parsed: &DesktopSshParsedCommand,
+            _control_path: &Path,
+            bind_host: &str,
+            local_port: u16,
+            remote_port: u16,
+        ) -> Result<Box<dyn SshProcess>> {
+            let listener = TcpListener::bind((bind_host, local_port))
+                .with_context(|| format!("fake forward failed to bind {bind_host}:{local_port}"))?;
+            listener.set_nonblocking(true)?;
+            let stop = Arc::new(AtomicBool::new(false));
+            serve_ok(listener, Arc::clone(&stop));
+
+            let exit: ExitCell = Arc::new(Mutex::new(None));
+            let mut state = self.state.lock().expect("fake state mutex");
+            state.forwards.push(format!(
+                "{}:{local_port}:127.0.0.1:{remote_port}",
+                bracket_host(bind_host)
+            ));
+            state.forward_exit = Some(Arc::clone(&exit));
+            state.forward_stop = Some(Arc::clone(&stop));
+            Ok(Box::new(FakeProcess {
+                exit,
+                stderr: String::new(),
+                stop: Some(stop),
+            }))
+        }
+
+        fn add_forward(
+            &self,
+            _parsed: &DesktopSshParsedCommand,
+            _control_path: &Path,
+            forward: &DesktopSshPortForward,
+        ) -> Result<()> {
+            let (flag, spec) = forward_spec(forward)?;
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .forwards
+                .push(format!("{flag} {spec}"));
+            Ok(())
+        }
+
+        fn cancel_forward(
+            &self,
+            _parsed: &DesktopSshParsedCommand,
+            _control_path: &Path,
+            forward: &DesktopSshPortForward,
+        ) -> Result<()> {
+            let (flag, spec) = forward_spec(forward)?;
+            self.state
+                .lock()
+                .expect("fake state mutex")
+                .cancelled_forwards
+                .push(format!("{flag} {spec}"));
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_agent_identities_pairs_fingerprints_with_public_keys() {
+        let listing = "256 SHA256:abc me@laptop (ED25519)\n\
+                       3072 SHA256:def deploy key (RSA)\n";
+        let public_keys = "ssh-ed25519 AAAA1 me@laptop\nssh-rsa AAAA2 deploy key\n";
+
+        let identities = parse_agent_identities(listing, public_keys);
+        assert_eq!(identities.len(), 2);
+        assert_eq!(identities[0].fingerprint, "SHA256:abc");
+        assert_eq!(identities[0].bits, Some(256));
+        assert_eq!(identities[0].key_type.as_deref(), Some("ED25519"));
+        assert_eq!(identities[0].comment, "me@laptop");
+        assert_eq!(identities[0].public_key, "ssh-ed25519 AAAA1 me@laptop");
+        assert_eq!(identities[1].comment, "deploy key");
+        assert_eq!(identities[1].bits, Some(3072));
+    }
+    #[test]
+    fn master_args_apply_multiplexing_options() {
+        let path = Path::new("/tmp/oc.sock");
+        let defaults = master_args(path, &SshMasterOptions::default());
+        assert!(defaults.contains(&"ControlPersist=300".to_string()));
+        assert!(!defaults.iter().any(|arg| arg.starts_with("ServerAlive")));
+
+        let tuned = master_args(
+            path,
+            &SshMasterOptions {
+                persist_sec: 0,
+                keep_alive_interval_sec: Some(15),
+                compression: true,
+            },
+        );
+        assert!(tuned.contains(&"ControlPersist=no".to_string()));
+        assert!(tuned.contains(&"ServerAliveInterval=15".to_string()));
+        assert!(tuned.contains(&"Compression=yes".to_string()));
+        assert_eq!(tuned.last().map(String::as_str), Some("-N"));
+    }
+
+    #[test]
+    fn forward_spec_brackets_ipv6_hosts() {
+        let forward = DesktopSshPortForward {
+            id: "db".to_string(),
+            enabled: true,
+            forward_type: DesktopSshPortForwardType::Local,
+            local_host: Some("::1".to_string()),
+            local_port: Some(5432),
+            remote_host: Some("fd00::5".to_string()),
+            remote_port: Some(5432),
+        };
+        assert_eq!(
+            forward_spec(&forward).expect("spec"),
+            ("-L", "[::1]:5432:[fd00::5]:5432".to_string())
+        );
+
+        let socks = DesktopSshPortForward {
+            forward_type: DesktopSshPortForwardType::Dynamic,
+            local_host: Some("10.8.0.2".to_string()),
+            remote_host: None,
+            remote_port: None,
+            ..forward
+        };
+        assert_eq!(
+            forward_spec(&socks).expect("spec"),
+            ("-D", "10.8.0.2:5432".to_string())
+        );
+    }
+}
@@ -12,6 +12,7 @@ import { ErrorBoundary } from '@/components/ui/ErrorBoundary';
 // useEventStream removed — replaced by SyncProvider + SyncBridge
 import { useKeyboardShortcuts } from '@/hooks/useKeyboardShortcuts';
 import { useMenuActions } from '@/hooks/useMenuActions';
+import { useDesktopOpenProject } from '@/hooks/useDesktopOpenProject';
 import { useSessionStatusBootstrap } from '@/hooks/useSessionStatusBootstrap';
 import { useSessionAutoCleanup } from '@/hooks/useSessionAutoCleanup';
 import { useQueuedMessageAutoSend } from '@/hooks/useQueuedMessageAutoSend';
@@ -624,6 +625,24 @@ function App({ apis }: AppProps) {
     return () => window.removeEventListener('openchamber:open-project', handler as EventListener);
   }, []);
 
+  // The desktop shell gives up restarting a crashing sidecar by re-injecting
+  // a recovery boot outcome; switch to it without waiting for a reload.
+  React.useEffect(() => {
+    if (!isDesktopRuntime) return;
+
+    const handler = (event: Event) => {
+      const detail = (event as CustomEvent<{ gaveUp?: boolean }>).detail;
+      if (detail?.gaveUp !== true) return;
+      const outcome = getInjectedBootOutcome();
+      if (!outcome) return;
+      setBootInjectionStatus('valid');
+      setBootView(resolveDesktopBootView({ isDesktopShell: true, bootOutcome: outcome }));
+    };
+
+    window.addEventListener('openchamber:sidecar-down', handler as EventListener);
+    return () => window.removeEventListener('openchamber:sidecar-down', handler as EventListener);
+  }, [isDesktopRuntime]);
+
   React.useEffect(() => {
     if (typeof window === 'undefined') return;
     if (!isInitialized || isSwitchingDirectory) return;
@@ -649,6 +668,7 @@ function App({ apis }: AppProps) {
   }, []);
 
   useMenuActions(handleToggleMemoryDebug);
+  useDesktopOpenProject();
 
   useSessionStatusBootstrap({ enabled: embeddedBackgroundWorkEnabled });
 
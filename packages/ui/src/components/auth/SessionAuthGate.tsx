@@ -140,6 +140,7 @@ export const SessionAuthGate: React.FC<SessionAuthGateProps> = ({ children }) =>
   const [errorMessage, setErrorMessage] = React.useState('');
   const [retryAfter, setRetryAfter] = React.useState<number | undefined>(undefined);
   const [isTunnelLocked, setIsTunnelLocked] = React.useState(false);
+  const [isDesktopPairingRequired, setIsDesktopPairingRequired] = React.useState(false);
   const [passkeyStatus, setPasskeyStatus] = React.useState<PasskeyStatus>(defaultPasskeyStatus);
   const [supportsPasskeys, setSupportsPasskeys] = React.useState(false);
   const [isPasskeyBusy, setIsPasskeyBusy] = React.useState(false);
@@ -220,12 +221,13 @@ export const SessionAuthGate: React.FC<SessionAuthGateProps> = ({ children }) =>
           console.log('[Frontend Auth] Session is authenticated');
           setState('authenticated');
           setIsTunnelLocked(false);
+          setIsDesktopPairingRequired(false);
           setErrorMessage('');
           setRetryAfter(undefined);
           return;
         }
         if (response.status === 401) {
-          let data: { tunnelLocked?: boolean; debug?: { hasRefreshToken: boolean; message: string } } = {};
+          let data: { tunnelLocked?: boolean; desktopPairingRequired?: boolean; debug?: { hasRefreshToken: boolean; message: string } } = {};
           try {
             data = JSON.parse(responseText);
           } catch {
@@ -236,6 +238,7 @@ export const SessionAuthGate: React.FC<SessionAuthGateProps> = ({ children }) =>
             console.warn('[Frontend Auth] Debug info:', data.debug);
           }
           setIsTunnelLocked(data.tunnelLocked === true);
+          setIsDesktopPairingRequired(data.desktopPairingRequired === true);
           setPasskeyStatus(latestPasskeyStatus);
           setState('locked');
           setRetryAfter(undefined);
@@ -479,16 +482,22 @@ export const SessionAuthGate: React.FC<SessionAuthGateProps> = ({ children }) =>
         <div className="flex flex-col items-center gap-6 w-full max-w-xs">
           <div className="flex flex-col items-center gap-1 text-center">
             <h1 className="text-xl font-semibold text-foreground">
-              {isTunnelLocked ? t('sessionAuth.locked.tunnelTitle') : t('sessionAuth.locked.unlockTitle')}
+              {isTunnelLocked
+                ? t('sessionAuth.locked.tunnelTitle')
+                : isDesktopPairingRequired
+                  ? t('sessionAuth.locked.desktopPairingTitle')
+                  : t('sessionAuth.locked.unlockTitle')}
             </h1>
             <p className="typography-meta text-muted-foreground">
               {isTunnelLocked
                 ? t('sessionAuth.locked.tunnelDescription')
-                : t('sessionAuth.locked.passwordDescription')}
+                : isDesktopPairingRequired
+                  ? t('sessionAuth.locked.desktopPairingDescription')
+                  : t('sessionAuth.locked.passwordDescription')}
             </p>
           </div>
 
-          {!isTunnelLocked && (
+          {!isTunnelLocked && !isDesktopPairingRequired && (
             <form onSubmit={handleSubmit} className="w-full space-y-2">
               {canUsePasskey && (
                 <Button
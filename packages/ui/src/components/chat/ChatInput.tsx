@@ -64,6 +64,7 @@ import { useChatSearchDirectory } from '@/hooks/useChatSearchDirectory';
 import { opencodeClient } from '@/lib/opencode/client';
 import { useProjectsStore } from '@/stores/useProjectsStore';
 import { PROJECT_COLOR_MAP, PROJECT_ICON_MAP, getProjectIconImageUrl } from '@/lib/projectMeta';
+import { useDesktopAuthImages } from '@/hooks/useDesktopAuthImages';
 import { useGitBranches, useGitStore, useIsGitRepo } from '@/stores/useGitStore';
 import { useDirectoryStore } from '@/stores/useDirectoryStore';
 import { useRuntimeAPIs } from '@/hooks/useRuntimeAPIs';
@@ -795,6 +796,7 @@ const ChatInputComponent: React.FC<ChatInputProps> = ({ onOpenSettings, scrollTo
     const setExpandedInput = useUIStore((state) => state.setExpandedInput);
     const { git: runtimeGit } = useRuntimeAPIs();
     const { currentTheme } = useThemeSystem();
+    const desktopAuthImagesVersion = useDesktopAuthImages();
     const chatSearchDirectory = useChatSearchDirectory();
     const isGitRepo = useIsGitRepo(currentDirectory);
     const currentGitStatus = useGitStore((state) =>
@@ -3210,7 +3212,7 @@ const ChatInputComponent: React.FC<ChatInputProps> = ({ onOpenSettings, scrollTo
                 <span className="truncate">{getProjectDisplayLabel(project)}</span>
             </span>
         );
-    }, [currentTheme.colors.surface.foreground, currentTheme.metadata.variant]);
+    }, [currentTheme.colors.surface.foreground, currentTheme.metadata.variant, desktopAuthImagesVersion]);
 
     React.useEffect(() => {
         if (!showDraftTargetSelectors || !selectedDraftProject || !selectedDraftDirectory) {
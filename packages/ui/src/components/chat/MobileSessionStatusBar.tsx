@@ -37,6 +37,7 @@ import {
 import { CSS } from '@dnd-kit/utilities';
 import type { SessionContextUsage } from '@/stores/types/sessionTypes';
 import { PROJECT_ICON_MAP, PROJECT_COLOR_MAP, getProjectIconImageUrl } from '@/lib/projectMeta';
+import { useDesktopAuthImages } from '@/hooks/useDesktopAuthImages';
 import { useDirectoryStore } from '@/stores/useDirectoryStore';
 import { sessionEvents } from '@/lib/sessionEvents';
 import {
@@ -609,6 +610,7 @@ function SortableProjectItem({
   formatProjectLabel,
 }: SortableProjectItemProps) {
   const { currentTheme } = useThemeSystem();
+  useDesktopAuthImages();
   const {
     attributes,
     listeners,
@@ -862,6 +864,7 @@ function ProjectButton({
   formatProjectLabel,
 }: ProjectButtonProps) {
   const { currentTheme } = useThemeSystem();
+  useDesktopAuthImages();
   const [imageFailed, setImageFailed] = React.useState(false);
   const ProjectIcon = project.icon ? PROJECT_ICON_MAP[project.icon] : null;
   const projectIconImageUrl = !imageFailed
@@ -1430,6 +1433,7 @@ export const MobileSessionStatusBar: React.FC<MobileSessionStatusBarProps> = ({
 }) => {
   const { t } = useI18n();
   const { currentTheme } = useThemeSystem();
+  useDesktopAuthImages();
   const sessions = useSessions();
   const currentSessionId = useSessionUIStore((state) => state.currentSessionId);
   const sessionStatus = useAllSessionStatuses();
@@ -50,6 +50,8 @@ import {
   type HostProbeResult,
 } from '@/lib/desktopHosts';
 import {
+  desktopLocalProfileStart,
+  desktopLocalProfilesGet,
   desktopSshConnect,
   desktopSshDisconnect,
   desktopSshInstancesGet,
@@ -162,6 +164,7 @@ const sshPhaseLabelKey = (phase: DesktopSshInstanceStatus['phase'] | undefined):
     case 'server_detecting':
       return 'desktopHostSwitcher.sshPhase.detectingServer';
     case 'server_starting':
+    case 'container_starting':
       return 'desktopHostSwitcher.sshPhase.startingServer';
     case 'forwarding':
       return 'desktopHostSwitcher.sshPhase.forwardingPorts';
@@ -277,6 +280,7 @@ export function DesktopHostSwitcherDialog({
   const [isSaving, setIsSaving] = React.useState(false);
   const [switchingHostId, setSwitchingHostId] = React.useState<string | null>(null);
   const [sshHostIds, setSshHostIds] = React.useState<Record<string, true>>({});
+  const [profileHostIds, setProfileHostIds] = React.useState<Record<string, true>>({});
   const [sshStatusesById, setSshStatusesById] = React.useState<Record<string, DesktopSshInstanceStatus>>({});
   const [sshSwitchModal, setSshSwitchModal] = React.useState<{
     open: boolean;
@@ -346,24 +350,31 @@ export function DesktopHostSwitcherDialog({
     setIsLoading(true);
     setError('');
     try {
-      const [cfg, sshCfg, sshStatusMap] = await Promise.all([
+      const [cfg, sshCfg, profilesCfg, sshStatusMap] = await Promise.all([
         desktopHostsGet(),
         desktopSshInstancesGet().catch(() => ({ instances: [] })),
+        desktopLocalProfilesGet().catch(() => ({ profiles: [] })),
         getSshStatusById(),
       ]);
       const nextSshHostIds: Record<string, true> = {};
       for (const instance of sshCfg.instances) {
         nextSshHostIds[instance.id] = true;
       }
+      const nextProfileHostIds: Record<string, true> = {};
+      for (const profile of profilesCfg.profiles) {
+        nextProfileHostIds[profile.id] = true;
+      }
       setConfigHosts(cfg.hosts || []);
       setDefaultHostId(cfg.defaultHostId ?? null);
       setSshHostIds(nextSshHostIds);
+      setProfileHostIds(nextProfileHostIds);
       setSshStatusesById(sshStatusMap);
     } catch (err) {
       setError(err instanceof Error ? err.message : t('desktopHostSwitcher.error.failedToLoad'));
       setConfigHosts([]);
       setDefaultHostId(null);
       setSshHostIds({});
+      setProfileHostIds({});
       setSshStatusesById({});
     } finally {
       setIsLoading(false);
@@ -531,6 +542,24 @@ export function DesktopHostSwitcherDialog({
       }
     }
 
+    if (host.id !== LOCAL_HOST_ID && profileHostIds[host.id] && isTauriShell()) {
+      // Local profiles are started on demand; the shell resolves once the server is healthy.
+      setSwitchingHostId(host.id);
+      try {
+        const url = await desktopLocalProfileStart(host.id);
+        const target = toNavigationUrl(normalizeHostUrl(url || '') || origin);
+        onHostSwitched?.();
+        window.location.assign(target);
+      } catch (err) {
+        toast.error(t('desktopHostSwitcher.toast.profileFailedToStart', { host: host.label }), {
+          description: err instanceof Error ? err.message : String(err),
+        });
+      } finally {
+        setSwitchingHostId(null);
+      }
+      return;
+    }
+
     if (host.id !== LOCAL_HOST_ID && isTauriShell()) {
       setSwitchingHostId(host.id);
       const probe = await desktopHostProbe(origin).catch((): HostProbeResult => ({ status: 'unreachable', latencyMs: 0 }));
@@ -554,7 +583,7 @@ export function DesktopHostSwitcherDialog({
     } catch {
       window.location.href = target;
     }
-  }, [onHostSwitched, sshHostIds, sshStatusesById, t]);
+  }, [onHostSwitched, profileHostIds, sshHostIds, sshStatusesById, t]);
 
   const beginEdit = React.useCallback((host: DesktopHost) => {
     setEditingId(host.id);
@@ -11,6 +11,7 @@ import { Input } from '@/components/ui/input';
 import { toast } from '@/components/ui';
 import { cn } from '@/lib/utils';
 import { PROJECT_ICONS, PROJECT_COLORS, PROJECT_COLOR_MAP, getProjectIconImageUrl } from '@/lib/projectMeta';
+import { useDesktopAuthImages } from '@/hooks/useDesktopAuthImages';
 import { useProjectsStore } from '@/stores/useProjectsStore';
 import { useThemeSystem } from '@/contexts/useThemeSystem';
 import { useI18n } from '@/lib/i18n';
@@ -57,6 +58,7 @@ export const ProjectEditDialog: React.FC<ProjectEditDialogProps> = ({
   const discoverProjectIcon = useProjectsStore((state) => state.discoverProjectIcon);
   const currentIconImage = useProjectsStore((state) => state.projects.find((project) => project.id === projectId)?.iconImage ?? null);
   const { currentTheme } = useThemeSystem();
+  useDesktopAuthImages();
   const [name, setName] = React.useState(projectName);
   const [icon, setIcon] = React.useState<string | null>(initialIcon);
   const [color, setColor] = React.useState<string | null>(initialColor);
@@ -24,6 +24,7 @@ import { FileMentionAutocomplete, type FileMentionHandle } from '@/components/ch
 import { isDesktopShell } from '@/lib/desktop';
 import { useThemeSystem } from '@/contexts/useThemeSystem';
 import { PROJECT_ICON_MAP, PROJECT_COLOR_MAP, getProjectIconImageUrl } from '@/lib/projectMeta';
+import { useDesktopAuthImages } from '@/hooks/useDesktopAuthImages';
 import type { ProjectEntry } from '@/lib/api/types';
 import { startDesktopWindowDrag } from '@/lib/desktopNative';
 import { useI18n } from '@/lib/i18n';
@@ -157,6 +158,7 @@ export const MultiRunLauncher: React.FC<MultiRunLauncherProps> = ({
   }, [activeProjectId, setActiveProjectIdOnly]);
 
   const { currentTheme } = useThemeSystem();
+  const desktopAuthImagesVersion = useDesktopAuthImages();
 
   const renderProjectLabel = React.useCallback((project: ProjectEntry) => {
     const displayLabel = project.label?.trim() || formatDirectoryName(project.path, homeDirectory);
@@ -187,7 +189,7 @@ export const MultiRunLauncher: React.FC<MultiRunLauncherProps> = ({
         <span className="truncate">{displayLabel}</span>
       </span>
     );
-  }, [homeDirectory, currentTheme.metadata.variant, currentTheme.colors.surface.foreground]);
+  }, [homeDirectory, currentTheme.metadata.variant, currentTheme.colors.surface.foreground, desktopAuthImagesVersion]);
 
   const projectRef = React.useMemo<ProjectRef | null>(() => {
     if (selectedProject?.path) {
@@ -2,6 +2,8 @@ import * as React from 'react';
 
 import { Button } from '@/components/ui/button';
 import { Checkbox } from '@/components/ui/checkbox';
+import { toast } from '@/components/ui';
+import { copyTextToClipboard } from '@/lib/clipboard';
 import { getDesktopLanAddress, isDesktopLocalOriginActive, isDesktopShell, restartDesktopApp } from '@/lib/desktop';
 import { useI18n } from '@/lib/i18n';
 
@@ -14,6 +16,8 @@ export const DesktopNetworkSettings: React.FC = () => {
   const [isSaving, setIsSaving] = React.useState(false);
   const [error, setError] = React.useState<string | null>(null);
   const [lanAddress, setLanAddress] = React.useState<string | null>(null);
+  const [pairingUrl, setPairingUrl] = React.useState<string | null>(null);
+  const [isPairing, setIsPairing] = React.useState(false);
 
   React.useEffect(() => {
     if (!isLocalDesktop) {
@@ -88,6 +92,58 @@ export const DesktopNetworkSettings: React.FC = () => {
   }, []);
   const lanUrl = draftValue && lanAddress && currentPort ? `http://${lanAddress}:${currentPort}` : null;
 
+  const canPairDevices = savedValue && !isDirty && Boolean(lanUrl);
+
+  const handleCreatePairingLink = React.useCallback(async () => {
+    if (!lanUrl) {
+      return;
+    }
+
+    setIsPairing(true);
+    setError(null);
+    try {
+      const response = await fetch('/api/desktop/pairing', {
+        method: 'POST',
+        headers: { Accept: 'application/json' },
+      });
+      const data = (await response.json().catch(() => null)) as null | { path?: unknown };
+      if (!response.ok || typeof data?.path !== 'string') {
+        throw new Error(t('settings.openchamber.desktopNetwork.error.pairingFailed'));
+      }
+
+      const url = `${lanUrl}${data.path}`;
+      setPairingUrl(url);
+      const copied = await copyTextToClipboard(url);
+      if (copied.ok) {
+        toast.success(t('settings.openchamber.desktopNetwork.toast.pairingLinkCopied'));
+      }
+    } catch (cause) {
+      setError(cause instanceof Error ? cause.message : t('settings.openchamber.desktopNetwork.error.pairingFailed'));
+    } finally {
+      setIsPairing(false);
+    }
+  }, [lanUrl, t]);
+
+  const handleForgetDevices = React.useCallback(async () => {
+    setIsPairing(true);
+    setError(null);
+    try {
+      const response = await fetch('/api/desktop/devices', {
+        method: 'DELETE',
+        headers: { Accept: 'application/json' },
+      });
+      if (!response.ok) {
+        throw new Error(t('settings.openchamber.desktopNetwork.error.forgetDevicesFailed'));
+      }
+      setPairingUrl(null);
+      toast.success(t('settings.openchamber.desktopNetwork.toast.devicesForgotten'));
+    } catch (cause) {
+      setError(cause instanceof Error ? cause.message : t('settings.openchamber.desktopNetwork.error.forgetDevicesFailed'));
+    } finally {
+      setIsPairing(false);
+    }
+  }, [t]);
+
   const handleToggle = React.useCallback(() => {
     setDraftValue((current) => !current);
   }, []);
@@ -179,6 +235,42 @@ export const DesktopNetworkSettings: React.FC = () => {
           </div>
         ) : null}
 
+        {canPairDevices ? (
+          <div className="space-y-1.5 px-2">
+            <div className="typography-micro text-muted-foreground/80">
+              {t('settings.openchamber.desktopNetwork.hint.pairingRequired')}
+            </div>
+            {pairingUrl ? (
+              <div className="typography-micro text-muted-foreground/80">
+                {t('settings.openchamber.desktopNetwork.hint.pairingLink')}
+                <span className="break-all font-mono text-foreground">{pairingUrl}</span>
+              </div>
+            ) : null}
+            <div className="flex flex-wrap gap-2">
+              <Button
+                type="button"
+                size="xs"
+                variant="outline"
+                onClick={handleCreatePairingLink}
+                disabled={isPairing}
+                className="shrink-0 !font-normal"
+              >
+                {t('settings.openchamber.desktopNetwork.actions.pairDevice')}
+              </Button>
+              <Button
+                type="button"
+                size="xs"
+                variant="ghost"
+                onClick={handleForgetDevices}
+                disabled={isPairing}
+                className="shrink-0 !font-normal"
+              >
+                {t('settings.openchamber.desktopNetwork.actions.forgetDevices')}
+              </Button>
+            </div>
+          </div>
+        ) : null}
+
         <div className="flex justify-start py-1.5">
           <Button
             type="button"
@@ -11,6 +11,7 @@ import { VoiceSettings } from './VoiceSettings';
 import { TunnelSettings } from './TunnelSettings';
 import { OpenCodeCliSettings } from './OpenCodeCliSettings';
 import { DesktopNetworkSettings } from './DesktopNetworkSettings';
+import { SidecarEnvSettings } from './SidecarEnvSettings';
 import { KeyboardShortcutsSettings } from './KeyboardShortcutsSettings';
 import { ScrollableOverlay } from '@/components/ui/ScrollableOverlay';
 import { useDeviceInfo } from '@/lib/device';
@@ -50,6 +51,11 @@ export const OpenChamberPage: React.FC<OpenChamberPageProps> = ({ section }) =>
                             <DesktopNetworkSettings />
                         </div>
                     )}
+                    {showDesktopNetworkSettings && (
+                        <div className="border-t border-border/40 pt-6">
+                            <SidecarEnvSettings />
+                        </div>
+                    )}
                     <div className="border-t border-border/40 pt-6">
                         <SessionRetentionSettings />
                     </div>
@@ -148,6 +154,11 @@ const SessionsSectionContent: React.FC = () => {
                     <DesktopNetworkSettings />
                 </div>
             )}
+            {showDesktopNetworkSettings && (
+                <div className="border-t border-border/40 pt-6">
+                    <SidecarEnvSettings />
+                </div>
+            )}
             <div className="border-t border-border/40 pt-6">
                 <SessionRetentionSettings />
             </div>
@@ -2,8 +2,16 @@ import * as React from 'react';
 import { Button } from '@/components/ui/button';
 import { Input } from '@/components/ui/input';
 import { Tooltip, TooltipContent, TooltipTrigger } from '@/components/ui/tooltip';
+import { toast } from '@/components/ui';
 import { RiFolderLine, RiInformationLine } from '@remixicon/react';
-import { isDesktopShell, isTauriShell } from '@/lib/desktop';
+import {
+  detectDesktopOpencode,
+  isDesktopLocalOriginActive,
+  isDesktopShell,
+  isTauriShell,
+  selectDesktopOpencode,
+  type DesktopOpencodeDetection,
+} from '@/lib/desktop';
 import { updateDesktopSettings } from '@/lib/persistence';
 import { reloadOpenCodeConfiguration } from '@/stores/useAgentsStore';
 import { useI18n } from '@/lib/i18n';
@@ -13,6 +21,9 @@ export const OpenCodeCliSettings: React.FC = () => {
   const [value, setValue] = React.useState('');
   const [isLoading, setIsLoading] = React.useState(true);
   const [isSaving, setIsSaving] = React.useState(false);
+  const [detection, setDetection] = React.useState<DesktopOpencodeDetection | null>(null);
+  const [isDetecting, setIsDetecting] = React.useState(false);
+  const canDetect = isTauriShell() && isDesktopLocalOriginActive();
 
   React.useEffect(() => {
     let cancelled = false;
@@ -86,6 +97,43 @@ export const OpenCodeCliSettings: React.FC = () => {
     }
   }, [t, value]);
 
+  const handleDetect = React.useCallback(async () => {
+    setIsDetecting(true);
+    try {
+      setDetection(await detectDesktopOpencode());
+    } finally {
+      setIsDetecting(false);
+    }
+  }, []);
+
+  const handleUseCandidate = React.useCallback(async (path: string) => {
+    setIsSaving(true);
+    try {
+      // The shell restarts the server and reloads this window on success.
+      await selectDesktopOpencode(path);
+      setValue(path);
+    } catch (error) {
+      toast.error(t('settings.openchamber.opencodeCli.toast.selectFailed'), {
+        description: error instanceof Error ? error.message : String(error),
+      });
+    } finally {
+      setIsSaving(false);
+    }
+  }, [t]);
+
+  const sourceLabel = React.useCallback((source: string) => {
+    switch (source) {
+      case 'settings':
+        return t('settings.openchamber.opencodeCli.detect.source.settings');
+      case 'env':
+        return t('settings.openchamber.opencodeCli.detect.source.env');
+      case 'path':
+        return t('settings.openchamber.opencodeCli.detect.source.path');
+      default:
+        return t('settings.openchamber.opencodeCli.detect.source.installDir');
+    }
+  }, [t]);
+
   return (
     <div className="mb-8">
       <div className="mb-1 px-1">
@@ -148,7 +196,7 @@ export const OpenCodeCliSettings: React.FC = () => {
           </div>
         </div>
 
-        <div className="flex justify-start py-1.5">
+        <div className="flex justify-start gap-2 py-1.5">
           <Button
             type="button"
             size="xs"
@@ -158,7 +206,73 @@ export const OpenCodeCliSettings: React.FC = () => {
           >
             {isSaving ? t('settings.common.actions.saving') : t('settings.openchamber.opencodeCli.actions.saveAndReload')}
           </Button>
+          {canDetect && (
+            <Button
+              type="button"
+              variant="outline"
+              size="xs"
+              onClick={handleDetect}
+              disabled={isLoading || isSaving || isDetecting}
+              className="shrink-0 !font-normal"
+            >
+              {isDetecting
+                ? t('settings.openchamber.opencodeCli.actions.detecting')
+                : t('settings.openchamber.opencodeCli.actions.detect')}
+            </Button>
+          )}
         </div>
+
+        {detection && (
+          <div className="space-y-1 py-1.5">
+            <div className="typography-micro text-muted-foreground/70">
+              {t('settings.openchamber.opencodeCli.detect.minimumVersion', { version: detection.minimumVersion })}
+            </div>
+            {detection.candidates.length === 0 ? (
+              <div className="typography-meta text-muted-foreground">
+                {t('settings.openchamber.opencodeCli.detect.empty')}
+              </div>
+            ) : (
+              detection.candidates.map((candidate) => (
+                <div
+                  key={candidate.path}
+                  className="flex items-center gap-3 rounded-md border border-border/60 px-2 py-1.5"
+                >
+                  <div className="flex min-w-0 flex-1 flex-col">
+                    <span className="truncate font-mono text-xs text-foreground" title={candidate.path}>
+                      {candidate.path}
+                    </span>
+                    <span className="typography-micro text-muted-foreground">
+                      {candidate.version ?? t('settings.openchamber.opencodeCli.detect.unknownVersion')}
+                      {' · '}
+                      {candidate.sources.map(sourceLabel).join(', ')}
+                    </span>
+                    {candidate.error && (
+                      <span className="typography-micro text-[var(--status-error)]" title={candidate.versionOutput ?? undefined}>
+                        {candidate.error}
+                      </span>
+                    )}
+                  </div>
+                  {candidate.configured ? (
+                    <span className="typography-micro shrink-0 text-muted-foreground">
+                      {t('settings.openchamber.opencodeCli.detect.current')}
+                    </span>
+                  ) : (
+                    <Button
+                      type="button"
+                      variant="outline"
+                      size="xs"
+                      onClick={() => void handleUseCandidate(candidate.path)}
+                      disabled={!candidate.compatible || isSaving}
+                      className="shrink-0 !font-normal"
+                    >
+                      {t('settings.openchamber.opencodeCli.actions.use')}
+                    </Button>
+                  )}
+                </div>
+              ))
+            )}
+          </div>
+        )}
       </section>
     </div>
   );
@@ -0,0 +1,207 @@
+import * as React from 'react';
+
+import { Button } from '@/components/ui/button';
+import { Checkbox } from '@/components/ui/checkbox';
+import { Input } from '@/components/ui/input';
+import { Textarea } from '@/components/ui/textarea';
+import { RiAddLine, RiDeleteBinLine } from '@remixicon/react';
+import {
+  isDesktopLocalOriginActive,
+  isDesktopShell,
+  readDesktopSidecarEnv,
+  saveDesktopSidecarEnv,
+  type DesktopSidecarEnvEntry,
+  type DesktopSidecarEnvSettings,
+} from '@/lib/desktop';
+import { useI18n } from '@/lib/i18n';
+
+type DraftEntry = DesktopSidecarEnvEntry & { id: number };
+
+let nextDraftId = 0;
+
+const toDraft = (entries: DesktopSidecarEnvEntry[]): DraftEntry[] =>
+  entries.map((entry) => ({ ...entry, id: nextDraftId++ }));
+
+const parseArgs = (raw: string): string[] =>
+  raw.split('\n').map((line) => line.trim()).filter((line) => line.length > 0);
+
+export const SidecarEnvSettings: React.FC = () => {
+  const { t } = useI18n();
+  const isLocalDesktop = isDesktopShell() && isDesktopLocalOriginActive();
+  const [saved, setSaved] = React.useState<DesktopSidecarEnvSettings | null>(null);
+  const [entries, setEntries] = React.useState<DraftEntry[]>([]);
+  const [argsText, setArgsText] = React.useState('');
+  const [isSaving, setIsSaving] = React.useState(false);
+  const [error, setError] = React.useState<string | null>(null);
+
+  React.useEffect(() => {
+    if (!isLocalDesktop) {
+      return;
+    }
+
+    let cancelled = false;
+    void readDesktopSidecarEnv().then((settings) => {
+      if (cancelled || !settings) {
+        return;
+      }
+      setSaved(settings);
+      setEntries(toDraft(settings.env));
+      setArgsText(settings.args.join('\n'));
+    });
+    return () => {
+      cancelled = true;
+    };
+  }, [isLocalDesktop]);
+
+  const isDirty = React.useMemo(() => {
+    if (!saved) {
+      return false;
+    }
+    const draft: DesktopSidecarEnvSettings = {
+      env: entries.map(({ name, value, secret }) => ({ name, value, secret })),
+      args: parseArgs(argsText),
+    };
+    return JSON.stringify(draft) !== JSON.stringify(saved);
+  }, [argsText, entries, saved]);
+
+  const updateEntry = React.useCallback((id: number, patch: Partial<DesktopSidecarEnvEntry>) => {
+    setEntries((current) => current.map((entry) => (entry.id === id ? { ...entry, ...patch } : entry)));
+  }, []);
+
+  const handleAdd = React.useCallback(() => {
+    setEntries((current) => [...current, { id: nextDraftId++, name: '', value: '', secret: false }]);
+  }, []);
+
+  const handleRemove = React.useCallback((id: number) => {
+    setEntries((current) => current.filter((entry) => entry.id !== id));
+  }, []);
+
+  const handleSaveAndRestart = React.useCallback(async () => {
+    setIsSaving(true);
+    setError(null);
+    try {
+      // The shell restarts the server and reloads this window when anything changed.
+      const next = await saveDesktopSidecarEnv({
+        env: entries.map(({ name, value, secret }) => ({ name: name.trim(), value, secret })),
+        args: parseArgs(argsText),
+      });
+      if (next) {
+        setSaved(next);
+        setEntries(toDraft(next.env));
+        setArgsText(next.args.join('\n'));
+      }
+    } catch (cause) {
+      setError(cause instanceof Error ? cause.message : String(cause));
+    } finally {
+      setIsSaving(false);
+    }
+  }, [argsText, entries]);
+
+  if (!isLocalDesktop) {
+    return null;
+  }
+
+  return (
+    <div className="mb-8">
+      <div className="mb-1 px-1">
+        <h3 className="typography-ui-header font-medium text-foreground">{t('settings.openchamber.sidecarEnv.title')}</h3>
+        <div className="typography-micro text-muted-foreground/70">{t('settings.openchamber.sidecarEnv.description')}</div>
+      </div>
+
+      <section className="space-y-2 px-2 pb-2 pt-0">
+        <div className="space-y-1.5 py-1.5">
+          <span className="typography-ui-label text-foreground">{t('settings.openchamber.sidecarEnv.field.variables')}</span>
+          {entries.length === 0 ? (
+            <div className="typography-micro text-muted-foreground/70">{t('settings.openchamber.sidecarEnv.empty')}</div>
+          ) : null}
+          {entries.map((entry) => (
+            <div key={entry.id} className="flex flex-wrap items-center gap-2">
+              <Input
+                value={entry.name}
+                onChange={(event) => updateEntry(entry.id, { name: event.target.value })}
+                placeholder={t('settings.openchamber.sidecarEnv.field.namePlaceholder')}
+                disabled={isSaving}
+                className="h-7 w-40 font-mono text-xs"
+              />
+              <Input
+                type={entry.secret ? 'password' : 'text'}
+                value={entry.value ?? ''}
+                onChange={(event) => updateEntry(entry.id, { value: event.target.value })}
+                placeholder={
+                  entry.secret && entry.value === null
+                    ? t('settings.openchamber.sidecarEnv.field.secretUnchanged')
+                    : t('settings.openchamber.sidecarEnv.field.valuePlaceholder')
+                }
+                disabled={isSaving}
+                className="h-7 min-w-0 flex-1 font-mono text-xs"
+              />
+              <label className="flex items-center gap-1.5 typography-micro text-muted-foreground">
+                <Checkbox
+                  checked={entry.secret}
+                  onChange={() => updateEntry(entry.id, {
+                    secret: !entry.secret,
+                    // Turning a stored secret into a plain value requires retyping it.
+                    value: entry.secret && entry.value === null ? '' : entry.value,
+                  })}
+                  ariaLabel={t('settings.openchamber.sidecarEnv.field.secretAria')}
+                  disabled={isSaving}
+                />
+                {t('settings.openchamber.sidecarEnv.field.secret')}
+              </label>
+              <Button
+                type="button"
+                variant="ghost"
+                size="xs"
+                onClick={() => handleRemove(entry.id)}
+                disabled={isSaving}
+                className="h-7 w-7 p-0"
+                aria-label={t('settings.openchamber.sidecarEnv.actions.removeAria')}
+              >
+                <RiDeleteBinLine className="h-4 w-4" />
+              </Button>
+            </div>
+          ))}
+          <Button
+            type="button"
+            variant="outline"
+            size="xs"
+            onClick={handleAdd}
+            disabled={isSaving}
+            className="shrink-0 !font-normal"
+          >
+            <RiAddLine className="h-4 w-4" />
+            {t('settings.openchamber.sidecarEnv.actions.add')}
+          </Button>
+        </div>
+
+        <div className="space-y-1.5 py-1.5">
+          <span className="typography-ui-label text-foreground">{t('settings.openchamber.sidecarEnv.field.args')}</span>
+          <Textarea
+            value={argsText}
+            onChange={(event) => setArgsText(event.target.value)}
+            placeholder={t('settings.openchamber.sidecarEnv.field.argsPlaceholder')}
+            disabled={isSaving}
+            className="min-h-[72px] max-w-[30rem] font-mono text-xs"
+          />
+          <div className="typography-micro text-muted-foreground/70">{t('settings.openchamber.sidecarEnv.field.argsHint')}</div>
+        </div>
+
+        {error ? (
+          <div className="px-2 typography-micro text-[var(--status-error)]">{error}</div>
+        ) : null}
+
+        <div className="flex justify-start py-1.5">
+          <Button
+            type="button"
+            size="xs"
+            onClick={handleSaveAndRestart}
+            disabled={!saved || isSaving || !isDirty}
+            className="shrink-0 !font-normal"
+          >
+            {isSaving ? t('settings.common.actions.saving') : t('settings.openchamber.sidecarEnv.actions.saveAndRestart')}
+          </Button>
+        </div>
+      </section>
+    </div>
+  );
+};
@@ -76,6 +76,7 @@ const phaseLabelKey = (phase?: string): I18nKey => {
     case 'server_detecting':
       return 'settings.remoteInstances.page.phase.detectingServer';
     case 'server_starting':
+    case 'container_starting':
       return 'settings.remoteInstances.page.phase.startingServer';
     case 'forwarding':
       return 'settings.remoteInstances.page.phase.forwardingPorts';
@@ -99,6 +100,7 @@ const CONNECTING_PHASES = new Set<string>([
   'updating',
   'server_detecting',
   'server_starting',
+  'container_starting',
   'forwarding',
 ]);
 
@@ -233,11 +235,7 @@ const normalizeForSave = (instance: DesktopSshInstance): DesktopSshInstance => {
     connectionTimeoutSec: Math.max(5, Math.min(240, Math.round(instance.connectionTimeoutSec || 60))),
     localForward: {
       ...instance.localForward,
-      bindHost:
-        instance.localForward.bindHost === 'localhost' ||
-        instance.localForward.bindHost === '0.0.0.0'
-          ? instance.localForward.bindHost
-          : '127.0.0.1',
+      bindHost: instance.localForward.bindHost.trim() || '127.0.0.1',
       preferredLocalPort:
         typeof instance.localForward.preferredLocalPort === 'number'
           ? Math.max(1, Math.min(65535, Math.round(instance.localForward.preferredLocalPort)))
@@ -44,6 +44,7 @@ const phaseLabelKey = (phase?: string) => {
     case 'forwarding':
       return 'settings.remoteInstances.sidebar.phase.forwarding';
     case 'server_starting':
+    case 'container_starting':
       return 'settings.remoteInstances.sidebar.phase.starting';
     case 'master_connecting':
       return 'settings.remoteInstances.sidebar.phase.connecting';
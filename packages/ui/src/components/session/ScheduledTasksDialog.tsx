@@ -30,6 +30,7 @@ import { useDirectoryStore } from '@/stores/useDirectoryStore';
 import { refreshGlobalSessions } from '@/stores/useGlobalSessionsStore';
 import { subscribeOpenchamberEvents } from '@/lib/openchamberEvents';
 import { PROJECT_COLOR_MAP, PROJECT_ICON_MAP, getProjectIconImageUrl } from '@/lib/projectMeta';
+import { useDesktopAuthImages } from '@/hooks/useDesktopAuthImages';
 import { useThemeSystem } from '@/contexts/useThemeSystem';
 import { cn, formatDirectoryName } from '@/lib/utils';
 import { useI18n } from '@/lib/i18n';
@@ -189,6 +190,7 @@ export function ScheduledTasksDialog() {
   const activeProject = useProjectsStore((state) => state.getActiveProject());
   const homeDirectory = useDirectoryStore((state) => state.homeDirectory);
   const { currentTheme } = useThemeSystem();
+  const desktopAuthImagesVersion = useDesktopAuthImages();
 
   const [selectedProjectID, setSelectedProjectID] = React.useState<string>('');
   const [tasks, setTasks] = React.useState<ScheduledTask[]>([]);
@@ -231,7 +233,7 @@ export function ScheduledTasksDialog() {
         <span className="truncate">{displayLabel}</span>
       </span>
     );
-  }, [homeDirectory, currentTheme.metadata.variant, currentTheme.colors.surface.foreground]);
+  }, [homeDirectory, currentTheme.metadata.variant, currentTheme.colors.surface.foreground, desktopAuthImagesVersion]);
 
   const reloadTasks = React.useCallback(async (projectID: string, options?: { silent?: boolean }) => {
     if (!projectID) {
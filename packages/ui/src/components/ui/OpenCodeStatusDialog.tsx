@@ -9,6 +9,7 @@ import {
 import { toast } from '@/components/ui';
 import { useUIStore } from '@/stores/useUIStore';
 import { copyTextToClipboard } from '@/lib/clipboard';
+import { isDesktopLocalOriginActive, isTauriShell, saveDesktopLogs } from '@/lib/desktop';
 import { useI18n } from '@/lib/i18n';
 
 export const OpenCodeStatusDialog: React.FC = () => {
@@ -16,6 +17,8 @@ export const OpenCodeStatusDialog: React.FC = () => {
   const isOpenCodeStatusDialogOpen = useUIStore((state) => state.isOpenCodeStatusDialogOpen);
   const setOpenCodeStatusDialogOpen = useUIStore((state) => state.setOpenCodeStatusDialogOpen);
   const openCodeStatusText = useUIStore((state) => state.openCodeStatusText);
+  const canSaveDesktopLogs = React.useMemo(() => isTauriShell() && isDesktopLocalOriginActive(), []);
+  const [isSavingLogs, setIsSavingLogs] = React.useState(false);
 
   const handleCopy = React.useCallback(async () => {
     if (!openCodeStatusText) {
@@ -30,6 +33,23 @@ export const OpenCodeStatusDialog: React.FC = () => {
     toast.error(t('openCodeStatusDialog.toast.copyFailed'));
   }, [openCodeStatusText, t]);
 
+  const handleDownloadLogs = React.useCallback(async () => {
+    if (isSavingLogs) {
+      return;
+    }
+    setIsSavingLogs(true);
+    try {
+      const savedPath = await saveDesktopLogs(openCodeStatusText);
+      if (savedPath) {
+        toast.success(t('openCodeStatusDialog.toast.logsSaved'), { description: savedPath });
+      }
+    } catch {
+      toast.error(t('openCodeStatusDialog.toast.logsSaveFailed'));
+    } finally {
+      setIsSavingLogs(false);
+    }
+  }, [isSavingLogs, openCodeStatusText, t]);
+
   return (
     <Dialog open={isOpenCodeStatusDialogOpen} onOpenChange={setOpenCodeStatusDialogOpen}>
       <DialogContent className="max-w-2xl">
@@ -40,7 +60,17 @@ export const OpenCodeStatusDialog: React.FC = () => {
           </DialogDescription>
         </DialogHeader>
 
-        <div className="flex items-center justify-end">
+        <div className="flex items-center justify-end gap-1">
+          {canSaveDesktopLogs ? (
+            <button
+              type="button"
+              onClick={handleDownloadLogs}
+              disabled={isSavingLogs}
+              className="app-region-no-drag inline-flex h-9 items-center justify-center rounded-md px-3 typography-ui-label font-medium text-muted-foreground transition-colors hover:bg-interactive-hover hover:text-foreground focus-visible:outline-none focus-visible:ring-2 focus-visible:ring-primary disabled:opacity-50"
+            >
+              {t('openCodeStatusDialog.actions.downloadLogs')}
+            </button>
+          ) : null}
           <button
             type="button"
             onClick={handleCopy}
@@ -0,0 +1,10 @@
+import React from 'react';
+import { getDesktopAuthImagesVersion, subscribeDesktopAuthImages } from '@/lib/desktopAuth';
+
+/**
+ * Re-render once a header-authenticated image of a desktop window has
+ * loaded. The returned version changes with every loaded image, so
+ * memoized renderers can list it as a dependency.
+ */
+export const useDesktopAuthImages = (): number =>
+  React.useSyncExternalStore(subscribeDesktopAuthImages, getDesktopAuthImagesVersion, getDesktopAuthImagesVersion);
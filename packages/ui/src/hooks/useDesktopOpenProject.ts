@@ -0,0 +1,53 @@
+import React from 'react';
+import { toast } from '@/components/ui';
+import { isDesktopShell, takeDesktopPendingProjects } from '@/lib/desktop';
+import { useProjectsStore } from '@/stores/useProjectsStore';
+
+const OPEN_PROJECT_EVENT = 'openchamber:open-project';
+const APP_READY_EVENT = 'openchamber:app-ready';
+
+const isAppReady = () => Boolean((window as unknown as { __openchamberAppReady?: boolean }).__openchamberAppReady);
+
+const openPendingProjects = async () => {
+  const paths = await takeDesktopPendingProjects();
+  for (const path of paths) {
+    const entry = useProjectsStore.getState().addProject(path);
+    if (!entry) {
+      toast.error(`Could not open project ${path}`);
+    }
+  }
+};
+
+/**
+ * Opens projects queued by the desktop shell, e.g. from a second launch
+ * with a directory argument or an `openchamber://open` link. The queue is
+ * the only hand-over: the shell pokes an open window with an event, and a
+ * new window takes the queue once loaded. Nothing is taken before the stored
+ * project list has loaded, so new entries are not overwritten.
+ */
+export const useDesktopOpenProject = () => {
+  React.useEffect(() => {
+    if (typeof window === 'undefined' || !isDesktopShell()) return;
+
+    const handleOpenProject = () => {
+      if (isAppReady()) {
+        void openPendingProjects();
+      }
+    };
+    const handleAppReady = () => {
+      void openPendingProjects();
+    };
+    window.addEventListener(OPEN_PROJECT_EVENT, handleOpenProject);
+
+    if (isAppReady()) {
+      void openPendingProjects();
+    } else {
+      window.addEventListener(APP_READY_EVENT, handleAppReady, { once: true });
+    }
+
+    return () => {
+      window.removeEventListener(OPEN_PROJECT_EVENT, handleOpenProject);
+      window.removeEventListener(APP_READY_EVENT, handleAppReady);
+    };
+  }, []);
+};
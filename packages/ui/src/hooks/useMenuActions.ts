@@ -52,6 +52,7 @@ const copyCurrentSelectionFallback = async (): Promise<boolean> => {
 
 const MENU_ACTION_EVENT = 'openchamber:menu-action';
 const CHECK_FOR_UPDATES_EVENT = 'openchamber:check-for-updates';
+const SIDECAR_SHUTDOWN_EVENT = 'openchamber:sidecar-shutdown';
 
 type TauriEventApi = {
   listen?: (
@@ -314,4 +315,46 @@ export const useMenuActions = (
       void cleanup();
     };
   }, [handleAction]);
+
+  React.useEffect(() => {
+    if (typeof window === 'undefined') return;
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const listen = tauri?.event?.listen;
+    if (typeof listen !== 'function') return;
+
+    let unlisten: null | (() => void | Promise<void>) = null;
+
+    // Shown while the desktop app waits for the sidecar to finish busy sessions on quit.
+    listen(SIDECAR_SHUTDOWN_EVENT, (evt) => {
+      const payload = (evt?.payload ?? {}) as { busySessionsCount?: unknown; timeoutMs?: unknown };
+      const busy = typeof payload.busySessionsCount === 'number' ? payload.busySessionsCount : 0;
+      const timeoutSeconds = typeof payload.timeoutMs === 'number' ? Math.round(payload.timeoutMs / 1000) : null;
+      if (busy > 0) {
+        toast.loading(`Waiting for ${busy} session${busy === 1 ? '' : 's'} to finish...`, {
+          id: SIDECAR_SHUTDOWN_EVENT,
+          description: timeoutSeconds
+            ? `OpenChamber will quit when they are done, or after ${timeoutSeconds} seconds.`
+            : undefined,
+          duration: Infinity,
+        });
+        return;
+      }
+      toast.loading('Quitting OpenChamber...', { id: SIDECAR_SHUTDOWN_EVENT, duration: Infinity });
+    })
+      .then((fn) => {
+        unlisten = fn;
+      })
+      .catch(() => {
+        // ignore
+      });
+
+    return () => {
+      try {
+        const result = unlisten?.();
+        if (result instanceof Promise) void result.catch(() => {});
+      } catch {
+        // ignore
+      }
+    };
+  }, []);
 };
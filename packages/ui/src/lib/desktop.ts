@@ -773,3 +773,307 @@ export const clearDesktopCache = async (): Promise<boolean> => {
     return false;
   }
 };
+
+export type DesktopLogSource = 'sidecar' | 'desktop';
+
+export type DesktopLogFileInfo = {
+  name: string;
+  size: number;
+  modifiedAt: number | null;
+};
+
+export type DesktopLogsReadResult = {
+  source: DesktopLogSource;
+  files: DesktopLogFileInfo[];
+  lines: string[];
+  truncated: boolean;
+};
+
+export const readDesktopLogs = async (
+  source: DesktopLogSource,
+  options?: { lines?: number; query?: string },
+): Promise<DesktopLogsReadResult | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const result = await tauri?.core?.invoke?.('desktop_logs_read', {
+      source,
+      lines: options?.lines,
+      query: options?.query?.trim() || undefined,
+    });
+    if (!result || typeof result !== 'object') {
+      return null;
+    }
+    const record = result as Record<string, unknown>;
+    return {
+      source,
+      files: Array.isArray(record.files)
+        ? record.files
+          .filter((file): file is Record<string, unknown> => Boolean(file) && typeof file === 'object')
+          .map((file) => ({
+            name: typeof file.name === 'string' ? file.name : '',
+            size: typeof file.size === 'number' ? file.size : 0,
+            modifiedAt: typeof file.modifiedAt === 'number' ? file.modifiedAt : null,
+          }))
+        : [],
+      lines: Array.isArray(record.lines)
+        ? record.lines.filter((line): line is string => typeof line === 'string')
+        : [],
+      truncated: record.truncated === true,
+    };
+  } catch (error) {
+    console.warn('Failed to read desktop logs (tauri)', error);
+    return null;
+  }
+};
+
+/**
+ * Save the desktop and sidecar log files, prefixed by `statusReport`.
+ * Resolves to the saved path, or null when cancelled or unavailable.
+ */
+export const saveDesktopLogs = async (statusReport?: string): Promise<string | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const result = await tauri?.core?.invoke?.('desktop_logs_save', {
+      statusReport: statusReport ?? null,
+    });
+    return typeof result === 'string' && result.trim().length > 0 ? result : null;
+  } catch (error) {
+    console.warn('Failed to save desktop logs (tauri)', error);
+    throw error;
+  }
+};
+
+export type DesktopLoginEnvInfo = {
+  shell: string | null;
+  resolvedAt: number | null;
+  fromCache: boolean;
+  error: string | null;
+  path: string[];
+  allowlist: string[];
+  forwarded: Record<string, string>;
+};
+
+/**
+ * Inspect the login-shell environment the sidecar is launched with.
+ * `refresh` re-runs the shell instead of using the cache.
+ */
+export const readDesktopLoginEnv = async (refresh = false): Promise<DesktopLoginEnvInfo | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const result = await tauri?.core?.invoke?.('desktop_login_env_get', { refresh });
+    if (!result || typeof result !== 'object') {
+      return null;
+    }
+    const record = result as Record<string, unknown>;
+    const forwarded: Record<string, string> = {};
+    if (record.forwarded && typeof record.forwarded === 'object') {
+      for (const [name, value] of Object.entries(record.forwarded as Record<string, unknown>)) {
+        if (typeof value === 'string') {
+          forwarded[name] = value;
+        }
+      }
+    }
+    const strings = (value: unknown) =>
+      Array.isArray(value) ? value.filter((entry): entry is string => typeof entry === 'string') : [];
+    return {
+      shell: typeof record.shell === 'string' ? record.shell : null,
+      resolvedAt: typeof record.resolvedAt === 'number' ? record.resolvedAt : null,
+      fromCache: record.fromCache === true,
+      error: typeof record.error === 'string' ? record.error : null,
+      path: strings(record.path),
+      allowlist: strings(record.allowlist),
+      forwarded,
+    };
+  } catch (error) {
+    console.warn('Failed to read desktop login environment (tauri)', error);
+    return null;
+  }
+};
+
+export type DesktopOpencodeCandidate = {
+  path: string;
+  sources: string[];
+  version: string | null;
+  versionOutput: string | null;
+  compatible: boolean;
+  configured: boolean;
+  error: string | null;
+};
+
+export type DesktopOpencodeDetection = {
+  configured: string | null;
+  minimumVersion: string;
+  candidates: DesktopOpencodeCandidate[];
+};
+
+const parseOpencodeCandidate = (value: unknown): DesktopOpencodeCandidate | null => {
+  if (!value || typeof value !== 'object') {
+    return null;
+  }
+  const record = value as Record<string, unknown>;
+  if (typeof record.path !== 'string') {
+    return null;
+  }
+  return {
+    path: record.path,
+    sources: Array.isArray(record.sources)
+      ? record.sources.filter((entry): entry is string => typeof entry === 'string')
+      : [],
+    version: typeof record.version === 'string' ? record.version : null,
+    versionOutput: typeof record.versionOutput === 'string' ? record.versionOutput : null,
+    compatible: record.compatible === true,
+    configured: record.configured === true,
+    error: typeof record.error === 'string' ? record.error : null,
+  };
+};
+
+/**
+ * List every opencode binary the desktop shell can find (settings override,
+ * env vars, PATH and common install dirs) with its version.
+ */
+export const detectDesktopOpencode = async (): Promise<DesktopOpencodeDetection | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const result = await tauri?.core?.invoke?.('desktop_detect_opencode');
+    if (!result || typeof result !== 'object') {
+      return null;
+    }
+    const record = result as Record<string, unknown>;
+    return {
+      configured: typeof record.configured === 'string' ? record.configured : null,
+      minimumVersion: typeof record.minimumVersion === 'string' ? record.minimumVersion : '',
+      candidates: Array.isArray(record.candidates)
+        ? record.candidates
+            .map(parseOpencodeCandidate)
+            .filter((entry): entry is DesktopOpencodeCandidate => entry !== null)
+        : [],
+    };
+  } catch (error) {
+    console.warn('Failed to detect opencode binaries (tauri)', error);
+    return null;
+  }
+};
+
+/**
+ * Persist `path` as `opencodeBinary` (`null` clears it) and restart the
+ * local sidecar. Rejects with the shell's message when the binary is
+ * missing or too old.
+ */
+export const selectDesktopOpencode = async (path: string | null): Promise<DesktopOpencodeCandidate | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const result = await tauri?.core?.invoke?.('desktop_select_opencode', { path });
+  return parseOpencodeCandidate(result);
+};
+
+export type DesktopSidecarEnvEntry = {
+  name: string;
+  /** `null` for secrets: hidden when read, "keep the stored value" when saved. */
+  value: string | null;
+  secret: boolean;
+};
+
+export type DesktopSidecarEnvSettings = {
+  env: DesktopSidecarEnvEntry[];
+  args: string[];
+};
+
+const parseSidecarEnvSettings = (value: unknown): DesktopSidecarEnvSettings | null => {
+  if (!value || typeof value !== 'object') {
+    return null;
+  }
+  const record = value as Record<string, unknown>;
+  const env = Array.isArray(record.env)
+    ? record.env.flatMap((entry): DesktopSidecarEnvEntry[] => {
+        if (!entry || typeof entry !== 'object') {
+          return [];
+        }
+        const item = entry as Record<string, unknown>;
+        if (typeof item.name !== 'string') {
+          return [];
+        }
+        return [{
+          name: item.name,
+          value: typeof item.value === 'string' ? item.value : null,
+          secret: item.secret === true,
+        }];
+      })
+    : [];
+  const args = Array.isArray(record.args)
+    ? record.args.filter((entry): entry is string => typeof entry === 'string')
+    : [];
+  return { env, args };
+};
+
+/** Read `sidecarEnv`/`sidecarArgs`; secret values are never returned. */
+export const readDesktopSidecarEnv = async (): Promise<DesktopSidecarEnvSettings | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    return parseSidecarEnvSettings(await tauri?.core?.invoke?.('desktop_sidecar_env_get'));
+  } catch (error) {
+    console.warn('Failed to read sidecar environment (tauri)', error);
+    return null;
+  }
+};
+
+/**
+ * Project paths the shell queued for this window (from a second launch or
+ * an `openchamber://open` link). Each path is handed out once.
+ */
+export const takeDesktopPendingProjects = async (): Promise<string[]> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return [];
+  }
+
+  try {
+    const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+    const raw = await tauri?.core?.invoke?.('desktop_take_pending_projects');
+    return Array.isArray(raw) ? raw.filter((item): item is string => typeof item === 'string') : [];
+  } catch (error) {
+    console.warn('Failed to read pending projects (tauri)', error);
+    return [];
+  }
+};
+
+/**
+ * Save `sidecarEnv`/`sidecarArgs`. When anything changed the shell restarts
+ * the local server and reloads this window. Rejects with the shell's
+ * validation message.
+ */
+export const saveDesktopSidecarEnv = async (
+  settings: DesktopSidecarEnvSettings,
+): Promise<DesktopSidecarEnvSettings | null> => {
+  if (!isTauriShell() || !isDesktopLocalOriginActive()) {
+    return null;
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const result = await tauri?.core?.invoke?.('desktop_sidecar_env_set', {
+    env: settings.env,
+    args: settings.args,
+  });
+  return parseSidecarEnvSettings(result);
+};
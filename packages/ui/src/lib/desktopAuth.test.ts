@@ -0,0 +1,97 @@
+import { describe, expect, test } from 'bun:test';
+import {
+  createDesktopAuthFetch,
+  DESKTOP_AUTH_HEADER,
+  desktopAuthWebSocketProtocols,
+  isSameOriginTarget,
+  openDesktopAuthEventSource,
+  resolveDesktopAuthImageUrl,
+} from './desktopAuth';
+
+const LOCAL_ORIGIN = 'http://127.0.0.1:3000';
+const TOKEN = 'a'.repeat(64);
+
+const recordingFetch = () => {
+  const calls: Array<{ url: string; headers: Headers }> = [];
+  const fetchImpl = (async (input: RequestInfo | URL, init?: RequestInit) => {
+    const url = input instanceof Request ? input.url : String(input);
+    calls.push({ url, headers: new Headers(init?.headers) });
+    return new Response(null, { status: 204 });
+  }) as typeof fetch;
+  return { calls, fetchImpl };
+};
+
+describe('desktop auth token', () => {
+  test('requests to the local origin carry the token header', async () => {
+    const { calls, fetchImpl } = recordingFetch();
+    const authFetch = createDesktopAuthFetch(fetchImpl, TOKEN, LOCAL_ORIGIN);
+
+    await authFetch('/api/config', { headers: { Accept: 'application/json' } });
+    await authFetch(`${LOCAL_ORIGIN}/auth/session`);
+
+    expect(calls.map((call) => call.headers.get(DESKTOP_AUTH_HEADER))).toEqual([TOKEN, TOKEN]);
+    expect(calls[0].headers.get('Accept')).toBe('application/json');
+  });
+
+  test('requests to tunnel and forwarded ports on the same host never carry the token', async () => {
+    const { calls, fetchImpl } = recordingFetch();
+    const authFetch = createDesktopAuthFetch(fetchImpl, TOKEN, LOCAL_ORIGIN);
+
+    await authFetch('http://127.0.0.1:4100/api/config');
+    await authFetch('http://localhost:3000/api/config');
+    await authFetch('https://tunnel.example.com/api/config');
+
+    expect(calls.every((call) => call.headers.get(DESKTOP_AUTH_HEADER) === null)).toBe(true);
+    expect(desktopAuthWebSocketProtocols('ws://127.0.0.1:4100/api/global/event/ws', TOKEN, LOCAL_ORIGIN)).toEqual([]);
+  });
+
+  test('same-origin WebSockets offer the token as a subprotocol, never in the URL', () => {
+    expect(isSameOriginTarget('ws://127.0.0.1:3000/api/terminal/ws', LOCAL_ORIGIN)).toBe(true);
+    expect(desktopAuthWebSocketProtocols('ws://127.0.0.1:3000/api/terminal/ws', TOKEN, LOCAL_ORIGIN))
+      .toEqual(['openchamber', `openchamber.desktop-token.${TOKEN}`]);
+    expect(desktopAuthWebSocketProtocols('/api/global/event/ws', '', LOCAL_ORIGIN)).toEqual([]);
+  });
+
+  test('event streams are read with the token header', async () => {
+    const requests: Array<{ url: string; headers: Headers }> = [];
+    const fetchImpl = async (url: string, init?: RequestInit) => {
+      requests.push({ url, headers: new Headers(init?.headers) });
+      return new Response('data: {"type":"connected"}\n\n: heartbeat\n\nevent: other\ndata: skipped\n\ndata: a\ndata: b\n\n', {
+        headers: { 'Content-Type': 'text/event-stream' },
+      });
+    };
+
+    const messages: string[] = [];
+    const source = openDesktopAuthEventSource('/api/openchamber/events', TOKEN, LOCAL_ORIGIN, fetchImpl);
+    const ended = new Promise<number>((resolve) => {
+      source.onerror = () => resolve(source.readyState);
+    });
+    source.onmessage = (event) => messages.push(event.data);
+
+    expect(await ended).toBe(0);
+    expect(requests).toHaveLength(1);
+    expect(requests[0].url).toBe('/api/openchamber/events');
+    expect(requests[0].headers.get(DESKTOP_AUTH_HEADER)).toBe(TOKEN);
+    expect(messages).toEqual(['{"type":"connected"}', 'a\nb']);
+  });
+
+  test('rejected event streams close instead of reconnecting', async () => {
+    const source = openDesktopAuthEventSource('/api/terminal/1/stream', TOKEN, LOCAL_ORIGIN, async () => new Response(null, { status: 401 }));
+    const state = await new Promise<number>((resolve) => {
+      source.onerror = () => resolve(source.readyState);
+    });
+    expect(state).toBe(2);
+  });
+
+  test('images are fetched with the token header and served from object URLs', async () => {
+    const { calls, fetchImpl } = recordingFetch();
+    const iconUrl = '/api/projects/p1/icon?v=1';
+
+    expect(resolveDesktopAuthImageUrl(iconUrl, '', LOCAL_ORIGIN, fetchImpl)).toBe(iconUrl);
+    expect(resolveDesktopAuthImageUrl(iconUrl, TOKEN, LOCAL_ORIGIN, fetchImpl)).toBeNull();
+    expect(resolveDesktopAuthImageUrl(iconUrl, TOKEN, LOCAL_ORIGIN, fetchImpl)).toBeNull();
+    expect(calls).toHaveLength(1);
+    expect(calls[0].headers.get(DESKTOP_AUTH_HEADER)).toBe(TOKEN);
+    expect(calls[0].url).toBe(iconUrl);
+  });
+});
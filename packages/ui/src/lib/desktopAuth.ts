@@ -0,0 +1,259 @@
+/**
+ * Desktop sidecar auth for local-origin windows.
+ *
+ * The desktop shell hands its per-launch token to the page through
+ * `window.__OPENCHAMBER_DESKTOP_TOKEN__`, and only when the page is served
+ * from the local sidecar origin. Requests to that origin carry it in the
+ * `X-OpenChamber-Desktop-Token` header, never in a URL where it would end up
+ * in logs, history or Referer headers. WebSockets cannot set that header, so
+ * they offer the token as a `Sec-WebSocket-Protocol` entry instead; event
+ * streams and images are read through `fetch`. Other origins, including SSH
+ * and tunnel ports on the same loopback host, never see the token.
+ */
+
+export const DESKTOP_AUTH_HEADER = 'X-OpenChamber-Desktop-Token';
+export const DESKTOP_AUTH_WS_PROTOCOL = 'openchamber';
+export const DESKTOP_AUTH_WS_TOKEN_PREFIX = 'openchamber.desktop-token.';
+
+type FetchLike = typeof fetch;
+type GetLike = (url: string, init?: RequestInit) => Promise<Response>;
+
+const httpOrigin = (url: URL): string => {
+  const protocol = url.protocol === 'wss:' ? 'https:' : url.protocol === 'ws:' ? 'http:' : url.protocol;
+  return `${protocol}//${url.host}`;
+};
+
+const resolveUrl = (target: string | URL, pageOrigin: string): URL | null => {
+  try {
+    return new URL(target, pageOrigin);
+  } catch {
+    return null;
+  }
+};
+
+/** True when `target` (absolute or relative) resolves to `pageOrigin`, including ws(s) URLs. */
+export const isSameOriginTarget = (target: string | URL, pageOrigin: string): boolean => {
+  const url = resolveUrl(target, pageOrigin);
+  const page = resolveUrl(pageOrigin, pageOrigin);
+  return Boolean(url && page && httpOrigin(url) === httpOrigin(page));
+};
+
+export const getDesktopAuthToken = (): string => {
+  if (typeof window === 'undefined') return '';
+  const token = window.__OPENCHAMBER_DESKTOP_TOKEN__;
+  return typeof token === 'string' ? token : '';
+};
+
+const currentPageOrigin = (): string => (typeof window === 'undefined' ? '' : window.location.origin);
+
+const usesDesktopAuth = (target: string, token: string, pageOrigin: string): boolean =>
+  Boolean(token && pageOrigin && isSameOriginTarget(target, pageOrigin));
+
+/**
+ * Subprotocols for a WebSocket to `target`. The plain protocol comes first
+ * so the server echoes it rather than the token entry.
+ */
+export const desktopAuthWebSocketProtocols = (
+  target: string,
+  token = getDesktopAuthToken(),
+  pageOrigin = currentPageOrigin(),
+): string[] => {
+  if (!usesDesktopAuth(target, token, pageOrigin)) {
+    return [];
+  }
+  return [DESKTOP_AUTH_WS_PROTOCOL, `${DESKTOP_AUTH_WS_TOKEN_PREFIX}${token}`];
+};
+
+/** The subset of `EventSource` the stream consumers rely on. */
+export interface DesktopAuthEventSource {
+  readonly readyState: number;
+  onopen: ((event: Event) => void) | null;
+  onmessage: ((event: MessageEvent<string>) => void) | null;
+  onerror: ((event: Event) => void) | null;
+  close(): void;
+}
+
+const STREAM_CONNECTING = 0;
+const STREAM_OPEN = 1;
+const STREAM_CLOSED = 2;
+
+/**
+ * Server-sent events read through `fetch`, so the request can carry the
+ * token header. Unlike `EventSource` it does not reconnect by itself: a
+ * dropped stream reports an error in the CONNECTING state and a rejected
+ * one in the CLOSED state, and the caller decides what to do next.
+ */
+class HeaderEventSource implements DesktopAuthEventSource {
+  readyState = STREAM_CONNECTING;
+  onopen: ((event: Event) => void) | null = null;
+  onmessage: ((event: MessageEvent<string>) => void) | null = null;
+  onerror: ((event: Event) => void) | null = null;
+  private readonly abort = new AbortController();
+
+  constructor(url: string, token: string, fetchImpl: GetLike) {
+    void this.run(url, token, fetchImpl);
+  }
+
+  close(): void {
+    this.readyState = STREAM_CLOSED;
+    this.abort.abort();
+  }
+
+  private async run(url: string, token: string, fetchImpl: GetLike): Promise<void> {
+    try {
+      const response = await fetchImpl(url, {
+        headers: { Accept: 'text/event-stream', [DESKTOP_AUTH_HEADER]: token },
+        cache: 'no-store',
+        signal: this.abort.signal,
+      });
+      if (!response.ok || !response.body) {
+        this.fail(STREAM_CLOSED);
+        return;
+      }
+      if (this.abort.signal.aborted) {
+        return;
+      }
+      this.readyState = STREAM_OPEN;
+      this.onopen?.(new Event('open'));
+
+      const reader = response.body.getReader();
+      const decoder = new TextDecoder();
+      let buffer = '';
+      for (;;) {
+        const { value, done } = await reader.read();
+        if (done || this.abort.signal.aborted) {
+          break;
+        }
+        buffer = this.dispatch((buffer + decoder.decode(value, { stream: true })).replace(/\r\n/g, '\n'));
+      }
+      this.fail(STREAM_CONNECTING);
+    } catch {
+      this.fail(STREAM_CONNECTING);
+    }
+  }
+
+  /** Dispatch every complete event in `buffer` and return the remainder. */
+  private dispatch(buffer: string): string {
+    const blocks = buffer.split('\n\n');
+    const rest = blocks.pop() ?? '';
+    for (const block of blocks) {
+      let type = 'message';
+      const data: string[] = [];
+      for (const line of block.split('\n')) {
+        if (!line || line.startsWith(':')) {
+          continue;
+        }
+        const colon = line.indexOf(':');
+        const field = colon < 0 ? line : line.slice(0, colon);
+        const value = colon < 0 ? '' : line.slice(colon + 1).replace(/^ /, '');
+        if (field === 'data') {
+          data.push(value);
+        } else if (field === 'event') {
+          type = value;
+        }
+      }
+      if (data.length > 0 && type === 'message' && !this.abort.signal.aborted) {
+        this.onmessage?.(new MessageEvent('message', { data: data.join('\n') }));
+      }
+    }
+    return rest;
+  }
+
+  private fail(readyState: number): void {
+    if (this.abort.signal.aborted) {
+      return;
+    }
+    this.readyState = readyState;
+    this.onerror?.(new Event('error'));
+  }
+}
+
+/**
+ * Open an event stream. Same-origin streams of a desktop window are read
+ * through `fetch` with the token header; anything else uses `EventSource`.
+ */
+export const openDesktopAuthEventSource = (
+  target: string,
+  token = getDesktopAuthToken(),
+  pageOrigin = currentPageOrigin(),
+  fetchImpl: GetLike = (url, init) => fetch(url, init),
+): DesktopAuthEventSource => {
+  if (!usesDesktopAuth(target, token, pageOrigin)) {
+    return new EventSource(target);
+  }
+  return new HeaderEventSource(target, token, fetchImpl);
+};
+
+const authorizedImages = new Map<string, string>();
+const imageListeners = new Set<() => void>();
+let imagesVersion = 0;
+
+/**
+ * URL to put in an `<img>` tag for `target`. Images cannot send headers,
+ * so a same-origin image of a desktop window is fetched once with the
+ * token and served from an object URL. Until it has loaded this returns
+ * null; subscribe with `subscribeDesktopAuthImages` to render it then.
+ */
+export const resolveDesktopAuthImageUrl = (
+  target: string,
+  token = getDesktopAuthToken(),
+  pageOrigin = currentPageOrigin(),
+  fetchImpl: GetLike = (url, init) => fetch(url, init),
+): string | null => {
+  if (!usesDesktopAuth(target, token, pageOrigin)) {
+    return target;
+  }
+  const cached = authorizedImages.get(target);
+  if (cached !== undefined) {
+    return cached || null;
+  }
+  authorizedImages.set(target, '');
+  void fetchImpl(target, { headers: { [DESKTOP_AUTH_HEADER]: token } })
+    .then((response) => (response.ok ? response.blob() : null))
+    .then((blob) => {
+      if (!blob) {
+        return;
+      }
+      authorizedImages.set(target, URL.createObjectURL(blob));
+      imagesVersion += 1;
+      for (const listener of imageListeners) {
+        listener();
+      }
+    })
+    .catch(() => undefined);
+  return null;
+};
+
+export const subscribeDesktopAuthImages = (listener: () => void): (() => void) => {
+  imageListeners.add(listener);
+  return () => {
+    imageListeners.delete(listener);
+  };
+};
+
+export const getDesktopAuthImagesVersion = (): number => imagesVersion;
+
+/** Wrap `baseFetch` so same-origin requests carry the token header. */
+export const createDesktopAuthFetch = (baseFetch: FetchLike, token: string, pageOrigin: string): FetchLike => {
+  const wrapped = (input: RequestInfo | URL, init?: RequestInit): Promise<Response> => {
+    const target = input instanceof Request ? input.url : input;
+    if (!isSameOriginTarget(target, pageOrigin)) {
+      return baseFetch(input, init);
+    }
+    const headers = new Headers(init?.headers ?? (input instanceof Request ? input.headers : undefined));
+    headers.set(DESKTOP_AUTH_HEADER, token);
+    return baseFetch(input, { ...init, headers });
+  };
+  return wrapped as FetchLike;
+};
+
+let installed = false;
+
+/** Patch `window.fetch` once when the shell injected a token for this page. */
+export const installDesktopAuthFetch = (): void => {
+  if (installed || typeof window === 'undefined') return;
+  const token = getDesktopAuthToken();
+  if (!token) return;
+  installed = true;
+  window.fetch = createDesktopAuthFetch(window.fetch.bind(window), token, window.location.origin);
+};
@@ -36,6 +36,43 @@ export type DesktopSshPortForward = {
   remotePort?: number;
 };
 
+export type DesktopSshJumpHost = {
+  host: string;
+  user?: string;
+  port?: number;
+  identityFile?: string;
+  sshPassword?: DesktopSshStoredSecret;
+};
+
+export type DesktopSshIdentitySource = 'agent' | 'file';
+
+export type DesktopSshIdentityChoice = {
+  source: DesktopSshIdentitySource;
+  value: string;
+  identitiesOnly: boolean;
+};
+
+export type DesktopSshIdentityCandidate = {
+  source: DesktopSshIdentitySource;
+  value: string;
+  label: string;
+  keyType?: string;
+  bits?: number;
+  available: boolean;
+};
+
+export type DesktopSshIdentityList = {
+  identities: DesktopSshIdentityCandidate[];
+  agentError?: string;
+};
+
+export type DesktopSshMultiplexConfig = {
+  controlPersistSec: number;
+  keepAliveIntervalSec?: number;
+  compression: boolean;
+  reuseUserMaster: boolean;
+};
+
 export type DesktopSshInstance = {
   id: string;
   nickname?: string;
@@ -51,20 +88,49 @@ export type DesktopSshInstance = {
     preferredPort?: number;
     installMethod: DesktopSshInstallMethod;
     uploadBundleOverSsh: boolean;
+    idleStopAfterMin?: number;
   };
   localForward: {
     preferredLocalPort?: number;
-    bindHost: '127.0.0.1' | 'localhost' | '0.0.0.0';
+    /** `localhost` or an IPv4/IPv6 address of this machine; `0.0.0.0`/`::` bind every interface. */
+    bindHost: string;
   };
   auth: {
     sshPassword?: DesktopSshStoredSecret;
     openchamberPassword?: DesktopSshStoredSecret;
   };
   portForwards: DesktopSshPortForward[];
+  jumpHosts: DesktopSshJumpHost[];
+  identity?: DesktopSshIdentityChoice;
+  tags: string[];
+  group?: string;
+  multiplex: DesktopSshMultiplexConfig;
 };
 
 export type DesktopSshInstancesConfig = {
   instances: DesktopSshInstance[];
+  autoConnectGroups?: string[];
+};
+
+export type DesktopSshImportAction = 'add' | 'rename' | 'skip';
+
+export type DesktopSshImportEntry = {
+  sourceId: string;
+  id: string;
+  label: string;
+  action: DesktopSshImportAction;
+  error?: string;
+};
+
+export type DesktopSshImportPreview = {
+  version: number;
+  entries: DesktopSshImportEntry[];
+  applied: boolean;
+};
+
+export type DesktopSshSelector = {
+  tag?: string;
+  group?: string;
 };
 
 export type DesktopSshPhase =
@@ -77,6 +143,7 @@ export type DesktopSshPhase =
   | 'updating'
   | 'server_detecting'
   | 'server_starting'
+  | 'container_starting'
   | 'forwarding'
   | 'ready'
   | 'degraded'
@@ -92,9 +159,33 @@ export type DesktopSshInstanceStatus = {
   startedByUs: boolean;
   retryAttempt: number;
   requiresUserAction: boolean;
+  failedJumpHost?: number;
+  exposedBeyondLoopback: boolean;
   updatedAtMs: number;
 };
 
+export type DesktopSshRemoteMetrics = {
+  id: string;
+  collectedAtMs: number;
+  loadAverage?: [number, number, number];
+  memTotalBytes?: number;
+  memAvailableBytes?: number;
+  diskMount?: string;
+  diskTotalBytes?: number;
+  diskFreeBytes?: number;
+  diskLow: boolean;
+  serverPid?: number;
+  serverRssBytes?: number;
+  serverStartedAt?: string;
+  serverUptimeSec?: number;
+  error?: string;
+};
+
+export type DesktopSshBulkStatus = {
+  status: DesktopSshInstanceStatus;
+  sessionAlive: boolean;
+};
+
 export type DesktopSshImportCandidate = {
   host: string;
   pattern: boolean;
@@ -170,6 +261,68 @@ const parseForward = (value: unknown): DesktopSshPortForward | null => {
   };
 };
 
+const parseJumpHost = (value: unknown): DesktopSshJumpHost | null => {
+  if (!isRecord(value)) return null;
+  const host = readString(value, 'host')?.trim();
+  if (!host) return null;
+  const user = readString(value, 'user') || undefined;
+  const port = readNumber(value, 'port') ?? undefined;
+  const identityFile = readString(value, 'identityFile') || readString(value, 'identity_file') || undefined;
+  const sshPassword = parseStoredSecret(value.sshPassword || value.ssh_password);
+  return {
+    host,
+    ...(user ? { user } : {}),
+    ...(typeof port === 'number' ? { port } : {}),
+    ...(identityFile ? { identityFile } : {}),
+    ...(sshPassword ? { sshPassword } : {}),
+  };
+};
+
+const parseIdentitySource = (value: unknown): DesktopSshIdentitySource | null => {
+  return value === 'agent' || value === 'file' ? value : null;
+};
+
+const parseIdentityChoice = (value: unknown): DesktopSshIdentityChoice | undefined => {
+  if (!isRecord(value)) return undefined;
+  const source = parseIdentitySource(readString(value, 'source'));
+  const identityValue = readString(value, 'value')?.trim();
+  if (!source || !identityValue) return undefined;
+  return {
+    source,
+    value: identityValue,
+    identitiesOnly: readBoolean(value, 'identitiesOnly') ?? readBoolean(value, 'identities_only') ?? true,
+  };
+};
+
+const parseIdentityCandidate = (value: unknown): DesktopSshIdentityCandidate | null => {
+  if (!isRecord(value)) return null;
+  const source = parseIdentitySource(readString(value, 'source'));
+  const identityValue = readString(value, 'value');
+  if (!source || !identityValue) return null;
+  const keyType = readString(value, 'keyType') || readString(value, 'key_type') || undefined;
+  const bits = readNumber(value, 'bits') ?? undefined;
+  return {
+    source,
+    value: identityValue,
+    label: readString(value, 'label') || identityValue,
+    ...(keyType ? { keyType } : {}),
+    ...(typeof bits === 'number' ? { bits } : {}),
+    available: readBoolean(value, 'available') ?? false,
+  };
+};
+
+const parseMultiplex = (value: unknown): DesktopSshMultiplexConfig => {
+  const raw = isRecord(value) ? value : {};
+  const keepAliveIntervalSec =
+    readNumber(raw, 'keepAliveIntervalSec') ?? readNumber(raw, 'keep_alive_interval_sec');
+  return {
+    controlPersistSec: readNumber(raw, 'controlPersistSec') ?? readNumber(raw, 'control_persist_sec') ?? 300,
+    ...(keepAliveIntervalSec ? { keepAliveIntervalSec } : {}),
+    compression: readBoolean(raw, 'compression') ?? false,
+    reuseUserMaster: readBoolean(raw, 'reuseUserMaster') ?? readBoolean(raw, 'reuse_user_master') ?? false,
+  };
+};
+
 const parseInstance = (value: unknown): DesktopSshInstance | null => {
   if (!isRecord(value)) return null;
   const id = readString(value, 'id');
@@ -214,8 +367,7 @@ const parseInstance = (value: unknown): DesktopSshInstance | null => {
     readString(localRaw, 'bindHost') ||
     readString(localRaw, 'bind_host') ||
     '127.0.0.1';
-  const bindHost: '127.0.0.1' | 'localhost' | '0.0.0.0' =
-    bindHostRaw === 'localhost' || bindHostRaw === '0.0.0.0' ? bindHostRaw : '127.0.0.1';
+  const bindHost = bindHostRaw.trim() || '127.0.0.1';
 
   const forwardsRaw = Array.isArray(value.portForwards)
     ? value.portForwards
@@ -227,11 +379,25 @@ const parseInstance = (value: unknown): DesktopSshInstance | null => {
     .map((item) => parseForward(item))
     .filter((item): item is DesktopSshPortForward => Boolean(item));
 
+  const jumpHostsRaw = Array.isArray(value.jumpHosts)
+    ? value.jumpHosts
+    : Array.isArray(value.jump_hosts)
+      ? value.jump_hosts
+      : [];
+
+  const jumpHosts = jumpHostsRaw
+    .map((item) => parseJumpHost(item))
+    .filter((item): item is DesktopSshJumpHost => Boolean(item));
+
   const preferredPort = readNumber(remoteRaw, 'preferredPort') ?? readNumber(remoteRaw, 'preferred_port');
+  const idleStopAfterMin =
+    readNumber(remoteRaw, 'idleStopAfterMin') ?? readNumber(remoteRaw, 'idle_stop_after_min');
   const preferredLocalPort =
     readNumber(localRaw, 'preferredLocalPort') ?? readNumber(localRaw, 'preferred_local_port');
   const sshPassword = parseStoredSecret(authRaw.sshPassword || authRaw.ssh_password);
   const openchamberPassword = parseStoredSecret(authRaw.openchamberPassword || authRaw.openchamber_password);
+  const identity = parseIdentityChoice(value.identity);
+  const group = readString(value, 'group')?.trim();
 
   return {
     id,
@@ -251,6 +417,7 @@ const parseInstance = (value: unknown): DesktopSshInstance | null => {
         readBoolean(remoteRaw, 'uploadBundleOverSsh') ??
         readBoolean(remoteRaw, 'upload_bundle_over_ssh') ??
         false,
+      ...(idleStopAfterMin ? { idleStopAfterMin } : {}),
     },
     localForward: {
       ...(preferredLocalPort ? { preferredLocalPort } : {}),
@@ -261,6 +428,11 @@ const parseInstance = (value: unknown): DesktopSshInstance | null => {
       ...(openchamberPassword ? { openchamberPassword } : {}),
     },
     portForwards,
+    jumpHosts,
+    ...(identity ? { identity } : {}),
+    tags: asStringArray(value.tags),
+    ...(group ? { group } : {}),
+    multiplex: parseMultiplex(value.multiplex),
   };
 };
 
@@ -274,6 +446,7 @@ const parsePhase = (value: unknown): DesktopSshPhase => {
     case 'updating':
     case 'server_detecting':
     case 'server_starting':
+    case 'container_starting':
     case 'forwarding':
     case 'ready':
     case 'degraded':
@@ -307,6 +480,14 @@ const parseStatus = (value: unknown): DesktopSshInstanceStatus | null => {
     retryAttempt: readNumber(value, 'retryAttempt') ?? readNumber(value, 'retry_attempt') ?? 0,
     requiresUserAction:
       readBoolean(value, 'requiresUserAction') ?? readBoolean(value, 'requires_user_action') ?? false,
+    ...(typeof (readNumber(value, 'failedJumpHost') ?? readNumber(value, 'failed_jump_host')) === 'number'
+      ? {
+          failedJumpHost:
+            readNumber(value, 'failedJumpHost') ?? readNumber(value, 'failed_jump_host') ?? undefined,
+        }
+      : {}),
+    exposedBeyondLoopback:
+      readBoolean(value, 'exposedBeyondLoopback') ?? readBoolean(value, 'exposed_beyond_loopback') ?? false,
     updatedAtMs: readNumber(value, 'updatedAtMs') ?? readNumber(value, 'updated_at_ms') ?? Date.now(),
   };
 };
@@ -341,6 +522,13 @@ export const createDesktopSshInstance = (id: string, sshCommand: string): Deskto
     },
     auth: {},
     portForwards: [],
+    jumpHosts: [],
+    tags: [],
+    multiplex: {
+      controlPersistSec: 300,
+      compression: false,
+      reuseUserMaster: false,
+    },
   };
 };
 
@@ -365,7 +553,11 @@ export const desktopSshInstancesGet = async (): Promise<DesktopSshInstancesConfi
     .map((item) => parseInstance(item))
     .filter((item): item is DesktopSshInstance => Boolean(item));
 
-  return { instances };
+  const groupsRaw = raw.autoConnectGroups ?? raw.auto_connect_groups;
+  return {
+    instances,
+    ...(Array.isArray(groupsRaw) ? { autoConnectGroups: asStringArray(groupsRaw) } : {}),
+  };
 };
 
 export const desktopSshInstancesSet = async (config: DesktopSshInstancesConfig): Promise<void> => {
@@ -374,10 +566,55 @@ export const desktopSshInstancesSet = async (config: DesktopSshInstancesConfig):
   await invoke('desktop_ssh_instances_set', {
     config: {
       instances: config.instances,
+      ...(config.autoConnectGroups ? { autoConnectGroups: config.autoConnectGroups } : {}),
     },
   });
 };
 
+export const desktopSshInstancesExport = async (path: string, ids?: string[]): Promise<number> => {
+  const invoke = getInvoke();
+  if (!invoke) return 0;
+  const raw = await invoke('desktop_ssh_instances_export', {
+    path,
+    ...(ids ? { ids } : {}),
+  });
+  return typeof raw === 'number' ? raw : 0;
+};
+
+const parseImportAction = (value: unknown): DesktopSshImportAction => {
+  return value === 'rename' || value === 'skip' ? value : 'add';
+};
+
+export const desktopSshInstancesImport = async (
+  path: string,
+  apply = false,
+): Promise<DesktopSshImportPreview | null> => {
+  const invoke = getInvoke();
+  if (!invoke) return null;
+  const raw = await invoke('desktop_ssh_instances_import', { path, apply });
+  if (!isRecord(raw)) return null;
+  const entries = (Array.isArray(raw.entries) ? raw.entries : []).flatMap((item) => {
+    if (!isRecord(item)) return [];
+    const id = readString(item, 'id');
+    if (!id) return [];
+    const error = readString(item, 'error') || undefined;
+    return [
+      {
+        sourceId: readString(item, 'sourceId') || readString(item, 'source_id') || id,
+        id,
+        label: readString(item, 'label') || id,
+        action: parseImportAction(readString(item, 'action')),
+        ...(error ? { error } : {}),
+      },
+    ];
+  });
+  return {
+    version: readNumber(raw, 'version') ?? 1,
+    entries,
+    applied: readBoolean(raw, 'applied') ?? false,
+  };
+};
+
 export const desktopSshImportHosts = async (): Promise<DesktopSshImportCandidate[]> => {
   const invoke = getInvoke();
   if (!invoke) return [];
@@ -429,6 +666,154 @@ export const desktopSshLogsClear = async (id: string): Promise<void> => {
   await invoke('desktop_ssh_logs_clear', { id });
 };
 
+const parseStatusList = (raw: unknown): DesktopSshInstanceStatus[] => {
+  if (!Array.isArray(raw)) return [];
+  return raw
+    .map((item) => parseStatus(item))
+    .filter((item): item is DesktopSshInstanceStatus => Boolean(item));
+};
+
+export const desktopSshBulkConnect = async (
+  selector: DesktopSshSelector,
+  concurrency?: number,
+): Promise<DesktopSshInstanceStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_ssh_bulk_connect', {
+    selector,
+    ...(typeof concurrency === 'number' ? { concurrency } : {}),
+  });
+  return parseStatusList(raw);
+};
+
+export const desktopSshBulkDisconnect = async (
+  selector: DesktopSshSelector,
+  concurrency?: number,
+): Promise<DesktopSshInstanceStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_ssh_bulk_disconnect', {
+    selector,
+    ...(typeof concurrency === 'number' ? { concurrency } : {}),
+  });
+  return parseStatusList(raw);
+};
+
+export const desktopSshBulkStatus = async (
+  selector: DesktopSshSelector,
+  concurrency?: number,
+): Promise<DesktopSshBulkStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_ssh_bulk_status', {
+    selector,
+    ...(typeof concurrency === 'number' ? { concurrency } : {}),
+  });
+  if (!Array.isArray(raw)) return [];
+  return raw.flatMap((item) => {
+    if (!isRecord(item)) return [];
+    const status = parseStatus(item.status);
+    if (!status) return [];
+    return [
+      {
+        status,
+        sessionAlive: readBoolean(item, 'sessionAlive') ?? readBoolean(item, 'session_alive') ?? false,
+      },
+    ];
+  });
+};
+
+export const desktopSshListIdentities = async (options?: {
+  id?: string;
+  sshCommand?: string;
+}): Promise<DesktopSshIdentityList> => {
+  const invoke = getInvoke();
+  if (!invoke) return { identities: [] };
+  const raw = await invoke('desktop_ssh_list_identities', {
+    ...(options?.id ? { id: options.id } : {}),
+    ...(options?.sshCommand ? { sshCommand: options.sshCommand } : {}),
+  });
+  if (!isRecord(raw)) return { identities: [] };
+  const identities = (Array.isArray(raw.identities) ? raw.identities : [])
+    .map((item) => parseIdentityCandidate(item))
+    .filter((item): item is DesktopSshIdentityCandidate => Boolean(item));
+  const agentError = readString(raw, 'agentError') || readString(raw, 'agent_error') || undefined;
+  return {
+    identities,
+    ...(agentError ? { agentError } : {}),
+  };
+};
+
+const parseMetrics = (value: unknown): DesktopSshRemoteMetrics | null => {
+  if (!isRecord(value)) return null;
+  const id = readString(value, 'id');
+  if (!id) return null;
+  const loadRaw = value.loadAverage;
+  const loadAverage =
+    Array.isArray(loadRaw) && loadRaw.length === 3 && loadRaw.every((item) => typeof item === 'number')
+      ? (loadRaw as [number, number, number])
+      : undefined;
+  const optionalNumber = (key: string) => readNumber(value, key) ?? undefined;
+  const diskMount = readString(value, 'diskMount') || undefined;
+  const serverStartedAt = readString(value, 'serverStartedAt') || undefined;
+  const error = readString(value, 'error') || undefined;
+  const numbers = {
+    memTotalBytes: optionalNumber('memTotalBytes'),
+    memAvailableBytes: optionalNumber('memAvailableBytes'),
+    diskTotalBytes: optionalNumber('diskTotalBytes'),
+    diskFreeBytes: optionalNumber('diskFreeBytes'),
+    serverPid: optionalNumber('serverPid'),
+    serverRssBytes: optionalNumber('serverRssBytes'),
+    serverUptimeSec: optionalNumber('serverUptimeSec'),
+  };
+  return {
+    id,
+    collectedAtMs: readNumber(value, 'collectedAtMs') ?? Date.now(),
+    ...(loadAverage ? { loadAverage } : {}),
+    ...Object.fromEntries(Object.entries(numbers).filter(([, item]) => typeof item === 'number')),
+    ...(diskMount ? { diskMount } : {}),
+    diskLow: readBoolean(value, 'diskLow') ?? false,
+    ...(serverStartedAt ? { serverStartedAt } : {}),
+    ...(error ? { error } : {}),
+  };
+};
+
+export const desktopSshMetrics = async (id?: string): Promise<DesktopSshRemoteMetrics[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_ssh_metrics', {
+    ...(id ? { id } : {}),
+  });
+  if (!Array.isArray(raw)) return [];
+  return raw
+    .map((item) => parseMetrics(item))
+    .filter((item): item is DesktopSshRemoteMetrics => Boolean(item));
+};
+
+export const listenDesktopSshMetrics = async (
+  listener: (metrics: DesktopSshRemoteMetrics) => void,
+): Promise<() => Promise<void>> => {
+  if (!isTauriShell()) {
+    return async () => {};
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const listen = tauri?.event?.listen;
+  if (typeof listen !== 'function') {
+    return async () => {};
+  }
+
+  const unlisten = await listen('openchamber:ssh-instance-metrics', (event) => {
+    const metrics = parseMetrics(event?.payload);
+    if (!metrics) return;
+    listener(metrics);
+  });
+
+  return async () => {
+    await unlisten();
+  };
+};
+
 export const listenDesktopSshStatus = async (
   listener: (status: DesktopSshInstanceStatus) => void,
 ): Promise<() => Promise<void>> => {
@@ -452,3 +837,482 @@ export const listenDesktopSshStatus = async (
     await unlisten();
   };
 };
+
+export type DesktopDockerContainerConfig = {
+  name: string;
+  /** Image used when the container does not exist yet. */
+  image?: string;
+  volumes: string[];
+  /** `KEY=VALUE` entries. */
+  env: string[];
+  workdir?: string;
+  removeOnDisconnect: boolean;
+};
+
+export type DesktopDockerInstance = {
+  id: string;
+  nickname?: string;
+  /** `DOCKER_HOST` override; remote daemons need a non-loopback bind host. */
+  dockerHost?: string;
+  container: DesktopDockerContainerConfig;
+  remoteOpenchamber: DesktopSshInstance['remoteOpenchamber'];
+  localForward: DesktopSshInstance['localForward'];
+};
+
+export type DesktopDockerInstancesConfig = {
+  instances: DesktopDockerInstance[];
+};
+
+const parseDockerInstance = (value: unknown): DesktopDockerInstance | null => {
+  if (!isRecord(value)) return null;
+  const id = readString(value, 'id');
+  const containerRaw = isRecord(value.container) ? value.container : null;
+  const name = containerRaw ? readString(containerRaw, 'name') : null;
+  if (!id || !containerRaw || !name) return null;
+
+  const remoteRaw = isRecord(value.remoteOpenchamber)
+    ? value.remoteOpenchamber
+    : isRecord(value.remote_openchamber)
+      ? value.remote_openchamber
+      : {};
+  const localRaw = isRecord(value.localForward)
+    ? value.localForward
+    : isRecord(value.local_forward)
+      ? value.local_forward
+      : {};
+
+  const nickname = readString(value, 'nickname');
+  const dockerHost = readString(value, 'dockerHost') || readString(value, 'docker_host');
+  const image = readString(containerRaw, 'image');
+  const workdir = readString(containerRaw, 'workdir');
+  const rawInstallMethod = readString(remoteRaw, 'installMethod') || readString(remoteRaw, 'install_method');
+  const preferredPort = readNumber(remoteRaw, 'preferredPort') ?? readNumber(remoteRaw, 'preferred_port');
+  const preferredLocalPort =
+    readNumber(localRaw, 'preferredLocalPort') ?? readNumber(localRaw, 'preferred_local_port');
+  const bindHost = (readString(localRaw, 'bindHost') || readString(localRaw, 'bind_host') || '').trim();
+
+  return {
+    id,
+    ...(nickname ? { nickname } : {}),
+    ...(dockerHost ? { dockerHost } : {}),
+    container: {
+      name,
+      ...(image ? { image } : {}),
+      volumes: asStringArray(containerRaw.volumes),
+      env: asStringArray(containerRaw.env),
+      ...(workdir ? { workdir } : {}),
+      removeOnDisconnect:
+        readBoolean(containerRaw, 'removeOnDisconnect') ??
+        readBoolean(containerRaw, 'remove_on_disconnect') ??
+        false,
+    },
+    remoteOpenchamber: {
+      mode: readString(remoteRaw, 'mode')?.toLowerCase() === 'external' ? 'external' : 'managed',
+      keepRunning: readBoolean(remoteRaw, 'keepRunning') ?? readBoolean(remoteRaw, 'keep_running') ?? true,
+      ...(preferredPort ? { preferredPort } : {}),
+      installMethod: rawInstallMethod === 'npm' ? 'npm' : 'bun',
+      uploadBundleOverSsh: false,
+    },
+    localForward: {
+      ...(preferredLocalPort ? { preferredLocalPort } : {}),
+      bindHost: bindHost || '127.0.0.1',
+    },
+  };
+};
+
+export const createDesktopDockerInstance = (id: string, containerName: string): DesktopDockerInstance => {
+  return {
+    id,
+    container: {
+      name: containerName,
+      volumes: [],
+      env: [],
+      removeOnDisconnect: false,
+    },
+    remoteOpenchamber: {
+      mode: 'managed',
+      keepRunning: true,
+      installMethod: 'bun',
+      uploadBundleOverSsh: false,
+    },
+    localForward: {
+      bindHost: '127.0.0.1',
+    },
+  };
+};
+
+export const desktopDockerInstancesGet = async (): Promise<DesktopDockerInstancesConfig> => {
+  const invoke = getInvoke();
+  if (!invoke) {
+    return { instances: [] };
+  }
+
+  const raw = await invoke('desktop_docker_instances_get');
+  if (!isRecord(raw) || !Array.isArray(raw.instances)) {
+    return { instances: [] };
+  }
+
+  return {
+    instances: raw.instances
+      .map((item) => parseDockerInstance(item))
+      .filter((item): item is DesktopDockerInstance => Boolean(item)),
+  };
+};
+
+export const desktopDockerInstancesSet = async (config: DesktopDockerInstancesConfig): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_docker_instances_set', { config: { instances: config.instances } });
+};
+
+export const desktopDockerConnect = async (id: string): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_docker_connect', { id });
+};
+
+export const desktopDockerDisconnect = async (id: string): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_docker_disconnect', { id });
+};
+
+export const desktopDockerStatus = async (id?: string): Promise<DesktopSshInstanceStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_docker_status', {
+    ...(id ? { id } : {}),
+  });
+  return parseStatusList(raw);
+};
+
+export const desktopDockerLogs = async (id: string, limit?: number): Promise<string[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_docker_logs', {
+    id,
+    ...(typeof limit === 'number' ? { limit } : {}),
+  });
+  if (!Array.isArray(raw)) return [];
+  return raw.filter((line): line is string => typeof line === 'string');
+};
+
+export const listenDesktopDockerStatus = async (
+  listener: (status: DesktopSshInstanceStatus) => void,
+): Promise<() => Promise<void>> => {
+  if (!isTauriShell()) {
+    return async () => {};
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const listen = tauri?.event?.listen;
+  if (typeof listen !== 'function') {
+    return async () => {};
+  }
+
+  const unlisten = await listen('openchamber:docker-instance-status', (event) => {
+    const status = parseStatus(event?.payload);
+    if (!status) return;
+    listener(status);
+  });
+
+  return async () => {
+    await unlisten();
+  };
+};
+
+export type DesktopKubeInstance = {
+  id: string;
+  nickname?: string;
+  context?: string;
+  namespace?: string;
+  /** Fixed pod name; exactly one of `pod` and `selector` is set. */
+  pod?: string;
+  /** Label selector, re-resolved on every reconnect. */
+  selector?: string;
+  container?: string;
+  remoteOpenchamber: DesktopSshInstance['remoteOpenchamber'];
+  localForward: DesktopSshInstance['localForward'];
+  /** Only `local` forwards to ports of the pod itself. */
+  portForwards: DesktopSshPortForward[];
+};
+
+export type DesktopKubeInstancesConfig = {
+  instances: DesktopKubeInstance[];
+};
+
+const parseKubeInstance = (value: unknown): DesktopKubeInstance | null => {
+  if (!isRecord(value)) return null;
+  const id = readString(value, 'id');
+  if (!id) return null;
+
+  const remoteRaw = isRecord(value.remoteOpenchamber)
+    ? value.remoteOpenchamber
+    : isRecord(value.remote_openchamber)
+      ? value.remote_openchamber
+      : {};
+  const localRaw = isRecord(value.localForward)
+    ? value.localForward
+    : isRecord(value.local_forward)
+      ? value.local_forward
+      : {};
+  const forwardsRaw = Array.isArray(value.portForwards)
+    ? value.portForwards
+    : Array.isArray(value.port_forwards)
+      ? value.port_forwards
+      : [];
+
+  const optional = (key: string) => {
+    const raw = readString(value, key)?.trim();
+    return raw ? { [key]: raw } : {};
+  };
+  const rawInstallMethod = readString(remoteRaw, 'installMethod') || readString(remoteRaw, 'install_method');
+  const preferredPort = readNumber(remoteRaw, 'preferredPort') ?? readNumber(remoteRaw, 'preferred_port');
+  const preferredLocalPort =
+    readNumber(localRaw, 'preferredLocalPort') ?? readNumber(localRaw, 'preferred_local_port');
+  const bindHost = (readString(localRaw, 'bindHost') || readString(localRaw, 'bind_host') || '').trim();
+
+  return {
+    id,
+    ...optional('nickname'),
+    ...optional('context'),
+    ...optional('namespace'),
+    ...optional('pod'),
+    ...optional('selector'),
+    ...optional('container'),
+    remoteOpenchamber: {
+      mode: readString(remoteRaw, 'mode')?.toLowerCase() === 'external' ? 'external' : 'managed',
+      keepRunning: readBoolean(remoteRaw, 'keepRunning') ?? readBoolean(remoteRaw, 'keep_running') ?? true,
+      ...(preferredPort ? { preferredPort } : {}),
+      installMethod: rawInstallMethod === 'npm' ? 'npm' : 'bun',
+      uploadBundleOverSsh: false,
+    },
+    localForward: {
+      ...(preferredLocalPort ? { preferredLocalPort } : {}),
+      bindHost: bindHost || '127.0.0.1',
+    },
+    portForwards: forwardsRaw
+      .map((item) => parseForward(item))
+      .filter((item): item is DesktopSshPortForward => Boolean(item)),
+  };
+};
+
+export const createDesktopKubeInstance = (id: string, selector: string): DesktopKubeInstance => {
+  return {
+    id,
+    selector,
+    remoteOpenchamber: {
+      mode: 'managed',
+      keepRunning: true,
+      installMethod: 'bun',
+      uploadBundleOverSsh: false,
+    },
+    localForward: {
+      bindHost: '127.0.0.1',
+    },
+    portForwards: [],
+  };
+};
+
+export const desktopKubeInstancesGet = async (): Promise<DesktopKubeInstancesConfig> => {
+  const invoke = getInvoke();
+  if (!invoke) {
+    return { instances: [] };
+  }
+
+  const raw = await invoke('desktop_kube_instances_get');
+  if (!isRecord(raw) || !Array.isArray(raw.instances)) {
+    return { instances: [] };
+  }
+
+  return {
+    instances: raw.instances
+      .map((item) => parseKubeInstance(item))
+      .filter((item): item is DesktopKubeInstance => Boolean(item)),
+  };
+};
+
+export const desktopKubeInstancesSet = async (config: DesktopKubeInstancesConfig): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_kube_instances_set', { config: { instances: config.instances } });
+};
+
+export const desktopKubeConnect = async (id: string): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_kube_connect', { id });
+};
+
+export const desktopKubeDisconnect = async (id: string): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_kube_disconnect', { id });
+};
+
+export const desktopKubeStatus = async (id?: string): Promise<DesktopSshInstanceStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_kube_status', {
+    ...(id ? { id } : {}),
+  });
+  return parseStatusList(raw);
+};
+
+export const desktopKubeLogs = async (id: string, limit?: number): Promise<string[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_kube_logs', {
+    id,
+    ...(typeof limit === 'number' ? { limit } : {}),
+  });
+  if (!Array.isArray(raw)) return [];
+  return raw.filter((line): line is string => typeof line === 'string');
+};
+
+export const listenDesktopKubeStatus = async (
+  listener: (status: DesktopSshInstanceStatus) => void,
+): Promise<() => Promise<void>> => {
+  if (!isTauriShell()) {
+    return async () => {};
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const listen = tauri?.event?.listen;
+  if (typeof listen !== 'function') {
+    return async () => {};
+  }
+
+  const unlisten = await listen('openchamber:kube-instance-status', (event) => {
+    const status = parseStatus(event?.payload);
+    if (!status) return;
+    listener(status);
+  });
+
+  return async () => {
+    await unlisten();
+  };
+};
+
+export type DesktopLocalProfile = {
+  id: string;
+  nickname?: string;
+  /** Fixed port; a free one is picked on every start when unset. */
+  port?: number;
+  /** Defaults to `profiles/<id>` next to `settings.json`. */
+  dataDir?: string;
+  opencodeBinary?: string;
+  /** Keep opencode config and auth inside the profile data directory. */
+  isolateOpencode: boolean;
+  /** `KEY=VALUE` entries. */
+  env: string[];
+  args: string[];
+};
+
+export type DesktopLocalProfilesConfig = {
+  profiles: DesktopLocalProfile[];
+};
+
+const parseLocalProfile = (value: unknown): DesktopLocalProfile | null => {
+  if (!isRecord(value)) return null;
+  const id = readString(value, 'id');
+  if (!id) return null;
+
+  const nickname = readString(value, 'nickname');
+  const port = readNumber(value, 'port');
+  const dataDir = readString(value, 'dataDir') || readString(value, 'data_dir');
+  const opencodeBinary = readString(value, 'opencodeBinary') || readString(value, 'opencode_binary');
+
+  return {
+    id,
+    ...(nickname ? { nickname } : {}),
+    ...(port ? { port } : {}),
+    ...(dataDir ? { dataDir } : {}),
+    ...(opencodeBinary ? { opencodeBinary } : {}),
+    isolateOpencode: readBoolean(value, 'isolateOpencode') ?? readBoolean(value, 'isolate_opencode') ?? true,
+    env: asStringArray(value.env),
+    args: asStringArray(value.args),
+  };
+};
+
+export const desktopLocalProfilesGet = async (): Promise<DesktopLocalProfilesConfig> => {
+  const invoke = getInvoke();
+  if (!invoke) {
+    return { profiles: [] };
+  }
+
+  const raw = await invoke('desktop_local_profiles_get');
+  if (!isRecord(raw) || !Array.isArray(raw.profiles)) {
+    return { profiles: [] };
+  }
+
+  return {
+    profiles: raw.profiles
+      .map((item) => parseLocalProfile(item))
+      .filter((item): item is DesktopLocalProfile => Boolean(item)),
+  };
+};
+
+export const desktopLocalProfilesSet = async (config: DesktopLocalProfilesConfig): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_local_profiles_set', { config: { profiles: config.profiles } });
+};
+
+/** Starts the profile's server if needed and resolves with its URL once healthy. */
+export const desktopLocalProfileStart = async (id: string): Promise<string | null> => {
+  const invoke = getInvoke();
+  if (!invoke) return null;
+  const raw = await invoke('desktop_local_profile_start', { id });
+  return typeof raw === 'string' ? raw : null;
+};
+
+export const desktopLocalProfileStop = async (id: string): Promise<void> => {
+  const invoke = getInvoke();
+  if (!invoke) return;
+  await invoke('desktop_local_profile_stop', { id });
+};
+
+export const desktopLocalProfileStatus = async (id?: string): Promise<DesktopSshInstanceStatus[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_local_profile_status', {
+    ...(id ? { id } : {}),
+  });
+  return parseStatusList(raw);
+};
+
+export const desktopLocalProfileLogs = async (id: string, limit?: number): Promise<string[]> => {
+  const invoke = getInvoke();
+  if (!invoke) return [];
+  const raw = await invoke('desktop_local_profile_logs', {
+    id,
+    ...(typeof limit === 'number' ? { limit } : {}),
+  });
+  if (!Array.isArray(raw)) return [];
+  return raw.filter((line): line is string => typeof line === 'string');
+};
+
+export const listenDesktopLocalProfileStatus = async (
+  listener: (status: DesktopSshInstanceStatus) => void,
+): Promise<() => Promise<void>> => {
+  if (!isTauriShell()) {
+    return async () => {};
+  }
+
+  const tauri = (window as unknown as { __TAURI__?: TauriGlobal }).__TAURI__;
+  const listen = tauri?.event?.listen;
+  if (typeof listen !== 'function') {
+    return async () => {};
+  }
+
+  const unlisten = await listen('openchamber:local-profile-status', (event) => {
+    const status = parseStatus(event?.payload);
+    if (!status) return;
+    listener(status);
+  });
+
+  return async () => {
+    await unlisten();
+  };
+};
@@ -681,6 +681,14 @@ export const settingsDict = {
   'settings.openchamber.desktopNetwork.error.loadFailed': 'Failed to load desktop settings',
   'settings.openchamber.desktopNetwork.error.saveFailed': 'Failed to save desktop settings',
   'settings.openchamber.desktopNetwork.error.savedRestartFailed': 'Saved, but failed to restart app',
+  'settings.openchamber.desktopNetwork.actions.pairDevice': 'Pair a device',
+  'settings.openchamber.desktopNetwork.actions.forgetDevices': 'Forget paired devices',
+  'settings.openchamber.desktopNetwork.hint.pairingRequired': 'Other devices must be paired with this app before they can connect.',
+  'settings.openchamber.desktopNetwork.hint.pairingLink': 'Open this link on the other device within 5 minutes: ',
+  'settings.openchamber.desktopNetwork.toast.pairingLinkCopied': 'Pairing link copied',
+  'settings.openchamber.desktopNetwork.toast.devicesForgotten': 'Paired devices forgotten',
+  'settings.openchamber.desktopNetwork.error.pairingFailed': 'Failed to create pairing link',
+  'settings.openchamber.desktopNetwork.error.forgetDevicesFailed': 'Failed to forget paired devices',
   'settings.openchamber.opencodeCli.title': 'OpenCode CLI',
   'settings.openchamber.opencodeCli.tooltipPrefix': 'Optional absolute path to the',
   'settings.openchamber.opencodeCli.tooltipSuffix': 'binary.',
@@ -693,6 +701,33 @@ export const settingsDict = {
   'settings.openchamber.opencodeCli.dialog.selectBinaryTitle': 'Select opencode binary',
   'settings.openchamber.opencodeCli.tipPrefix': 'Tip: you can also use',
   'settings.openchamber.opencodeCli.tipMiddle': 'env var, but this setting persists in',
+  'settings.openchamber.opencodeCli.actions.detect': 'Detect',
+  'settings.openchamber.opencodeCli.actions.detecting': 'Detecting…',
+  'settings.openchamber.opencodeCli.actions.use': 'Use',
+  'settings.openchamber.opencodeCli.detect.minimumVersion': 'Requires opencode {version} or newer',
+  'settings.openchamber.opencodeCli.detect.empty': 'No opencode binaries found',
+  'settings.openchamber.opencodeCli.detect.unknownVersion': 'Unknown version',
+  'settings.openchamber.opencodeCli.detect.current': 'Current',
+  'settings.openchamber.opencodeCli.detect.source.settings': 'settings',
+  'settings.openchamber.opencodeCli.detect.source.env': 'env var',
+  'settings.openchamber.opencodeCli.detect.source.path': 'PATH',
+  'settings.openchamber.opencodeCli.detect.source.installDir': 'install dir',
+  'settings.openchamber.opencodeCli.toast.selectFailed': 'Failed to switch OpenCode binary',
+  'settings.openchamber.sidecarEnv.title': 'Server Environment',
+  'settings.openchamber.sidecarEnv.description': 'Extra environment variables and arguments for the local OpenChamber server. Saving restarts the server.',
+  'settings.openchamber.sidecarEnv.empty': 'No custom variables',
+  'settings.openchamber.sidecarEnv.field.variables': 'Environment variables',
+  'settings.openchamber.sidecarEnv.field.namePlaceholder': 'NAME',
+  'settings.openchamber.sidecarEnv.field.valuePlaceholder': 'value',
+  'settings.openchamber.sidecarEnv.field.secretUnchanged': 'Unchanged (stored secret)',
+  'settings.openchamber.sidecarEnv.field.secret': 'Secret',
+  'settings.openchamber.sidecarEnv.field.secretAria': 'Mark value as secret',
+  'settings.openchamber.sidecarEnv.field.args': 'Extra arguments',
+  'settings.openchamber.sidecarEnv.field.argsPlaceholder': '--example-flag',
+  'settings.openchamber.sidecarEnv.field.argsHint': 'One argument per line. --port and --host are managed by OpenChamber.',
+  'settings.openchamber.sidecarEnv.actions.add': 'Add variable',
+  'settings.openchamber.sidecarEnv.actions.removeAria': 'Remove variable',
+  'settings.openchamber.sidecarEnv.actions.saveAndRestart': 'Save + Restart server',
   'settings.mcp.sidebar.title': 'MCP Servers',
   'settings.mcp.sidebar.total': 'Total {count}',
   'settings.mcp.sidebar.actions.refreshStatusAria': 'Refresh MCP status',
@@ -1651,10 +1651,13 @@ export const dict = {
   'openCodeStatusDialog.title': 'OpenCode Status',
   'openCodeStatusDialog.description': 'Inspect current OpenCode status and diagnostics.',
   'openCodeStatusDialog.actions.copy': 'Copy',
+  'openCodeStatusDialog.actions.downloadLogs': 'Download logs',
   'openCodeStatusDialog.empty.noData': 'No data.',
   'openCodeStatusDialog.toast.copiedTitle': 'Copied',
   'openCodeStatusDialog.toast.copiedDescription': 'Status copied to clipboard',
   'openCodeStatusDialog.toast.copyFailed': 'Failed to copy status',
+  'openCodeStatusDialog.toast.logsSaved': 'Logs saved',
+  'openCodeStatusDialog.toast.logsSaveFailed': 'Failed to save logs',
   'saveProjectPlanDialog.title': 'Save Project Plan',
   'saveProjectPlanDialog.description': 'Save this plan into your project as a Markdown file.',
   'saveProjectPlanDialog.field.title': 'Title',
@@ -1861,6 +1864,8 @@ export const dict = {
   'sessionAuth.locked.tunnelTitle': 'Tunnel access required',
   'sessionAuth.locked.unlockTitle': 'Unlock OpenChamber',
   'sessionAuth.locked.tunnelDescription': 'Open this tunnel using the one-time connect link from the desktop app.',
+  'sessionAuth.locked.desktopPairingTitle': 'Pair this device',
+  'sessionAuth.locked.desktopPairingDescription': 'Open a pairing link from the desktop app: Settings → Desktop Network Access → Pair a device.',
   'sessionAuth.locked.passwordDescription': 'This session is password-protected.',
   'sessionAuth.locked.hostSwitcherHint': 'Use Local if remote is unreachable.',
   'sessionAuth.actions.cancelPasskey': 'Cancel passkey',
@@ -1943,6 +1948,7 @@ export const dict = {
   'desktopHostSwitcher.startup.failed': 'Failed to connect the default SSH instance.',
   'desktopHostSwitcher.toast.sshConnected': 'SSH instance "{host}" connected',
   'desktopHostSwitcher.toast.sshFailedToConnect': 'SSH instance "{host}" failed to connect',
+  'desktopHostSwitcher.toast.profileFailedToStart': 'Local profile "{host}" failed to start',
   'desktopHostSwitcher.toast.instanceUnreachable': 'Instance "{host}" is unreachable',
   'errorBoundary.title': 'Something went wrong',
   'errorBoundary.description': 'The application encountered an unexpected error. This has been logged for debugging.',
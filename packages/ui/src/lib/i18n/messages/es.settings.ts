@@ -681,6 +681,14 @@ export const settingsDict = {
   "settings.openchamber.desktopNetwork.error.loadFailed": "No se pudo cargar la configuración de escritorio",
   "settings.openchamber.desktopNetwork.error.saveFailed": "No se pudo guardar la configuración de escritorio",
   "settings.openchamber.desktopNetwork.error.savedRestartFailed": "Guardado, pero no se pudo reiniciar la aplicación",
+  "settings.openchamber.desktopNetwork.actions.pairDevice": "Emparejar un dispositivo",
+  "settings.openchamber.desktopNetwork.actions.forgetDevices": "Olvidar dispositivos emparejados",
+  "settings.openchamber.desktopNetwork.hint.pairingRequired": "Los demás dispositivos deben emparejarse con esta aplicación antes de conectarse.",
+  "settings.openchamber.desktopNetwork.hint.pairingLink": "Abre este enlace en el otro dispositivo en menos de 5 minutos: ",
+  "settings.openchamber.desktopNetwork.toast.pairingLinkCopied": "Enlace de emparejamiento copiado",
+  "settings.openchamber.desktopNetwork.toast.devicesForgotten": "Dispositivos emparejados olvidados",
+  "settings.openchamber.desktopNetwork.error.pairingFailed": "No se pudo crear el enlace de emparejamiento",
+  "settings.openchamber.desktopNetwork.error.forgetDevicesFailed": "No se pudieron olvidar los dispositivos emparejados",
   "settings.openchamber.opencodeCli.title": "CLI de OpenCode",
   "settings.openchamber.opencodeCli.tooltipPrefix": "Ruta absoluta opcional al",
   "settings.openchamber.opencodeCli.tooltipSuffix": "ejecutable.",
@@ -693,6 +701,33 @@ export const settingsDict = {
   "settings.openchamber.opencodeCli.dialog.selectBinaryTitle": "Seleccionar ejecutable de opencode",
   "settings.openchamber.opencodeCli.tipPrefix": "Consejo: también puedes usar",
   "settings.openchamber.opencodeCli.tipMiddle": "variable de entorno, pero esta configuración persiste en",
+  "settings.openchamber.opencodeCli.actions.detect": "Detectar",
+  "settings.openchamber.opencodeCli.actions.detecting": "Detectando…",
+  "settings.openchamber.opencodeCli.actions.use": "Usar",
+  "settings.openchamber.opencodeCli.detect.minimumVersion": "Requiere opencode {version} o posterior",
+  "settings.openchamber.opencodeCli.detect.empty": "No se encontraron binarios de opencode",
+  "settings.openchamber.opencodeCli.detect.unknownVersion": "Versión desconocida",
+  "settings.openchamber.opencodeCli.detect.current": "Actual",
+  "settings.openchamber.opencodeCli.detect.source.settings": "configuración",
+  "settings.openchamber.opencodeCli.detect.source.env": "variable de entorno",
+  "settings.openchamber.opencodeCli.detect.source.path": "PATH",
+  "settings.openchamber.opencodeCli.detect.source.installDir": "directorio de instalación",
+  "settings.openchamber.opencodeCli.toast.selectFailed": "No se pudo cambiar el binario de OpenCode",
+  "settings.openchamber.sidecarEnv.title": "Entorno del servidor",
+  "settings.openchamber.sidecarEnv.description": "Variables de entorno y argumentos adicionales para el servidor local de OpenChamber. Al guardar se reinicia el servidor.",
+  "settings.openchamber.sidecarEnv.empty": "Sin variables personalizadas",
+  "settings.openchamber.sidecarEnv.field.variables": "Variables de entorno",
+  "settings.openchamber.sidecarEnv.field.namePlaceholder": "NOMBRE",
+  "settings.openchamber.sidecarEnv.field.valuePlaceholder": "valor",
+  "settings.openchamber.sidecarEnv.field.secretUnchanged": "Sin cambios (secreto guardado)",
+  "settings.openchamber.sidecarEnv.field.secret": "Secreto",
+  "settings.openchamber.sidecarEnv.field.secretAria": "Marcar el valor como secreto",
+  "settings.openchamber.sidecarEnv.field.args": "Argumentos adicionales",
+  "settings.openchamber.sidecarEnv.field.argsPlaceholder": "--example-flag",
+  "settings.openchamber.sidecarEnv.field.argsHint": "Un argumento por línea. --port y --host los gestiona OpenChamber.",
+  "settings.openchamber.sidecarEnv.actions.add": "Añadir variable",
+  "settings.openchamber.sidecarEnv.actions.removeAria": "Eliminar variable",
+  "settings.openchamber.sidecarEnv.actions.saveAndRestart": "Guardar + Reiniciar servidor",
   "settings.mcp.sidebar.title": "Servidores MCP",
   "settings.mcp.sidebar.total": "Total {count}",
   "settings.mcp.sidebar.actions.refreshStatusAria": "Actualizar estado MCP",
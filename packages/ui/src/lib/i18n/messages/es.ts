@@ -1617,10 +1617,13 @@ export const dict: Record<I18nKey, string> = {
   "openCodeStatusDialog.title": "Estado de OpenCode",
   "openCodeStatusDialog.description": "Inspecciona el estado actual de OpenCode y diagnósticos.",
   "openCodeStatusDialog.actions.copy": "Copiar",
+  "openCodeStatusDialog.actions.downloadLogs": "Descargar registros",
   "openCodeStatusDialog.empty.noData": "No hay datos.",
   "openCodeStatusDialog.toast.copiedTitle": "Copiado",
   "openCodeStatusDialog.toast.copiedDescription": "Estado copiado al portapapeles",
   "openCodeStatusDialog.toast.copyFailed": "No se pudo copiar el estado",
+  "openCodeStatusDialog.toast.logsSaved": "Registros guardados",
+  "openCodeStatusDialog.toast.logsSaveFailed": "No se pudieron guardar los registros",
   "saveProjectPlanDialog.title": "Guardar plan del proyecto",
   "saveProjectPlanDialog.description": "Guarda este plan en tu proyecto como un archivo Markdown.",
   "saveProjectPlanDialog.field.title": "Título",
@@ -1827,6 +1830,8 @@ export const dict: Record<I18nKey, string> = {
   "sessionAuth.locked.tunnelTitle": "Se requiere acceso por túnel",
   "sessionAuth.locked.unlockTitle": "Desbloquear OpenChamber",
   "sessionAuth.locked.tunnelDescription": "Abre este túnel usando el enlace de conexión única desde la aplicación de escritorio.",
+  "sessionAuth.locked.desktopPairingTitle": "Empareja este dispositivo",
+  "sessionAuth.locked.desktopPairingDescription": "Abre un enlace de emparejamiento desde la aplicación de escritorio: Ajustes → Acceso de red del escritorio → Emparejar un dispositivo.",
   "sessionAuth.locked.passwordDescription": "Esta sesión está protegida con contraseña.",
   "sessionAuth.locked.hostSwitcherHint": "Usa Local si el servidor remoto no está disponible.",
   "sessionAuth.actions.cancelPasskey": "Cancelar clave de paso",
@@ -1909,6 +1914,7 @@ export const dict: Record<I18nKey, string> = {
   "desktopHostSwitcher.startup.failed": "No se pudo conectar con la instancia predeterminada de SSH.",
   "desktopHostSwitcher.toast.sshConnected": "Instancia SSH \"{host}\" conectada",
   "desktopHostSwitcher.toast.sshFailedToConnect": "No se pudo conectar con la instancia SSH \"{host}\"",
+  "desktopHostSwitcher.toast.profileFailedToStart": "No se pudo iniciar el perfil local \"{host}\"",
   "desktopHostSwitcher.toast.instanceUnreachable": "Instancia \"{host}\" no está disponible",
   "errorBoundary.title": "Algo salió mal",
   "errorBoundary.description": "La aplicación encontró un error inesperado. Esto se ha registrado para depuración.",
@@ -681,6 +681,14 @@ export const settingsDict = {
   'settings.openchamber.desktopNetwork.error.loadFailed': 'desktop 설정을 로드하지 못했습니다',
   'settings.openchamber.desktopNetwork.error.saveFailed': 'desktop 설정을 저장하지 못했습니다',
   'settings.openchamber.desktopNetwork.error.savedRestartFailed': '저장했지만 앱을 다시 시작하지 못했습니다',
+  'settings.openchamber.desktopNetwork.actions.pairDevice': '기기 페어링',
+  'settings.openchamber.desktopNetwork.actions.forgetDevices': '페어링된 기기 삭제',
+  'settings.openchamber.desktopNetwork.hint.pairingRequired': '다른 기기는 연결하기 전에 이 앱과 페어링해야 합니다.',
+  'settings.openchamber.desktopNetwork.hint.pairingLink': '5분 안에 다른 기기에서 이 링크를 여세요: ',
+  'settings.openchamber.desktopNetwork.toast.pairingLinkCopied': '페어링 링크를 복사했습니다',
+  'settings.openchamber.desktopNetwork.toast.devicesForgotten': '페어링된 기기를 삭제했습니다',
+  'settings.openchamber.desktopNetwork.error.pairingFailed': '페어링 링크를 만들지 못했습니다',
+  'settings.openchamber.desktopNetwork.error.forgetDevicesFailed': '페어링된 기기를 삭제하지 못했습니다',
   'settings.openchamber.opencodeCli.title': 'OpenCode CLI',
   'settings.openchamber.opencodeCli.tooltipPrefix': '선택적 절대 경로:',
   'settings.openchamber.opencodeCli.tooltipSuffix': 'binary.',
@@ -693,6 +701,33 @@ export const settingsDict = {
   'settings.openchamber.opencodeCli.dialog.selectBinaryTitle': 'opencode binary 선택',
   'settings.openchamber.opencodeCli.tipPrefix': '팁: 다음도 사용할 수 있습니다',
   'settings.openchamber.opencodeCli.tipMiddle': 'env var도 사용할 수 있습니다. 이 설정은 다음 위치에 저장됩니다',
+  'settings.openchamber.opencodeCli.actions.detect': '감지',
+  'settings.openchamber.opencodeCli.actions.detecting': '감지 중…',
+  'settings.openchamber.opencodeCli.actions.use': '사용',
+  'settings.openchamber.opencodeCli.detect.minimumVersion': 'opencode {version} 이상이 필요합니다',
+  'settings.openchamber.opencodeCli.detect.empty': 'opencode 바이너리를 찾지 못했습니다',
+  'settings.openchamber.opencodeCli.detect.unknownVersion': '알 수 없는 버전',
+  'settings.openchamber.opencodeCli.detect.current': '현재',
+  'settings.openchamber.opencodeCli.detect.source.settings': '설정',
+  'settings.openchamber.opencodeCli.detect.source.env': '환경 변수',
+  'settings.openchamber.opencodeCli.detect.source.path': 'PATH',
+  'settings.openchamber.opencodeCli.detect.source.installDir': '설치 디렉터리',
+  'settings.openchamber.opencodeCli.toast.selectFailed': 'OpenCode 바이너리를 전환하지 못했습니다',
+  'settings.openchamber.sidecarEnv.title': '서버 환경',
+  'settings.openchamber.sidecarEnv.description': '로컬 OpenChamber 서버에 전달할 추가 환경 변수와 인수입니다. 저장하면 서버가 다시 시작됩니다.',
+  'settings.openchamber.sidecarEnv.empty': '사용자 지정 변수 없음',
+  'settings.openchamber.sidecarEnv.field.variables': '환경 변수',
+  'settings.openchamber.sidecarEnv.field.namePlaceholder': '이름',
+  'settings.openchamber.sidecarEnv.field.valuePlaceholder': '값',
+  'settings.openchamber.sidecarEnv.field.secretUnchanged': '변경 없음 (저장된 비밀 값)',
+  'settings.openchamber.sidecarEnv.field.secret': '비밀',
+  'settings.openchamber.sidecarEnv.field.secretAria': '값을 비밀로 표시',
+  'settings.openchamber.sidecarEnv.field.args': '추가 인수',
+  'settings.openchamber.sidecarEnv.field.argsPlaceholder': '--example-flag',
+  'settings.openchamber.sidecarEnv.field.argsHint': '한 줄에 인수 하나씩 입력하세요. --port와 --host는 OpenChamber가 관리합니다.',
+  'settings.openchamber.sidecarEnv.actions.add': '변수 추가',
+  'settings.openchamber.sidecarEnv.actions.removeAria': '변수 제거',
+  'settings.openchamber.sidecarEnv.actions.saveAndRestart': '저장 + 서버 재시작',
   'settings.mcp.sidebar.title': 'MCP 서버',
   'settings.mcp.sidebar.total': '총 {count}개',
   'settings.mcp.sidebar.actions.refreshStatusAria': 'MCP 상태 새로고침',
@@ -1651,10 +1651,13 @@ export const dict: Record<I18nKey, string> = {
   'openCodeStatusDialog.title': 'OpenCode 상태',
   'openCodeStatusDialog.description': '현재 OpenCode 상태와 진단 정보를 확인합니다.',
   'openCodeStatusDialog.actions.copy': '복사',
+  'openCodeStatusDialog.actions.downloadLogs': '로그 다운로드',
   'openCodeStatusDialog.empty.noData': '데이터가 없습니다',
   'openCodeStatusDialog.toast.copiedTitle': '복사됨',
   'openCodeStatusDialog.toast.copiedDescription': '상태를 클립보드에 복사했습니다',
   'openCodeStatusDialog.toast.copyFailed': '상태 복사에 실패했습니다',
+  'openCodeStatusDialog.toast.logsSaved': '로그를 저장했습니다',
+  'openCodeStatusDialog.toast.logsSaveFailed': '로그 저장에 실패했습니다',
   'saveProjectPlanDialog.title': '프로젝트 계획 저장',
   'saveProjectPlanDialog.description': '이 플랜을 프로젝트에 Markdown 파일로 저장합니다.',
   'saveProjectPlanDialog.field.title': '제목',
@@ -1861,6 +1864,8 @@ export const dict: Record<I18nKey, string> = {
   'sessionAuth.locked.tunnelTitle': '터널 접근 필요',
   'sessionAuth.locked.unlockTitle': 'OpenChamber 잠금 해제',
   'sessionAuth.locked.tunnelDescription': '데스크톱 앱의 일회용 연결 링크로 이 터널을 여세요.',
+  'sessionAuth.locked.desktopPairingTitle': '이 기기 페어링',
+  'sessionAuth.locked.desktopPairingDescription': '데스크톱 앱에서 페어링 링크를 여세요: 설정 → 데스크톱 네트워크 액세스 → 기기 페어링.',
   'sessionAuth.locked.passwordDescription': '이 세션은 비밀번호로 보호됩니다.',
   'sessionAuth.locked.hostSwitcherHint': '원격에 연결할 수 없으면 로컬을 사용하세요.',
   'sessionAuth.actions.cancelPasskey': '패스키 취소',
@@ -1943,6 +1948,7 @@ export const dict: Record<I18nKey, string> = {
   'desktopHostSwitcher.startup.failed': '기본 SSH 인스턴스에 연결하지 못했습니다.',
   'desktopHostSwitcher.toast.sshConnected': 'SSH 인스턴스 "{host}"에 연결했습니다',
   'desktopHostSwitcher.toast.sshFailedToConnect': 'SSH 인스턴스 "{host}" 연결에 실패했습니다',
+  'desktopHostSwitcher.toast.profileFailedToStart': '로컬 프로필 "{host}"을(를) 시작하지 못했습니다',
   'desktopHostSwitcher.toast.instanceUnreachable': '인스턴스 "{host}"에 연결할 수 없음',
   'errorBoundary.title': '문제가 발생했습니다',
   'errorBoundary.description': '애플리케이션에서 예상치 못한 오류가 발생했습니다. 디버깅을 위해 기록되었습니다.',
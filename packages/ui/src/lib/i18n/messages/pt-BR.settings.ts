@@ -681,6 +681,14 @@ export const settingsDict = {
   "settings.openchamber.desktopNetwork.error.loadFailed": "Não foi possível carregar as configurações de desktop",
   "settings.openchamber.desktopNetwork.error.saveFailed": "Não foi possível salvar a configuração de desktop",
   "settings.openchamber.desktopNetwork.error.savedRestartFailed": "Salvo, mas não foi possível reiniciar o aplicativo",
+  "settings.openchamber.desktopNetwork.actions.pairDevice": "Parear um dispositivo",
+  "settings.openchamber.desktopNetwork.actions.forgetDevices": "Esquecer dispositivos pareados",
+  "settings.openchamber.desktopNetwork.hint.pairingRequired": "Outros dispositivos precisam ser pareados com este aplicativo antes de se conectar.",
+  "settings.openchamber.desktopNetwork.hint.pairingLink": "Abra este link no outro dispositivo em até 5 minutos: ",
+  "settings.openchamber.desktopNetwork.toast.pairingLinkCopied": "Link de pareamento copiado",
+  "settings.openchamber.desktopNetwork.toast.devicesForgotten": "Dispositivos pareados esquecidos",
+  "settings.openchamber.desktopNetwork.error.pairingFailed": "Falha ao criar o link de pareamento",
+  "settings.openchamber.desktopNetwork.error.forgetDevicesFailed": "Falha ao esquecer os dispositivos pareados",
   "settings.openchamber.opencodeCli.title": "CLI do OpenCode",
   "settings.openchamber.opencodeCli.tooltipPrefix": "Caminho absoluto opcional para o",
   "settings.openchamber.opencodeCli.tooltipSuffix": "executável.",
@@ -693,6 +701,33 @@ export const settingsDict = {
   "settings.openchamber.opencodeCli.dialog.selectBinaryTitle": "Selecionar executável de opencode",
   "settings.openchamber.opencodeCli.tipPrefix": "Dica: você também pode usar",
   "settings.openchamber.opencodeCli.tipMiddle": "variável de ambiente, mas esta configuração persiste em",
+  "settings.openchamber.opencodeCli.actions.detect": "Detectar",
+  "settings.openchamber.opencodeCli.actions.detecting": "Detectando…",
+  "settings.openchamber.opencodeCli.actions.use": "Usar",
+  "settings.openchamber.opencodeCli.detect.minimumVersion": "Requer opencode {version} ou mais recente",
+  "settings.openchamber.opencodeCli.detect.empty": "Nenhum binário do opencode encontrado",
+  "settings.openchamber.opencodeCli.detect.unknownVersion": "Versão desconhecida",
+  "settings.openchamber.opencodeCli.detect.current": "Atual",
+  "settings.openchamber.opencodeCli.detect.source.settings": "configurações",
+  "settings.openchamber.opencodeCli.detect.source.env": "variável de ambiente",
+  "settings.openchamber.opencodeCli.detect.source.path": "PATH",
+  "settings.openchamber.opencodeCli.detect.source.installDir": "diretório de instalação",
+  "settings.openchamber.opencodeCli.toast.selectFailed": "Falha ao trocar o binário do OpenCode",
+  "settings.openchamber.sidecarEnv.title": "Ambiente do servidor",
+  "settings.openchamber.sidecarEnv.description": "Variáveis de ambiente e argumentos extras para o servidor local do OpenChamber. Salvar reinicia o servidor.",
+  "settings.openchamber.sidecarEnv.empty": "Nenhuma variável personalizada",
+  "settings.openchamber.sidecarEnv.field.variables": "Variáveis de ambiente",
+  "settings.openchamber.sidecarEnv.field.namePlaceholder": "NOME",
+  "settings.openchamber.sidecarEnv.field.valuePlaceholder": "valor",
+  "settings.openchamber.sidecarEnv.field.secretUnchanged": "Inalterado (segredo salvo)",
+  "settings.openchamber.sidecarEnv.field.secret": "Secreto",
+  "settings.openchamber.sidecarEnv.field.secretAria": "Marcar valor como secreto",
+  "settings.openchamber.sidecarEnv.field.args": "Argumentos extras",
+  "settings.openchamber.sidecarEnv.field.argsPlaceholder": "--example-flag",
+  "settings.openchamber.sidecarEnv.field.argsHint": "Um argumento por linha. --port e --host são gerenciados pelo OpenChamber.",
+  "settings.openchamber.sidecarEnv.actions.add": "Adicionar variável",
+  "settings.openchamber.sidecarEnv.actions.removeAria": "Remover variável",
+  "settings.openchamber.sidecarEnv.actions.saveAndRestart": "Salvar + Reiniciar servidor",
   "settings.mcp.sidebar.title": "Servidores MCP",
   "settings.mcp.sidebar.total": "Total {count}",
   "settings.mcp.sidebar.actions.refreshStatusAria": "Atualizar status MCP",
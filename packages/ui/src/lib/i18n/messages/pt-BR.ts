@@ -1617,10 +1617,13 @@ export const dict: Record<I18nKey, string> = {
   "openCodeStatusDialog.title": "Status do OpenCode",
   "openCodeStatusDialog.description": "Inspecione o status atual do OpenCode e os diagnósticos.",
   "openCodeStatusDialog.actions.copy": "Copiar",
+  "openCodeStatusDialog.actions.downloadLogs": "Baixar logs",
   "openCodeStatusDialog.empty.noData": "Não há dados.",
   "openCodeStatusDialog.toast.copiedTitle": "Copiado",
   "openCodeStatusDialog.toast.copiedDescription": "Status copiado para a área de transferência",
   "openCodeStatusDialog.toast.copyFailed": "Não foi possível copiar o status",
+  "openCodeStatusDialog.toast.logsSaved": "Logs salvos",
+  "openCodeStatusDialog.toast.logsSaveFailed": "Não foi possível salvar os logs",
   "saveProjectPlanDialog.title": "Salvar plano do projeto",
   "saveProjectPlanDialog.description": "Salve este plano no seu projeto como um arquivo Markdown.",
   "saveProjectPlanDialog.field.title": "Título",
@@ -1827,6 +1830,8 @@ export const dict: Record<I18nKey, string> = {
   "sessionAuth.locked.tunnelTitle": "É necessário acesso por túnel",
   "sessionAuth.locked.unlockTitle": "Desbloquear OpenChamber",
   "sessionAuth.locked.tunnelDescription": "Abra este túnel usando o link de conexão única do aplicativo desktop.",
+  "sessionAuth.locked.desktopPairingTitle": "Pareie este dispositivo",
+  "sessionAuth.locked.desktopPairingDescription": "Abra um link de pareamento no aplicativo desktop: Configurações → Acesso de rede do desktop → Parear um dispositivo.",
   "sessionAuth.locked.passwordDescription": "Esta sessão está protegida com senha.",
   "sessionAuth.locked.hostSwitcherHint": "Use Local se o servidor remoto não estiver disponível.",
   "sessionAuth.actions.cancelPasskey": "Cancelar chave de acesso",
@@ -1909,6 +1914,7 @@ export const dict: Record<I18nKey, string> = {
   "desktopHostSwitcher.startup.failed": "Não foi possível conectar com a instância padrão de SSH.",
   "desktopHostSwitcher.toast.sshConnected": "Instância SSH \"{host}\" conectada",
   "desktopHostSwitcher.toast.sshFailedToConnect": "Não foi possível conectar com a instância SSH \"{host}\"",
+  "desktopHostSwitcher.toast.profileFailedToStart": "Não foi possível iniciar o perfil local \"{host}\"",
   "desktopHostSwitcher.toast.instanceUnreachable": "Instância \"{host}\" não está disponível",
   "errorBoundary.title": "Algo deu errado",
   "errorBoundary.description": "O aplicativo encontrou um erro inesperado. Isso foi registrado para depuração.",
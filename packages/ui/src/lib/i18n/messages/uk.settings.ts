@@ -681,6 +681,14 @@ export const settingsDict = {
   "settings.openchamber.desktopNetwork.error.loadFailed": "Не вдалося завантажити налаштування десктопного застосунку",
   "settings.openchamber.desktopNetwork.error.saveFailed": "Не вдалося зберегти налаштування десктопного застосунку",
   "settings.openchamber.desktopNetwork.error.savedRestartFailed": "Збережено, але не вдалося перезапустити застосунок",
+  "settings.openchamber.desktopNetwork.actions.pairDevice": "Під’єднати пристрій",
+  "settings.openchamber.desktopNetwork.actions.forgetDevices": "Забути під’єднані пристрої",
+  "settings.openchamber.desktopNetwork.hint.pairingRequired": "Інші пристрої мають під’єднатися до цієї програми, перш ніж зможуть її відкрити.",
+  "settings.openchamber.desktopNetwork.hint.pairingLink": "Відкрийте це посилання на іншому пристрої протягом 5 хвилин: ",
+  "settings.openchamber.desktopNetwork.toast.pairingLinkCopied": "Посилання для під’єднання скопійовано",
+  "settings.openchamber.desktopNetwork.toast.devicesForgotten": "Під’єднані пристрої забуто",
+  "settings.openchamber.desktopNetwork.error.pairingFailed": "Не вдалося створити посилання для під’єднання",
+  "settings.openchamber.desktopNetwork.error.forgetDevicesFailed": "Не вдалося забути під’єднані пристрої",
   "settings.openchamber.opencodeCli.title": "OpenCode CLI",
   "settings.openchamber.opencodeCli.tooltipPrefix": "Додатковий абсолютний шлях до",
   "settings.openchamber.opencodeCli.tooltipSuffix": "бінарного файлу.",
@@ -693,6 +701,33 @@ export const settingsDict = {
   "settings.openchamber.opencodeCli.dialog.selectBinaryTitle": "Вибрати виконуваний файл opencode",
   "settings.openchamber.opencodeCli.tipPrefix": "Порада: ви також можете використовувати",
   "settings.openchamber.opencodeCli.tipMiddle": "env var, але цей параметр зберігається в",
+  "settings.openchamber.opencodeCli.actions.detect": "Виявити",
+  "settings.openchamber.opencodeCli.actions.detecting": "Пошук…",
+  "settings.openchamber.opencodeCli.actions.use": "Використати",
+  "settings.openchamber.opencodeCli.detect.minimumVersion": "Потрібен opencode {version} або новіший",
+  "settings.openchamber.opencodeCli.detect.empty": "Бінарні файли opencode не знайдено",
+  "settings.openchamber.opencodeCli.detect.unknownVersion": "Невідома версія",
+  "settings.openchamber.opencodeCli.detect.current": "Поточний",
+  "settings.openchamber.opencodeCli.detect.source.settings": "налаштування",
+  "settings.openchamber.opencodeCli.detect.source.env": "змінна середовища",
+  "settings.openchamber.opencodeCli.detect.source.path": "PATH",
+  "settings.openchamber.opencodeCli.detect.source.installDir": "каталог встановлення",
+  "settings.openchamber.opencodeCli.toast.selectFailed": "Не вдалося змінити бінарний файл OpenCode",
+  "settings.openchamber.sidecarEnv.title": "Середовище сервера",
+  "settings.openchamber.sidecarEnv.description": "Додаткові змінні середовища та аргументи для локального сервера OpenChamber. Збереження перезапускає сервер.",
+  "settings.openchamber.sidecarEnv.empty": "Немає власних змінних",
+  "settings.openchamber.sidecarEnv.field.variables": "Змінні середовища",
+  "settings.openchamber.sidecarEnv.field.namePlaceholder": "НАЗВА",
+  "settings.openchamber.sidecarEnv.field.valuePlaceholder": "значення",
+  "settings.openchamber.sidecarEnv.field.secretUnchanged": "Без змін (збережений секрет)",
+  "settings.openchamber.sidecarEnv.field.secret": "Секрет",
+  "settings.openchamber.sidecarEnv.field.secretAria": "Позначити значення як секрет",
+  "settings.openchamber.sidecarEnv.field.args": "Додаткові аргументи",
+  "settings.openchamber.sidecarEnv.field.argsPlaceholder": "--example-flag",
+  "settings.openchamber.sidecarEnv.field.argsHint": "Один аргумент на рядок. --port і --host керує OpenChamber.",
+  "settings.openchamber.sidecarEnv.actions.add": "Додати змінну",
+  "settings.openchamber.sidecarEnv.actions.removeAria": "Видалити змінну",
+  "settings.openchamber.sidecarEnv.actions.saveAndRestart": "Зберегти + Перезапустити сервер",
   "settings.mcp.sidebar.title": "MCP сервери",
   "settings.mcp.sidebar.total": "Усього {count}",
   "settings.mcp.sidebar.actions.refreshStatusAria": "Оновити статус MCP",
@@ -1617,10 +1617,13 @@ export const dict: Record<I18nKey, string> = {
   "openCodeStatusDialog.title": "Статус OpenCode",
   "openCodeStatusDialog.description": "Перегляньте поточний стан OpenCode і діагностику.",
   "openCodeStatusDialog.actions.copy": "Копіювати",
+  "openCodeStatusDialog.actions.downloadLogs": "Завантажити журнали",
   "openCodeStatusDialog.empty.noData": "Немає даних.",
   "openCodeStatusDialog.toast.copiedTitle": "Скопійовано",
   "openCodeStatusDialog.toast.copiedDescription": "Статус скопійовано в буфер обміну",
   "openCodeStatusDialog.toast.copyFailed": "Не вдалося скопіювати статус",
+  "openCodeStatusDialog.toast.logsSaved": "Журнали збережено",
+  "openCodeStatusDialog.toast.logsSaveFailed": "Не вдалося зберегти журнали",
   "saveProjectPlanDialog.title": "Зберегти план проєкту",
   "saveProjectPlanDialog.description": "Збережіть цей план у своєму проєкті як файл Markdown.",
   "saveProjectPlanDialog.field.title": "Назва",
@@ -1827,6 +1830,8 @@ export const dict: Record<I18nKey, string> = {
   "sessionAuth.locked.tunnelTitle": "Потрібен доступ до тунелю",
   "sessionAuth.locked.unlockTitle": "Розблокувати OpenChamber",
   "sessionAuth.locked.tunnelDescription": "Відкрийте цей тунель за допомогою одноразового посилання для з’єднання з настільної програми.",
+  "sessionAuth.locked.desktopPairingTitle": "Під’єднайте цей пристрій",
+  "sessionAuth.locked.desktopPairingDescription": "Відкрийте посилання для під’єднання з настільної програми: Налаштування → Мережевий доступ до настільної програми → Під’єднати пристрій.",
   "sessionAuth.locked.passwordDescription": "Ця сесія захищена паролем.",
   "sessionAuth.locked.hostSwitcherHint": "Використовувати Local, якщо віддалений сервер недоступний.",
   "sessionAuth.actions.cancelPasskey": "Скасувати ключ доступу",
@@ -1909,6 +1914,7 @@ export const dict: Record<I18nKey, string> = {
   "desktopHostSwitcher.startup.failed": "Не вдалося підключити стандартний інстанс SSH.",
   "desktopHostSwitcher.toast.sshConnected": "Інстанс SSH \"{host}\" підключено",
   "desktopHostSwitcher.toast.sshFailedToConnect": "Не вдалося підключитися до інстанса SSH \"{host}\".",
+  "desktopHostSwitcher.toast.profileFailedToStart": "Не вдалося запустити локальний профіль \"{host}\".",
   "desktopHostSwitcher.toast.instanceUnreachable": "Інстанс \"{host}\" недоступний",
   "errorBoundary.title": "Щось пішло не так",
   "errorBoundary.description": "У програмі сталася неочікувана помилка. Це було зареєстровано для налагодження.",
@@ -681,6 +681,14 @@ export const settingsDict = {
   'settings.openchamber.desktopNetwork.error.loadFailed': '加载桌面设置失败',
   'settings.openchamber.desktopNetwork.error.saveFailed': '保存桌面设置失败',
   'settings.openchamber.desktopNetwork.error.savedRestartFailed': '已保存，但重启应用失败',
+  'settings.openchamber.desktopNetwork.actions.pairDevice': '配对设备',
+  'settings.openchamber.desktopNetwork.actions.forgetDevices': '忘记已配对设备',
+  'settings.openchamber.desktopNetwork.hint.pairingRequired': '其他设备必须先与此应用配对才能连接。',
+  'settings.openchamber.desktopNetwork.hint.pairingLink': '请在 5 分钟内于另一台设备上打开此链接：',
+  'settings.openchamber.desktopNetwork.toast.pairingLinkCopied': '已复制配对链接',
+  'settings.openchamber.desktopNetwork.toast.devicesForgotten': '已忘记已配对设备',
+  'settings.openchamber.desktopNetwork.error.pairingFailed': '创建配对链接失败',
+  'settings.openchamber.desktopNetwork.error.forgetDevicesFailed': '忘记已配对设备失败',
   'settings.openchamber.opencodeCli.title': 'OpenCode CLI',
   'settings.openchamber.opencodeCli.tooltipPrefix': '可选的',
   'settings.openchamber.opencodeCli.tooltipSuffix': '二进制绝对路径。',
@@ -693,6 +701,33 @@ export const settingsDict = {
   'settings.openchamber.opencodeCli.dialog.selectBinaryTitle': '选择 OpenCode 可执行文件',
   'settings.openchamber.opencodeCli.tipPrefix': '提示：你也可以使用',
   'settings.openchamber.opencodeCli.tipMiddle': '环境变量，但此设置会持久化到',
+  'settings.openchamber.opencodeCli.actions.detect': '检测',
+  'settings.openchamber.opencodeCli.actions.detecting': '检测中…',
+  'settings.openchamber.opencodeCli.actions.use': '使用',
+  'settings.openchamber.opencodeCli.detect.minimumVersion': '需要 opencode {version} 或更高版本',
+  'settings.openchamber.opencodeCli.detect.empty': '未找到 opencode 可执行文件',
+  'settings.openchamber.opencodeCli.detect.unknownVersion': '未知版本',
+  'settings.openchamber.opencodeCli.detect.current': '当前',
+  'settings.openchamber.opencodeCli.detect.source.settings': '设置',
+  'settings.openchamber.opencodeCli.detect.source.env': '环境变量',
+  'settings.openchamber.opencodeCli.detect.source.path': 'PATH',
+  'settings.openchamber.opencodeCli.detect.source.installDir': '安装目录',
+  'settings.openchamber.opencodeCli.toast.selectFailed': '切换 OpenCode 可执行文件失败',
+  'settings.openchamber.sidecarEnv.title': '服务器环境',
+  'settings.openchamber.sidecarEnv.description': '传递给本地 OpenChamber 服务器的额外环境变量和参数。保存后会重启服务器。',
+  'settings.openchamber.sidecarEnv.empty': '没有自定义变量',
+  'settings.openchamber.sidecarEnv.field.variables': '环境变量',
+  'settings.openchamber.sidecarEnv.field.namePlaceholder': '名称',
+  'settings.openchamber.sidecarEnv.field.valuePlaceholder': '值',
+  'settings.openchamber.sidecarEnv.field.secretUnchanged': '未更改（已保存的密钥）',
+  'settings.openchamber.sidecarEnv.field.secret': '密钥',
+  'settings.openchamber.sidecarEnv.field.secretAria': '将值标记为密钥',
+  'settings.openchamber.sidecarEnv.field.args': '额外参数',
+  'settings.openchamber.sidecarEnv.field.argsPlaceholder': '--example-flag',
+  'settings.openchamber.sidecarEnv.field.argsHint': '每行一个参数。--port 和 --host 由 OpenChamber 管理。',
+  'settings.openchamber.sidecarEnv.actions.add': '添加变量',
+  'settings.openchamber.sidecarEnv.actions.removeAria': '删除变量',
+  'settings.openchamber.sidecarEnv.actions.saveAndRestart': '保存并重启服务器',
   'settings.mcp.sidebar.title': 'MCP 服务器',
   'settings.mcp.sidebar.total': '总计 {count}',
   'settings.mcp.sidebar.actions.refreshStatusAria': '刷新 MCP 状态',
@@ -1617,10 +1617,13 @@ export const dict: Record<I18nKey, string> = {
   'openCodeStatusDialog.title': 'OpenCode 状态',
   'openCodeStatusDialog.description': '查看当前 OpenCode 状态与诊断信息。',
   'openCodeStatusDialog.actions.copy': '复制',
+  'openCodeStatusDialog.actions.downloadLogs': '下载日志',
   'openCodeStatusDialog.empty.noData': '无数据。',
   'openCodeStatusDialog.toast.copiedTitle': '已复制',
   'openCodeStatusDialog.toast.copiedDescription': '状态已复制到剪贴板',
   'openCodeStatusDialog.toast.copyFailed': '复制状态失败',
+  'openCodeStatusDialog.toast.logsSaved': '日志已保存',
+  'openCodeStatusDialog.toast.logsSaveFailed': '保存日志失败',
   'saveProjectPlanDialog.title': '保存项目计划',
   'saveProjectPlanDialog.description': '将此计划以 Markdown 文件保存到你的项目中。',
   'saveProjectPlanDialog.field.title': '标题',
@@ -1827,6 +1830,8 @@ export const dict: Record<I18nKey, string> = {
   'sessionAuth.locked.tunnelTitle': '需要隧道访问',
   'sessionAuth.locked.unlockTitle': '解锁 OpenChamber',
   'sessionAuth.locked.tunnelDescription': '请使用桌面应用提供的一次性连接链接打开该隧道。',
+  'sessionAuth.locked.desktopPairingTitle': '配对此设备',
+  'sessionAuth.locked.desktopPairingDescription': '请从桌面应用打开配对链接：设置 → 桌面网络访问 → 配对设备。',
   'sessionAuth.locked.passwordDescription': '此会话受密码保护。',
   'sessionAuth.locked.hostSwitcherHint': '如果远程不可达，请使用 Local。',
   'sessionAuth.actions.cancelPasskey': '取消 passkey',
@@ -1909,6 +1914,7 @@ export const dict: Record<I18nKey, string> = {
   'desktopHostSwitcher.startup.failed': '连接默认 SSH 实例失败。',
   'desktopHostSwitcher.toast.sshConnected': 'SSH 实例“{host}”已连接',
   'desktopHostSwitcher.toast.sshFailedToConnect': 'SSH 实例“{host}”连接失败',
+  'desktopHostSwitcher.toast.profileFailedToStart': '本地配置“{host}”启动失败',
   'desktopHostSwitcher.toast.instanceUnreachable': '实例“{host}”不可达',
   'errorBoundary.title': '发生错误',
   'errorBoundary.description': '应用遇到意外错误，已记录用于调试。',
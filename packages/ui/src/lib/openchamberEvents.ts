@@ -1,3 +1,5 @@
+import { openDesktopAuthEventSource, type DesktopAuthEventSource } from '@/lib/desktopAuth';
+
 export type ScheduledTaskRanEvent = {
   type: 'scheduled-task-ran';
   projectId: string;
@@ -10,7 +12,7 @@ export type ScheduledTaskRanEvent = {
 type OpenChamberEvent = ScheduledTaskRanEvent;
 type Listener = (event: OpenChamberEvent) => void;
 
-let eventSource: EventSource | null = null;
+let eventSource: DesktopAuthEventSource | null = null;
 let reconnectTimer: ReturnType<typeof setTimeout> | null = null;
 let heartbeatTimer: ReturnType<typeof setTimeout> | null = null;
 let reconnectAttempt = 0;
@@ -129,7 +131,7 @@ const connect = () => {
 
   cleanupSource();
 
-  const source = new EventSource('/api/openchamber/events');
+  const source = openDesktopAuthEventSource('/api/openchamber/events');
   source.onopen = () => {
     resetHeartbeatTimer();
   };
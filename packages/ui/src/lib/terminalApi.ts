@@ -1,3 +1,5 @@
+import { desktopAuthWebSocketProtocols, openDesktopAuthEventSource, type DesktopAuthEventSource } from '@/lib/desktopAuth';
+
 export interface TerminalWebSocketDescriptor {
   path: string;
   v?: number;
@@ -379,7 +381,7 @@ class TerminalTransportManager {
       };
 
       try {
-        const socket = new WebSocket(this.socketUrl);
+        const socket = new WebSocket(this.socketUrl, desktopAuthWebSocketProtocols(this.socketUrl));
         socket.binaryType = 'arraybuffer';
 
         socket.onopen = () => {
@@ -777,7 +779,7 @@ const connectTerminalStreamViaSse = (
   const maxRetryDelay = options.maxRetryDelay ?? 8000;
   const connectionTimeout = options.connectionTimeout ?? 10000;
 
-  let eventSource: EventSource | null = null;
+  let eventSource: DesktopAuthEventSource | null = null;
   let retryCount = 0;
   let retryTimeout: ReturnType<typeof setTimeout> | null = null;
   let connectionTimeoutId: ReturnType<typeof setTimeout> | null = null;
@@ -845,7 +847,7 @@ const connectTerminalStreamViaSse = (
       return;
     }
 
-    eventSource = new EventSource(`/api/terminal/${sessionId}/stream`);
+    eventSource = openDesktopAuthEventSource(`/api/terminal/${sessionId}/stream`);
 
     connectionTimeoutId = setTimeout(() => {
       if (!hasDispatchedOpen && eventSource?.readyState !== EventSource.OPEN) {
@@ -1,4 +1,5 @@
 import { StrictMode } from 'react'
+import { installDesktopAuthFetch } from './lib/desktopAuth'
 import { createRoot } from 'react-dom/client'
 import './styles/fonts'
 import './index.css'
@@ -25,6 +26,8 @@ const runtimeAPIs = (typeof window !== 'undefined' && window.__OPENCHAMBER_RUNTI
   throw new Error('Runtime APIs not provided for legacy UI entrypoint.');
 })();
 
+// Before any request: same-origin calls need the desktop token header.
+installDesktopAuthFetch();
 initializeLocale();
 
 // Initialize settings asynchronously — the app renders with defaults first
@@ -10,6 +10,7 @@
 
 import type { Event, OpencodeClient } from "@opencode-ai/sdk/v2/client"
 import { opencodeClient } from "@/lib/opencode/client"
+import { desktopAuthWebSocketProtocols } from "@/lib/desktopAuth"
 import { syncDebug } from "./debug"
 
 export type QueuedEvent = {
@@ -385,7 +386,8 @@ export function createEventPipeline(input: EventPipelineInput) {
       let settled = false
       let opened = false
       let readyAt = 0
-      const socket = new WebSocket(buildGlobalEventWsUrl(lastEventId))
+      const socketUrl = buildGlobalEventWsUrl(lastEventId)
+      const socket = new WebSocket(socketUrl, desktopAuthWebSocketProtocols(socketUrl))
       const setFallbackCode = (error: Error, force = false) => {
         if ((force || !opened) && transport === "auto") {
           wsFallbackUntil = Date.now() + WS_FALLBACK_WINDOW_MS
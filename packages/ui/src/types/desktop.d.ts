@@ -5,6 +5,7 @@ declare global {
     __OPENCHAMBER_HOME__?: string;
     __OPENCHAMBER_MACOS_MAJOR__?: number;
     __OPENCHAMBER_LOCAL_ORIGIN__?: string;
+    __OPENCHAMBER_DESKTOP_TOKEN__?: string;
     __OPENCHAMBER_ELECTRON__?: { runtime?: string };
     __OPENCHAMBER_DESKTOP_BOOT_OUTCOME__?: DesktopBootOutcome;
   }
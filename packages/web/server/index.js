@@ -69,6 +69,8 @@ import { createStartupPipelineRuntime } from './lib/opencode/startup-pipeline-ru
 import { runCliEntryIfMain } from './lib/opencode/cli-entry-runtime.js';
 import { registerNotificationRoutes } from './lib/notifications/routes.js';
 import { createNotificationEmitterRuntime } from './lib/notifications/emitter-runtime.js';
+import { createDesktopControlRuntime } from './lib/desktop-control/runtime.js';
+import { createDesktopAuthRuntime, readDesktopAuthToken } from './lib/desktop-auth/runtime.js';
 import { createNotificationTriggerRuntime } from './lib/notifications/runtime.js';
 import { createPushRuntime } from './lib/notifications/push-runtime.js';
 import { createNotificationTemplateRuntime } from './lib/notifications/template-runtime.js';
@@ -366,6 +368,12 @@ const notificationEmitterRuntime = createNotificationEmitterRuntime({
   getBroadcastGlobalUiEvent: () => broadcastGlobalUiEvent,
 });
 
+const desktopControlRuntime = createDesktopControlRuntime({
+  process,
+  getEnabled: () => ENV_DESKTOP_PROTOCOL,
+  logger: console,
+});
+
 const writeSseEvent = (...args) => notificationEmitterRuntime.writeSseEvent(...args);
 const emitDesktopNotification = (...args) => notificationEmitterRuntime.emitDesktopNotification(...args);
 const broadcastGlobalUiEvent = createGlobalUiEventBroadcaster({
@@ -421,6 +429,15 @@ const tunnelProviderRegistry = createTunnelProviderRegistry([
 ]);
 tunnelProviderRegistry.seal();
 const tunnelAuthController = createTunnelAuth();
+const desktopAuthRuntime = createDesktopAuthRuntime({
+  token: readDesktopAuthToken(process.env),
+  crypto,
+  fs,
+  path,
+  dataDir: OPENCHAMBER_DATA_DIR,
+  tunnelAuthController,
+  logger: console,
+});
 let runtimeManagedRemoteTunnelToken = '';
 let runtimeManagedRemoteTunnelHostname = '';
 let terminalRuntime = null;
@@ -497,6 +514,7 @@ const ENV_DESKTOP_NOTIFY = (() => {
   const argv1 = typeof process.argv?.[1] === 'string' ? process.argv[1] : '';
   return /openchamber-server/i.test(argv0) || /openchamber-server/i.test(argv1);
 })();
+const ENV_DESKTOP_PROTOCOL = Number.parseInt(process.env.OPENCHAMBER_DESKTOP_PROTOCOL || '', 10) >= 1;
 const ENV_CONFIGURED_OPENCODE_WSL_DISTRO =
   typeof process.env.OPENCODE_WSL_DISTRO === 'string' && process.env.OPENCODE_WSL_DISTRO.trim().length > 0
     ? process.env.OPENCODE_WSL_DISTRO.trim()
@@ -990,6 +1008,7 @@ const gracefulShutdownRuntime = createGracefulShutdownRuntime({
   },
   tunnelAuthController,
   scheduledTasksRuntime,
+  desktopControlRuntime,
 });
 
 const gracefulShutdown = (...args) => gracefulShutdownRuntime.gracefulShutdown(...args);
@@ -1028,6 +1047,9 @@ async function main(options = {}) {
   }
   if (typeof options.onDesktopNotification === 'function') {
     notificationEmitterRuntime.setOnDesktopNotification(options.onDesktopNotification);
+  } else if (desktopControlRuntime.isEnabled()) {
+    notificationEmitterRuntime.setOnDesktopNotification(desktopControlRuntime.notify);
+    desktopControlRuntime.start();
   }
 
   console.log(`Starting OpenChamber on port ${port === 0 ? 'auto' : port}`);
@@ -1088,6 +1110,7 @@ async function main(options = {}) {
     },
     uiPassword,
     tunnelAuthController,
+    desktopAuthRuntime,
     readSettingsFromDiskMigrated,
     normalizeTunnelSessionTtlMs,
     resolveZenModel,
@@ -1227,18 +1250,24 @@ async function main(options = {}) {
     console.warn('[ScheduledTasks] Failed to start runtime:', error?.message || error);
   }
 
+  const getQuitRiskStatus = () => ({
+    tunnel: {
+      active: Boolean(tunnelService.getPublicUrl()),
+    },
+    scheduledTasks: scheduledTasksRuntime.getStatus(),
+    sessions: {
+      busyCount: sessionRuntime.getBusySessionCount(),
+    },
+  });
+  desktopControlRuntime.watchQuitRisk(getQuitRiskStatus);
+
   return {
     expressApp: app,
     httpServer: server,
     getPort: () => tunnelRuntimeContext.getActivePort(),
     getOpenCodePort: () => openCodePort,
     getTunnelUrl: () => tunnelService.getPublicUrl(),
-    getQuitRiskStatus: () => ({
-      tunnel: {
-        active: Boolean(tunnelService.getPublicUrl()),
-      },
-      scheduledTasks: scheduledTasksRuntime.getStatus(),
-    }),
+    getQuitRiskStatus,
     isReady: () => isOpenCodeReady,
     restartOpenCode: () => restartOpenCode(),
     stop: (shutdownOptions = {}) =>
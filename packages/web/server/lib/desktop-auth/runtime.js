@@ -0,0 +1,426 @@
+export const DESKTOP_AUTH_TOKEN_ENV = 'OPENCHAMBER_DESKTOP_TOKEN';
+export const DESKTOP_AUTH_HEADER = 'x-openchamber-desktop-token';
+export const DESKTOP_AUTH_WS_TOKEN_PREFIX = 'openchamber.desktop-token.';
+export const DESKTOP_DEVICE_COOKIE = 'oc_desktop_device';
+
+const DEVICES_FILE_NAME = 'desktop-devices.json';
+const PAIRING_CODE_TTL_MS = 5 * 60 * 1000;
+const DEVICE_SESSION_TTL_MS = 30 * 24 * 60 * 60 * 1000;
+const PAIRING_RATE_LIMIT_WINDOW_MS = 5 * 60 * 1000;
+const PAIRING_RATE_LIMIT_MAX_ATTEMPTS = 10;
+
+const parseCookies = (cookieHeader) => {
+  if (!cookieHeader || typeof cookieHeader !== 'string') {
+    return {};
+  }
+
+  return cookieHeader.split(';').reduce((acc, segment) => {
+    const [name, ...rest] = segment.split('=');
+    const key = name?.trim();
+    if (!key) {
+      return acc;
+    }
+    const value = rest.join('=').trim();
+    try {
+      acc[key] = decodeURIComponent(value || '');
+    } catch {
+      acc[key] = value || '';
+    }
+    return acc;
+  }, {});
+};
+
+const isSecureRequest = (req) => {
+  if (req.secure) {
+    return true;
+  }
+  const forwardedProto = req.headers['x-forwarded-proto'];
+  if (typeof forwardedProto === 'string') {
+    return forwardedProto.split(',')[0]?.trim().toLowerCase() === 'https';
+  }
+  return false;
+};
+
+const buildCookie = ({ name, value, maxAge, secure }) => {
+  const attributes = [
+    `${name}=${value}`,
+    'Path=/',
+    'HttpOnly',
+    'SameSite=Strict',
+    `Max-Age=${Math.max(0, Math.floor(maxAge))}`,
+  ];
+  if (secure) {
+    attributes.push('Secure');
+  }
+  return attributes.join('; ');
+};
+
+const getClientIp = (req) => {
+  const ip = req.ip || req.socket?.remoteAddress || '';
+  return ip.startsWith('::ffff:') ? ip.substring(7) : ip;
+};
+
+const isTunnelScope = (scope) => scope === 'tunnel' || scope === 'unknown-public';
+
+/**
+ * Token offered as a WebSocket subprotocol. Browsers cannot set other
+ * headers on a WebSocket handshake, and pages cannot set `Sec-` headers on
+ * any other request, so only the handshake itself can carry it this way.
+ */
+const readWebSocketProtocolToken = (req) => {
+  const header = req.headers?.['sec-websocket-protocol'];
+  if (typeof header !== 'string') {
+    return null;
+  }
+  const entry = header
+    .split(',')
+    .map((value) => value.trim())
+    .find((value) => value.startsWith(DESKTOP_AUTH_WS_TOKEN_PREFIX));
+  return entry ? entry.slice(DESKTOP_AUTH_WS_TOKEN_PREFIX.length) : null;
+};
+
+/**
+ * Read the per-launch token handed over by the desktop shell and drop it
+ * from the environment so OpenCode, terminals and other children never
+ * inherit it.
+ */
+export const readDesktopAuthToken = (env) => {
+  const raw = typeof env?.[DESKTOP_AUTH_TOKEN_ENV] === 'string' ? env[DESKTOP_AUTH_TOKEN_ENV].trim() : '';
+  if (env && DESKTOP_AUTH_TOKEN_ENV in env) {
+    delete env[DESKTOP_AUTH_TOKEN_ENV];
+  }
+  return raw || null;
+};
+
+/**
+ * Sidecar auth for the Tauri desktop shell.
+ *
+ * The shell generates a token on every launch and passes it through
+ * `OPENCHAMBER_DESKTOP_TOKEN`. Its own HTTP calls and its local-origin
+ * windows send the token as a header, or as a WebSocket subprotocol on
+ * handshakes. It is never put in a URL, where logs, history and Referer
+ * headers would keep it, nor in a cookie: cookies on 127.0.0.1 are shared
+ * with every other port, such as SSH and tunnel forwards. Any other
+ * local-scope client (typically a LAN device) must pair first: the shell
+ * asks for a one-time code and the device opens `/desktop/pair?code=...`,
+ * which exchanges it for a long-lived device session cookie.
+ */
+export const createDesktopAuthRuntime = (dependencies) => {
+  const {
+    token,
+    crypto,
+    fs,
+    path,
+    dataDir,
+    tunnelAuthController,
+    now = () => Date.now(),
+    logger = console,
+  } = dependencies;
+
+  const enabled = typeof token === 'string' && token.length > 0;
+  const tokenBuffer = enabled ? Buffer.from(token) : null;
+  const devicesFilePath = dataDir ? path.join(dataDir, DEVICES_FILE_NAME) : null;
+  const pairingCodes = new Map();
+  const pairingRateLimiter = new Map();
+  let devices = null;
+
+  const hashSecret = (value) => crypto.createHash('sha256').update(value).digest('hex');
+
+  const loadDevices = () => {
+    if (devices) {
+      return devices;
+    }
+    devices = new Map();
+    if (!devicesFilePath) {
+      return devices;
+    }
+    try {
+      const parsed = JSON.parse(fs.readFileSync(devicesFilePath, 'utf8'));
+      for (const device of Array.isArray(parsed?.devices) ? parsed.devices : []) {
+        if (typeof device?.sessionHash === 'string' && typeof device.id === 'string') {
+          devices.set(device.sessionHash, device);
+        }
+      }
+    } catch (error) {
+      if (error?.code !== 'ENOENT') {
+        logger.warn?.('[DesktopAuth] Failed to read paired devices:', error?.message || error);
+      }
+    }
+    return devices;
+  };
+
+  const persistDevices = () => {
+    if (!devicesFilePath) {
+      return;
+    }
+    try {
+      fs.mkdirSync(path.dirname(devicesFilePath), { recursive: true });
+      fs.writeFileSync(
+        devicesFilePath,
+        JSON.stringify({ devices: Array.from(loadDevices().values()) }, null, 2),
+        { mode: 0o600 },
+      );
+    } catch (error) {
+      logger.warn?.('[DesktopAuth] Failed to persist paired devices:', error?.message || error);
+    }
+  };
+
+  const matchesToken = (candidate) => {
+    if (!enabled || typeof candidate !== 'string' || candidate.length === 0) {
+      return false;
+    }
+    const candidateBuffer = Buffer.from(candidate);
+    return candidateBuffer.length === tokenBuffer.length && crypto.timingSafeEqual(candidateBuffer, tokenBuffer);
+  };
+
+  /** True for the desktop shell itself and its local-origin windows. */
+  const isDesktopShellRequest = (req) => {
+    const header = req.headers?.[DESKTOP_AUTH_HEADER];
+    if (matchesToken(Array.isArray(header) ? header[0] : header)) {
+      return true;
+    }
+    return matchesToken(readWebSocketProtocolToken(req));
+  };
+
+  const getPairedDevice = (req) => {
+    const sessionId = parseCookies(req.headers?.cookie)[DESKTOP_DEVICE_COOKIE];
+    if (!enabled || !sessionId) {
+      return null;
+    }
+    const device = loadDevices().get(hashSecret(sessionId));
+    if (!device || device.expiresAt <= now()) {
+      return null;
+    }
+    device.lastSeenAt = now();
+    return device;
+  };
+
+  const isAuthorized = (req) => {
+    if (!enabled) {
+      return true;
+    }
+    const scope = tunnelAuthController?.classifyRequestScope?.(req);
+    if (isTunnelScope(scope)) {
+      return Boolean(tunnelAuthController.getTunnelSessionFromRequest(req));
+    }
+    return isDesktopShellRequest(req) || Boolean(getPairedDevice(req));
+  };
+
+  const createPairingCode = () => {
+    const createdAt = now();
+    for (const [codeHash, record] of pairingCodes) {
+      if (record.expiresAt <= createdAt) {
+        pairingCodes.delete(codeHash);
+      }
+    }
+    const code = crypto.randomBytes(24).toString('base64url');
+    const expiresAt = createdAt + PAIRING_CODE_TTL_MS;
+    pairingCodes.set(hashSecret(code), { expiresAt });
+    return { code, expiresAt };
+  };
+
+  const checkPairingRateLimit = (req) => {
+    const key = getClientIp(req) || 'no-ip';
+    const current = now();
+    const record = pairingRateLimiter.get(key);
+    if (!record || current - record.firstAttemptAt > PAIRING_RATE_LIMIT_WINDOW_MS) {
+      pairingRateLimiter.set(key, { firstAttemptAt: current, count: 1 });
+      return { allowed: true, retryAfter: 0 };
+    }
+    record.count += 1;
+    if (record.count > PAIRING_RATE_LIMIT_MAX_ATTEMPTS) {
+      return {
+        allowed: false,
+        retryAfter: Math.ceil((record.firstAttemptAt + PAIRING_RATE_LIMIT_WINDOW_MS - current) / 1000),
+      };
+    }
+    return { allowed: true, retryAfter: 0 };
+  };
+
+  const exchangePairingCode = (req, res, code) => {
+    const rateLimit = checkPairingRateLimit(req);
+    if (!rateLimit.allowed) {
+      return { ok: false, reason: 'rate-limited', retryAfter: rateLimit.retryAfter };
+    }
+
+    const codeHash = typeof code === 'string' && code ? hashSecret(code) : null;
+    const record = codeHash ? pairingCodes.get(codeHash) : null;
+    if (!record) {
+      return { ok: false, reason: 'invalid' };
+    }
+    pairingCodes.delete(codeHash);
+    if (record.expiresAt <= now()) {
+      return { ok: false, reason: 'expired' };
+    }
+
+    const sessionId = crypto.randomBytes(32).toString('base64url');
+    const createdAt = now();
+    const userAgent = typeof req.headers?.['user-agent'] === 'string' ? req.headers['user-agent'] : '';
+    const device = {
+      id: crypto.randomUUID(),
+      sessionHash: hashSecret(sessionId),
+      label: userAgent.slice(0, 200) || null,
+      address: getClientIp(req) || null,
+      createdAt,
+      lastSeenAt: createdAt,
+      expiresAt: createdAt + DEVICE_SESSION_TTL_MS,
+    };
+    loadDevices().set(device.sessionHash, device);
+    persistDevices();
+
+    res.setHeader('Set-Cookie', buildCookie({
+      name: DESKTOP_DEVICE_COOKIE,
+      value: encodeURIComponent(sessionId),
+      maxAge: DEVICE_SESSION_TTL_MS / 1000,
+      secure: isSecureRequest(req),
+    }));
+    return { ok: true, deviceId: device.id };
+  };
+
+  const listDevices = () => Array.from(loadDevices().values())
+    .filter((device) => device.expiresAt > now())
+    .map(({ sessionHash: _sessionHash, ...device }) => device);
+
+  const revokeDevices = () => {
+    const count = loadDevices().size;
+    loadDevices().clear();
+    persistDevices();
+    return count;
+  };
+
+  const respondPairingRequired = (req, res) => {
+    res.status(401);
+    if (req.headers?.accept?.includes('application/json') || req.path?.startsWith('/api')) {
+      res.json({ error: 'Desktop pairing required', locked: true, desktopPairingRequired: true });
+    } else {
+      res.type('text/plain').send('Desktop pairing required');
+    }
+  };
+
+  /**
+   * Layer desktop auth over the UI password controller. Requests from the
+   * shell, paired devices or tunnel sessions pass; anything else falls
+   * back to the password session when one is configured.
+   */
+  const wrapUiAuthController = (baseController) => {
+    if (!enabled) {
+      return baseController;
+    }
+
+    const requireAuth = async (req, res, next) => {
+      if (req.method === 'OPTIONS' || isAuthorized(req)) {
+        return next();
+      }
+      if (baseController.enabled) {
+        return baseController.requireAuth(req, res, next);
+      }
+      return respondPairingRequired(req, res);
+    };
+
+    const handleSessionStatus = async (req, res) => {
+      if (isAuthorized(req)) {
+        res.json({ authenticated: true, scope: 'desktop' });
+        return;
+      }
+      if (baseController.enabled) {
+        await baseController.handleSessionStatus(req, res);
+        return;
+      }
+      res.status(401).json({ authenticated: false, locked: true, desktopPairingRequired: true });
+    };
+
+    const ensureSessionToken = async (req, res) => {
+      if (baseController.enabled) {
+        const sessionToken = await baseController.ensureSessionToken(req, res);
+        if (sessionToken) {
+          return sessionToken;
+        }
+      }
+      if (!isAuthorized(req)) {
+        return null;
+      }
+      if (!baseController.enabled && res) {
+        return baseController.ensureSessionToken(req, res);
+      }
+      return getPairedDevice(req)?.id || 'desktop';
+    };
+
+    return {
+      ...baseController,
+      enabled: true,
+      requireAuth,
+      handleSessionStatus,
+      ensureSessionToken,
+    };
+  };
+
+  const requireDesktopShell = (req, res, next) => {
+    if (isDesktopShellRequest(req)) {
+      return next();
+    }
+    return res.status(403).json({ error: 'Only the desktop app can manage paired devices' });
+  };
+
+  /**
+   * Register pairing routes and guard `/api/system`. Must run before the
+   * server status routes so shutdown requires auth as well.
+   */
+  const registerRoutes = (app, { uiAuthController }) => {
+    if (!enabled) {
+      return;
+    }
+
+    app.use('/api/system', (req, res, next) => {
+      const scope = tunnelAuthController?.classifyRequestScope?.(req);
+      if (isTunnelScope(scope)) {
+        return tunnelAuthController.requireTunnelSession(req, res, next);
+      }
+      return uiAuthController.requireAuth(req, res, next);
+    });
+
+    app.post('/api/desktop/pairing', requireDesktopShell, (_req, res) => {
+      const { code, expiresAt } = createPairingCode();
+      res.setHeader('Cache-Control', 'no-store');
+      res.json({ code, expiresAt, path: `/desktop/pair?code=${encodeURIComponent(code)}` });
+    });
+
+    app.get('/api/desktop/devices', requireDesktopShell, (_req, res) => {
+      res.json({ devices: listDevices() });
+    });
+
+    app.delete('/api/desktop/devices', requireDesktopShell, (_req, res) => {
+      res.json({ revoked: revokeDevices() });
+    });
+
+    app.get('/desktop/pair', (req, res) => {
+      res.setHeader('Cache-Control', 'no-store');
+      const scope = tunnelAuthController?.classifyRequestScope?.(req);
+      if (isTunnelScope(scope)) {
+        return res.status(403).type('text/plain').send('Pairing is not available over a tunnel.');
+      }
+
+      const code = typeof req.query?.code === 'string' ? req.query.code : '';
+      const exchange = exchangePairingCode(req, res, code);
+      if (!exchange.ok) {
+        if (exchange.reason === 'rate-limited') {
+          res.setHeader('Retry-After', String(exchange.retryAfter || 60));
+          return res.status(429).type('text/plain').send('Too many attempts. Please try again later.');
+        }
+        return res.status(401).type('text/plain').send('Pairing link is invalid or expired.');
+      }
+      return res.redirect(302, '/');
+    });
+  };
+
+  return {
+    enabled,
+    isDesktopShellRequest,
+    isAuthorized,
+    createPairingCode,
+    exchangePairingCode,
+    listDevices,
+    revokeDevices,
+    wrapUiAuthController,
+    registerRoutes,
+  };
+};
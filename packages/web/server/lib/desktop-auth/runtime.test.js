@@ -0,0 +1,144 @@
+import crypto from 'crypto';
+import fs from 'fs';
+import os from 'os';
+import path from 'path';
+import { afterEach, describe, expect, it } from 'vitest';
+import {
+  createDesktopAuthRuntime,
+  readDesktopAuthToken,
+  DESKTOP_AUTH_HEADER,
+  DESKTOP_AUTH_WS_TOKEN_PREFIX,
+  DESKTOP_DEVICE_COOKIE,
+} from './runtime.js';
+
+const TOKEN = 'a'.repeat(64);
+const tempDirs = [];
+
+const createRuntime = (overrides = {}) => {
+  const dataDir = fs.mkdtempSync(path.join(os.tmpdir(), 'desktop-auth-'));
+  tempDirs.push(dataDir);
+  return createDesktopAuthRuntime({
+    token: TOKEN,
+    crypto,
+    fs,
+    path,
+    dataDir,
+    tunnelAuthController: { classifyRequestScope: () => 'local' },
+    logger: { warn: () => {} },
+    ...overrides,
+  });
+};
+
+const createRequest = (headers = {}, overrides = {}) => ({
+  method: 'GET',
+  path: '/api/config',
+  url: '/api/config',
+  ip: '192.168.1.20',
+  headers,
+  ...overrides,
+});
+
+const createResponse = () => {
+  const res = { statusCode: 200, headers: {}, body: null };
+  res.status = (code) => {
+    res.statusCode = code;
+    return res;
+  };
+  res.json = (body) => {
+    res.body = body;
+    return res;
+  };
+  res.setHeader = (name, value) => {
+    res.headers[name.toLowerCase()] = value;
+  };
+  return res;
+};
+
+const passwordlessController = {
+  enabled: false,
+  requireAuth: (_req, _res, next) => next(),
+  handleSessionStatus: (_req, res) => res.json({ authenticated: true, disabled: true }),
+  ensureSessionToken: async () => 'ui-session',
+};
+
+afterEach(() => {
+  for (const dir of tempDirs.splice(0)) {
+    fs.rmSync(dir, { recursive: true, force: true });
+  }
+});
+
+describe('desktop auth runtime', () => {
+  it('reads the token once and removes it from the environment', () => {
+    const env = { OPENCHAMBER_DESKTOP_TOKEN: ` ${TOKEN} ` };
+    expect(readDesktopAuthToken(env)).toBe(TOKEN);
+    expect(env).toEqual({});
+    expect(readDesktopAuthToken(env)).toBeNull();
+  });
+
+  it('accepts the desktop token from the header or a WebSocket subprotocol and rejects everyone else', async () => {
+    const runtime = createRuntime();
+    const controller = runtime.wrapUiAuthController(passwordlessController);
+
+    expect(runtime.isAuthorized(createRequest({ [DESKTOP_AUTH_HEADER]: TOKEN }))).toBe(true);
+    expect(runtime.isAuthorized(createRequest({
+      'sec-websocket-protocol': `openchamber, ${DESKTOP_AUTH_WS_TOKEN_PREFIX}${TOKEN}`,
+    }, { url: '/api/global/event/ws' }))).toBe(true);
+    expect(runtime.isAuthorized(createRequest({}, { url: `/api/openchamber/events?desktopToken=${TOKEN}` }))).toBe(false);
+    expect(runtime.isAuthorized(createRequest({ 'sec-websocket-protocol': TOKEN }))).toBe(false);
+    expect(runtime.isAuthorized(createRequest({ cookie: `oc_desktop_token=${TOKEN}` }))).toBe(false);
+
+    let passed = false;
+    const res = createResponse();
+    await controller.requireAuth(createRequest(), res, () => {
+      passed = true;
+    });
+    expect(passed).toBe(false);
+    expect(res.statusCode).toBe(401);
+    expect(res.body).toMatchObject({ desktopPairingRequired: true });
+    expect(await controller.ensureSessionToken(createRequest(), null)).toBeNull();
+  });
+
+  it('pairs a device with a one-time code and remembers it across restarts', () => {
+    const runtime = createRuntime();
+    const { code } = runtime.createPairingCode();
+
+    const res = createResponse();
+    expect(runtime.exchangePairingCode(createRequest(), res, code)).toMatchObject({ ok: true });
+    expect(runtime.exchangePairingCode(createRequest(), createResponse(), code)).toEqual({ ok: false, reason: 'invalid' });
+
+    const cookie = res.headers['set-cookie'].split(';')[0];
+    expect(cookie.startsWith(`${DESKTOP_DEVICE_COOKIE}=`)).toBe(true);
+    expect(runtime.isAuthorized(createRequest({ cookie }))).toBe(true);
+
+    const restarted = createDesktopAuthRuntime({
+      token: 'b'.repeat(64),
+      crypto,
+      fs,
+      path,
+      dataDir: tempDirs[0],
+      tunnelAuthController: { classifyRequestScope: () => 'local' },
+    });
+    expect(restarted.isAuthorized(createRequest({ cookie }))).toBe(true);
+    expect(restarted.revokeDevices()).toBe(1);
+    expect(restarted.isAuthorized(createRequest({ cookie }))).toBe(false);
+  });
+
+  it('rejects expired codes and rate-limits pairing attempts', () => {
+    let now = 1_000;
+    const runtime = createRuntime({ now: () => now });
+    const { code } = runtime.createPairingCode();
+    now += 6 * 60 * 1000;
+    expect(runtime.exchangePairingCode(createRequest(), createResponse(), code)).toEqual({ ok: false, reason: 'expired' });
+
+    for (let attempt = 0; attempt < 9; attempt += 1) {
+      runtime.exchangePairingCode(createRequest(), createResponse(), 'guess');
+    }
+    expect(runtime.exchangePairingCode(createRequest(), createResponse(), 'guess')).toMatchObject({ reason: 'rate-limited' });
+  });
+
+  it('leaves the password controller untouched when no token was provided', () => {
+    const runtime = createRuntime({ token: null });
+    expect(runtime.enabled).toBe(false);
+    expect(runtime.wrapUiAuthController(passwordlessController)).toBe(passwordlessController);
+  });
+});
@@ -0,0 +1,245 @@
+import readline from 'node:readline';
+
+export const DESKTOP_CONTROL_PREFIX = '[OpenChamberDesktop] ';
+export const DESKTOP_CONTROL_PROTOCOL_VERSION = 1;
+
+const DEFAULT_REQUEST_TIMEOUT_MS = 15000;
+const DEFAULT_QUIT_RISK_INTERVAL_MS = 1000;
+
+/**
+ * Flatten the in-process quit-risk snapshot into the `quitRisk` message body.
+ */
+export const buildQuitRiskPayload = (status) => {
+  const scheduled = status?.scheduledTasks && typeof status.scheduledTasks === 'object'
+    ? status.scheduledTasks
+    : {};
+  const enabledCount = Number.isFinite(scheduled.enabledScheduledTasksCount)
+    ? Math.max(0, Math.trunc(scheduled.enabledScheduledTasksCount))
+    : 0;
+  const runningCount = Number.isFinite(scheduled.runningScheduledTasksCount)
+    ? Math.max(0, Math.trunc(scheduled.runningScheduledTasksCount))
+    : 0;
+  const busySessionsCount = Number.isFinite(status?.sessions?.busyCount)
+    ? Math.max(0, Math.trunc(status.sessions.busyCount))
+    : 0;
+
+  return {
+    hasActiveTunnel: status?.tunnel?.active === true,
+    hasEnabledScheduledTasks: scheduled.hasEnabledScheduledTasks === true || enabledCount > 0,
+    hasRunningScheduledTasks: scheduled.hasRunningScheduledTasks === true || runningCount > 0,
+    enabledScheduledTasksCount: enabledCount,
+    runningScheduledTasksCount: runningCount,
+    busySessionsCount,
+  };
+};
+
+/**
+ * Sidecar end of the desktop control protocol.
+ *
+ * Sidecar -> desktop: one `${prefix}{json}` line per message on stdout.
+ * Desktop -> sidecar: one `{json}` line per message on stdin.
+ * Every message carries `v` (protocol version) and `type`; requests also
+ * carry an `id` that the desktop echoes in its `reply`.
+ */
+export const createDesktopControlRuntime = (dependencies) => {
+  const {
+    process,
+    getEnabled,
+    prefix = DESKTOP_CONTROL_PREFIX,
+    requestTimeoutMs = DEFAULT_REQUEST_TIMEOUT_MS,
+    logger = console,
+  } = dependencies;
+
+  const pending = new Map();
+  const listeners = new Set();
+  let nextRequestId = 1;
+  let stdinReader = null;
+  let quitRiskTimer = null;
+  let lastQuitRiskKey = null;
+  let desktopProtocolVersion = null;
+  let shellDetached = false;
+
+  const isEnabled = () => getEnabled() === true;
+
+  const write = (message) => {
+    if (!isEnabled() || shellDetached) {
+      return false;
+    }
+    try {
+      process.stdout.write(`${prefix}${JSON.stringify({ v: DESKTOP_CONTROL_PROTOCOL_VERSION, ...message })}\n`);
+      return true;
+    } catch {
+      return false;
+    }
+  };
+
+  const send = (type, payload = {}) => write({ ...payload, type });
+
+  const request = (type, payload = {}) => {
+    if (!isEnabled()) {
+      return Promise.reject(new Error('Desktop control protocol is not active'));
+    }
+
+    const id = `s${nextRequestId++}`;
+    return new Promise((resolve, reject) => {
+      const timeout = setTimeout(() => {
+        pending.delete(id);
+        reject(new Error(`Desktop did not reply to ${type}`));
+      }, requestTimeoutMs);
+      timeout.unref?.();
+      pending.set(id, { resolve, reject, timeout });
+
+      if (!write({ ...payload, type, id })) {
+        clearTimeout(timeout);
+        pending.delete(id);
+        reject(new Error(`Failed to send ${type} to desktop`));
+      }
+    });
+  };
+
+  const handleLine = (line) => {
+    const trimmed = typeof line === 'string' ? line.trim() : '';
+    if (!trimmed) {
+      return;
+    }
+
+    let message;
+    try {
+      message = JSON.parse(trimmed);
+    } catch {
+      logger.warn?.('[DesktopControl] ignoring malformed line from desktop');
+      return;
+    }
+    if (!message || typeof message !== 'object' || typeof message.type !== 'string') {
+      return;
+    }
+    if (Number.isFinite(message.v) && message.v > DESKTOP_CONTROL_PROTOCOL_VERSION) {
+      logger.warn?.(`[DesktopControl] desktop speaks protocol v${message.v}; using v${DESKTOP_CONTROL_PROTOCOL_VERSION}`);
+    }
+
+    if (message.type === 'reply') {
+      const entry = pending.get(message.id);
+      if (!entry) {
+        return;
+      }
+      pending.delete(message.id);
+      clearTimeout(entry.timeout);
+      if (message.ok === true) {
+        entry.resolve(message.result ?? null);
+      } else {
+        entry.reject(new Error(typeof message.error === 'string' ? message.error : 'Desktop request failed'));
+      }
+      return;
+    }
+
+    if (message.type === 'hello') {
+      desktopProtocolVersion = Number.isFinite(message.protocol) ? message.protocol : null;
+    }
+
+    if (message.type === 'hello' || message.type === 'event') {
+      const event = message.type === 'hello' ? 'hello' : message.event;
+      if (typeof event !== 'string') {
+        return;
+      }
+      for (const listener of listeners) {
+        try {
+          listener(event, message.type === 'hello' ? message : (message.data ?? null));
+        } catch {
+          // ignore listener throw
+        }
+      }
+    }
+  };
+
+  const onEvent = (listener) => {
+    listeners.add(listener);
+    return () => listeners.delete(listener);
+  };
+
+  const start = () => {
+    if (!isEnabled() || stdinReader || !process.stdin) {
+      return;
+    }
+    stdinReader = readline.createInterface({ input: process.stdin, crlfDelay: Infinity });
+    stdinReader.on('line', handleLine);
+    // If the shell dies, its end of our stdio goes away. Keep serving so a
+    // restarted shell can reuse this sidecar instead of crashing on EPIPE.
+    stdinReader.on('close', () => {
+      shellDetached = true;
+    });
+    const handleStreamError = (error) => {
+      if (error?.code === 'EPIPE') {
+        shellDetached = true;
+      }
+    };
+    process.stdout.on?.('error', handleStreamError);
+    process.stderr?.on?.('error', handleStreamError);
+    send('hello', { protocol: DESKTOP_CONTROL_PROTOCOL_VERSION });
+  };
+
+  const publishQuitRisk = (status) => {
+    const payload = buildQuitRiskPayload(status);
+    const key = JSON.stringify(payload);
+    if (key === lastQuitRiskKey) {
+      return false;
+    }
+    lastQuitRiskKey = key;
+    send('quitRisk', payload);
+    return true;
+  };
+
+  /**
+   * Push quit-risk state whenever it changes. `getStatus` is read in
+   * process, so this is cheap compared to the desktop polling over HTTP.
+   */
+  const watchQuitRisk = (getStatus, intervalMs = DEFAULT_QUIT_RISK_INTERVAL_MS) => {
+    if (!isEnabled() || quitRiskTimer) {
+      return;
+    }
+    const tick = () => {
+      try {
+        publishQuitRisk(getStatus());
+      } catch {
+        // status is best-effort
+      }
+    };
+    tick();
+    quitRiskTimer = setInterval(tick, intervalMs);
+    quitRiskTimer.unref?.();
+  };
+
+  const stop = () => {
+    if (quitRiskTimer) {
+      clearInterval(quitRiskTimer);
+      quitRiskTimer = null;
+    }
+    if (stdinReader) {
+      stdinReader.close();
+      stdinReader = null;
+    }
+    for (const [id, entry] of pending) {
+      clearTimeout(entry.timeout);
+      entry.reject(new Error('Desktop control protocol stopped'));
+      pending.delete(id);
+    }
+  };
+
+  return {
+    isEnabled,
+    getDesktopProtocolVersion: () => desktopProtocolVersion,
+    isShellDetached: () => shellDetached,
+    start,
+    stop,
+    send,
+    request,
+    onEvent,
+    handleLine,
+    publishQuitRisk,
+    watchQuitRisk,
+    notify: (payload) => send('notify', payload),
+    openWindow: (url) => request('openWindow', { url }),
+    setBadge: (count) => send('setBadge', { count }),
+    requestAttention: (critical = false) => send('requestAttention', { critical }),
+    setProgress: (value, state) => send('progress', { value, ...(state ? { state } : {}) }),
+  };
+};
@@ -0,0 +1,105 @@
+import { EventEmitter } from 'node:events';
+import { PassThrough } from 'node:stream';
+import { describe, expect, it } from 'vitest';
+import { createDesktopControlRuntime, DESKTOP_CONTROL_PREFIX } from './runtime.js';
+
+const createHarness = () => {
+  const lines = [];
+  const process = {
+    stdout: {
+      write: (chunk) => {
+        lines.push(chunk);
+        return true;
+      },
+    },
+  };
+  const runtime = createDesktopControlRuntime({
+    process,
+    getEnabled: () => true,
+    requestTimeoutMs: 1000,
+    logger: { warn: () => {} },
+  });
+  const messages = () => lines.map((line) => {
+    expect(line.startsWith(DESKTOP_CONTROL_PREFIX)).toBe(true);
+    return JSON.parse(line.slice(DESKTOP_CONTROL_PREFIX.length));
+  });
+  return { runtime, messages };
+};
+
+describe('desktop control runtime', () => {
+  it('writes versioned messages with the protocol prefix', () => {
+    const { runtime, messages } = createHarness();
+    runtime.setBadge(3);
+    expect(messages()).toEqual([{ v: 1, type: 'setBadge', count: 3 }]);
+  });
+
+  it('resolves and rejects requests from desktop replies', async () => {
+    const { runtime, messages } = createHarness();
+    const opened = runtime.openWindow('http://127.0.0.1:3000');
+    const failed = runtime.request('openWindow', { url: 'file:///etc' });
+    const [first, second] = messages();
+    expect(first).toMatchObject({ type: 'openWindow', url: 'http://127.0.0.1:3000' });
+
+    runtime.handleLine(JSON.stringify({ v: 1, type: 'reply', id: first.id, ok: true, result: { label: 'main-2' } }));
+    runtime.handleLine(JSON.stringify({ v: 1, type: 'reply', id: second.id, ok: false, error: 'Unsupported URL scheme: file' }));
+
+    await expect(opened).resolves.toEqual({ label: 'main-2' });
+    await expect(failed).rejects.toThrow('Unsupported URL scheme: file');
+  });
+
+  it('delivers desktop lifecycle events to listeners', () => {
+    const { runtime } = createHarness();
+    const events = [];
+    runtime.onEvent((event, data) => events.push([event, data]));
+
+    runtime.handleLine('not json');
+    runtime.handleLine(JSON.stringify({ v: 1, type: 'hello', protocol: 1 }));
+    runtime.handleLine(JSON.stringify({ v: 1, type: 'event', event: 'focusChanged', data: { anyFocused: false } }));
+
+    expect(runtime.getDesktopProtocolVersion()).toBe(1);
+    expect(events.map(([event]) => event)).toEqual(['hello', 'focusChanged']);
+    expect(events[1][1]).toEqual({ anyFocused: false });
+  });
+
+  it('publishes quit risk only when it changes', () => {
+    const { runtime, messages } = createHarness();
+    const idle = { tunnel: { active: false }, scheduledTasks: { enabledScheduledTasksCount: 0 } };
+    const busy = {
+      tunnel: { active: true },
+      scheduledTasks: { runningScheduledTasksCount: 2 },
+      sessions: { busyCount: 3 },
+    };
+
+    expect(runtime.publishQuitRisk(idle)).toBe(true);
+    expect(runtime.publishQuitRisk(idle)).toBe(false);
+    expect(runtime.publishQuitRisk(busy)).toBe(true);
+
+    expect(messages()[1]).toEqual({
+      v: 1,
+      type: 'quitRisk',
+      hasActiveTunnel: true,
+      hasEnabledScheduledTasks: false,
+      hasRunningScheduledTasks: true,
+      enabledScheduledTasksCount: 0,
+      runningScheduledTasksCount: 2,
+      busySessionsCount: 3,
+    });
+  });
+
+  it('stops writing once the desktop shell goes away', async () => {
+    const stdout = Object.assign(new EventEmitter(), { write: () => true });
+    const stdin = new PassThrough();
+    const runtime = createDesktopControlRuntime({
+      process: { stdin, stdout },
+      getEnabled: () => true,
+      logger: { warn: () => {} },
+    });
+    runtime.start();
+    expect(runtime.setBadge(1)).toBe(true);
+
+    stdout.emit('error', Object.assign(new Error('write EPIPE'), { code: 'EPIPE' }));
+    expect(runtime.isShellDetached()).toBe(true);
+    expect(runtime.setBadge(2)).toBe(false);
+    runtime.stop();
+  });
+});
@@ -20,6 +20,7 @@ export const createBootstrapRuntime = (dependencies) => {
       getHealthSnapshot,
       uiPassword,
       tunnelAuthController,
+      desktopAuthRuntime,
       readSettingsFromDiskMigrated,
       normalizeTunnelSessionTtlMs,
       resolveZenModel,
@@ -50,6 +51,21 @@ export const createBootstrapRuntime = (dependencies) => {
       setAutoAcceptSession,
     } = options;
 
+    const passwordAuthController = createUiAuth({
+      password: uiPassword,
+      readSettingsFromDiskMigrated,
+    });
+    if (passwordAuthController.enabled) {
+      console.log('UI password protection enabled for browser sessions');
+    }
+    const uiAuthController = desktopAuthRuntime
+      ? desktopAuthRuntime.wrapUiAuthController(passwordAuthController)
+      : passwordAuthController;
+    if (desktopAuthRuntime?.enabled) {
+      desktopAuthRuntime.registerRoutes(app, { uiAuthController });
+      console.log('Desktop auth enabled: LAN clients must pair with the desktop app');
+    }
+
     registerServerStatusRoutes(app, {
       express,
       process,
@@ -62,14 +78,6 @@ export const createBootstrapRuntime = (dependencies) => {
 
     registerCommonRequestMiddleware(app, { express });
 
-    const uiAuthController = createUiAuth({
-      password: uiPassword,
-      readSettingsFromDiskMigrated,
-    });
-    if (uiAuthController.enabled) {
-      console.log('UI password protection enabled for browser sessions');
-    }
-
     registerAuthAndAccessRoutes(app, {
       tunnelAuthController,
       uiAuthController,
@@ -146,9 +146,16 @@ export const registerServerStatusRoutes = (app, dependencies) => {
     });
   });
 
-  app.post('/api/system/shutdown', (_req, res) => {
+  app.post('/api/system/shutdown', express.json({ limit: '64kb' }), (req, res) => {
     res.json({ ok: true });
-    gracefulShutdown({ exitProcess: true }).catch((error) => {
+    // The desktop shell passes how long busy sessions may keep running
+    // before OpenCode is stopped.
+    const sessionDrainTimeoutMs = Number(req.body?.sessionDrainTimeoutMs);
+    const options = { exitProcess: true };
+    if (Number.isFinite(sessionDrainTimeoutMs) && sessionDrainTimeoutMs > 0) {
+      options.sessionDrainTimeoutMs = sessionDrainTimeoutMs;
+    }
+    gracefulShutdown(options).catch((error) => {
       console.error('Shutdown request failed:', error?.message || error);
     });
   });
@@ -14,6 +14,7 @@ describe('core-routes', () => {
       getHealthSnapshot: () => ({ status: 'ok' }),
       openchamberVersion: '1.0.0',
       runtimeName: 'test',
+      express,
     };
 
     registerServerStatusRoutes(app, dependencies);
@@ -23,4 +24,21 @@ describe('core-routes', () => {
     expect(dependencies.gracefulShutdown).toHaveBeenCalled();
     expect(shutdownOpts).toEqual({ exitProcess: true });
   });
+
+  it('should forward the session drain timeout from the desktop shell', async () => {
+    const app = express();
+    const gracefulShutdown = vi.fn(async () => {});
+
+    registerServerStatusRoutes(app, {
+      express,
+      gracefulShutdown,
+      getHealthSnapshot: () => ({ status: 'ok' }),
+      openchamberVersion: '1.0.0',
+      runtimeName: 'test',
+    });
+
+    await request(app).post('/api/system/shutdown').send({ sessionDrainTimeoutMs: 25000 });
+
+    expect(gracefulShutdown).toHaveBeenCalledWith({ exitProcess: true, sessionDrainTimeoutMs: 25000 });
+  });
 });
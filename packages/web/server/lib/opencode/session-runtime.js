@@ -274,6 +274,14 @@ export const createSessionRuntime = ({ writeSseEvent, getNotificationClients, br
     return result;
   };
 
+  const getBusySessionCount = () => {
+    let count = 0;
+    for (const data of sessionActivityPhases.values()) {
+      if (data.phase === 'busy') count += 1;
+    }
+    return count;
+  };
+
   const resetAllSessionActivityToIdle = () => {
     for (const timer of sessionActivityCooldowns.values()) {
       clearTimeout(timer);
@@ -330,6 +338,7 @@ export const createSessionRuntime = ({ writeSseEvent, getNotificationClients, br
   return {
     processOpenCodeSsePayload,
     getSessionActivitySnapshot,
+    getBusySessionCount,
     getSessionStateSnapshot,
     getSessionAttentionSnapshot,
     getSessionState,
@@ -65,4 +65,28 @@ describe('session runtime', () => {
       },
     });
   });
+
+  it('counts only sessions that are still busy', () => {
+    const runtime = createSessionRuntime({
+      writeSseEvent() {},
+      getNotificationClients: () => new Set(),
+      broadcastEvent() {},
+    });
+    runtimes.push(runtime);
+
+    const setStatus = (sessionID, type) => {
+      runtime.processOpenCodeSsePayload({
+        type: 'session.status',
+        properties: { sessionID, info: { type } },
+      });
+    };
+
+    expect(runtime.getBusySessionCount()).toBe(0);
+    setStatus('session-1', 'busy');
+    setStatus('session-2', 'retry');
+    setStatus('session-3', 'busy');
+    setStatus('session-3', 'idle');
+
+    expect(runtime.getBusySessionCount()).toBe(2);
+  });
 });
@@ -100,6 +100,9 @@ export const createSettingsHelpers = (dependencies) => {
     if (typeof candidate.desktopLanAccessEnabled === 'boolean') {
       result.desktopLanAccessEnabled = candidate.desktopLanAccessEnabled;
     }
+    if (typeof candidate.desktopShutdownTimeoutSeconds === 'number' && Number.isFinite(candidate.desktopShutdownTimeoutSeconds)) {
+      result.desktopShutdownTimeoutSeconds = Math.min(600, Math.max(1, Math.round(candidate.desktopShutdownTimeoutSeconds)));
+    }
     if (Array.isArray(candidate.projects)) {
       const projects = sanitizeProjects(candidate.projects);
       if (projects) {
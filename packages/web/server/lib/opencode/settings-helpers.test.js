@@ -51,4 +51,16 @@ describe('settings helpers', () => {
       desktopLanAccessEnabled: false,
     });
   });
+
+  it('clamps desktopShutdownTimeoutSeconds to a sane range', () => {
+    const helpers = createTestHelpers();
+
+    expect(helpers.sanitizeSettingsUpdate({ desktopShutdownTimeoutSeconds: 45 })).toEqual({
+      desktopShutdownTimeoutSeconds: 45,
+    });
+    expect(helpers.sanitizeSettingsUpdate({ desktopShutdownTimeoutSeconds: 0 })).toEqual({
+      desktopShutdownTimeoutSeconds: 1,
+    });
+    expect(helpers.sanitizeSettingsUpdate({ desktopShutdownTimeoutSeconds: '30' })).toEqual({});
+  });
 });
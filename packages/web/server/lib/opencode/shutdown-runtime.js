@@ -9,6 +9,7 @@ export const createGracefulShutdownRuntime = (dependencies) => {
     openCodeWatcherRuntime,
     sessionRuntime,
     scheduledTasksRuntime,
+    desktopControlRuntime,
     getHealthCheckInterval,
     clearHealthCheckInterval,
     getTerminalRuntime,
@@ -27,8 +28,29 @@ export const createGracefulShutdownRuntime = (dependencies) => {
     getActiveTunnelController,
     setActiveTunnelController,
     tunnelAuthController,
+    sessionDrainPollIntervalMs = 250,
   } = dependencies;
 
+  // Give sessions that are mid-response a bounded chance to finish so
+  // OpenCode is not stopped while an agent is still writing.
+  const waitForBusySessions = async (timeoutMs) => {
+    const getBusyCount = () => sessionRuntime.getBusySessionCount?.() ?? 0;
+    let busyCount = getBusyCount();
+    if (busyCount === 0) return;
+
+    console.log(`Waiting up to ${Math.round(timeoutMs / 1000)}s for ${busyCount} busy session(s) to finish...`);
+    const deadline = Date.now() + timeoutMs;
+    while (busyCount > 0 && Date.now() < deadline) {
+      await new Promise((resolve) => setTimeout(resolve, sessionDrainPollIntervalMs));
+      busyCount = getBusyCount();
+    }
+    if (busyCount > 0) {
+      console.warn(`Stopping with ${busyCount} session(s) still busy`);
+    } else {
+      console.log('All sessions finished');
+    }
+  };
+
   const gracefulShutdown = async (options = {}) => {
     if (getIsShuttingDown()) return;
 
@@ -37,9 +59,14 @@ export const createGracefulShutdownRuntime = (dependencies) => {
     console.log('Starting graceful shutdown...');
     const exitProcess = typeof options.exitProcess === 'boolean' ? options.exitProcess : getExitOnShutdown();
 
+    if (Number.isFinite(options.sessionDrainTimeoutMs) && options.sessionDrainTimeoutMs > 0) {
+      await waitForBusySessions(options.sessionDrainTimeoutMs);
+    }
+
     openCodeWatcherRuntime.stop();
     sessionRuntime.dispose();
     scheduledTasksRuntime?.stop?.();
+    desktopControlRuntime?.stop?.();
 
     const healthCheckInterval = getHealthCheckInterval();
     if (healthCheckInterval) {